@@ -76,7 +76,7 @@ pub fn dump(arg: &ArgMatches) -> anyhow::Result<()> {
         .map_err(|err| anyhow!("{}", [err].formattable(&source, true)))?;
 
     if dump_bytecode {
-        let buffer = dash_middle::compiler::format::serialize(bytecode)?;
+        let buffer = bytecode.serialize(interner)?;
         io::stdout().write_all(&buffer)?;
         return Ok(());
     }
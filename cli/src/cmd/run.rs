@@ -1,5 +1,8 @@
+use dash_middle::interner::StringInterner;
+use dash_middle::lexer::token::TokenType;
 use dash_middle::parser::error::IntoFormattableErrors;
 use dash_optimizer::OptLevel;
+use dash_parser::ParseMode;
 use dash_rt::format_value;
 use dash_rt::runtime::Runtime;
 use dash_rt::state::State;
@@ -24,6 +27,7 @@ pub fn run(args: &ArgMatches) -> anyhow::Result<()> {
     let opt = util::opt_level_from_matches(args)?;
     let before = args.is_present("timing").then(Instant::now);
     let quiet = args.is_present("quiet");
+    let module = args.is_present("module");
 
     if nodejs {
         #[cfg(feature = "nodejs")]
@@ -35,7 +39,7 @@ pub fn run(args: &ArgMatches) -> anyhow::Result<()> {
             anyhow::bail!("dash needs to be compiled with the `nodejs` feature to support node-compat mode");
         }
     } else {
-        run_normal_mode(path, opt, quiet, initial_gc_threshold)?;
+        run_normal_mode(path, opt, quiet, module, initial_gc_threshold)?;
     }
 
     if let Some(before) = before {
@@ -45,23 +49,54 @@ pub fn run(args: &ArgMatches) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn run_normal_mode(path: &str, opt: OptLevel, quiet: bool, initial_gc_threshold: Option<usize>) -> anyhow::Result<()> {
+fn run_normal_mode(
+    path: &str,
+    opt: OptLevel,
+    quiet: bool,
+    module: bool,
+    initial_gc_threshold: Option<usize>,
+) -> anyhow::Result<()> {
     let source = fs::read_to_string(path).context("Failed to read source")?;
+    let mode = if module || path.ends_with(".mjs") || has_module_syntax(&source) {
+        ParseMode::Module
+    } else {
+        ParseMode::Script
+    };
 
     let async_rt = tokio::runtime::Runtime::new()?;
-    async_rt.block_on(inner(source, opt, quiet, initial_gc_threshold))?;
+    async_rt.block_on(inner(source, opt, quiet, mode, initial_gc_threshold))?;
 
     Ok(())
 }
 
-async fn inner(source: String, opt: OptLevel, quiet: bool, initial_gc_threshold: Option<usize>) -> anyhow::Result<()> {
+/// Checks if the source contains `import` or `export` declarations, ignoring `import()` and `import.meta`
+fn has_module_syntax(source: &str) -> bool {
+    let Ok(tokens) = dash_lexer::Lexer::new(&mut StringInterner::new(), source).scan_all() else {
+        // Let the parser report the errors
+        return false;
+    };
+
+    tokens.windows(2).any(|window| match window[0].ty {
+        TokenType::Export => true,
+        TokenType::Import => !matches!(window[1].ty, TokenType::LeftParen | TokenType::Dot),
+        _ => false,
+    })
+}
+
+async fn inner(
+    source: String,
+    opt: OptLevel,
+    quiet: bool,
+    mode: ParseMode,
+    initial_gc_threshold: Option<usize>,
+) -> anyhow::Result<()> {
     let mut rt = Runtime::new(initial_gc_threshold).await;
 
     let module = dash_rt_modules::init_modules();
     rt.set_module_manager(module);
 
     let mut scope = rt.vm_mut().scope();
-    let value = match scope.eval(&source, opt) {
+    let value = match scope.eval_with_mode(&source, opt, mode) {
         Ok(val) => val.root(&mut scope),
         Err(EvalError::Exception(val)) => val.root(&mut scope),
         Err(EvalError::Middle(errs)) => {
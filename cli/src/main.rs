@@ -39,6 +39,12 @@ fn main() -> anyhow::Result<()> {
                 .arg(Arg::new("file").required(true))
                 .arg(Arg::new("timing").short('t').long("timing").takes_value(false))
                 .arg(Arg::new("quiet").short('q').long("quiet").takes_value(false))
+                .arg(
+                    Arg::new("module")
+                        .help("Runs the file as a module. Implied for .mjs files and files that use import/export.")
+                        .long("module")
+                        .takes_value(false),
+                )
                 .arg(opt_level.clone())
                 .arg(nodejs)
                 .arg(initial_gc_threshold.clone()),
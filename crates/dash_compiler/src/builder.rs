@@ -23,6 +23,10 @@ pub enum Label {
     LoopIncrement {
         loop_id: usize,
     },
+    /// The end of a labeled statement that is not a loop or switch statement
+    LabeledEnd {
+        labeled_id: usize,
+    },
     SwitchCaseCondition {
         case_id: u16,
     },
@@ -32,6 +36,19 @@ pub enum Label {
     SwitchEnd {
         switch_id: usize,
     },
+    /// Where an optional chain jumps to if an optional part is nullish
+    OptionalChainShortCircuit {
+        chain_id: usize,
+    },
+    OptionalChainEnd {
+        chain_id: usize,
+    },
+    /// An optional method call whose callee is nullish, i.e. `foo.bar?.()`
+    OptionalCallNullishCallee,
+    OptionalCallEnd,
+    /// The strings array of a tagged template has not been created yet
+    CreateTemplateObject,
+    TemplateObjectEnd,
     Catch,
     Finally {
         finally_id: usize,
@@ -4,22 +4,34 @@ use dash_middle::interner::StringInterner;
 use dash_middle::parser::error::Error;
 use dash_optimizer::type_infer::TypeInferCtx;
 use dash_optimizer::OptLevel;
-use dash_parser::Parser;
+use dash_parser::{ParseMode, Parser};
 
 use crate::FunctionCompiler;
 
 impl<'interner> FunctionCompiler<'interner> {
+    /// Compiles the given source code as a script
     pub fn compile_str(
         interner: &'interner mut StringInterner,
         input: &str,
         opt: OptLevel,
+    ) -> Result<CompileResult, Vec<Error>> {
+        Self::compile_str_with_mode(interner, input, opt, ParseMode::Script)
+    }
+
+    /// Compiles the given source code, parsing it either as a module or as a script
+    pub fn compile_str_with_mode(
+        interner: &'interner mut StringInterner,
+        input: &str,
+        opt: OptLevel,
+        mode: ParseMode,
     ) -> Result<CompileResult, Vec<Error>> {
         let tokens = Lexer::new(interner, input).scan_all()?;
-        let (ast, counter) = Parser::new(interner, input, tokens).parse_all()?;
+        let (ast, counter) = Parser::new(interner, input, tokens).with_mode(mode).parse_all()?;
 
         let tcx = TypeInferCtx::new(counter);
 
         Self::new(input, opt, tcx, interner)
+            .with_strict(mode.is_strict())
             .compile_ast(ast, true)
             .map_err(|err| vec![err])
     }
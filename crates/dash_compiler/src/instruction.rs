@@ -46,6 +46,10 @@ impl<'cx, 'interner> InstructionBuilder<'cx, 'interner> {
         build_pos Instruction::Pos,
         build_neg Instruction::Neg,
         build_typeof Instruction::TypeOf,
+        build_to_string Instruction::ToString,
+        build_template_object Instruction::TemplateObject,
+        build_private_symbol Instruction::PrivateSymbol,
+        build_super_call Instruction::SuperCall,
         build_bitnot Instruction::BitNot,
         build_not Instruction::Not,
         build_this Instruction::This,
@@ -73,6 +77,7 @@ impl<'cx, 'interner> InstructionBuilder<'cx, 'interner> {
         build_undef Instruction::Undef,
         build_symbol_iterator Instruction::CallSymbolIterator,
         build_for_in_iterator Instruction::CallForInIterator,
+        build_async_iterator Instruction::CallAsyncIterator,
         build_dynamic_delete Instruction::DeletePropertyDynamic
     }
 
@@ -197,9 +202,11 @@ impl<'cx, 'interner> InstructionBuilder<'cx, 'interner> {
 
     pub fn build_static_prop_access(&mut self, ident: Symbol, preserve_this: bool) -> Result<(), LimitExceededError> {
         let id = self.current_function_mut().cp.add(Constant::Identifier(ident))?;
+        let cache = self.current_function_mut().add_inline_cache()?;
         self.write_instr(Instruction::StaticPropAccess);
         self.writew(id);
         self.write(preserve_this.into());
+        self.writew(cache);
 
         Ok(())
     }
@@ -274,7 +281,7 @@ impl<'cx, 'interner> InstructionBuilder<'cx, 'interner> {
                 | ObjectMemberKind::DynamicGetter(_)
                 | ObjectMemberKind::DynamicSetter(_) => self.write(kind_id),
                 ObjectMemberKind::Static(name) => compile_object_member_kind(self, span, name, kind_id)?,
-                ObjectMemberKind::Spread => self.write(kind_id),
+                ObjectMemberKind::Spread | ObjectMemberKind::Prototype => self.write(kind_id),
                 ObjectMemberKind::Getter(name) | ObjectMemberKind::Setter(name) => {
                     compile_object_member_kind(self, span, name, kind_id)?
                 }
@@ -291,6 +298,11 @@ impl<'cx, 'interner> InstructionBuilder<'cx, 'interner> {
         self.writew(path_id);
     }
 
+    pub fn build_export_all(&mut self, path_id: u16) {
+        self.write_instr(Instruction::ExportAll);
+        self.writew(path_id);
+    }
+
     pub fn build_dynamic_import(&mut self) {
         self.write_instr(Instruction::ImportDyn);
     }
@@ -340,8 +352,15 @@ impl<'cx, 'interner> InstructionBuilder<'cx, 'interner> {
         self.writew(count);
     }
 
-    pub fn build_arraydestruct(&mut self, count: u16) {
+    pub fn build_arraydestruct(&mut self, count: u16, rest: Option<u16>) {
         self.write_instr(Instruction::ArrayDestruct);
+        self.writew(rest.map_or_else(
+            || u16::MAX,
+            |v| {
+                assert!(v != u16::MAX);
+                v
+            },
+        ));
         self.writew(count);
     }
 
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use std::rc::Rc;
 
 use dash_log::{debug, span, Level};
-use dash_middle::compiler::constant::{Buffer, Constant, ConstantPool, Function};
+use dash_middle::compiler::constant::{Buffer, Constant, ConstantPool, Function, LimitExceededError};
 use dash_middle::compiler::external::External;
 use dash_middle::compiler::instruction::{AssignKind, Instruction, IntrinsicOperation};
 use dash_middle::compiler::scope::{CompileValueType, Scope, ScopeLocal};
@@ -14,13 +14,14 @@ use dash_middle::parser::error::Error;
 use dash_middle::parser::expr::{
     ArrayLiteral, ArrayMemberKind, AssignmentExpr, AssignmentTarget, BinaryExpr, CallArgumentKind, ConditionalExpr,
     Expr, ExprKind, FunctionCall, GroupingExpr, LiteralExpr, ObjectLiteral, ObjectMemberKind, Postfix,
-    PropertyAccessExpr, Seq, UnaryExpr,
+    PropertyAccessExpr, Seq, TaggedTemplate, TemplateLiteral, UnaryExpr,
 };
 use dash_middle::parser::statement::{
-    Asyncness, BlockStatement, Class, ClassMember, ClassMemberKey, ClassMemberValue, DoWhileLoop, ExportKind,
-    ForInLoop, ForLoop, ForOfLoop, FuncId, FunctionDeclaration, FunctionKind, IfStatement, ImportKind, Loop, Parameter,
-    ReturnStatement, SpecifierKind, Statement, StatementKind, SwitchCase, SwitchStatement, TryCatch, VariableBinding,
-    VariableDeclaration, VariableDeclarationKind, VariableDeclarationName, VariableDeclarations, WhileLoop,
+    has_use_strict_directive, Asyncness, BlockStatement, Class, ClassMember, ClassMemberKey, ClassMemberValue,
+    DoWhileLoop, ExportKind, ForInLoop, ForLoop, ForOfLoop, FuncId, FunctionDeclaration, FunctionKind, IfStatement,
+    ImportKind, Loop, Parameter, ReturnStatement, SpecifierKind, Statement, StatementKind, SwitchCase, SwitchStatement,
+    TryCatch, VariableBinding, VariableDeclaration, VariableDeclarationKind, VariableDeclarationName,
+    VariableDeclarations, WhileLoop,
 };
 use dash_middle::sourcemap::Span;
 use dash_middle::util::Counter;
@@ -50,10 +51,57 @@ macro_rules! unimplementedc {
     };
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 enum Breakable {
-    Loop { loop_id: usize },
-    Switch { switch_id: usize },
+    Loop {
+        loop_id: usize,
+        labels: Vec<Symbol>,
+        try_block_count: usize,
+    },
+    Switch {
+        switch_id: usize,
+        labels: Vec<Symbol>,
+        try_block_count: usize,
+    },
+    /// Any other labeled statement, which can only be exited using `break label`
+    Labeled {
+        labeled_id: usize,
+        label: Symbol,
+        try_block_count: usize,
+    },
+}
+
+impl Breakable {
+    /// Whether this breakable can be targeted using `label`. A loop or switch statement can have several labels,
+    /// as in `a: b: for (;;) {}`
+    fn has_label(&self, label: Symbol) -> bool {
+        match self {
+            Breakable::Loop { labels, .. } | Breakable::Switch { labels, .. } => labels.contains(&label),
+            Breakable::Labeled { label: own, .. } => *own == label,
+        }
+    }
+
+    /// The number of try blocks that enclose this breakable, used to determine which try blocks a jump leaves
+    fn try_block_count(&self) -> usize {
+        match *self {
+            Breakable::Loop { try_block_count, .. }
+            | Breakable::Switch { try_block_count, .. }
+            | Breakable::Labeled { try_block_count, .. } => try_block_count,
+        }
+    }
+}
+
+/// A try-catch-finally statement that is currently being compiled
+#[derive(Debug)]
+struct EnclosingTry {
+    /// The label and body of the `finally` block, if present
+    ///
+    /// The body is compiled once after the `catch` block, and additionally inlined before every `break` or `continue`
+    /// that jumps out of this statement.
+    finally: Option<(Label, Statement)>,
+    /// Whether the `catch` block is being compiled.
+    /// If there is no `finally` block, the try block has already been popped at runtime at this point.
+    in_catch: bool,
 }
 
 /// Function-specific state, such as
@@ -67,46 +115,83 @@ struct FunctionLocalState {
     cp: ConstantPool,
     /// Current `try` depth (note that this does NOT include `catch`es)
     try_depth: u16,
-    /// A stack of try-catch-finally blocks and their optional `finally` label that can be jumped to
-    finally_labels: Vec<Option<Label>>,
+    /// A stack of try-catch-finally statements that are currently being compiled
+    enclosing_trys: Vec<EnclosingTry>,
     finally_counter: Counter<usize>,
     /// The type of function that this FunctionCompiler compiles
     ty: FunctionKind,
     /// Container, used for storing global labels that can be jumped to
     jc: JumpContainer,
-    /// A stack of breakable labels (loop/switch)
+    /// A stack of breakable labels (loop/switch/labeled statement)
     breakables: Vec<Breakable>,
+    /// The labels of the labeled statements whose body is a loop or switch that is about to be compiled
+    pending_labels: Vec<Symbol>,
     /// Keeps track of the total number of loops to be able to have unique IDs
     loop_counter: usize,
     /// Keeps track of the total number of loops to be able to have unique IDs
     switch_counter: usize,
+    /// Keeps track of the total number of labeled statements to be able to have unique IDs
+    labeled_counter: usize,
+    /// A stack of the optional chains that are currently being compiled
+    optional_chains: Vec<usize>,
+    /// Keeps track of the total number of optional chains to be able to have unique IDs
+    optional_chain_counter: usize,
     id: FuncId,
     debug_symbols: DebugSymbols,
     /// Whether this function references `arguments` anywhere in its body
     ///
     /// Also tracks the span for error reporting, but is discarded past the compiler stage.
     references_arguments: Option<Span>,
+    /// The number of inline caches allocated for static property accesses in this function
+    inline_caches: u16,
+    /// Whether this function is in strict mode
+    is_strict: bool,
+    /// If this is the constructor of a derived class, where `this` is only initialized by calling `super()`
+    derived_constructor: Option<DerivedConstructor>,
+}
+
+/// State of a derived class constructor
+#[derive(Debug)]
+struct DerivedConstructor {
+    /// The local that is set to `true` once `this` has been initialized by a `super()` call
+    this_initialized: u16,
+    /// The field initializers of the class, which run as soon as `super()` returns
+    field_initializers: Vec<ClassMember>,
 }
 
 impl FunctionLocalState {
-    pub fn new(ty: FunctionKind, id: FuncId) -> Self {
+    pub fn new(ty: FunctionKind, id: FuncId, is_strict: bool) -> Self {
         Self {
             buf: Vec::new(),
             cp: ConstantPool::new(),
             try_depth: 0,
-            finally_labels: Vec::new(),
+            enclosing_trys: Vec::new(),
             finally_counter: Counter::new(),
             ty,
             jc: JumpContainer::new(),
             breakables: Vec::new(),
+            pending_labels: Vec::new(),
             loop_counter: 0,
             switch_counter: 0,
+            labeled_counter: 0,
+            optional_chains: Vec::new(),
+            optional_chain_counter: 0,
             id,
             debug_symbols: DebugSymbols::default(),
             references_arguments: None,
+            inline_caches: 0,
+            is_strict,
+            derived_constructor: None,
         }
     }
 
+    /// Allocates a new inline cache for a property access site and returns its index
+    fn add_inline_cache(&mut self) -> Result<u16, LimitExceededError> {
+        let id = self.inline_caches;
+        self.inline_caches = id.checked_add(1).ok_or(LimitExceededError)?;
+        Ok(id)
+    }
+
     /// "Prepares" a loop and returns a unique ID that identifies this loop
     ///
     /// Specifically, this function increments a FunctionCompiler-local loop counter and
@@ -114,7 +199,12 @@ impl FunctionLocalState {
     /// statements can be resolved at compile-time
     fn prepare_loop(&mut self) -> usize {
         let loop_id = self.loop_counter;
-        self.breakables.push(Breakable::Loop { loop_id });
+        let labels = std::mem::take(&mut self.pending_labels);
+        self.breakables.push(Breakable::Loop {
+            loop_id,
+            labels,
+            try_block_count: self.enclosing_trys.len(),
+        });
         self.loop_counter += 1;
         loop_id
     }
@@ -122,15 +212,34 @@ impl FunctionLocalState {
     /// Same as [`prepare_loop`] but for switch statements
     fn prepare_switch(&mut self) -> usize {
         let switch_id = self.switch_counter;
-        self.breakables.push(Breakable::Switch { switch_id });
+        let labels = std::mem::take(&mut self.pending_labels);
+        self.breakables.push(Breakable::Switch {
+            switch_id,
+            labels,
+            try_block_count: self.enclosing_trys.len(),
+        });
         self.switch_counter += 1;
         switch_id
     }
 
+    /// Same as [`prepare_loop`] but for labeled statements that are neither loops nor switch statements
+    fn prepare_labeled(&mut self, label: Symbol) -> usize {
+        let labeled_id = self.labeled_counter;
+        self.breakables.push(Breakable::Labeled {
+            labeled_id,
+            label,
+            try_block_count: self.enclosing_trys.len(),
+        });
+        self.labeled_counter += 1;
+        labeled_id
+    }
+
     fn exit_loop(&mut self) {
         let item = self.breakables.pop();
         match item {
-            None | Some(Breakable::Switch { .. }) => panic!("Tried to exit loop, but no breakable was found"),
+            None | Some(Breakable::Switch { .. } | Breakable::Labeled { .. }) => {
+                panic!("Tried to exit loop, but no breakable was found")
+            }
             Some(Breakable::Loop { .. }) => {}
         }
     }
@@ -138,11 +247,23 @@ impl FunctionLocalState {
     fn exit_switch(&mut self) {
         let item = self.breakables.pop();
         match item {
-            None | Some(Breakable::Loop { .. }) => panic!("Tried to exit switch, but no breakable was found"),
+            None | Some(Breakable::Loop { .. } | Breakable::Labeled { .. }) => {
+                panic!("Tried to exit switch, but no breakable was found")
+            }
             Some(Breakable::Switch { .. }) => {}
         }
     }
 
+    fn exit_labeled(&mut self) {
+        let item = self.breakables.pop();
+        match item {
+            None | Some(Breakable::Loop { .. } | Breakable::Switch { .. }) => {
+                panic!("Tried to exit labeled statement, but no breakable was found")
+            }
+            Some(Breakable::Labeled { .. }) => {}
+        }
+    }
+
     fn add_global_label(&mut self, label: Label) {
         jump_container::add_label(&mut self.jc, label, &mut self.buf)
     }
@@ -154,13 +275,17 @@ impl FunctionLocalState {
 
     pub fn is_async(&self) -> bool {
         match self.ty {
-            FunctionKind::Function(a) => matches!(a, Asyncness::Yes),
-            FunctionKind::Generator | FunctionKind::Arrow => false,
+            FunctionKind::Function(a) | FunctionKind::Arrow(a) | FunctionKind::Generator(a) => {
+                matches!(a, Asyncness::Yes)
+            }
         }
     }
 
     fn enclosing_finally(&self) -> Option<Label> {
-        self.finally_labels.iter().copied().rev().find_map(|lbl| lbl)
+        self.enclosing_trys
+            .iter()
+            .rev()
+            .find_map(|tc| tc.finally.as_ref().map(|&(label, _)| label))
     }
 }
 
@@ -173,6 +298,21 @@ pub struct FunctionCompiler<'interner> {
     #[allow(unused)]
     opt_level: OptLevel,
     source: Rc<str>,
+    /// Whether the top level code is in strict mode regardless of a `"use strict"` directive, e.g. in modules
+    is_strict: bool,
+    /// The superclass of the class whose members are currently being compiled, used to resolve `super.x`
+    super_binding: Option<SuperBinding>,
+    /// The function id of the derived class constructor that is currently being compiled
+    derived_constructor: Option<FuncId>,
+}
+
+/// A reference to the superclass of a class, which is stored in a local of the function that contains the class
+#[derive(Debug, Clone, Copy)]
+struct SuperBinding {
+    func_id: FuncId,
+    local_id: u16,
+    /// Whether `super` refers to the superclass itself (in static members), or to its prototype
+    is_static: bool,
 }
 
 impl<'interner> FunctionCompiler<'interner> {
@@ -183,13 +323,24 @@ impl<'interner> FunctionCompiler<'interner> {
             interner,
             function_stack: Vec::new(),
             source: Rc::from(source),
+            is_strict: false,
+            super_binding: None,
+            derived_constructor: None,
         }
     }
 
+    /// Sets whether the code is compiled in strict mode, even without a `"use strict"` directive
+    pub fn with_strict(mut self, is_strict: bool) -> Self {
+        self.is_strict = is_strict;
+        self
+    }
+
     pub fn compile_ast(mut self, mut ast: Vec<Statement>, implicit_return: bool) -> Result<CompileResult, Error> {
         let compile_span = span!(Level::TRACE, "compile ast");
         let _enter = compile_span.enter();
 
+        let is_strict = self.is_strict || has_use_strict_directive(&ast);
+
         transformations::hoist_declarations(&mut ast);
         if implicit_return {
             transformations::ast_patch_implicit_return(&mut ast);
@@ -226,6 +377,7 @@ impl<'interner> FunctionCompiler<'interner> {
         self.function_stack.push(FunctionLocalState::new(
             FunctionKind::Function(Asyncness::No),
             FuncId::ROOT,
+            is_strict,
         ));
 
         self.accept_multiple(ast)?;
@@ -246,6 +398,8 @@ impl<'interner> FunctionCompiler<'interner> {
             externals,
             source: self.source,
             debug_symbols: root.debug_symbols,
+            inline_caches: root.inline_caches.into(),
+            is_strict: root.is_strict,
         })
     }
 
@@ -318,6 +472,204 @@ impl<'interner> FunctionCompiler<'interner> {
         self.find_local_in_scope(ident, func_id)
     }
 
+    /// Compiles `target op= value` as `target = target op value`, so that the target is read before the value is
+    /// evaluated. The object and key of a property target are stored in temporaries, so they are only evaluated once.
+    fn visit_compound_assignment(
+        &mut self,
+        span: Span,
+        target: Expr,
+        value: Expr,
+        operator: TokenType,
+    ) -> Result<(), Error> {
+        let target = match target.kind {
+            ExprKind::PropertyAccess(PropertyAccessExpr {
+                computed,
+                target: object,
+                property,
+            }) => {
+                let object = self.store_temporary(span, *object)?;
+                let property = match computed {
+                    true => self.store_temporary(span, *property)?,
+                    false => *property,
+                };
+                Expr {
+                    span: target.span,
+                    kind: ExprKind::property_access(computed, object, property),
+                }
+            }
+            _ => target,
+        };
+
+        let value = Expr {
+            span,
+            kind: ExprKind::binary(target.clone(), value, operator),
+        };
+        self.visit_assignment_expression(
+            span,
+            AssignmentExpr::new_expr_place(target, value, TokenType::Assignment),
+        )
+    }
+
+    /// Evaluates an expression into a new unnameable local and returns an expression that loads it
+    fn store_temporary(&mut self, span: Span, expr: Expr) -> Result<Expr, Error> {
+        let mut ib = InstructionBuilder::new(self);
+        let id = ib
+            .current_scope_mut()
+            .add_local(sym::compound_assignment_tmp, VariableDeclarationKind::Unnameable, None)
+            .map_err(|_| Error::LocalLimitExceeded(span))?;
+
+        ib.accept_expr(expr)?;
+        ib.build_local_store(AssignKind::Assignment, id, false);
+        ib.build_pop();
+
+        Ok(Expr {
+            span: Span::COMPILER_GENERATED,
+            kind: ExprKind::compiled(compile_local_load(id, false)),
+        })
+    }
+
+    /// Resolves a local declared in the function `declaring_func_id` from the function `func_id`,
+    /// marking it as an extern local in all functions in between
+    fn resolve_enclosing_local(&mut self, func_id: FuncId, declaring_func_id: FuncId, local_id: u16) -> (u16, bool) {
+        if func_id == declaring_func_id {
+            return (local_id, false);
+        }
+
+        let parent = self
+            .tcx
+            .scope_node(func_id)
+            .parent()
+            .expect("Local must be declared in an enclosing function");
+        let (local_id, nested_extern) = self.resolve_enclosing_local(parent.into(), declaring_func_id, local_id);
+        let external_id = self.add_external_to_func(func_id, local_id, nested_extern) as u16;
+        (external_id, true)
+    }
+
+    /// Creates a `throw new <error>(<message>)` statement
+    fn throw_error_statement(&mut self, error: Symbol, message: &str) -> Statement {
+        let message = self.interner.intern(message);
+
+        Statement {
+            span: Span::COMPILER_GENERATED,
+            kind: StatementKind::Throw(Expr {
+                span: Span::COMPILER_GENERATED,
+                kind: ExprKind::function_call(
+                    Expr {
+                        span: Span::COMPILER_GENERATED,
+                        kind: ExprKind::identifier(error),
+                    },
+                    vec![CallArgumentKind::Normal(Expr {
+                        span: Span::COMPILER_GENERATED,
+                        kind: ExprKind::string_literal(message),
+                    })],
+                    true,
+                ),
+            }),
+        }
+    }
+
+    /// In the constructor of a derived class, throws a `ReferenceError` at runtime
+    /// if whether `this` has been initialized matches `initialized`
+    fn throw_if_this_initialized(&mut self, initialized: bool, message: &str) -> Result<(), Error> {
+        let Some(derived) = &self.current_function().derived_constructor else {
+            return Ok(());
+        };
+
+        let mut condition = Expr {
+            span: Span::COMPILER_GENERATED,
+            kind: ExprKind::compiled(compile_local_load(derived.this_initialized, false)),
+        };
+        if !initialized {
+            condition = Expr {
+                span: Span::COMPILER_GENERATED,
+                kind: ExprKind::unary(TokenType::LogicalNot, condition),
+            };
+        }
+
+        let throw = self.throw_error_statement(sym::ReferenceError, message);
+        self.visit_if_statement(
+            Span::COMPILER_GENERATED,
+            IfStatement::new(condition, throw, Vec::new(), None),
+        )
+    }
+
+    /// Leaves all try blocks that were entered inside of the given breakable, in preparation for a jump out of it.
+    ///
+    /// Try blocks are popped from the innermost to the outermost, and the body of any `finally` block is inlined
+    /// right after popping its try block. The inlined body is compiled as if it appeared after the try statement,
+    /// so that `break`, `continue` and `return` within it refer to the right targets.
+    fn leave_try_blocks(&mut self, breakable: &Breakable) -> Result<(), Error> {
+        let mut ib = InstructionBuilder::new(self);
+        let try_depth = ib.current_function().try_depth;
+        let mut left_trys = Vec::new();
+        let mut left_breakables = Vec::new();
+
+        while ib.current_function().enclosing_trys.len() > breakable.try_block_count() {
+            let function = ib.current_function_mut();
+            let tc = function.enclosing_trys.pop().unwrap();
+            while let Some(inner) = function.breakables.last() {
+                if inner.try_block_count() <= function.enclosing_trys.len() {
+                    break;
+                }
+                left_breakables.push(function.breakables.pop().unwrap());
+            }
+            if !tc.in_catch {
+                function.try_depth -= 1;
+            }
+
+            // A catch block without a finally block is not guarded by a try block at runtime
+            if !tc.in_catch || tc.finally.is_some() {
+                ib.build_try_end();
+            }
+            if let Some((_, finally)) = &tc.finally {
+                ib.accept(finally.clone())?;
+            }
+
+            left_trys.push(tc);
+        }
+
+        let function = ib.current_function_mut();
+        function.enclosing_trys.extend(left_trys.into_iter().rev());
+        function.breakables.extend(left_breakables.into_iter().rev());
+        function.try_depth = try_depth;
+
+        Ok(())
+    }
+
+    /// Returns the superclass of the class whose members are currently being compiled
+    fn super_class(&mut self, span: Span) -> Result<Expr, Error> {
+        let Some(SuperBinding { func_id, local_id, .. }) = self.super_binding else {
+            unimplementedc!(span, "super keyword outside of a derived class")
+        };
+
+        let (id, is_extern) = self.resolve_enclosing_local(self.current_function().id, func_id, local_id);
+        Ok(Expr {
+            span: Span::COMPILER_GENERATED,
+            kind: ExprKind::compiled(compile_local_load(id, is_extern)),
+        })
+    }
+
+    /// Returns the object that `super` refers to in `super.x` expressions
+    fn super_base(&mut self, span: Span) -> Result<Expr, Error> {
+        let superclass = self.super_class(span)?;
+
+        if self.super_binding.is_some_and(|binding| binding.is_static) {
+            Ok(superclass)
+        } else {
+            Ok(Expr {
+                span: Span::COMPILER_GENERATED,
+                kind: ExprKind::property_access(
+                    false,
+                    superclass,
+                    Expr {
+                        span: Span::COMPILER_GENERATED,
+                        kind: ExprKind::identifier(sym::prototype),
+                    },
+                ),
+            })
+        }
+    }
+
     fn visit_for_each_kinded_loop(
         &mut self,
         kind: ForEachLoopKind,
@@ -353,6 +705,10 @@ impl<'interner> FunctionCompiler<'interner> {
         //     console.log(x)
         // }
 
+        // For-Await-Of loops are desugared like For-Of loops, except that the iterator is obtained
+        // through `@@asyncIterator` and every call to `next()` is awaited:
+        // while (!(__forOfGenStep = await __forOfIter.next()).done) { ... }
+
         let mut ib = InstructionBuilder::new(self);
         let for_of_iter_id = ib
             .current_scope_mut()
@@ -367,6 +723,7 @@ impl<'interner> FunctionCompiler<'interner> {
         ib.accept_expr(expr)?;
         match kind {
             ForEachLoopKind::ForOf => ib.build_symbol_iterator(),
+            ForEachLoopKind::ForAwaitOf(_) => ib.build_async_iterator(),
             ForEachLoopKind::ForIn => ib.build_for_in_iterator(),
         }
         ib.build_local_store(AssignKind::Assignment, for_of_iter_id, false);
@@ -418,6 +775,36 @@ impl<'interner> FunctionCompiler<'interner> {
         }
 
         let for_of_iter_binding_bc = compile_local_load(for_of_iter_id, false);
+        let next_step = Expr {
+            span: Span::COMPILER_GENERATED,
+            kind: ExprKind::function_call(
+                Expr {
+                    span: Span::COMPILER_GENERATED,
+                    kind: ExprKind::property_access(
+                        false,
+                        Expr {
+                            span: Span::COMPILER_GENERATED,
+                            kind: ExprKind::compiled(for_of_iter_binding_bc),
+                        },
+                        Expr {
+                            span: Span::COMPILER_GENERATED,
+                            kind: ExprKind::identifier(sym::next),
+                        },
+                    ),
+                },
+                Vec::new(),
+                false,
+            ),
+        };
+        let next_step = match kind {
+            // The await expression uses the span of the loop, so that `for await` outside of an async function
+            // can be reported
+            ForEachLoopKind::ForAwaitOf(span) => Expr {
+                span,
+                kind: ExprKind::unary(TokenType::Await, next_step),
+            },
+            ForEachLoopKind::ForOf | ForEachLoopKind::ForIn => next_step,
+        };
 
         // for..of -> while loop rewrite
         ib.visit_while_loop(
@@ -435,27 +822,7 @@ impl<'interner> FunctionCompiler<'interner> {
                                     span: Span::COMPILER_GENERATED,
                                     kind: ExprKind::assignment_local_space(
                                         for_of_gen_step_id,
-                                        Expr {
-                                            span: Span::COMPILER_GENERATED,
-                                            kind: ExprKind::function_call(
-                                                Expr {
-                                                    span: Span::COMPILER_GENERATED,
-                                                    kind: ExprKind::property_access(
-                                                        false,
-                                                        Expr {
-                                                            span: Span::COMPILER_GENERATED,
-                                                            kind: ExprKind::compiled(for_of_iter_binding_bc),
-                                                        },
-                                                        Expr {
-                                                            span: Span::COMPILER_GENERATED,
-                                                            kind: ExprKind::identifier(sym::next),
-                                                        },
-                                                    ),
-                                                },
-                                                Vec::new(),
-                                                false,
-                                            ),
-                                        },
+                                        next_step,
                                         TokenType::Assignment,
                                     ),
                                 },
@@ -475,8 +842,11 @@ impl<'interner> FunctionCompiler<'interner> {
     }
 }
 
+#[allow(clippy::enum_variant_names)]
 enum ForEachLoopKind {
     ForOf,
+    /// A `for await..of` loop, with the span of the loop
+    ForAwaitOf(Span),
     ForIn,
 }
 
@@ -499,8 +869,9 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
             StatementKind::Import(i) => self.visit_import_statement(span, i),
             StatementKind::Export(e) => self.visit_export_statement(span, e),
             StatementKind::Class(c) => self.visit_class_declaration(span, c),
-            StatementKind::Continue => self.visit_continue(span),
-            StatementKind::Break => self.visit_break(span),
+            StatementKind::Continue(label) => self.visit_continue(span, label),
+            StatementKind::Break(label) => self.visit_break(span, label),
+            StatementKind::Labeled(label, body) => self.visit_labeled(span, label, body),
             StatementKind::Debugger => self.visit_debugger(span),
             StatementKind::Empty => self.visit_empty_statement(),
             StatementKind::Switch(s) => self.visit_switch_statement(span, s),
@@ -525,6 +896,10 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
             ExprKind::Class(e) => self.visit_class_expr(span, e),
             ExprKind::Array(e) => self.visit_array_literal(span, e),
             ExprKind::Object(e) => self.visit_object_literal(span, e),
+            ExprKind::Template(e) => self.visit_template_literal(span, e),
+            ExprKind::TaggedTemplate(e) => self.visit_tagged_template(span, e),
+            ExprKind::OptionalChain(e) => self.visit_optional_chain(span, e),
+            ExprKind::Optional(e) => self.visit_optional(span, e),
             ExprKind::Compiled(mut buf) => {
                 self.current_function_mut().buf.append(&mut buf);
                 Ok(())
@@ -708,14 +1083,28 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
         let mut ib = InstructionBuilder::new(self);
 
         match ident {
-            sym::this => ib.build_this(),
+            sym::this => {
+                ib.throw_if_this_initialized(
+                    false,
+                    "Must call super constructor in derived class before accessing 'this'",
+                )?;
+                ib.build_this()
+            }
             // super() handled specifically in call visitor
             sym::super_ => unimplementedc!(span, "super keyword outside of a call"),
             sym::globalThis => ib.build_global(),
             sym::Infinity => ib.build_infinity(),
             sym::NaN => ib.build_nan(),
             sym::arguments => {
-                ib.current_function_mut().references_arguments = Some(span);
+                // Arrow functions don't have their own `arguments` object and instead capture it from
+                // the closest enclosing non-arrow function, so that function needs to create it
+                let function = ib
+                    .function_stack
+                    .iter_mut()
+                    .rev()
+                    .find(|f| !matches!(f.ty, FunctionKind::Arrow(_)))
+                    .expect("Root function must be present");
+                function.references_arguments = Some(span);
                 ib.build_arguments();
             }
             ident => match ib.find_local(ident) {
@@ -773,6 +1162,10 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                     }
                 },
                 ExprKind::Literal(LiteralExpr::Identifier(ident)) => {
+                    if ib.current_function().is_strict {
+                        return Err(Error::DeleteOfIdentifierInStrictMode(span));
+                    }
+
                     ib.build_global();
                     let id = ib
                         .current_function_mut()
@@ -781,6 +1174,18 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                         .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
                     ib.build_static_delete(id);
                 }
+                ExprKind::OptionalChain(chain) if matches!(chain.kind, ExprKind::PropertyAccess(_)) => {
+                    // `delete foo?.bar` evaluates to true without deleting anything if the chain short-circuits
+                    compile_optional_chain(&mut ib, span, Constant::Boolean(true), |ib| {
+                        ib.visit_unary_expression(
+                            span,
+                            UnaryExpr {
+                                operator: TokenType::Delete,
+                                expr: chain,
+                            },
+                        )
+                    })?;
+                }
                 _ => {
                     ib.build_constant(Constant::Boolean(true))
                         .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
@@ -802,7 +1207,7 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                 ib.build_undef();
             }
             TokenType::Yield => {
-                if !matches!(ib.current_function().ty, FunctionKind::Generator) {
+                if !matches!(ib.current_function().ty, FunctionKind::Generator(_)) {
                     return Err(Error::YieldOutsideGenerator { yield_expr: span });
                 }
 
@@ -857,10 +1262,10 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                     ib.build_objdestruct(field_count, rest_id);
 
                     for (name, alias) in fields {
-                        let name = alias.unwrap_or(name);
+                        // The property is looked up by its original name, but stored in the alias (if any)
                         let id = ib
                             .current_scope_mut()
-                            .add_local(name, binding.kind, None)
+                            .add_local(alias.unwrap_or(name), binding.kind, None)
                             .map_err(|_| Error::LocalLimitExceeded(span))?;
 
                         let var_id = ib
@@ -878,9 +1283,10 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                     }
                 }
                 VariableDeclarationName::ArrayDestructuring { fields, rest } => {
-                    if rest.is_some() {
-                        unimplementedc!(span, "rest operator in array destructuring");
-                    }
+                    let rest_id = rest
+                        .map(|rest| ib.current_scope_mut().add_local(rest, binding.kind, None))
+                        .transpose()
+                        .map_err(|_| Error::LocalLimitExceeded(span))?;
 
                     let field_count = fields
                         .len()
@@ -890,13 +1296,19 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                     let value = value.ok_or(Error::MissingInitializerInDestructuring(span))?;
                     ib.accept_expr(value)?;
 
-                    ib.build_arraydestruct(field_count);
+                    ib.build_arraydestruct(field_count, rest_id);
 
                     for name in fields {
-                        let id = ib
-                            .current_scope_mut()
-                            .add_local(name, binding.kind, None)
-                            .map_err(|_| Error::LocalLimitExceeded(span))?;
+                        // Holes are stored in an unnameable local, as the instruction needs a local for every element
+                        let id = match name {
+                            Some(name) => ib.current_scope_mut().add_local(name, binding.kind, None),
+                            None => ib.current_scope_mut().add_local(
+                                sym::destructure_tmp,
+                                VariableDeclarationKind::Unnameable,
+                                None,
+                            ),
+                        }
+                        .map_err(|_| Error::LocalLimitExceeded(span))?;
 
                         let var_id = ib
                             .current_function_mut()
@@ -1003,6 +1415,9 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
 
         let loop_id = ib.current_function_mut().prepare_loop();
 
+        // `continue` jumps straight to the condition, as there is no increment step
+        ib.current_function_mut()
+            .add_global_label(Label::LoopIncrement { loop_id });
         ib.current_function_mut()
             .add_global_label(Label::LoopCondition { loop_id });
         ib.accept_expr(condition)?;
@@ -1028,6 +1443,9 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
 
         ib.accept(*body)?;
 
+        // `continue` skips the rest of the body and evaluates the condition
+        ib.current_function_mut()
+            .add_global_label(Label::LoopIncrement { loop_id });
         ib.accept_expr(condition)?;
         ib.build_jmptruep(Label::LoopCondition { loop_id }, false);
 
@@ -1045,9 +1463,21 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
     ) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
 
+        // Compound assignments read the target before evaluating the right-hand side
+        let left = match (left, compound_assignment_operator(operator)) {
+            (AssignmentTarget::Expr(left), Some(operator)) => {
+                return ib.visit_compound_assignment(span, *left, *right, operator);
+            }
+            (left, _) => left,
+        };
+
         match left {
             AssignmentTarget::Expr(left) => match left.kind {
                 ExprKind::Literal(LiteralExpr::Identifier(ident)) => {
+                    if operator != TokenType::Assignment {
+                        unimplementedc!(span, "unknown assignment operator {}", operator.fmt_for_expected_tys());
+                    }
+
                     let local = ib.find_local(ident);
 
                     if let Some((id, local, is_extern)) = local {
@@ -1055,166 +1485,28 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                             return Err(Error::ConstAssignment(span));
                         }
 
-                        macro_rules! assign {
-                            ($kind:expr) => {{
-                                ib.accept_expr(*right)?;
-                                ib.build_local_store($kind, id, is_extern);
-                            }};
-                        }
-
-                        match operator {
-                            TokenType::Assignment => assign!(AssignKind::Assignment),
-                            TokenType::AdditionAssignment => assign!(AssignKind::AddAssignment),
-                            TokenType::SubtractionAssignment => assign!(AssignKind::SubAssignment),
-                            TokenType::MultiplicationAssignment => assign!(AssignKind::MulAssignment),
-                            TokenType::DivisionAssignment => assign!(AssignKind::DivAssignment),
-                            TokenType::RemainderAssignment => assign!(AssignKind::RemAssignment),
-                            TokenType::ExponentiationAssignment => assign!(AssignKind::PowAssignment),
-                            TokenType::LeftShiftAssignment => assign!(AssignKind::ShlAssignment),
-                            TokenType::RightShiftAssignment => assign!(AssignKind::ShrAssignment),
-                            TokenType::UnsignedRightShiftAssignment => assign!(AssignKind::UshrAssignment),
-                            TokenType::BitwiseAndAssignment => assign!(AssignKind::BitAndAssignment),
-                            TokenType::BitwiseOrAssignment => assign!(AssignKind::BitOrAssignment),
-                            TokenType::BitwiseXorAssignment => assign!(AssignKind::BitXorAssignment),
-                            _ => unimplementedc!(span, "unknown assignment operator {}", operator.fmt_for_expected_tys()),
-                        }
+                        ib.accept_expr(*right)?;
+                        ib.build_local_store(AssignKind::Assignment, id, is_extern);
                     } else {
-                        macro_rules! assign {
-                            ($kind:expr) => {{
-                                ib.accept_expr(*right)?;
-                                ib.build_global_store($kind, ident)
-                                    .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
-                            }};
-                        }
-
-                        match operator {
-                            TokenType::Assignment => assign!(AssignKind::Assignment),
-                            TokenType::AdditionAssignment => assign!(AssignKind::AddAssignment),
-                            TokenType::SubtractionAssignment => assign!(AssignKind::SubAssignment),
-                            TokenType::MultiplicationAssignment => assign!(AssignKind::MulAssignment),
-                            TokenType::DivisionAssignment => assign!(AssignKind::DivAssignment),
-                            TokenType::RemainderAssignment => assign!(AssignKind::RemAssignment),
-                            TokenType::ExponentiationAssignment => assign!(AssignKind::PowAssignment),
-                            TokenType::LeftShiftAssignment => assign!(AssignKind::ShlAssignment),
-                            TokenType::RightShiftAssignment => assign!(AssignKind::ShrAssignment),
-                            TokenType::UnsignedRightShiftAssignment => assign!(AssignKind::UshrAssignment),
-                            TokenType::BitwiseAndAssignment => assign!(AssignKind::BitAndAssignment),
-                            TokenType::BitwiseOrAssignment => assign!(AssignKind::BitOrAssignment),
-                            TokenType::BitwiseXorAssignment => assign!(AssignKind::BitXorAssignment),
-                            _ => unimplementedc!(span, "unknown assignment operator {}", operator.fmt_for_expected_tys()),
-                        }
+                        ib.accept_expr(*right)?;
+                        ib.build_global_store(AssignKind::Assignment, ident)
+                            .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
                     }
                 }
                 ExprKind::PropertyAccess(prop) => {
                     ib.accept_expr(*prop.target)?;
 
-                    macro_rules! staticassign {
-                        ($ident:expr, $kind:expr) => {{
-                            ib.accept_expr(*right)?;
-                            ib.build_static_prop_assign($kind, $ident)
-                                .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
-                        }};
-                    }
-                    macro_rules! dynamicassign {
-                        ($prop:expr, $kind:expr) => {{
-                            ib.accept_expr(*right)?;
-                            ib.accept_expr($prop)?;
-                            ib.build_dynamic_prop_assign($kind);
-                        }};
-                    }
-
                     match (*prop.property, prop.computed, operator) {
                         (Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. }, false, TokenType::Assignment) => {
-                            staticassign!(ident, AssignKind::Assignment)
-                        }
-                        (Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. }, false, TokenType::AdditionAssignment) => {
-                            staticassign!(ident, AssignKind::AddAssignment)
-                        }
-                        (
-                            Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. },
-                            false,
-                            TokenType::SubtractionAssignment,
-                        ) => {
-                            staticassign!(ident, AssignKind::SubAssignment)
-                        }
-                        (
-                            Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. },
-                            false,
-                            TokenType::MultiplicationAssignment,
-                        ) => {
-                            staticassign!(ident, AssignKind::MulAssignment)
-                        }
-                        (Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. }, false, TokenType::DivisionAssignment) => {
-                            staticassign!(ident, AssignKind::DivAssignment)
-                        }
-                        (Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. }, false, TokenType::RemainderAssignment) => {
-                            staticassign!(ident, AssignKind::RemAssignment)
-                        }
-                        (
-                            Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. },
-                            false,
-                            TokenType::ExponentiationAssignment,
-                        ) => {
-                            staticassign!(ident, AssignKind::PowAssignment)
-                        }
-                        (Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. }, false, TokenType::LeftShiftAssignment) => {
-                            staticassign!(ident, AssignKind::ShlAssignment)
-                        }
-                        (Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. }, false, TokenType::RightShiftAssignment) => {
-                            staticassign!(ident, AssignKind::ShrAssignment)
-                        }
-                        (
-                            Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. },
-                            false,
-                            TokenType::UnsignedRightShiftAssignment,
-                        ) => {
-                            staticassign!(ident, AssignKind::UshrAssignment)
-                        }
-                        (Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. }, false, TokenType::BitwiseAndAssignment) => {
-                            staticassign!(ident, AssignKind::BitAndAssignment)
-                        }
-                        (Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. }, false, TokenType::BitwiseOrAssignment) => {
-                            staticassign!(ident, AssignKind::BitOrAssignment)
-                        }
-                        (Expr { kind:ExprKind::Literal(LiteralExpr::Identifier(ident)), .. }, false, TokenType::BitwiseXorAssignment) => {
-                            staticassign!(ident, AssignKind::BitXorAssignment)
-                        }
-                        (expr, true, TokenType::Assignment) => dynamicassign!(expr, AssignKind::Assignment),
-                        (expr, true, TokenType::AdditionAssignment) => {
-                            dynamicassign!(expr, AssignKind::AddAssignment)
-                        }
-                        (expr, true, TokenType::SubtractionAssignment) => {
-                            dynamicassign!(expr, AssignKind::SubAssignment)
-                        }
-                        (expr, true, TokenType::MultiplicationAssignment) => {
-                            dynamicassign!(expr, AssignKind::MulAssignment)
-                        }
-                        (expr, true, TokenType::DivisionAssignment) => {
-                            dynamicassign!(expr, AssignKind::DivAssignment)
-                        }
-                        (expr, true, TokenType::RemainderAssignment) => {
-                            dynamicassign!(expr, AssignKind::RemAssignment)
-                        }
-                        (expr, true, TokenType::ExponentiationAssignment) => {
-                            dynamicassign!(expr, AssignKind::PowAssignment)
-                        }
-                        (expr, true, TokenType::LeftShiftAssignment) => {
-                            dynamicassign!(expr, AssignKind::ShlAssignment)
-                        }
-                        (expr, true, TokenType::RightShiftAssignment) => {
-                            dynamicassign!(expr, AssignKind::ShrAssignment)
-                        }
-                        (expr, true, TokenType::UnsignedRightShiftAssignment) => {
-                            dynamicassign!(expr, AssignKind::UshrAssignment)
-                        }
-                        (expr, true, TokenType::BitwiseAndAssignment) => {
-                            dynamicassign!(expr, AssignKind::BitAndAssignment)
-                        }
-                        (expr, true, TokenType::BitwiseOrAssignment) => {
-                            dynamicassign!(expr, AssignKind::BitOrAssignment)
+                            ib.accept_expr(*right)?;
+                            ib.build_static_prop_assign(AssignKind::Assignment, ident)
+                                .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
                         }
-                        (expr, true, TokenType::BitwiseXorAssignment) => {
-                            dynamicassign!(expr, AssignKind::BitXorAssignment)
+                        // The key must be evaluated before the value: `obj[f()] = g()` calls `f` first
+                        (expr, true, TokenType::Assignment) => {
+                            ib.accept_expr(expr)?;
+                            ib.accept_expr(*right)?;
+                            ib.build_dynamic_prop_assign(AssignKind::Assignment);
                         }
                         other => unimplementedc!(span, "assignment to computed property {other:?}"),
                     }
@@ -1225,6 +1517,68 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                 ib.accept_expr(*right)?;
                 ib.build_local_store(AssignKind::Assignment, id, false);
             }
+            AssignmentTarget::ArrayDestructuring(..) | AssignmentTarget::ObjectDestructuring(..) => {
+                if operator != TokenType::Assignment {
+                    unimplementedc!(span, "destructuring with operator {}", operator.fmt_for_expected_tys());
+                }
+
+                // Desugar `[a, b.c] = value` into `tmp = value, a = tmp[0], b.c = tmp[1], tmp`
+                let tmp_id = ib
+                    .current_scope_mut()
+                    .add_local(sym::destructure_tmp, VariableDeclarationKind::Unnameable, None)
+                    .map_err(|_| Error::LocalLimitExceeded(span))?;
+
+                ib.accept_expr(*right)?;
+                ib.build_local_store(AssignKind::Assignment, tmp_id, false);
+                ib.build_pop();
+
+                let tmp = Expr {
+                    span: Span::COMPILER_GENERATED,
+                    kind: ExprKind::compiled(compile_local_load(tmp_id, false)),
+                };
+
+                let fields: Vec<(Expr, bool, AssignmentTarget)> = match left {
+                    AssignmentTarget::ArrayDestructuring(fields) => fields
+                        .into_iter()
+                        .enumerate()
+                        .filter_map(|(index, target)| {
+                            let key = Expr {
+                                span: Span::COMPILER_GENERATED,
+                                kind: ExprKind::number_literal(index as f64),
+                            };
+                            target.map(|target| (key, true, target))
+                        })
+                        .collect(),
+                    AssignmentTarget::ObjectDestructuring(fields) => fields
+                        .into_iter()
+                        .map(|(name, target)| {
+                            let key = Expr {
+                                span: Span::COMPILER_GENERATED,
+                                kind: ExprKind::identifier(name),
+                            };
+                            (key, false, target)
+                        })
+                        .collect(),
+                    _ => unreachable!(),
+                };
+
+                for (key, computed, target) in fields {
+                    ib.visit_expression_statement(Expr {
+                        span: Span::COMPILER_GENERATED,
+                        kind: ExprKind::Assignment(AssignmentExpr::new(
+                            target,
+                            Expr {
+                                span: Span::COMPILER_GENERATED,
+                                kind: ExprKind::property_access(computed, tmp.clone(), key),
+                            },
+                            TokenType::Assignment,
+                        )),
+                    })?;
+                }
+
+                // The value of a destructuring assignment is the right hand side
+                ib.accept_expr(tmp)?;
+            }
         }
 
         Ok(())
@@ -1326,123 +1680,119 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
             return Ok(());
         }
 
+        if let ExprKind::PropertyAccess(PropertyAccessExpr {
+            target: super_target, ..
+        }) = &target.kind
+        {
+            if let ExprKind::Literal(LiteralExpr::Identifier(sym::super_)) = super_target.kind {
+                // super.method(...args) is lowered to super.method.call(this, ...args)
+                let mut call_arguments = vec![CallArgumentKind::Normal(Expr {
+                    span: Span::COMPILER_GENERATED,
+                    kind: ExprKind::identifier(sym::this),
+                })];
+                call_arguments.extend(arguments);
+
+                return ib.visit_function_call(
+                    span,
+                    FunctionCall {
+                        constructor_call: false,
+                        target: Box::new(Expr {
+                            span: Span::COMPILER_GENERATED,
+                            kind: ExprKind::property_access(
+                                false,
+                                *target,
+                                Expr {
+                                    span: Span::COMPILER_GENERATED,
+                                    kind: ExprKind::identifier(sym::call),
+                                },
+                            ),
+                        }),
+                        arguments: call_arguments,
+                    },
+                );
+            }
+        }
+
         if let ExprKind::Literal(LiteralExpr::Identifier(sym::super_)) = target.kind {
-            // super() call lowering
-            let super_id = ib
-                .current_scope_mut()
-                .add_local(sym::super_, VariableDeclarationKind::Unnameable, None)
-                .map_err(|_| Error::LocalLimitExceeded(span))?;
+            // super(...args) constructs the superclass into `this` and then runs the field initializers
+            let Some(this_initialized) = ib
+                .current_function()
+                .derived_constructor
+                .as_ref()
+                .map(|derived| derived.this_initialized)
+            else {
+                unimplementedc!(span, "super() outside of a derived class constructor")
+            };
+
+            ib.throw_if_this_initialized(true, "Super constructor may only be called once")?;
 
-            // __super = new this.constructor.__proto__()
-            let superclass_constructor_call = Expr {
+            let superclass = ib.super_class(span)?;
+            ib.accept_expr(superclass)?;
+            let arguments = arguments
+                .into_iter()
+                .map(|arg| match arg {
+                    CallArgumentKind::Normal(expr) => ArrayMemberKind::Item(expr),
+                    CallArgumentKind::Spread(expr) => ArrayMemberKind::Spread(expr),
+                })
+                .collect();
+            ib.accept_expr(Expr {
                 span: Span::COMPILER_GENERATED,
-                kind: ExprKind::function_call(
-                    Expr {
-                        span: Span::COMPILER_GENERATED,
-                        kind: ExprKind::property_access(
-                            false,
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::property_access(
-                                    false,
-                                    Expr {
-                                        span: Span::COMPILER_GENERATED,
-                                        kind: ExprKind::identifier(sym::this),
-                                    },
-                                    Expr {
-                                        span: Span::COMPILER_GENERATED,
-                                        kind: ExprKind::identifier(sym::constructor),
-                                    },
-                                ),
-                            },
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::identifier(sym::__proto__),
-                            },
-                        ),
-                    },
-                    arguments,
-                    true,
-                ),
-            };
-
-            ib.visit_expression_statement(Expr {
-                span: Span::COMPILER_GENERATED,
-                kind: ExprKind::assignment_local_space(super_id, superclass_constructor_call, TokenType::Assignment),
+                kind: ExprKind::array_literal(arguments),
             })?;
+            ib.build_super_call();
 
-            // __super.__proto__ = this.__proto__
             ib.visit_expression_statement(Expr {
                 span: Span::COMPILER_GENERATED,
-                kind: ExprKind::assignment(
-                    Expr {
-                        span: Span::COMPILER_GENERATED,
-                        kind: ExprKind::property_access(
-                            false,
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::compiled(compile_local_load(super_id, false)),
-                            },
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::identifier(sym::__proto__),
-                            },
-                        ),
-                    },
+                kind: ExprKind::assignment_local_space(
+                    this_initialized,
                     Expr {
                         span: Span::COMPILER_GENERATED,
-                        kind: ExprKind::property_access(
-                            false,
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::Literal(LiteralExpr::Identifier(sym::this)),
-                            },
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::identifier(sym::__proto__),
-                            },
-                        ),
+                        kind: ExprKind::bool_literal(true),
                     },
                     TokenType::Assignment,
                 ),
             })?;
 
-            // this.__proto__ = __super
-            ib.visit_assignment_expression(
-                Span::COMPILER_GENERATED,
-                AssignmentExpr::new_expr_place(
-                    Expr {
-                        span: Span::COMPILER_GENERATED,
-                        kind: ExprKind::property_access(
-                            false,
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::Literal(LiteralExpr::Identifier(sym::this)),
-                            },
-                            Expr {
-                                span: Span::COMPILER_GENERATED,
-                                kind: ExprKind::identifier(sym::__proto__),
-                            },
-                        ),
-                    },
-                    Expr {
-                        span: Span::COMPILER_GENERATED,
-                        kind: ExprKind::compiled(compile_local_load(super_id, false)),
-                    },
-                    TokenType::Assignment,
-                ),
-            )?;
-            // Assignment expression leaves `super` on the stack, as it is needed by expressions
+            let field_initializers = ib
+                .current_function()
+                .derived_constructor
+                .as_ref()
+                .map(|derived| derived.field_initializers.clone())
+                .unwrap_or_default();
+            if !field_initializers.is_empty() {
+                let members = compile_class_members(&mut ib, span, field_initializers)?;
+                ib.build_this();
+                ib.build_object_member_like_instruction(span, members, Instruction::AssignProperties)?;
+            }
+            // SuperCall leaves `this` on the stack, which is what the super() expression evaluates to
 
             return Ok(());
         }
 
-        let has_this = if let ExprKind::PropertyAccess(p) = target.kind {
-            ib.visit_property_access_expr(target.span, p, true)?;
-            true
-        } else {
-            ib.accept_expr(*target)?;
-            false
+        let mut is_optional_method_call = false;
+        let has_this = match target.kind {
+            ExprKind::PropertyAccess(p) => {
+                ib.visit_property_access_expr(target.span, p, true)?;
+                true
+            }
+            ExprKind::Optional(callee) if matches!(callee.kind, ExprKind::PropertyAccess(_)) => {
+                // foo.bar?.(): `this` is kept on the stack below the callee, so it needs to be popped as well
+                // if the callee is nullish
+                let ExprKind::PropertyAccess(p) = callee.kind else {
+                    unreachable!()
+                };
+                ib.visit_property_access_expr(callee.span, p, true)?;
+                ib.build_jmpnullishnp(Label::OptionalCallNullishCallee, true);
+                is_optional_method_call = true;
+                true
+            }
+            kind => {
+                ib.accept_expr(Expr {
+                    kind,
+                    span: target_span,
+                })?;
+                false
+            }
         };
 
         let argc = arguments.len();
@@ -1466,13 +1816,40 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
 
         ib.build_call(meta, spread_arg_indices, target_span);
 
+        if is_optional_method_call {
+            ib.build_jmp(Label::OptionalCallEnd, true);
+
+            ib.add_local_label(Label::OptionalCallNullishCallee);
+            ib.build_pop();
+            let chain_id = *ib
+                .current_function()
+                .optional_chains
+                .last()
+                .expect("optional call outside of an optional chain");
+            ib.build_jmp(Label::OptionalChainShortCircuit { chain_id }, false);
+
+            ib.add_local_label(Label::OptionalCallEnd);
+        }
+
         Ok(())
     }
 
-    fn visit_return_statement(&mut self, _span: Span, ReturnStatement(stmt): ReturnStatement) -> Result<(), Error> {
+    fn visit_return_statement(&mut self, span: Span, ReturnStatement(stmt): ReturnStatement) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
+        // Implicit returns inserted by the compiler have no user span and are always allowed in the root function
+        if ib.current_function().id == FuncId::ROOT && span.is_user_span() {
+            return Err(Error::ReturnOutsideFunction(span));
+        }
+
         let finally = ib.current_function().enclosing_finally();
 
+        if let ExprKind::Literal(LiteralExpr::Undefined) = stmt.kind {
+            ib.throw_if_this_initialized(
+                false,
+                "Must call super constructor in derived class before returning from derived constructor",
+            )?;
+        }
+
         let tc_depth = ib.current_function().try_depth;
         ib.accept_expr(stmt)?;
         if let Some(finally) = finally {
@@ -1517,7 +1894,13 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
     ) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
 
-        ib.accept_expr(*target)?;
+        match target.kind {
+            ExprKind::Literal(LiteralExpr::Identifier(sym::super_)) => {
+                let base = ib.super_base(target.span)?;
+                ib.accept_expr(base)?;
+            }
+            _ => ib.accept_expr(*target)?,
+        }
 
         match (*property, computed) {
             (
@@ -1700,24 +2083,53 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
             ty,
             ty_segment: _,
             constructor_initializers,
+            has_use_strict_directive,
         }: FunctionDeclaration,
     ) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
-        ib.function_stack.push(FunctionLocalState::new(ty, id));
+        // Functions nested in strict mode code are always strict
+        let is_strict = ib.current_function().is_strict || has_use_strict_directive;
+
+        let param_name = |param: &Parameter| match *param {
+            Parameter::Identifier(ident) => ident,
+            Parameter::Spread(ident) => ident,
+        };
+        // If a parameter name is used more than once, the last parameter wins.
+        // Earlier ones still need a local, as arguments are passed by position.
+        let is_shadowed = |index: usize| {
+            let name = param_name(&arguments[index].0);
+            arguments[index + 1..]
+                .iter()
+                .any(|(other, ..)| param_name(other) == name)
+        };
+        if is_strict {
+            let duplicate = arguments.iter().enumerate().find(|(index, (param, ..))| {
+                arguments[..*index]
+                    .iter()
+                    .any(|(other, ..)| param_name(other) == param_name(param))
+            });
+            if let Some((_, (.., param_span))) = duplicate {
+                return Err(Error::DuplicateParameterInStrictMode(*param_span));
+            }
+        }
+
+        ib.function_stack.push(FunctionLocalState::new(ty, id, is_strict));
 
         let mut rest_local = None;
 
-        for (param, default, _ty) in &arguments {
-            let name = match *param {
-                Parameter::Identifier(ident) => ident,
-                Parameter::Spread(ident) => ident,
-            };
+        for (index, (param, default, ..)) in arguments.iter().enumerate() {
+            let name = param_name(param);
 
-            let id = ib
-                .tcx
-                .scope_mut(id)
-                .add_local(name, VariableDeclarationKind::Var, None)
-                .map_err(|_| Error::LocalLimitExceeded(span))?;
+            let id = if is_shadowed(index) {
+                // Parameters always occupy the first locals, and the type inference pass has already
+                // reserved an unnameable local for this one
+                index as u16
+            } else {
+                ib.tcx
+                    .scope_mut(id)
+                    .add_local(name, VariableDeclarationKind::Var, None)
+                    .map_err(|_| Error::LocalLimitExceeded(span))?
+            };
 
             if let Parameter::Spread(..) = param {
                 rest_local = Some(id);
@@ -1742,8 +2154,18 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
         transformations::hoist_declarations(&mut statements);
         transformations::ast_insert_implicit_return(&mut statements);
 
-        // Insert initializers
-        if let Some(members) = constructor_initializers {
+        if ib.derived_constructor == Some(id) {
+            // `this` is uninitialized until `super()` is called, which then runs the initializers
+            let this_initialized = ib
+                .current_scope_mut()
+                .add_local(sym::super_, VariableDeclarationKind::Unnameable, None)
+                .map_err(|_| Error::LocalLimitExceeded(span))?;
+            ib.current_function_mut().derived_constructor = Some(DerivedConstructor {
+                this_initialized,
+                field_initializers: constructor_initializers.unwrap_or_default(),
+            });
+        } else if let Some(members) = constructor_initializers {
+            // Insert initializers
             let members = compile_class_members(&mut ib, span, members)?;
             ib.build_this();
             ib.build_object_member_like_instruction(span, members, Instruction::AssignProperties)?;
@@ -1773,6 +2195,8 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
             debug_symbols: cmp.debug_symbols,
             source: Rc::clone(&ib.source),
             references_arguments: cmp.references_arguments.is_some(),
+            inline_caches: (0..cmp.inline_caches).map(|_| Cell::default()).collect(),
+            is_strict: cmp.is_strict,
         };
         ib.build_constant(Constant::Function(Rc::new(function)))
             .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
@@ -1827,28 +2251,168 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
         Ok(())
     }
 
+    fn visit_template_literal(
+        &mut self,
+        span: Span,
+        TemplateLiteral { strings, expressions, .. }: TemplateLiteral,
+    ) -> Result<(), Error> {
+        let mut ib = InstructionBuilder::new(self);
+        let mut strings = strings.into_iter();
+
+        // Empty strings are skipped, unless the template consists of nothing but an empty string
+        let head = strings.next().unwrap_or(sym::empty);
+        let mut has_value = head != sym::empty || expressions.is_empty();
+        if has_value {
+            ib.build_constant(Constant::String(head))
+                .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
+        }
+
+        for (expr, string) in expressions.into_iter().zip(strings) {
+            ib.accept_expr(expr)?;
+            ib.build_to_string();
+            if has_value {
+                ib.build_add();
+            }
+            has_value = true;
+
+            if string != sym::empty {
+                ib.build_constant(Constant::String(string))
+                    .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
+                ib.build_add();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_tagged_template(
+        &mut self,
+        span: Span,
+        TaggedTemplate {
+            tag,
+            template: TemplateLiteral {
+                strings,
+                raw_strings,
+                expressions,
+            },
+        }: TaggedTemplate,
+    ) -> Result<(), Error> {
+        let mut ib = InstructionBuilder::new(self);
+
+        // The strings array is only created once per call site and must be the same object every time
+        // the tagged template is evaluated, so it is cached in a local of the root function
+        let cache_id = ib
+            .tcx
+            .scope_mut(FuncId::ROOT)
+            .add_local(sym::template_object, VariableDeclarationKind::Unnameable, None)
+            .map_err(|_| Error::LocalLimitExceeded(span))?;
+        let func_id = ib.current_function().id;
+        let (cache_id, is_extern) = ib.resolve_enclosing_local(func_id, FuncId::ROOT, cache_id);
+
+        let string_array = |strings: Vec<Symbol>| {
+            ArrayLiteral(
+                strings
+                    .into_iter()
+                    .map(|string| {
+                        ArrayMemberKind::Item(Expr {
+                            span: Span::COMPILER_GENERATED,
+                            kind: ExprKind::string_literal(string),
+                        })
+                    })
+                    .collect(),
+            )
+        };
+
+        // if (__template_object === undefined) {
+        //     __template_object = [...strings];
+        //     __template_object.raw = [...raw_strings];
+        //     Object.freeze(__template_object.raw);
+        //     Object.freeze(__template_object);
+        // }
+        ib.build_local_load(cache_id, is_extern);
+        ib.build_jmpnullishp(Label::CreateTemplateObject, true);
+        ib.build_jmp(Label::TemplateObjectEnd, true);
+
+        ib.add_local_label(Label::CreateTemplateObject);
+        ib.visit_array_literal(span, string_array(strings))?;
+        ib.visit_array_literal(span, string_array(raw_strings))?;
+        ib.build_template_object();
+        ib.build_local_store(AssignKind::Assignment, cache_id, is_extern);
+        ib.build_pop();
+        ib.add_local_label(Label::TemplateObjectEnd);
+
+        // tag(__template_object, ...expressions)
+        let mut arguments = vec![CallArgumentKind::Normal(Expr {
+            span: Span::COMPILER_GENERATED,
+            kind: ExprKind::compiled(compile_local_load(cache_id, is_extern)),
+        })];
+        arguments.extend(expressions.into_iter().map(CallArgumentKind::Normal));
+
+        ib.visit_function_call(
+            span,
+            FunctionCall {
+                constructor_call: false,
+                target: tag,
+                arguments,
+            },
+        )
+    }
+
+    fn visit_optional_chain(&mut self, span: Span, expr: Box<Expr>) -> Result<(), Error> {
+        let mut ib = InstructionBuilder::new(self);
+        compile_optional_chain(&mut ib, span, Constant::Undefined, |ib| ib.accept_expr(*expr))
+    }
+
+    fn visit_optional(&mut self, _span: Span, expr: Box<Expr>) -> Result<(), Error> {
+        let mut ib = InstructionBuilder::new(self);
+        ib.accept_expr(*expr)?;
+
+        let chain_id = *ib
+            .current_function()
+            .optional_chains
+            .last()
+            .expect("optional expression outside of an optional chain");
+        ib.build_jmpnullishnp(Label::OptionalChainShortCircuit { chain_id }, false);
+
+        Ok(())
+    }
+
     fn visit_try_catch(&mut self, span: Span, TryCatch { try_, catch, finally }: TryCatch) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
 
-        let finally = finally.map(|f| (ib.current_function_mut().finally_counter.inc(), f));
+        let finally_id = finally
+            .as_ref()
+            .map(|_| ib.current_function_mut().finally_counter.inc());
 
-        ib.build_try_block(catch.is_some(), finally.as_ref().map(|&(id, _)| id));
+        ib.build_try_block(catch.is_some(), finally_id);
 
         ib.current_function_mut().try_depth += 1;
-        ib.current_function_mut()
-            .finally_labels
-            .push(finally.as_ref().map(|&(finally_id, _)| Label::Finally { finally_id }));
+        ib.current_function_mut().enclosing_trys.push(EnclosingTry {
+            finally: finally_id
+                .zip(finally)
+                .map(|(finally_id, f)| (Label::Finally { finally_id }, *f)),
+            in_catch: false,
+        });
         ib.current_scope_mut().enter();
         let res = ib.accept(*try_); // TODO: some API for making this nicer
         ib.current_scope_mut().exit();
         ib.current_function_mut().try_depth -= 1;
         res?;
 
+        if let Some(tc) = ib.current_function_mut().enclosing_trys.last_mut() {
+            tc.in_catch = true;
+        }
+
+        if finally_id.is_none() {
+            // Entering the catch block already removes the try block, so only the try body may pop it.
+            // Otherwise the catch body falling through would pop the try block of an enclosing try statement.
+            ib.build_try_end();
+        }
         ib.build_jmp(Label::TryEnd, true);
 
         ib.current_scope_mut().enter();
 
-        if catch.is_none() && finally.is_none() {
+        if catch.is_none() && finally_id.is_none() {
             // FIXME: make it a real error
             unimplementedc!(span, "try block has no catch or finally");
         }
@@ -1856,10 +2420,33 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
         if let Some(catch) = catch {
             ib.add_local_label(Label::Catch);
 
-            if let Some(ident) = catch.ident {
+            let mut body = catch.body;
+            // The locals bound by the catch parameter, which are only visible in the catch block
+            let mut catch_locals = Vec::new();
+
+            if let Some(mut binding) = catch.binding {
+                // A destructuring pattern receives the error in a temporary local and is
+                // desugared into a declaration at the start of the catch body:
+                // catch ({ message }) { ... } -> catch (tmp) { let { message } = tmp; ... }
+                let (ident, kind, names) = match binding.name {
+                    VariableDeclarationName::Identifier(ident) => (ident, VariableDeclarationKind::Let, Vec::new()),
+                    VariableDeclarationName::ArrayDestructuring { ref fields, rest } => {
+                        let names = fields.iter().copied().flatten().chain(rest).collect();
+                        (sym::destructure_tmp, VariableDeclarationKind::Unnameable, names)
+                    }
+                    VariableDeclarationName::ObjectDestructuring { ref fields, rest } => {
+                        let names = fields
+                            .iter()
+                            .map(|&(name, ident)| ident.unwrap_or(name))
+                            .chain(rest)
+                            .collect();
+                        (sym::destructure_tmp, VariableDeclarationKind::Unnameable, names)
+                    }
+                };
+
                 let id = ib
                     .current_scope_mut()
-                    .add_local(ident, VariableDeclarationKind::Var, None)
+                    .add_shadowing_local(ident, kind)
                     .map_err(|_| Error::LocalLimitExceeded(span))?;
 
                 if id == u16::MAX {
@@ -1868,28 +2455,61 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                 }
 
                 ib.writew(id);
+                catch_locals.push(id);
+
+                // Declare the destructured names upfront, so that the declaration reuses these locals
+                // rather than locals of the same name in the enclosing function
+                for name in names {
+                    let id = ib
+                        .current_scope_mut()
+                        .add_shadowing_local(name, VariableDeclarationKind::Let)
+                        .map_err(|_| Error::LocalLimitExceeded(span))?;
+                    catch_locals.push(id);
+                }
+
+                if !matches!(binding.name, VariableDeclarationName::Identifier(_)) {
+                    binding.kind = VariableDeclarationKind::Let;
+                    let var = Statement {
+                        span: Span::COMPILER_GENERATED,
+                        kind: StatementKind::Variable(VariableDeclarations(vec![VariableDeclaration::new(
+                            binding,
+                            Some(Expr {
+                                span: Span::COMPILER_GENERATED,
+                                kind: ExprKind::compiled(compile_local_load(id, false)),
+                            }),
+                        )])),
+                    };
+
+                    let old_body = std::mem::replace(&mut *body, Statement::dummy_empty());
+                    *body = Statement {
+                        span: old_body.span,
+                        kind: StatementKind::Block(BlockStatement(vec![var, old_body])),
+                    };
+                }
             } else {
                 ib.writew(u16::MAX);
             }
 
-            ib.accept(*catch.body)?;
+            ib.accept(*body)?;
+
+            for id in catch_locals {
+                ib.current_scope_mut().hide_local(id);
+            }
         }
         ib.current_scope_mut().exit();
-        ib.current_function_mut().finally_labels.pop();
+        let finally = ib.current_function_mut().enclosing_trys.pop().and_then(|tc| tc.finally);
 
-        if let Some((finally_id, finally)) = finally {
-            ib.current_function_mut()
-                .add_global_label(Label::Finally { finally_id });
+        if let Some((finally_label, finally)) = finally {
+            ib.current_function_mut().add_global_label(finally_label);
             ib.add_local_label(Label::TryEnd);
             ib.build_try_end();
 
-            ib.accept(*finally)?;
+            ib.accept(finally)?;
 
             ib.write_instr(Instruction::FinallyEnd);
             ib.writew(ib.current_function().try_depth);
         } else {
             ib.add_local_label(Label::TryEnd);
-            ib.build_try_end();
         }
 
         Ok(())
@@ -1949,8 +2569,21 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
         Ok(())
     }
 
-    fn visit_for_of_loop(&mut self, _span: Span, ForOfLoop { binding, expr, body }: ForOfLoop) -> Result<(), Error> {
-        self.visit_for_each_kinded_loop(ForEachLoopKind::ForOf, binding, expr, body)
+    fn visit_for_of_loop(
+        &mut self,
+        span: Span,
+        ForOfLoop {
+            binding,
+            expr,
+            body,
+            is_await,
+        }: ForOfLoop,
+    ) -> Result<(), Error> {
+        let kind = match is_await {
+            true => ForEachLoopKind::ForAwaitOf(span),
+            false => ForEachLoopKind::ForOf,
+        };
+        self.visit_for_each_kinded_loop(kind, binding, expr, body)
     }
 
     fn visit_for_in_loop(&mut self, _span: Span, ForInLoop { binding, expr, body }: ForInLoop) -> Result<(), Error> {
@@ -2006,6 +2639,15 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                 ib.accept_expr(expr)?;
                 ib.build_default_export();
             }
+            ExportKind::All(path) => {
+                let path_id = ib
+                    .current_function_mut()
+                    .cp
+                    .add(Constant::String(path))
+                    .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
+
+                ib.build_export_all(path_id);
+            }
             ExportKind::Named(names) => {
                 let mut it = Vec::with_capacity(names.len());
 
@@ -2038,7 +2680,7 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                     match var.binding.name {
                         VariableDeclarationName::Identifier(ident) => it.push(ident),
                         VariableDeclarationName::ArrayDestructuring { ref fields, rest } => {
-                            it.extend(fields.iter().copied());
+                            it.extend(fields.iter().copied().flatten());
                             it.extend(rest);
                         }
                         VariableDeclarationName::ObjectDestructuring { ref fields, rest } => {
@@ -2059,53 +2701,96 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
         Ok(())
     }
 
-    fn visit_break(&mut self, span: Span) -> Result<(), Error> {
+    fn visit_break(&mut self, span: Span, label: Option<Symbol>) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
 
-        if ib.current_function().enclosing_finally().is_some() {
-            unimplementedc!(span, "`break` in a try-finally block");
+        let mut breakables = ib.current_function().breakables.iter().rev();
+        let breakable = match label {
+            Some(label) => breakables
+                .find(|breakable| breakable.has_label(label))
+                .ok_or(Error::UndefinedLabel(span))?,
+            // Labeled statements that are not loops can only be exited using `break label`
+            None => breakables
+                .find(|breakable| !matches!(breakable, Breakable::Labeled { .. }))
+                .ok_or(Error::IllegalBreak(span))?,
         }
+        .clone();
 
-        let breakable = *ib
-            .current_function_mut()
-            .breakables
-            .last()
-            .ok_or(Error::IllegalBreak(span))?;
+        ib.leave_try_blocks(&breakable)?;
 
         match breakable {
-            Breakable::Loop { loop_id } => {
+            Breakable::Loop { loop_id, .. } => {
                 ib.build_jmp(Label::LoopEnd { loop_id }, false);
             }
-            Breakable::Switch { switch_id } => {
+            Breakable::Switch { switch_id, .. } => {
                 ib.build_jmp(Label::SwitchEnd { switch_id }, false);
             }
+            Breakable::Labeled { labeled_id, .. } => {
+                ib.build_jmp(Label::LabeledEnd { labeled_id }, false);
+            }
         }
         Ok(())
     }
 
-    fn visit_continue(&mut self, span: Span) -> Result<(), Error> {
+    fn visit_continue(&mut self, span: Span, label: Option<Symbol>) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
 
-        if ib.current_function().enclosing_finally().is_some() {
-            unimplementedc!(span, "`continue` in a try-finally block");
+        let mut breakables = ib.current_function().breakables.iter().rev();
+        let breakable = match label {
+            // The label must refer to a loop
+            Some(label) => breakables
+                .find(|breakable| breakable.has_label(label))
+                .ok_or(Error::UndefinedLabel(span))?,
+            // `continue` ignores any switch and labeled statements and targets the nearest enclosing loop
+            None => breakables
+                .find(|breakable| matches!(breakable, Breakable::Loop { .. }))
+                .ok_or(Error::IllegalBreak(span))?,
         }
+        .clone();
+        let Breakable::Loop { loop_id, .. } = breakable else {
+            return Err(Error::IllegalBreak(span));
+        };
+
+        ib.leave_try_blocks(&breakable)?;
+
+        ib.build_jmp(Label::LoopIncrement { loop_id }, false);
+        Ok(())
+    }
 
-        let breakable = *ib
-            .current_function_mut()
+    fn visit_labeled(&mut self, span: Span, label: Symbol, body: Box<Statement>) -> Result<(), Error> {
+        let mut ib = InstructionBuilder::new(self);
+
+        let is_duplicate = ib
+            .current_function()
             .breakables
-            .last()
-            .ok_or(Error::IllegalBreak(span))?;
+            .iter()
+            .any(|breakable| breakable.has_label(label))
+            || ib.current_function().pending_labels.contains(&label);
+        if is_duplicate {
+            return Err(Error::DuplicateLabel(span));
+        }
 
-        match breakable {
-            Breakable::Loop { loop_id } => {
-                ib.build_jmp(Label::LoopIncrement { loop_id }, false);
+        // Labels directly enclosing each other all refer to the innermost statement
+        let mut target = &body.kind;
+        while let StatementKind::Labeled(_, inner) = target {
+            target = &inner.kind;
+        }
+
+        match target {
+            StatementKind::Loop(_) | StatementKind::Switch(_) => {
+                // The loop or switch statement picks up the labels when it is added to the breakables
+                ib.current_function_mut().pending_labels.push(label);
+                ib.accept(*body)
             }
-            Breakable::Switch { .. } => {
-                // TODO: make it possible to use `continue` in loops even if its used in a switch
-                unimplementedc!(span, "`continue` used inside of a switch statement");
+            _ => {
+                let labeled_id = ib.current_function_mut().prepare_labeled(label);
+                let res = ib.accept(*body);
+                ib.current_function_mut()
+                    .add_global_label(Label::LabeledEnd { labeled_id });
+                ib.current_function_mut().exit_labeled();
+                res
             }
         }
-        Ok(())
     }
 
     fn visit_debugger(&mut self, _span: Span) -> Result<(), Error> {
@@ -2120,6 +2805,31 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
     fn visit_class_expr(&mut self, span: Span, class: Class) -> Result<(), Error> {
         let mut ib = InstructionBuilder::new(self);
 
+        // Every private name of this class gets a `#x` binding holding a fresh private symbol,
+        // which private member accesses and brand checks in the class body use as the property key
+        // const #x = <private symbol>;
+        let mut private_names = Vec::new();
+        for member in &class.members {
+            if let (true, ClassMemberKey::Named(name)) = (member.private, &member.key) {
+                if !private_names.contains(name) {
+                    private_names.push(*name);
+                }
+            }
+        }
+        let mut private_ids = Vec::with_capacity(private_names.len());
+        for name in private_names {
+            let id = ib
+                .current_scope_mut()
+                .add_shadowing_local(name, VariableDeclarationKind::Const)
+                .map_err(|_| Error::LocalLimitExceeded(span))?;
+            ib.build_constant(Constant::String(name))
+                .map_err(|_| Error::ConstantPoolLimitExceeded(span))?;
+            ib.build_private_symbol();
+            ib.build_local_store(AssignKind::Assignment, id, false);
+            ib.build_pop();
+            private_ids.push(id);
+        }
+
         let load_super_class = match class.extends.as_deref() {
             Some(expr) => {
                 let extend_id = ib
@@ -2137,10 +2847,37 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
                     }),
                 })?;
 
-                Some(Expr {
+                let load_super_class = Expr {
                     span: Span::COMPILER_GENERATED,
                     kind: ExprKind::Compiled(compile_local_load(extend_id, false)),
-                })
+                };
+
+                // if (typeof __super !== "function") throw new TypeError(...)
+                let throw = ib.throw_error_statement(sym::TypeError, "Class extends value is not a constructor");
+                ib.visit_if_statement(
+                    Span::COMPILER_GENERATED,
+                    IfStatement::new(
+                        Expr {
+                            span: Span::COMPILER_GENERATED,
+                            kind: ExprKind::binary(
+                                Expr {
+                                    span: Span::COMPILER_GENERATED,
+                                    kind: ExprKind::unary(TokenType::Typeof, load_super_class.clone()),
+                                },
+                                Expr {
+                                    span: Span::COMPILER_GENERATED,
+                                    kind: ExprKind::string_literal(sym::function),
+                                },
+                                TokenType::StrictInequality,
+                            ),
+                        },
+                        throw,
+                        Vec::new(),
+                        None,
+                    ),
+                )?;
+
+                Some((extend_id, load_super_class))
             }
             None => None,
         };
@@ -2159,12 +2896,54 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
 
         let (parameters, statements, id) = match constructor {
             Some(fun) => (fun.parameters, fun.statements, fun.id),
+            None if load_super_class.is_some() => {
+                // Derived classes without a constructor call the superclass constructor with all arguments
+                // constructor(...args) { super(...args) }
+                let parent = ib.current_function().id;
+                let super_call = Statement {
+                    span: Span::COMPILER_GENERATED,
+                    kind: StatementKind::Expression(Expr {
+                        span: Span::COMPILER_GENERATED,
+                        kind: ExprKind::function_call(
+                            Expr {
+                                span: Span::COMPILER_GENERATED,
+                                kind: ExprKind::identifier(sym::super_),
+                            },
+                            vec![CallArgumentKind::Spread(Expr {
+                                span: Span::COMPILER_GENERATED,
+                                kind: ExprKind::identifier(sym::implicit_constructor_args),
+                            })],
+                            false,
+                        ),
+                    }),
+                };
+                (
+                    vec![(
+                        Parameter::Spread(sym::implicit_constructor_args),
+                        None,
+                        None,
+                        Span::COMPILER_GENERATED,
+                    )],
+                    vec![super_call],
+                    ib.tcx.add_scope(Some(parent)),
+                )
+            }
             None => {
                 let parent = ib.current_function().id;
                 (Vec::new(), Vec::new(), ib.tcx.add_scope(Some(parent)))
             }
         };
 
+        // Methods, field initializers and the constructor of this class resolve `super.x` to the superclass
+        let super_binding = load_super_class.as_ref().map(|&(local_id, _)| SuperBinding {
+            func_id: ib.current_function().id,
+            local_id,
+            is_static: false,
+        });
+        let outer_super_binding = std::mem::replace(&mut ib.super_binding, super_binding);
+        let outer_derived_constructor =
+            std::mem::replace(&mut ib.derived_constructor, load_super_class.as_ref().map(|_| id));
+
         let fields = class
             .members
             .iter()
@@ -2178,6 +2957,8 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
             ty: FunctionKind::Function(Asyncness::No),
             ty_segment: None,
             constructor_initializers: Some(fields.clone().filter(|member| !member.static_).cloned().collect()),
+            // Class bodies are always strict
+            has_use_strict_directive: true,
         };
 
         ib.visit_expression_statement(Expr {
@@ -2216,19 +2997,28 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
             )
         });
 
-        let static_m = compile_class_members(&mut ib, span, methods.clone().filter(|method| method.static_).cloned())?;
-        ib.accept_expr(load_class_binding.clone())?;
-        ib.build_object_member_like_instruction(span, static_m, Instruction::AssignProperties)?;
-
-        let prototype_m = compile_class_members(&mut ib, span, methods.filter(|method| !method.static_).cloned())?;
+        let prototype_m =
+            compile_class_members(&mut ib, span, methods.clone().filter(|method| !method.static_).cloned())?;
         ib.accept_expr(class_prototype.clone())?;
         ib.build_object_member_like_instruction(span, prototype_m, Instruction::AssignProperties)?;
 
+        // In static members, `super` refers to the superclass itself
+        if let Some(binding) = &mut ib.super_binding {
+            binding.is_static = true;
+        }
+
+        let static_m = compile_class_members(&mut ib, span, methods.filter(|method| method.static_).cloned())?;
+        ib.accept_expr(load_class_binding.clone())?;
+        ib.build_object_member_like_instruction(span, static_m, Instruction::AssignProperties)?;
+
         let static_fields = compile_class_members(&mut ib, span, fields.filter(|member| member.static_).cloned())?;
         ib.accept_expr(load_class_binding.clone())?;
         ib.build_object_member_like_instruction(span, static_fields, Instruction::AssignProperties)?;
 
-        if let Some(super_id) = load_super_class {
+        ib.super_binding = outer_super_binding;
+        ib.derived_constructor = outer_derived_constructor;
+
+        if let Some((_, super_id)) = load_super_class {
             // Add the superclass' prototype to our prototype chain
             // Class.prototype.__proto__ = Superclass.prototype
 
@@ -2286,6 +3076,11 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
         // Load it one last time since the `class` expression ultimately should evaluate to that class
         ib.accept_expr(load_class_binding)?;
 
+        // Private names are only visible in the class body
+        for id in private_ids {
+            ib.current_scope_mut().hide_local(id);
+        }
+
         Ok(())
     }
 
@@ -2316,6 +3111,42 @@ impl<'interner> Visitor<Result<(), Error>> for FunctionCompiler<'interner> {
     }
 }
 
+/// Compiles an optional chain. `compile_chain` compiles the expression that makes up the chain.
+///
+/// If an optional part of the chain is nullish, the chain evaluates to `short_circuit_value`
+fn compile_optional_chain(
+    ib: &mut InstructionBuilder<'_, '_>,
+    span: Span,
+    short_circuit_value: Constant,
+    compile_chain: impl FnOnce(&mut InstructionBuilder<'_, '_>) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let chain_id = ib.current_function().optional_chain_counter;
+    ib.current_function_mut().optional_chain_counter += 1;
+
+    ib.current_function_mut().optional_chains.push(chain_id);
+    let res = compile_chain(ib);
+    ib.current_function_mut().optional_chains.pop();
+    res?;
+
+    ib.build_jmp(Label::OptionalChainEnd { chain_id }, false);
+
+    // An optional part was nullish: the nullish value is still on the stack and is replaced with the result
+    ib.current_function_mut()
+        .add_global_label(Label::OptionalChainShortCircuit { chain_id });
+    ib.build_pop();
+    match short_circuit_value {
+        Constant::Undefined => ib.build_undef(),
+        constant => ib
+            .build_constant(constant)
+            .map_err(|_| Error::ConstantPoolLimitExceeded(span))?,
+    }
+
+    ib.current_function_mut()
+        .add_global_label(Label::OptionalChainEnd { chain_id });
+
+    Ok(())
+}
+
 /// "Naive" switch lowering:
 /// ```js
 /// switch(1) {
@@ -2439,6 +3270,25 @@ fn compile_object_members(
     Ok(members)
 }
 
+/// Returns the binary operator that a compound assignment operator applies, e.g. `+` for `+=`
+fn compound_assignment_operator(operator: TokenType) -> Option<TokenType> {
+    match operator {
+        TokenType::AdditionAssignment => Some(TokenType::Plus),
+        TokenType::SubtractionAssignment => Some(TokenType::Minus),
+        TokenType::MultiplicationAssignment => Some(TokenType::Star),
+        TokenType::DivisionAssignment => Some(TokenType::Slash),
+        TokenType::RemainderAssignment => Some(TokenType::Remainder),
+        TokenType::ExponentiationAssignment => Some(TokenType::Exponentiation),
+        TokenType::LeftShiftAssignment => Some(TokenType::LeftShift),
+        TokenType::RightShiftAssignment => Some(TokenType::RightShift),
+        TokenType::UnsignedRightShiftAssignment => Some(TokenType::UnsignedRightShift),
+        TokenType::BitwiseAndAssignment => Some(TokenType::BitwiseAnd),
+        TokenType::BitwiseOrAssignment => Some(TokenType::BitwiseOr),
+        TokenType::BitwiseXorAssignment => Some(TokenType::BitwiseXor),
+        _ => None,
+    }
+}
+
 fn compile_class_members(
     ib: &mut InstructionBuilder<'_, '_>,
     span: Span,
@@ -2452,7 +3302,16 @@ fn compile_class_members(
     compile_object_members(
         ib,
         it.into_iter().map(|member| {
-            let (key, value) = match (member.key, member.value) {
+            // Private members are keyed by the private symbol bound to their name
+            let key = match member.key {
+                ClassMemberKey::Named(name) if member.private => ClassMemberKey::Computed(Expr {
+                    span,
+                    kind: ExprKind::identifier(name),
+                }),
+                key => key,
+            };
+
+            let (key, value) = match (key, member.value) {
                 (ClassMemberKey::Computed(key), ClassMemberValue::Method(value)) => {
                     (ObjectMemberKind::Dynamic(key), mk_fn(value))
                 }
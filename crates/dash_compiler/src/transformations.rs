@@ -18,8 +18,9 @@ pub fn ast_patch_implicit_return(ast: &mut Vec<Statement>) {
                 _ => unreachable!(),
             };
 
+            // The `return` itself is not something the user wrote, so it must not be reported as a top-level return
             ast.push(Statement {
-                span: expr.span,
+                span: Span::COMPILER_GENERATED,
                 kind: StatementKind::Return(ReturnStatement(expr)),
             });
         }
@@ -171,6 +172,9 @@ pub fn hoist_declarations(ast: &mut Vec<Statement>) {
             StatementKind::Loop(Loop::While(while_stmt)) => {
                 hoist_function_declaration(prepend_function_assigns, &mut while_stmt.body);
             }
+            StatementKind::Labeled(_, body) => {
+                hoist_function_declaration(prepend_function_assigns, body);
+            }
             StatementKind::Try(tc_stmt) => {
                 hoist_function_declaration(prepend_function_assigns, &mut tc_stmt.try_);
                 if let Some(catch) = &mut tc_stmt.catch {
@@ -208,6 +208,7 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                 Instruction::StaticPropAccess => {
                     let b = self.read_i16()?;
                     let _preserve_this = self.read()?;
+                    self.read_u16()?; // inline cache id, intentionally ignored
                     self.handle_op_instr("staticpropaccess", &[&self.display(&self.constants[b as usize])]);
                 }
                 Instruction::Ret => {
@@ -215,6 +216,10 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                     self.handle_opless_instr("ret")
                 }
                 Instruction::DelayedReturn => self.handle_opless_instr("delayedret"),
+                Instruction::ToString => self.handle_opless_instr("tostring"),
+                Instruction::TemplateObject => self.handle_opless_instr("templateobject"),
+                Instruction::PrivateSymbol => self.handle_opless_instr("privatesymbol"),
+                Instruction::SuperCall => self.handle_opless_instr("supercall"),
                 Instruction::Pos => self.handle_opless_instr("pos"),
                 Instruction::Neg => self.handle_opless_instr("neg"),
                 Instruction::TypeOfGlobalIdent => {
@@ -260,6 +265,7 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                             }
                             ObjectMemberKind::DynamicGetter => props.push(String::from("get <dynamic>")),
                             ObjectMemberKind::DynamicSetter => props.push(String::from("set <dynamic>")),
+                            ObjectMemberKind::Prototype => props.push(String::from("__proto__")),
                         }
                     }
                     let props = props.iter().map(|v| v as &dyn fmt::Display).collect::<Vec<_>>();
@@ -303,6 +309,10 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                 }
                 Instruction::ExportDefault => todo!(),
                 Instruction::ExportNamed => todo!(),
+                Instruction::ExportAll => {
+                    let _path_id = self.read_i16()?;
+                    self.handle_opless_instr("exportall")
+                }
                 Instruction::Debugger => self.handle_opless_instr("debugger"),
                 Instruction::Global => self.handle_opless_instr("global"),
                 Instruction::Super => self.handle_opless_instr("super"),
@@ -389,6 +399,7 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                 }
                 Instruction::CallSymbolIterator => self.handle_opless_instr("@@iterator"),
                 Instruction::CallForInIterator => self.handle_opless_instr("@@forInIterator"),
+                Instruction::CallAsyncIterator => self.handle_opless_instr("@@asyncIterator"),
                 Instruction::DeletePropertyStatic => self.handle_incw_op_instr("deletepropertystatic")?,
                 Instruction::DeletePropertyDynamic => self.handle_opless_instr("deletepropertydynamic"),
                 Instruction::ObjDestruct => {
@@ -400,6 +411,7 @@ impl<'interner, 'buf> FunctionDecompiler<'interner, 'buf> {
                     self.handle_op_map_instr("objdestruct", &[("count", &count)])
                 }
                 Instruction::ArrayDestruct => {
+                    self.read_u16()?; // discard rest id
                     let count = self.read_u16()?;
                     for _ in 0..count {
                         self.read_u16()?; // discard var id
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 use std::ops::Range;
 
 use dash_middle::interner::{sym, StringInterner, Symbol};
+pub use dash_middle::lexer::token::CommentKind;
 use dash_middle::lexer::token::{as_token, Token, TokenType};
 use dash_middle::parser::error::Error;
 use dash_middle::sourcemap::Span;
@@ -22,15 +23,8 @@ pub struct Lexer<'a, 'interner> {
     start: usize,
     line_idx: usize,
     template_literal_depths_stack: Vec<usize>,
-}
-
-/// Represents a comment
-#[derive(Debug)]
-pub enum CommentKind {
-    /// A multiline comment: /* */
-    Multiline,
-    /// A singleline comment: //
-    Singleline,
+    /// Whether comments and whitespaces are emitted as tokens
+    include_trivia: bool,
 }
 
 /// A lexer node (either a token or an error)
@@ -56,6 +50,7 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
             template_literal_depths_stack: Vec::new(),
             errors: Vec::new(),
             tokens: Vec::new(),
+            include_trivia: false,
         }
     }
 
@@ -474,20 +469,7 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
 
     /// Iterates through the input string and yields the next node
     pub fn scan_next(&mut self) -> Option<()> {
-        self.skip_whitespaces();
-        while self.current() == Some(b'/') {
-            let index_before_skip = self.idx;
-            self.skip_comments();
-
-            // We need to manually break out of the loop if the index didn't change
-            // This is the case when visiting a single slash
-            if self.idx == index_before_skip {
-                break;
-            }
-
-            self.skip_whitespaces();
-        }
-        self.skip_whitespaces();
+        self.skip_trivia();
         self.start = self.idx;
 
         let cur = self.next_char()?;
@@ -668,7 +650,7 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
                     TokenType::Yield,
                 ];
 
-                match self.tokens.last() {
+                match self.tokens.iter().rev().find(|token| !token.ty.is_trivia()) {
                     Some(token) if PRECEDING_TOKENS.contains(&token.ty) => self.read_regex_literal(),
                     None => self.read_regex_literal(),
                     _ => self.create_contextified_conditional_token(
@@ -729,6 +711,34 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
         Some(())
     }
 
+    /// Skips whitespaces and comments, and emits them as tokens if trivia is requested
+    fn skip_trivia(&mut self) {
+        loop {
+            self.start = self.idx;
+
+            self.skip_whitespaces();
+            if self.idx != self.start {
+                self.create_trivia_token(TokenType::Whitespace);
+                continue;
+            }
+
+            let kind = match (self.current(), self.peek()) {
+                (Some(b'/'), Some(b'/')) => CommentKind::Singleline,
+                (Some(b'/'), Some(b'*')) => CommentKind::Multiline,
+                // Either not a comment, or a single slash
+                _ => return,
+            };
+            self.skip_comments();
+            self.create_trivia_token(TokenType::Comment(kind));
+        }
+    }
+
+    fn create_trivia_token(&mut self, ty: TokenType) {
+        if self.include_trivia {
+            self.create_contextified_token(ty);
+        }
+    }
+
     /// Skips any meaningless whitespaces
     fn skip_whitespaces(&mut self) {
         while !self.is_eof() {
@@ -806,6 +816,15 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
         }
     }
 
+    /// Drives this lexer to completion and returns all tokens along with their spans, e.g. for syntax highlighting.
+    ///
+    /// Unlike [`Lexer::scan_all`], which produces the tokens for the parser, this can also include comments
+    /// and whitespaces as [`TokenType::Comment`] and [`TokenType::Whitespace`] tokens if `include_trivia` is true.
+    pub fn tokenize(mut self, include_trivia: bool) -> Result<Vec<Token>, Vec<Error>> {
+        self.include_trivia = include_trivia;
+        self.scan_all()
+    }
+
     /// Drives this lexer to completion
     ///
     /// Calling this function will exhaust the lexer and return all nodes
@@ -820,3 +839,72 @@ impl<'a, 'interner> Lexer<'a, 'interner> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dash_middle::interner::StringInterner;
+    use dash_middle::lexer::token::{CommentKind, TokenType};
+
+    use super::Lexer;
+
+    fn tokenize(source: &str, include_trivia: bool) -> Vec<(TokenType, &str)> {
+        let mut interner = StringInterner::new();
+        Lexer::new(&mut interner, source)
+            .tokenize(include_trivia)
+            .unwrap()
+            .into_iter()
+            .map(|token| (token.ty, &source[token.span.lo as usize..token.span.hi as usize]))
+            .collect()
+    }
+
+    #[test]
+    fn tokenize_spans() {
+        let tokens = tokenize("let x = f(1);", false);
+        let types: Vec<_> = tokens.iter().map(|(ty, _)| *ty).collect();
+        let lexemes: Vec<_> = tokens.iter().map(|(_, lexeme)| *lexeme).collect();
+
+        assert!(matches!(
+            types[..],
+            [
+                TokenType::Let,
+                TokenType::Identifier(_),
+                TokenType::Assignment,
+                TokenType::Identifier(_),
+                TokenType::LeftParen,
+                TokenType::NumberDec(_),
+                TokenType::RightParen,
+                TokenType::Semicolon
+            ]
+        ));
+        assert_eq!(lexemes, ["let", "x", "=", "f", "(", "1", ")", ";"]);
+    }
+
+    #[test]
+    fn tokenize_trivia() {
+        let source = "// a\nreturn /* b */ x / 2;";
+        let tokens = tokenize(source, true);
+        let lexemes: Vec<_> = tokens.iter().map(|(_, lexeme)| *lexeme).collect();
+
+        assert!(matches!(
+            tokens[..5],
+            [
+                (TokenType::Comment(CommentKind::Singleline), _),
+                (TokenType::Whitespace, _),
+                (TokenType::Return, _),
+                (TokenType::Whitespace, _),
+                (TokenType::Comment(CommentKind::Multiline), _),
+            ]
+        ));
+        assert_eq!(
+            lexemes,
+            ["// a", "\n", "return", " ", "/* b */", " ", "x", " ", "/", " ", "2", ";"]
+        );
+
+        // Trivia is skipped by default, and does not affect whether a `/` starts a regex literal
+        assert_eq!(tokenize(source, false).len(), 5);
+        assert!(matches!(
+            tokenize("return /* a */ /b/", true)[..],
+            [.., (TokenType::RegexLiteral { .. }, "/b/")]
+        ));
+    }
+}
@@ -27,6 +27,16 @@ pub type JitFunction = unsafe extern "C" fn(
     *mut u64, // out pointer for the IP after exiting
 );
 
+/// A function that applies a JIT compiled function body to every element of an array of numbers.
+///
+/// The function is called once per element, with the element as its first parameter
+/// and the index as its second one, and the return value of every call is written to the output array.
+pub type NumericMapFunction = unsafe extern "C" fn(
+    *const f64, // input elements
+    *mut f64,   // output elements, must be the same length as the input
+    u64,        // number of elements
+);
+
 fn value_ty_in_context(cx: &llvm::Context, ee: &llvm::ExecutionEngine) -> llvm::Ty {
     let mut elements = [
         // Discriminant
@@ -51,6 +61,12 @@ fn function_type(cx: &llvm::Context, ee: &llvm::ExecutionEngine) -> llvm::Ty {
     cx.function_ty(&ret, &mut args)
 }
 
+fn numeric_map_function_type(cx: &llvm::Context) -> llvm::Ty {
+    let mut args = [cx.pointer_ty(&cx.f64_ty()), cx.pointer_ty(&cx.f64_ty()), cx.i64_ty()];
+    let ret = cx.void_ty();
+    cx.function_ty(&ret, &mut args)
+}
+
 /// Recursively registers all reachable basic blocks
 /// (i.e. actioned successor blocks)
 fn register_llvm_bbs(
@@ -106,7 +122,28 @@ impl JitConstant {
 }
 
 pub trait CodegenQuery {
-    fn get_constant(&self, cid: u16) -> JitConstant;
+    /// Returns the value of a constant, or `None` if it cannot be used in JIT compiled code
+    fn get_constant(&self, cid: u16) -> Option<JitConstant>;
+}
+
+/// The kind of function that is being compiled
+#[derive(Debug, Clone, Copy)]
+pub enum CodegenKind {
+    /// A loop trace, which operates on the locals on the VM stack and exits back to the interpreter at side exits
+    Trace,
+    /// A function body that is applied to every element of an array of numbers, see [`NumericMapFunction`]
+    NumericMap {
+        /// The number of parameters of the function
+        params: u16,
+    },
+}
+
+/// The loop over the elements in a [`CodegenKind::NumericMap`] function
+pub struct MapLoop {
+    /// Space for the index of the current element
+    pub index: llvm::Value,
+    /// The block that checks the index and loads the next element
+    pub header: llvm::BasicBlock,
 }
 
 pub struct CodegenCtxt<'a, 'q, Q> {
@@ -128,16 +165,28 @@ pub struct CodegenCtxt<'a, 'q, Q> {
     pub setup_block: llvm::BasicBlock,
     pub exit_block: llvm::BasicBlock,
     pub exit_guards: Vec<(usize, llvm::BasicBlock)>,
+    pub kind: CodegenKind,
+    pub map_loop: Option<MapLoop>,
 }
 
 impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
-    pub fn new(ty_map: &'q TypeMap, bb_map: &'q BasicBlockMap, bytecode: &'a [u8], query: &'q mut Q) -> Self {
+    pub fn new(
+        ty_map: &'q TypeMap,
+        bb_map: &'q BasicBlockMap,
+        bytecode: &'a [u8],
+        query: &'q mut Q,
+        kind: CodegenKind,
+    ) -> Self {
         let mut llcx = llvm::Context::new();
         let module = llcx.create_module();
         let ee = module.create_execution_engine();
         let pm = llvm::PassManager::new(LLVMCodeGenOptLevel::LLVMCodeGenLevelAggressive);
         let value_ty = value_ty_in_context(&llcx, &ee);
-        let function = module.create_c_function(&function_type(&llcx, &ee));
+        let function_ty = match kind {
+            CodegenKind::Trace => function_type(&llcx, &ee),
+            CodegenKind::NumericMap { .. } => numeric_map_function_type(&llcx),
+        };
+        let function = module.create_c_function(&function_ty);
         let locals = HashMap::new();
         let mut llvm_bbs = HashMap::new();
         let builder = llcx.create_builder();
@@ -165,6 +214,8 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
             exit_block,
             exit_guards,
             bytecode,
+            kind,
+            map_loop: None,
         }
     }
 
@@ -179,7 +230,41 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
 
     fn store_local(&self, id: u16, value: &Value) -> Value {
         let (dest, _) = &self.locals[&id];
-        self.builder.build_store(value, dest)
+        // Type inference allows storing integers in locals that were widened to f64
+        let value = match (&self.ty_map[&id], value.ty_kind()) {
+            (Type::F64, LLVMTypeKind::LLVMIntegerTypeKind) => self.cast_mir(value, &Type::I64, &Type::F64),
+            _ => value.clone(),
+        };
+        self.builder.build_store(&value, dest)
+    }
+
+    /// Pops two operands and pushes the result of `op`.
+    /// If one operand is an integer and the other one a float, the integer is converted to a float first.
+    fn numeric_binop(&self, stack: &mut ValueStack, op: impl Fn(&llvm::Builder, &Value, &Value) -> Value) {
+        stack.binop(|a, b| {
+            let (a, b) = match (a.ty_kind(), b.ty_kind()) {
+                (LLVMTypeKind::LLVMIntegerTypeKind, LLVMTypeKind::LLVMDoubleTypeKind) => {
+                    (self.cast_mir(&a, &Type::I64, &Type::F64), b)
+                }
+                (LLVMTypeKind::LLVMDoubleTypeKind, LLVMTypeKind::LLVMIntegerTypeKind) => {
+                    (a, self.cast_mir(&b, &Type::I64, &Type::F64))
+                }
+                _ => (a, b),
+            };
+            op(&self.builder, &a, &b)
+        });
+    }
+
+    /// Pops two operands, converts integers to floats and pushes the result of `op`.
+    /// Used for operations that always produce a float, like division, even if both operands are integers.
+    fn float_binop(&self, stack: &mut ValueStack, op: impl Fn(&llvm::Builder, &Value, &Value) -> Value) {
+        stack.binop(|a, b| {
+            let to_float = |v: Value| match v.ty_kind() {
+                LLVMTypeKind::LLVMIntegerTypeKind => self.cast_mir(&v, &Type::I64, &Type::F64),
+                _ => v,
+            };
+            op(&self.builder, &to_float(a), &to_float(b))
+        });
     }
 
     fn cast_mir(&self, value: &Value, from: &Type, to: &Type) -> Value {
@@ -289,6 +374,91 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
         self.builder.build_retvoid();
     }
 
+    /// Compiles the setup block and the loop over the elements of a numeric map function.
+    ///
+    /// For every element, the element and its index are stored in the first two parameters
+    /// before jumping to the function body. This function should be called before compiling other parts.
+    pub fn compile_map_setup_block(&mut self, params: u16) {
+        self.builder.position_at_end(&self.setup_block);
+
+        for (&id, ty) in self.ty_map.iter() {
+            let space = self.alloca_local(ty);
+            self.locals.insert(id, (space, self.llcx.mir_ty_to_llvm_ty(ty)));
+        }
+
+        let index = self.builder.build_alloca(&self.llcx.i64_ty());
+        self.builder.build_store(&self.llcx.const_i64(0), &index);
+
+        let header = self.llcx.append_basic_block(&self.function, cstr!("header"));
+        let element_block = self.llcx.append_basic_block(&self.function, cstr!("element"));
+        self.builder.build_br(&header);
+
+        self.builder.position_at_end(&header);
+        let current = self.builder.build_load(&self.llcx.i64_ty(), &index);
+        let len = self.function.get_param(2);
+        let has_next = self.builder.build_lt(&current, &len);
+        self.builder.build_condbr(&has_next, &element_block, &self.exit_block);
+
+        self.builder.position_at_end(&element_block);
+        let input = self.function.get_param(0);
+        let element_ptr = self
+            .builder
+            .build_gep(&self.llcx.f64_ty(), &input, &mut [current.clone()]);
+        let element = self.builder.build_load(&self.llcx.f64_ty(), &element_ptr);
+
+        // Parameters that are never used do not have a type and don't need to be set
+        if params > 0 && self.ty_map.contains_key(&0) {
+            self.store_local(0, &element);
+        }
+        if params > 1 && self.ty_map.contains_key(&1) {
+            self.store_local(1, &current);
+        }
+
+        self.builder.build_br(&self.llvm_bbs[&0]);
+        self.map_loop = Some(MapLoop { index, header });
+    }
+
+    /// Compiles the exit block of a numeric map function, which is reached once all elements have been mapped.
+    pub fn compile_map_exit_block(&mut self) {
+        self.builder.position_at_end(&self.exit_block);
+        self.builder.build_retvoid();
+    }
+
+    /// Writes the return value of a numeric map function to the output array and continues with the next element
+    fn compile_map_return(&mut self, value: Value) -> Result<(), Error> {
+        let value = match value.ty_kind() {
+            LLVMTypeKind::LLVMDoubleTypeKind => value,
+            LLVMTypeKind::LLVMIntegerTypeKind if value.ty().int_width() == 64 => {
+                self.cast_mir(&value, &Type::I64, &Type::F64)
+            }
+            _ => return Err(Error::NonNumericReturn),
+        };
+
+        let map_loop = self.map_loop.as_ref().expect("map loop was not set up");
+        let current = self.builder.build_load(&self.llcx.i64_ty(), &map_loop.index);
+        let output = self.function.get_param(1);
+        let dest = self
+            .builder
+            .build_gep(&self.llcx.f64_ty(), &output, &mut [current.clone()]);
+        self.builder.build_store(&value, &dest);
+
+        let next = self.builder.build_add(&current, &self.llcx.const_i64(1));
+        self.builder.build_store(&next, &map_loop.index);
+        self.builder.build_br(&map_loop.header);
+        Ok(())
+    }
+
+    /// Terminates basic blocks that were registered but never compiled,
+    /// e.g. the target of a jump that directly follows a return
+    fn terminate_uncompiled_bbs(&mut self) {
+        for (bbk, bb) in &self.llvm_bbs {
+            if !self.bbs_visited.contains(bbk) {
+                self.builder.position_at_end(bb);
+                self.builder.build_unreachable();
+            }
+        }
+    }
+
     pub fn compile_bb(&mut self, mut stack: ValueStack, bbk: BasicBlockKey) -> Result<(), Error> {
         if self.bbs_visited.contains(&bbk) {
             return Ok(());
@@ -307,17 +477,21 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
 
         while let Some((index, instr)) = dcx.next_instruction() {
             match instr {
-                Instruction::Add => stack.binop(|a, b| self.builder.build_add(&a, &b)),
-                Instruction::Sub => stack.binop(|a, b| self.builder.build_sub(&a, &b)),
-                Instruction::Mul => stack.binop(|a, b| self.builder.build_mul(&a, &b)),
-                Instruction::Div => stack.binop(|a, b| self.builder.build_div(&a, &b)),
-                Instruction::Rem => stack.binop(|a, b| self.builder.build_rem(&a, &b)),
-                Instruction::Lt => stack.binop(|a, b| self.builder.build_lt(&a, &b)),
-                Instruction::Gt => stack.binop(|a, b| self.builder.build_gt(&a, &b)),
-                Instruction::Le => stack.binop(|a, b| self.builder.build_le(&a, &b)),
-                Instruction::Ge => stack.binop(|a, b| self.builder.build_ge(&a, &b)),
-                Instruction::Eq => stack.binop(|a, b| self.builder.build_eq(&a, &b)),
-                Instruction::Ne => stack.binop(|a, b| self.builder.build_ne(&a, &b)),
+                Instruction::Add => self.numeric_binop(&mut stack, llvm::Builder::build_add),
+                Instruction::Sub => self.numeric_binop(&mut stack, llvm::Builder::build_sub),
+                Instruction::Mul => self.numeric_binop(&mut stack, llvm::Builder::build_mul),
+                Instruction::Div => self.float_binop(&mut stack, llvm::Builder::build_div),
+                Instruction::Rem => self.float_binop(&mut stack, llvm::Builder::build_rem),
+                Instruction::Lt => self.numeric_binop(&mut stack, llvm::Builder::build_lt),
+                Instruction::Gt => self.numeric_binop(&mut stack, llvm::Builder::build_gt),
+                Instruction::Le => self.numeric_binop(&mut stack, llvm::Builder::build_le),
+                Instruction::Ge => self.numeric_binop(&mut stack, llvm::Builder::build_ge),
+                Instruction::Eq => self.numeric_binop(&mut stack, llvm::Builder::build_eq),
+                Instruction::Ne => self.numeric_binop(&mut stack, llvm::Builder::build_ne),
+                Instruction::Neg => {
+                    let value = stack.pop();
+                    stack.push(self.builder.build_neg(&value));
+                }
                 Instruction::LdLocal => {
                     let id = dcx.next_byte();
                     let val = self.load_local(id.into());
@@ -334,7 +508,10 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
                 }
                 Instruction::Constant => {
                     let cid = dcx.next_byte();
-                    let constant = self.query.get_constant(cid.into());
+                    let constant = self
+                        .query
+                        .get_constant(cid.into())
+                        .ok_or(TcfgError::UnsupportedConstantType { index: cid.into() })?;
                     stack.push(constant.to_llvm_value(&self.llcx));
                 }
                 Instruction::Pop => drop(stack.pop()),
@@ -345,7 +522,7 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
                     };
                     let llbb = &self.llvm_bbs[target];
                     self.builder.build_br(llbb);
-                    self.compile_bb(stack.clone(), *target);
+                    self.compile_bb(stack.clone(), *target)?;
 
                     return Ok(());
                 }
@@ -380,7 +557,7 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
                     let (true_ip, false_ip) = match instr {
                         Instruction::JmpTrueNP | Instruction::JmpTrueP => (true_ip, false_ip),
                         Instruction::JmpFalseNP | Instruction::JmpFalseP => (false_ip, true_ip),
-                        _ => todo!(),
+                        _ => return Err(TcfgError::UnsupportedInstruction { instr }.into()),
                     };
                     let llbb = self.llvm_bbs[&bbk].clone();
 
@@ -389,22 +566,22 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
                             let true_bb = &self.llvm_bbs[&true_ip];
                             let false_bb = &self.llvm_bbs[&false_ip];
                             self.builder.build_condbr(&cond, true_bb, false_bb);
-                            self.compile_bb(stack.clone(), true_ip);
-                            self.compile_bb(stack.clone(), false_ip);
+                            self.compile_bb(stack.clone(), true_ip)?;
+                            self.compile_bb(stack.clone(), false_ip)?;
                         }
                         ConditionalBranchAction::NotTaken => {
                             let bb = &self.llvm_bbs[&true_ip];
                             self.exit_guards.push((false_ip, llbb));
 
                             self.builder.build_condbr(&cond, bb, &self.exit_block);
-                            self.compile_bb(stack.clone(), true_ip);
+                            self.compile_bb(stack.clone(), true_ip)?;
                         }
                         ConditionalBranchAction::Taken => {
                             let bb = &self.llvm_bbs[&false_ip];
                             self.exit_guards.push((true_ip, llbb));
 
                             self.builder.build_condbr(&cond, &self.exit_block, bb);
-                            self.compile_bb(stack.clone(), false_ip);
+                            self.compile_bb(stack.clone(), false_ip)?;
                         }
                     }
 
@@ -414,23 +591,17 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
                     let op = IntrinsicOperation::from_repr(dcx.next_byte()).unwrap();
 
                     match op {
-                        IntrinsicOperation::AddNumLR => stack.binop(|a, b| self.builder.build_add(&a, &b)),
-                        IntrinsicOperation::SubNumLR => stack.binop(|a, b| self.builder.build_sub(&a, &b)),
-                        IntrinsicOperation::MulNumLR => stack.binop(|a, b| self.builder.build_mul(&a, &b)),
-                        IntrinsicOperation::DivNumLR => stack.binop(|a, b| self.builder.build_div(&a, &b)),
-                        IntrinsicOperation::RemNumLR => stack.binop(|a, b| self.builder.build_rem(&a, &b)),
-                        IntrinsicOperation::GtNumLR => stack.binop(|a, b| self.builder.build_gt(&a, &b)),
-                        IntrinsicOperation::GeNumLR => stack.binop(|a, b| self.builder.build_ge(&a, &b)),
-                        IntrinsicOperation::LtNumLR => stack.binop(|a, b| self.builder.build_lt(&a, &b)),
-                        IntrinsicOperation::LeNumLR => stack.binop(|a, b| self.builder.build_le(&a, &b)),
-                        IntrinsicOperation::EqNumLR => stack.binop(|a, b| self.builder.build_eq(&a, &b)),
-                        IntrinsicOperation::NeNumLR => stack.binop(|a, b| self.builder.build_ne(&a, &b)),
-                        IntrinsicOperation::BitOrNumLR => todo!(),
-                        IntrinsicOperation::BitXorNumLR => todo!(),
-                        IntrinsicOperation::BitAndNumLR => todo!(),
-                        IntrinsicOperation::BitShlNumLR => todo!(),
-                        IntrinsicOperation::BitShrNumLR => todo!(),
-                        IntrinsicOperation::BitUshrNumLR => todo!(),
+                        IntrinsicOperation::AddNumLR => self.numeric_binop(&mut stack, llvm::Builder::build_add),
+                        IntrinsicOperation::SubNumLR => self.numeric_binop(&mut stack, llvm::Builder::build_sub),
+                        IntrinsicOperation::MulNumLR => self.numeric_binop(&mut stack, llvm::Builder::build_mul),
+                        IntrinsicOperation::DivNumLR => self.float_binop(&mut stack, llvm::Builder::build_div),
+                        IntrinsicOperation::RemNumLR => self.float_binop(&mut stack, llvm::Builder::build_rem),
+                        IntrinsicOperation::GtNumLR => self.numeric_binop(&mut stack, llvm::Builder::build_gt),
+                        IntrinsicOperation::GeNumLR => self.numeric_binop(&mut stack, llvm::Builder::build_ge),
+                        IntrinsicOperation::LtNumLR => self.numeric_binop(&mut stack, llvm::Builder::build_lt),
+                        IntrinsicOperation::LeNumLR => self.numeric_binop(&mut stack, llvm::Builder::build_le),
+                        IntrinsicOperation::EqNumLR => self.numeric_binop(&mut stack, llvm::Builder::build_eq),
+                        IntrinsicOperation::NeNumLR => self.numeric_binop(&mut stack, llvm::Builder::build_ne),
                         IntrinsicOperation::LtNumLConstR
                         | IntrinsicOperation::LeNumLConstR
                         | IntrinsicOperation::GtNumLConstR
@@ -523,8 +694,17 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
                     }
                 }
                 Instruction::Ret => {
-                    let _value = stack.pop();
+                    let value = stack.pop();
                     let _c = dcx.next_wide();
+
+                    match self.kind {
+                        // Returning from a trace would need to unwind the frame in the VM
+                        CodegenKind::Trace => return Err(TcfgError::UnsupportedInstruction { instr }.into()),
+                        CodegenKind::NumericMap { .. } => self.compile_map_return(value)?,
+                    }
+
+                    // Anything after a return in the same basic block is unreachable
+                    return Ok(());
                 }
                 _ => return Err(TcfgError::UnsupportedInstruction { instr }.into()),
             }
@@ -539,7 +719,7 @@ impl<'a, 'q, Q: CodegenQuery> CodegenCtxt<'a, 'q, Q> {
             };
             let next_bb = &self.llvm_bbs[&target];
             self.builder.build_br(next_bb);
-            self.compile_bb(stack, target);
+            self.compile_bb(stack, target)?;
         }
 
         Ok(())
@@ -595,12 +775,33 @@ pub fn compile_typed_cfg<Q: CodegenQuery>(
     tcfg: &TypedCfg,
     query: &mut Q,
 ) -> Result<JitFunction, Error> {
-    let mut codegenctxt = CodegenCtxt::new(&tcfg.ty_map, &tcfg.bb_map, bytecode, query);
+    let mut codegenctxt = CodegenCtxt::new(&tcfg.ty_map, &tcfg.bb_map, bytecode, query, CodegenKind::Trace);
     codegenctxt.compile_setup_block();
     codegenctxt.compile_bb(ValueStack::default(), 0)?;
     codegenctxt.compile_exit_block();
+    codegenctxt.terminate_uncompiled_bbs();
     codegenctxt.module.verify();
     codegenctxt.module.run_pass_manager(&codegenctxt.pm);
     let func = codegenctxt.ee.compile_fn(codegenctxt.function.name());
     Ok(func)
 }
+
+/// Compiles the body of a function that takes up to two numbers (an element and its index)
+/// and returns a number into a [`NumericMapFunction`] that calls it for every element of an array.
+pub fn compile_numeric_map<Q: CodegenQuery>(
+    bytecode: &[u8],
+    tcfg: &TypedCfg,
+    params: u16,
+    query: &mut Q,
+) -> Result<NumericMapFunction, Error> {
+    let kind = CodegenKind::NumericMap { params };
+    let mut codegenctxt = CodegenCtxt::new(&tcfg.ty_map, &tcfg.bb_map, bytecode, query, kind);
+    codegenctxt.compile_map_setup_block(params);
+    codegenctxt.compile_bb(ValueStack::default(), 0)?;
+    codegenctxt.compile_map_exit_block();
+    codegenctxt.terminate_uncompiled_bbs();
+    codegenctxt.module.verify();
+    codegenctxt.module.run_pass_manager(&codegenctxt.pm);
+    let func = codegenctxt.ee.compile_numeric_map_fn(codegenctxt.function.name());
+    Ok(func)
+}
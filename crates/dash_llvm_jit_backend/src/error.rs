@@ -5,4 +5,6 @@ use thiserror::Error;
 pub enum Error {
     #[error("{_0}")]
     TypedCfg(#[from] dash_typed_cfg::error::Error),
+    #[error("function does not return a number")]
+    NonNumericReturn,
 }
@@ -1,8 +1,9 @@
 use llvm_sys::core::{
     LLVMAddIncoming, LLVMBuildAdd, LLVMBuildAlloca, LLVMBuildBitCast, LLVMBuildBr, LLVMBuildCondBr, LLVMBuildFAdd,
-    LLVMBuildFCmp, LLVMBuildFDiv, LLVMBuildFMul, LLVMBuildFPToSI, LLVMBuildFRem, LLVMBuildFSub, LLVMBuildGEP2,
-    LLVMBuildICmp, LLVMBuildLoad2, LLVMBuildMul, LLVMBuildPhi, LLVMBuildRetVoid, LLVMBuildSDiv, LLVMBuildSExt,
-    LLVMBuildSIToFP, LLVMBuildSRem, LLVMBuildStore, LLVMBuildSub, LLVMBuildTrunc, LLVMPositionBuilderAtEnd,
+    LLVMBuildFCmp, LLVMBuildFDiv, LLVMBuildFMul, LLVMBuildFNeg, LLVMBuildFPToSI, LLVMBuildFRem, LLVMBuildFSub,
+    LLVMBuildGEP2, LLVMBuildICmp, LLVMBuildLoad2, LLVMBuildMul, LLVMBuildNeg, LLVMBuildPhi, LLVMBuildRetVoid,
+    LLVMBuildSDiv, LLVMBuildSExt, LLVMBuildSIToFP, LLVMBuildSRem, LLVMBuildStore, LLVMBuildSub, LLVMBuildTrunc,
+    LLVMBuildUnreachable, LLVMPositionBuilderAtEnd,
 };
 use llvm_sys::prelude::LLVMBuilderRef;
 use llvm_sys::{LLVMIntPredicate, LLVMRealPredicate, LLVMTypeKind};
@@ -92,6 +93,17 @@ impl Builder {
         })
     }
 
+    pub fn build_neg(&self, value: &Value) -> Value {
+        let ty = value.ty_kind();
+        Value(unsafe {
+            match ty {
+                LLVMTypeKind::LLVMIntegerTypeKind => LLVMBuildNeg(self.0, value.0, cstrp!("ineg")),
+                LLVMTypeKind::LLVMDoubleTypeKind => LLVMBuildFNeg(self.0, value.0, cstrp!("fneg")),
+                _ => panic!("unsupported type"),
+            }
+        })
+    }
+
     pub fn build_cmp(&self, a: &Value, b: &Value, pred: Predicate) -> Value {
         let ty = a.ty_kind();
         Value(unsafe {
@@ -211,6 +223,10 @@ impl Builder {
         Value(unsafe { LLVMBuildRetVoid(self.0) })
     }
 
+    pub fn build_unreachable(&self) -> Value {
+        Value(unsafe { LLVMBuildUnreachable(self.0) })
+    }
+
     pub fn build_br(&self, to: &BasicBlock) -> Value {
         Value(unsafe { LLVMBuildBr(self.0, to.0) })
     }
@@ -5,6 +5,7 @@ use llvm_sys::execution_engine::{LLVMExecutionEngineRef, LLVMGetExecutionEngineT
 use llvm_sys::target::LLVMSizeOfTypeInBits;
 
 use super::Ty;
+use crate::codegen::NumericMapFunction;
 
 pub type JitFunction = unsafe extern "C" fn(
     *mut (),  // stack pointer
@@ -23,12 +24,17 @@ impl ExecutionEngine {
         }
     }
 
+    fn function_address(&self, name: &CStr) -> u64 {
+        let addr = unsafe { LLVMGetFunctionAddress(self.0, name.as_ptr()) };
+        assert!(addr != 0);
+        addr
+    }
+
     pub fn compile_fn(&self, name: &CStr) -> JitFunction {
-        unsafe {
-            let addr = LLVMGetFunctionAddress(self.0, name.as_ptr());
-            assert!(addr != 0);
+        unsafe { mem::transmute::<u64, JitFunction>(self.function_address(name)) }
+    }
 
-            mem::transmute::<u64, JitFunction>(addr)
-        }
+    pub fn compile_numeric_map_fn(&self, name: &CStr) -> NumericMapFunction {
+        unsafe { mem::transmute::<u64, NumericMapFunction>(self.function_address(name)) }
     }
 }
@@ -1,6 +1,6 @@
 use std::slice;
 
-use llvm_sys::core::LLVMGetTypeKind;
+use llvm_sys::core::{LLVMGetIntTypeWidth, LLVMGetTypeKind};
 use llvm_sys::prelude::LLVMTypeRef;
 use llvm_sys::LLVMTypeKind;
 
@@ -17,4 +17,10 @@ impl Ty {
     pub fn kind(&self) -> LLVMTypeKind {
         unsafe { LLVMGetTypeKind(self.0) }
     }
+
+    /// Returns the number of bits of an integer type
+    pub fn int_width(&self) -> u32 {
+        debug_assert_eq!(self.kind(), LLVMTypeKind::LLVMIntegerTypeKind);
+        unsafe { LLVMGetIntTypeWidth(self.0) }
+    }
 }
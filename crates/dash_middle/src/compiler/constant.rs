@@ -1,7 +1,7 @@
 use core::fmt;
 use std::cell::{Cell, RefCell};
 use std::collections::HashSet;
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
 
 #[cfg(feature = "format")]
@@ -84,6 +84,49 @@ pub struct Function {
     pub source: Rc<str>,
     pub debug_symbols: DebugSymbols,
     pub references_arguments: bool,
+    /// Inline caches of the static property accesses in this function, indexed by the operand of the instruction
+    #[cfg_attr(feature = "format", serde(with = "empty_inline_caches"))]
+    pub inline_caches: Box<[Cell<InlineCache>]>,
+    /// Whether this function is in strict mode, either because of a `"use strict"` directive or because it is
+    /// nested in strict mode code
+    pub is_strict: bool,
+}
+
+/// A monomorphic inline cache for a single property access site.
+///
+/// This only describes where a property was found given the shape of the receiver.
+/// Shape ids are never reused for a different layout, so a stale entry simply won't match anymore.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct InlineCache {
+    /// The shape id of the receiver, or 0 if this cache is empty
+    pub shape: u64,
+    /// Address of the prototype that holds the property, or 0 if it is an own property of the receiver
+    pub holder: usize,
+    /// The shape id of the holder, if the property was found on the prototype
+    pub holder_shape: u64,
+    /// The slot of the property in the object that holds it
+    pub slot: u32,
+}
+
+/// Inline caches refer to shapes and objects that only exist in the VM that filled them,
+/// so only their number is serialized and they are empty again after deserializing.
+#[cfg(feature = "format")]
+mod empty_inline_caches {
+    use std::cell::Cell;
+
+    use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::InlineCache;
+
+    pub fn serialize<S: Serializer>(caches: &[Cell<InlineCache>], serializer: S) -> Result<S::Ok, S::Error> {
+        let count = u16::try_from(caches.len()).map_err(ser::Error::custom)?;
+        count.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<[Cell<InlineCache>]>, D::Error> {
+        let count = u16::deserialize(deserializer)?;
+        Ok((0..count).map(|_| Cell::default()).collect())
+    }
 }
 
 impl Function {
@@ -189,3 +232,9 @@ impl Deref for ConstantPool {
         &self.constants
     }
 }
+
+impl DerefMut for ConstantPool {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.constants
+    }
+}
@@ -1,26 +1,103 @@
-use super::CompileResult;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
-const BYTECODE_VERSION: u32 = 4;
+use crate::interner::{StringInterner, Symbol};
 
-pub fn serialize(cr: CompileResult) -> bincode::Result<Vec<u8>> {
-    let mut buffer = BYTECODE_VERSION.to_le_bytes().to_vec();
-    bincode::serialize_into(&mut buffer, &cr)?;
-    Ok(buffer)
-}
+use super::constant::Constant;
+use super::CompileResult;
+
+const BYTECODE_VERSION: u32 = 5;
 
 #[derive(Debug)]
 pub enum DeserializeError {
     Bincode(bincode::Error),
     InvalidVersion,
+    /// The bytecode refers to a symbol that is missing from its string table
+    UnknownSymbol,
+}
+
+/// Calls `f` with every symbol referenced by the given constants, including the ones in nested functions
+fn visit_symbols(constants: &[Constant], f: &mut impl FnMut(Symbol)) {
+    for constant in constants {
+        match constant {
+            Constant::String(sym) | Constant::Identifier(sym) => f(*sym),
+            Constant::Regex(regex) => f(regex.2),
+            Constant::Function(fun) => {
+                if let Some(name) = fun.name {
+                    f(name);
+                }
+                visit_symbols(&fun.constants, f);
+            }
+            Constant::Number(_) | Constant::Boolean(_) | Constant::Null | Constant::Undefined => {}
+        }
+    }
+}
+
+/// Replaces every symbol referenced by the given constants with the one it maps to
+fn remap_symbols(constants: &mut [Constant], map: &HashMap<Symbol, Symbol>) -> Result<(), DeserializeError> {
+    let remap = |sym: &mut Symbol| -> Result<(), DeserializeError> {
+        *sym = *map.get(sym).ok_or(DeserializeError::UnknownSymbol)?;
+        Ok(())
+    };
+
+    for constant in constants {
+        match constant {
+            Constant::String(sym) | Constant::Identifier(sym) => remap(sym)?,
+            Constant::Regex(regex) => remap(&mut regex.2)?,
+            Constant::Function(fun) => {
+                let fun = Rc::make_mut(fun);
+                if let Some(name) = &mut fun.name {
+                    remap(name)?;
+                }
+                remap_symbols(&mut fun.constants, map)?;
+            }
+            Constant::Number(_) | Constant::Boolean(_) | Constant::Null | Constant::Undefined => {}
+        }
+    }
+
+    Ok(())
 }
 
-pub fn deserialize(buf: &[u8]) -> Result<CompileResult, DeserializeError> {
-    let bytes = buf.try_into().map_err(|_| DeserializeError::InvalidVersion)?;
-    let version = u32::from_le_bytes(bytes);
+impl CompileResult {
+    /// Serializes the compiled code, so that it can be cached and later loaded with [`CompileResult::deserialize`].
+    ///
+    /// Symbols are only meaningful together with the interner they were created in,
+    /// so the strings of all referenced symbols are stored alongside the bytecode.
+    pub fn serialize(&self, interner: &StringInterner) -> bincode::Result<Vec<u8>> {
+        let mut seen = HashSet::new();
+        let mut symbols = Vec::new();
+        visit_symbols(&self.cp, &mut |sym| {
+            if seen.insert(sym) {
+                symbols.push(sym);
+            }
+        });
+        let strings = symbols
+            .into_iter()
+            .map(|sym| (sym, interner.resolve(sym)))
+            .collect::<Vec<_>>();
 
-    if version != BYTECODE_VERSION {
-        return Err(DeserializeError::InvalidVersion);
+        let mut buffer = BYTECODE_VERSION.to_le_bytes().to_vec();
+        bincode::serialize_into(&mut buffer, &(strings, self))?;
+        Ok(buffer)
     }
 
-    bincode::deserialize(&buf[4..]).map_err(DeserializeError::Bincode)
+    /// Deserializes code that was serialized with [`CompileResult::serialize`],
+    /// interning all of its strings in the given interner.
+    pub fn deserialize(buf: &[u8], interner: &mut StringInterner) -> Result<Self, DeserializeError> {
+        let (version, buf) = buf.split_first_chunk().ok_or(DeserializeError::InvalidVersion)?;
+        if u32::from_le_bytes(*version) != BYTECODE_VERSION {
+            return Err(DeserializeError::InvalidVersion);
+        }
+
+        let (strings, mut cr): (Vec<(Symbol, String)>, CompileResult) =
+            bincode::deserialize(buf).map_err(DeserializeError::Bincode)?;
+
+        let map = strings
+            .into_iter()
+            .map(|(sym, string)| (sym, interner.intern(string)))
+            .collect::<HashMap<_, _>>();
+        remap_symbols(&mut cr.cp, &map)?;
+
+        Ok(cr)
+    }
 }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use strum_macros::FromRepr;
 
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, FromRepr)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromRepr)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Instruction {
     Add,
@@ -80,6 +80,8 @@ pub enum Instruction {
     ImportStatic,
     ExportDefault,
     ExportNamed,
+    /// Re-exports all named exports of another module
+    ExportAll,
     Debugger,
     Global,
     Super,
@@ -91,12 +93,24 @@ pub enum Instruction {
     IntrinsicOp,
     CallSymbolIterator,
     CallForInIterator,
+    /// Calls `@@asyncIterator` of a value, or `@@iterator` if it does not have one
+    CallAsyncIterator,
     DeletePropertyStatic,
     DeletePropertyDynamic,
     ObjDestruct,
     ArrayDestruct,
     AssignProperties,
     DelayedReturn,
+    /// Converts the value on the stack to a string using the ToString operation
+    ToString,
+    /// Finishes a tagged template object: attaches the raw strings array (top of stack)
+    /// to the cooked strings array below it and freezes both
+    TemplateObject,
+    /// Creates a new private symbol for a private name, using the string on the stack as its description
+    PrivateSymbol,
+    /// Calls the superclass constructor with the superclass and an array of arguments on the stack,
+    /// and binds the constructed object as `this`
+    SuperCall,
     // Nop exists solely for the sake of benchmarking the raw throughput of the VM dispatch loop
     Nop,
 }
@@ -1 +1,227 @@
-pub trait InstructionIter {}
+use super::instruction::{Instruction, IntrinsicOperation};
+use super::{ExportPropertyKind, ObjectMemberKind};
+
+/// An iterator over the instructions of a bytecode buffer, yielding the offset of each instruction and its opcode.
+///
+/// Operands are skipped over. Iteration stops early if an invalid opcode or truncated operands are encountered.
+#[derive(Debug, Clone)]
+pub struct InstructionIter<'a> {
+    buf: &'a [u8],
+    ip: usize,
+    /// Offsets of catch blocks seen so far, which start with the local id of the catch binding rather than an instruction
+    catch_ips: Vec<usize>,
+}
+
+impl<'a> InstructionIter<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            buf,
+            ip: 0,
+            catch_ips: Vec::new(),
+        }
+    }
+
+    fn u8_at(&self, offset: usize) -> Option<u8> {
+        self.buf.get(self.ip + offset).copied()
+    }
+
+    fn u16_at(&self, offset: usize) -> Option<u16> {
+        let bytes = self.buf.get(self.ip + offset..self.ip + offset + 2)?;
+        Some(u16::from_ne_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Computes the length of the operands of the instruction that starts at the current ip
+    fn operand_len(&self, instr: Instruction) -> Option<usize> {
+        let len = match instr {
+            Instruction::Add
+            | Instruction::Sub
+            | Instruction::Mul
+            | Instruction::Div
+            | Instruction::Rem
+            | Instruction::Pow
+            | Instruction::Gt
+            | Instruction::Ge
+            | Instruction::Lt
+            | Instruction::Le
+            | Instruction::Eq
+            | Instruction::Ne
+            | Instruction::Pop
+            | Instruction::Pos
+            | Instruction::Neg
+            | Instruction::TypeOf
+            | Instruction::BitNot
+            | Instruction::Not
+            | Instruction::This
+            | Instruction::StrictEq
+            | Instruction::StrictNe
+            | Instruction::TryEnd
+            | Instruction::Throw
+            | Instruction::Yield
+            | Instruction::BitOr
+            | Instruction::BitXor
+            | Instruction::BitAnd
+            | Instruction::BitShl
+            | Instruction::BitShr
+            | Instruction::BitUshr
+            | Instruction::ObjIn
+            | Instruction::InstanceOf
+            | Instruction::ImportDyn
+            | Instruction::ExportDefault
+            | Instruction::Debugger
+            | Instruction::Global
+            | Instruction::Super
+            | Instruction::Arguments
+            | Instruction::Undef
+            | Instruction::Await
+            | Instruction::Nan
+            | Instruction::Infinity
+            | Instruction::CallSymbolIterator
+            | Instruction::CallForInIterator
+            | Instruction::CallAsyncIterator
+            | Instruction::DeletePropertyDynamic
+            | Instruction::DelayedReturn
+            | Instruction::ToString
+            | Instruction::TemplateObject
+            | Instruction::PrivateSymbol
+            | Instruction::SuperCall
+            | Instruction::Nop => 0,
+            Instruction::LdLocal
+            | Instruction::Constant
+            | Instruction::DynamicPropAccess
+            | Instruction::DynamicPropAssign
+            | Instruction::LdLocalExt => 1,
+            Instruction::LdLocalW
+            | Instruction::LdLocalExtW
+            | Instruction::LdGlobal
+            | Instruction::ConstantW
+            | Instruction::TypeOfGlobalIdent
+            | Instruction::StoreLocal
+            | Instruction::StoreGlobal
+            | Instruction::StoreLocalExt
+            | Instruction::Ret
+            | Instruction::FinallyEnd
+            | Instruction::ArrayLit
+            | Instruction::ExportAll
+            | Instruction::DeletePropertyStatic
+            | Instruction::Jmp
+            | Instruction::JmpFalseP
+            | Instruction::JmpFalseNP
+            | Instruction::JmpTrueP
+            | Instruction::JmpTrueNP
+            | Instruction::JmpNullishP
+            | Instruction::JmpNullishNP
+            | Instruction::JmpUndefinedP
+            | Instruction::JmpUndefinedNP => 2,
+            Instruction::StoreLocalW
+            | Instruction::StoreGlobalW
+            | Instruction::StoreLocalExtW
+            | Instruction::StaticPropAssign => 3,
+            Instruction::ArrayLitW => 4,
+            Instruction::StaticPropAccess | Instruction::ImportStatic => 5,
+            Instruction::Call => {
+                // Metadata, followed by the number of spread arguments and their indices
+                2 + usize::from(self.u8_at(2)?)
+            }
+            Instruction::Try => {
+                // Each of the catch and finally blocks has a flag, followed by an offset if present
+                let catch_len = 1 + 2 * usize::from(self.u8_at(1)?);
+                catch_len + 1 + 2 * usize::from(self.u8_at(1 + catch_len)?)
+            }
+            Instruction::ObjLit | Instruction::AssignProperties => {
+                let count = self.u16_at(1)?;
+                let mut len = 2;
+                for _ in 0..count {
+                    len += match ObjectMemberKind::from_repr(self.u8_at(1 + len)?)? {
+                        ObjectMemberKind::Static | ObjectMemberKind::Getter | ObjectMemberKind::Setter => 3,
+                        ObjectMemberKind::Dynamic
+                        | ObjectMemberKind::DynamicGetter
+                        | ObjectMemberKind::DynamicSetter
+                        | ObjectMemberKind::Spread
+                        | ObjectMemberKind::Prototype => 1,
+                    };
+                }
+                len
+            }
+            Instruction::ExportNamed => {
+                let count = self.u16_at(1)?;
+                let mut len = 2;
+                for _ in 0..count {
+                    len += match ExportPropertyKind::from_repr(self.u8_at(1 + len)?)? {
+                        ExportPropertyKind::Local => 5,
+                        ExportPropertyKind::Global => 3,
+                    };
+                }
+                len
+            }
+            // The rest local id and the field count, followed by the local and property name of every field
+            Instruction::ObjDestruct => 4 + 4 * usize::from(self.u16_at(3)?),
+            // The rest local id and the field count, followed by the local of every element
+            Instruction::ArrayDestruct => 4 + 2 * usize::from(self.u16_at(3)?),
+            Instruction::IntrinsicOp => {
+                1 + match IntrinsicOperation::from_repr(self.u8_at(1)?)? {
+                    IntrinsicOperation::AddNumLR
+                    | IntrinsicOperation::SubNumLR
+                    | IntrinsicOperation::MulNumLR
+                    | IntrinsicOperation::DivNumLR
+                    | IntrinsicOperation::RemNumLR
+                    | IntrinsicOperation::PowNumLR
+                    | IntrinsicOperation::GtNumLR
+                    | IntrinsicOperation::GeNumLR
+                    | IntrinsicOperation::LtNumLR
+                    | IntrinsicOperation::LeNumLR
+                    | IntrinsicOperation::EqNumLR
+                    | IntrinsicOperation::NeNumLR
+                    | IntrinsicOperation::BitOrNumLR
+                    | IntrinsicOperation::BitXorNumLR
+                    | IntrinsicOperation::BitAndNumLR
+                    | IntrinsicOperation::BitShlNumLR
+                    | IntrinsicOperation::BitShrNumLR
+                    | IntrinsicOperation::BitUshrNumLR => 0,
+                    IntrinsicOperation::GtNumLConstR32
+                    | IntrinsicOperation::GeNumLConstR32
+                    | IntrinsicOperation::LtNumLConstR32
+                    | IntrinsicOperation::LeNumLConstR32 => 4,
+                    // Local ids, constant operands and argument counts of math intrinsics
+                    _ => 1,
+                }
+            }
+        };
+
+        Some(len)
+    }
+}
+
+impl Iterator for InstructionIter<'_> {
+    type Item = (usize, Instruction);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(index) = self.catch_ips.iter().position(|&ip| ip == self.ip) {
+            self.catch_ips.swap_remove(index);
+            self.ip += 2;
+        }
+
+        let ip = self.ip;
+        let instr = Instruction::from_repr(self.u8_at(0)?);
+        let next_ip = instr
+            .and_then(|instr| self.operand_len(instr))
+            .map(|len| ip + 1 + len)
+            .filter(|&next_ip| next_ip <= self.buf.len());
+
+        match (instr, next_ip) {
+            (Some(instr), Some(next_ip)) => {
+                if instr == Instruction::Try && self.u8_at(1) == Some(1) {
+                    // The catch offset is relative to the end of the offset itself
+                    let offset = self.u16_at(2)?;
+                    self.catch_ips.push(ip + 4 + usize::from(offset));
+                }
+                self.ip = next_ip;
+                Some((ip, instr))
+            }
+            _ => {
+                // Invalid or truncated bytecode, stop iterating
+                self.ip = self.buf.len();
+                None
+            }
+        }
+    }
+}
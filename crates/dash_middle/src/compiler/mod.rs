@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use strum_macros::FromRepr;
@@ -5,8 +6,10 @@ use strum_macros::FromRepr;
 use crate::parser;
 use crate::sourcemap::Span;
 
-use self::constant::ConstantPool;
+use self::constant::{Constant, ConstantPool};
 use self::external::External;
+use self::instruction::Instruction;
+use self::instruction_iter::InstructionIter;
 
 #[cfg(feature = "format")]
 use serde::{Deserialize, Serialize};
@@ -27,6 +30,59 @@ pub struct CompileResult {
     pub externals: Vec<External>,
     pub debug_symbols: DebugSymbols,
     pub source: Rc<str>,
+    /// The number of inline caches used by the top level code
+    pub inline_caches: usize,
+    /// Whether the top level code is in strict mode
+    pub is_strict: bool,
+}
+
+impl CompileResult {
+    /// Calls `f` with the bytecode and constants of the top level code and of every function nested in it
+    fn for_each_function(&self, mut f: impl FnMut(&[u8], &[Constant])) {
+        fn visit_nested(constants: &[Constant], f: &mut impl FnMut(&[u8], &[Constant])) {
+            for constant in constants {
+                if let Constant::Function(fun) = constant {
+                    fun.buffer.with(|buffer| f(buffer, &fun.constants));
+                    visit_nested(&fun.constants, f);
+                }
+            }
+        }
+
+        f(&self.instructions, &self.cp);
+        visit_nested(&self.cp, &mut f);
+    }
+
+    /// Returns the total size of the bytecode in bytes, including nested functions
+    pub fn bytecode_size(&self) -> usize {
+        let mut size = 0;
+        self.for_each_function(|buffer, _| size += buffer.len());
+        size
+    }
+
+    /// Returns the total number of instructions, including nested functions
+    pub fn instruction_count(&self) -> usize {
+        let mut count = 0;
+        self.for_each_function(|buffer, _| count += InstructionIter::new(buffer).count());
+        count
+    }
+
+    /// Returns the total number of constants in the constant pools, including the ones of nested functions
+    pub fn constant_pool_size(&self) -> usize {
+        let mut size = 0;
+        self.for_each_function(|_, constants| size += constants.len());
+        size
+    }
+
+    /// Counts how often each opcode occurs in the bytecode, including nested functions
+    pub fn opcode_histogram(&self) -> HashMap<Instruction, usize> {
+        let mut histogram = HashMap::new();
+        self.for_each_function(|buffer, _| {
+            for (_, instr) in InstructionIter::new(buffer) {
+                *histogram.entry(instr).or_insert(0) += 1;
+            }
+        });
+        histogram
+    }
 }
 
 /// For error purposes, this contains source code snippets used to improve errors, e.g. `x is not a function`
@@ -132,6 +188,7 @@ pub enum ObjectMemberKind {
     Static,
     Dynamic,
     Spread,
+    Prototype,
 }
 
 use parser::expr::ObjectMemberKind as ParserObjectMemberKind;
@@ -146,6 +203,7 @@ impl From<&ParserObjectMemberKind> for ObjectMemberKind {
             ParserObjectMemberKind::Setter(..) => Self::Setter,
             ParserObjectMemberKind::Static(..) => Self::Static,
             ParserObjectMemberKind::Spread => Self::Spread,
+            ParserObjectMemberKind::Prototype => Self::Prototype,
         }
     }
 }
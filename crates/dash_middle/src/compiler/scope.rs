@@ -68,9 +68,11 @@ impl Scope {
     }
 
     pub fn find_local(&self, identifier: Symbol) -> Option<(u16, &ScopeLocal)> {
+        // Search backwards, so that shadowing locals take precedence
         self.locals
             .iter()
             .enumerate()
+            .rev()
             .find(|(_, l)| match l.binding() {
                 VariableBinding {
                     name: VariableDeclarationName::Identifier(name),
@@ -111,6 +113,30 @@ impl Scope {
         u16::try_from(self.locals.len() - 1).map_err(|_| LimitExceededError)
     }
 
+    /// Adds a local that shadows any existing local with the same name until it is hidden using [`Scope::hide_local`]
+    ///
+    /// This is used for bindings that are only visible in a part of the function,
+    /// such as the private names of a class or the parameter of a `catch` block.
+    pub fn add_shadowing_local(
+        &mut self,
+        name: Symbol,
+        kind: VariableDeclarationKind,
+    ) -> Result<u16, LimitExceededError> {
+        self.add_scope_local(ScopeLocal {
+            binding: VariableBinding {
+                name: VariableDeclarationName::Identifier(name),
+                kind,
+                ty: None,
+            },
+            inferred_type: RefCell::new(None),
+        })
+    }
+
+    /// Makes a local unnameable, once the part of the function it is visible in ends
+    pub fn hide_local(&mut self, id: u16) {
+        self.locals[usize::from(id)].binding.kind = VariableDeclarationKind::Unnameable;
+    }
+
     pub fn enter(&mut self) {
         self.depth += 1;
     }
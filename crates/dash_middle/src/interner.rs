@@ -70,6 +70,9 @@ pub mod sym {
                 for_of_iter,
                 for_of_gen_step,
                 switch_cond_desugar,
+                destructure_tmp,
+                compound_assignment_tmp,
+                template_object,
                 value,
                 done,
                 next,
@@ -84,6 +87,8 @@ pub mod sym {
                 expm1,
                 cbrt,
                 clz32,
+                imul,
+                fround,
                 atanh,
                 atan2,
                 round,
@@ -115,6 +120,7 @@ pub mod sym {
                 Error,
                 toString,
                 valueOf,
+                description,
                 object,
                 boolean,
                 number,
@@ -144,9 +150,11 @@ pub mod sym {
                 defineProperty,
                 defineProperties,
                 entries,
+                groupBy,
                 assign,
                 Object,
                 hasOwnProperty,
+                hasOwn,
                 tanh,
                 max,
                 min,
@@ -155,6 +163,7 @@ pub mod sym {
                 isFinite,
                 isNaN,
                 eval,
+                gc,
                 isSafeInteger,
                 EPSILON,
                 MAX_SAFE_INTEGER,
@@ -167,9 +176,11 @@ pub mod sym {
                 toFixed,
                 Boolean,
                 fromCharCode,
+                fromCodePoint,
                 String,
                 charAt,
                 charCodeAt,
+                codePointAt,
                 concat,
                 endsWith,
                 startsWith,
@@ -213,6 +224,8 @@ pub mod sym {
                 reduce,
                 find,
                 findIndex,
+                findLast,
+                findLastIndex,
                 flat,
                 forEach,
                 map,
@@ -236,6 +249,12 @@ pub mod sym {
                 JsSymbol: "Symbol",
                 ArrayBuffer,
                 byteLength,
+                TextEncoder,
+                TextDecoder,
+                encode,
+                decode,
+                encoding,
+                utf8: "utf-8",
                 Uint8Array,
                 Int8Array,
                 Uint16Array,
@@ -248,6 +267,13 @@ pub mod sym {
                 reject,
                 Promise,
                 then,
+                all,
+                race,
+                allSettled,
+                status,
+                reason,
+                fulfilled,
+                rejected,
                 Set,
                 add,
                 has,
@@ -257,13 +283,38 @@ pub mod sym {
                 RegExp,
                 test,
                 exec,
+                lastIndex,
                 now,
+                getTime,
+                getFullYear,
+                getMonth,
+                getDate,
+                getHours,
+                getMinutes,
+                getSeconds,
+                toISOString,
                 Date,
                 parse,
                 parseFloat,
                 parseInt,
+                btoa,
+                atob,
+                structuredClone,
+                queueMicrotask,
+                WeakRef,
+                deref,
+                WeakMap,
+                WeakSet,
+                FinalizationRegistry,
+                register,
+                unregister,
+                Reflect,
+                construct,
+                deleteProperty,
                 console,
                 JSON,
+                stringify,
+                toJSON,
                 isConcatSpreadable,
                 zero: "0",
                 one: "1",
@@ -272,7 +323,11 @@ pub mod sym {
                 isPrototypeOf,
                 arguments,
                 propertyIsEnumerable,
-                apply
+                apply,
+                use_strict: "use strict",
+                // Not a valid identifier, so that user code cannot refer to it
+                implicit_constructor_args: "<constructor args>",
+                raw
             }
         ]
     }
@@ -351,6 +351,23 @@ pub enum TokenType {
 
     #[display(fmt = "EOF")]
     Eof,
+
+    /// A comment, only emitted by the lexer if trivia is requested
+    #[display(fmt = "comment")]
+    Comment(CommentKind),
+
+    /// A sequence of whitespace characters, only emitted by the lexer if trivia is requested
+    #[display(fmt = "whitespace")]
+    Whitespace,
+}
+
+/// Represents a comment
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CommentKind {
+    /// A multiline comment: /* */
+    Multiline,
+    /// A singleline comment: //
+    Singleline,
 }
 
 /// Tokens that are used to assign
@@ -376,6 +393,11 @@ pub const ASSIGNMENT_TYPES: &[TokenType] = &[
 pub const VARIABLE_TYPES: &[TokenType] = &[TokenType::Var, TokenType::Let, TokenType::Const];
 
 impl TokenType {
+    /// Checks if this token is a comment or whitespace, which have no meaning to the parser
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, Self::Comment(_) | Self::Whitespace)
+    }
+
     /// Checks if this token is a variable kind
     pub fn is_variable(&self) -> bool {
         VARIABLE_TYPES.contains(self)
@@ -56,9 +56,127 @@ pub enum Error {
         await_expr: Span,
     },
     IllegalBreak(Span),
+    ReturnOutsideFunction(Span),
     MissingInitializerInDestructuring(Span),
     ArgumentsInRoot(Span),
     Unexpected(Span, &'static str),
+    /// An `import` or `export` declaration in code that is not parsed as a module
+    ModuleSyntaxInScript(Span),
+    DeleteOfIdentifierInStrictMode(Span),
+    DuplicateParameterInStrictMode(Span),
+    /// A label that is already used by an enclosing labeled statement
+    DuplicateLabel(Span),
+    /// A `break` or `continue` statement referring to a label that does not exist
+    UndefinedLabel(Span),
+    /// An object literal with more than one `__proto__: value` member
+    DuplicateProto(Span),
+}
+
+impl Error {
+    /// Returns the span of source code this error points to, if there is one
+    pub fn span(&self) -> Option<Span> {
+        match *self {
+            Self::UnknownCharacter(span, _)
+            | Self::InvalidEscapeSequence(span)
+            | Self::MultipleDefaultInSwitch(span)
+            | Self::ConstantPoolLimitExceeded(span)
+            | Self::LocalLimitExceeded(span)
+            | Self::IfBranchLimitExceeded(span)
+            | Self::SwitchCaseLimitExceeded(span)
+            | Self::ArrayLitLimitExceeded(span)
+            | Self::ObjectLitLimitExceeded(span)
+            | Self::ExportNameListLimitExceeded(span)
+            | Self::DestructureLimitExceeded(span)
+            | Self::ConstAssignment(span)
+            | Self::Unimplemented(span, _)
+            | Self::ParameterLimitExceeded(span)
+            | Self::YieldOutsideGenerator { yield_expr: span }
+            | Self::AwaitOutsideAsync { await_expr: span }
+            | Self::IllegalBreak(span)
+            | Self::ReturnOutsideFunction(span)
+            | Self::MissingInitializerInDestructuring(span)
+            | Self::ArgumentsInRoot(span)
+            | Self::Unexpected(span, _)
+            | Self::ModuleSyntaxInScript(span)
+            | Self::DeleteOfIdentifierInStrictMode(span)
+            | Self::DuplicateParameterInStrictMode(span)
+            | Self::DuplicateLabel(span)
+            | Self::UndefinedLabel(span)
+            | Self::DuplicateProto(span) => Some(span),
+            Self::UnknownToken(Token { span, .. })
+            | Self::UnexpectedToken(Token { span, .. }, _)
+            | Self::UnexpectedTokenMultiple(Token { span, .. }, _)
+            | Self::ParseIntError(Token { span, .. }, _)
+            | Self::InvalidAccessorParams {
+                token: Token { span, .. },
+                ..
+            }
+            | Self::MultipleRestInDestructuring(Token { span, .. })
+            | Self::RegexSyntaxError(Token { span, .. }, _)
+            | Self::IncompleteSpread(Token { span, .. }) => Some(span),
+            Self::UnexpectedEof => None,
+        }
+    }
+}
+
+impl Error {
+    /// Renders this error as a single line message for a JavaScript `SyntaxError`, including the line it occurred on,
+    /// e.g. "Unexpected token ')' at line 3, expected `;`".
+    pub fn syntax_error_message(&self, source: &str) -> String {
+        let mut message = match self {
+            Self::UnknownToken(token) | Self::UnexpectedToken(token, _) | Self::UnexpectedTokenMultiple(token, _) => {
+                format!("Unexpected token '{}'", token.span.res(source))
+            }
+            Self::UnexpectedEof => String::from("Unexpected end of input"),
+            _ => {
+                // Reuse the message of the diagnostic, which is the first line without the `error: ` prefix
+                let diagnostic = FormattableError {
+                    error: self,
+                    source,
+                    color: false,
+                }
+                .to_string();
+                let message = diagnostic.lines().next().unwrap_or_default();
+                let message = message.strip_prefix("error: ").unwrap_or(message);
+
+                let mut chars = message.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+        };
+
+        if let Some(span) = self.span().filter(|span| span.is_user_span()) {
+            let line = memchr::memchr_iter(b'\n', &source.as_bytes()[..span.lo as usize]).count() + 1;
+            let _ = write!(message, " at line {line}");
+        }
+
+        match self {
+            Self::UnexpectedToken(_, ty) => {
+                let _ = write!(message, ", expected `{}`", ty.fmt_for_expected_tys());
+            }
+            Self::UnexpectedTokenMultiple(_, [ty]) => {
+                let _ = write!(message, ", expected `{}`", ty.fmt_for_expected_tys());
+            }
+            Self::UnexpectedTokenMultiple(_, tys) => {
+                let _ = write!(message, ", expected one of: {}", expected_tys_list(tys));
+            }
+            _ => {}
+        }
+
+        message
+    }
+}
+
+fn expected_tys_list(tys: &[TokenType]) -> String {
+    tys.iter().fold(String::new(), |mut acc, ty| {
+        if !acc.is_empty() {
+            acc.push_str(", ");
+        }
+        let _ = write!(acc, "`{}`", ty.fmt_for_expected_tys());
+        acc
+    })
 }
 
 pub struct FormattableError<'a, 'buf> {
@@ -261,16 +379,7 @@ impl<'a, 'buf> fmt::Display for FormattableError<'a, 'buf> {
             Error::UnexpectedTokenMultiple(Token { span, .. }, tys) => {
                 diag.message("unexpected token");
                 diag.span_error(span, "");
-                diag.help(format!(
-                    "expected one of: {}",
-                    tys.iter().fold(String::new(), |mut acc, ty| {
-                        if !acc.is_empty() {
-                            acc.push_str(", ");
-                        }
-                        let _ = write!(acc, "`{}`", ty.fmt_for_expected_tys());
-                        acc
-                    })
-                ))
+                diag.help(format!("expected one of: {}", expected_tys_list(tys)))
             }
             Error::InvalidEscapeSequence(span) => {
                 diag.message("invalid escape sequence");
@@ -365,6 +474,10 @@ impl<'a, 'buf> fmt::Display for FormattableError<'a, 'buf> {
                 diag.message("`break` or `continue` statement outside of iteration statement encountered");
                 diag.span_error(span, "");
             }
+            Error::ReturnOutsideFunction(span) => {
+                diag.message("`return` statement outside of a function");
+                diag.span_error(span, "");
+            }
             Error::MissingInitializerInDestructuring(span) => {
                 diag.message("missing initializer in destructuring pattern");
                 diag.span_error(span, "consider adding an initializer to this variable declaration");
@@ -378,6 +491,31 @@ impl<'a, 'buf> fmt::Display for FormattableError<'a, 'buf> {
                 diag.message(format!("unexpected {descr}"));
                 diag.span_error(span, "");
             }
+            Error::ModuleSyntaxInScript(span) => {
+                diag.message("`import` and `export` declarations may only appear in modules");
+                diag.span_error(span, "");
+                diag.help("consider using a dynamic `import()` instead");
+            }
+            Error::DeleteOfIdentifierInStrictMode(span) => {
+                diag.message("deleting an unqualified identifier in strict mode");
+                diag.span_error(span, "");
+            }
+            Error::DuplicateParameterInStrictMode(span) => {
+                diag.message("duplicate parameter name not allowed in strict mode");
+                diag.span_error(span, "");
+            }
+            Error::DuplicateLabel(span) => {
+                diag.message("label has already been declared");
+                diag.span_error(span, "");
+            }
+            Error::UndefinedLabel(span) => {
+                diag.message("undefined label");
+                diag.span_error(span, "");
+            }
+            Error::DuplicateProto(span) => {
+                diag.message("duplicate __proto__ fields are not allowed in object literals");
+                diag.span_error(span, "");
+            }
         }
         fmt::Display::fmt(&diag, f)
     }
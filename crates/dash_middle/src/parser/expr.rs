@@ -53,6 +53,20 @@ pub enum ExprKind {
     Array(ArrayLiteral),
     /// An object literal expression
     Object(ObjectLiteral),
+    /// A template literal expression
+    Template(TemplateLiteral),
+    /// A tagged template expression, i.e. `` foo`bar${baz}` ``
+    #[display(fmt = "{}{}", "_0.tag", "_0.template")]
+    TaggedTemplate(TaggedTemplate),
+    /// An optional chain, i.e. `foo?.bar.baz`
+    ///
+    /// If any optional part of the chain is nullish, the remaining chain is skipped and the whole
+    /// expression evaluates to `undefined`
+    #[display(fmt = "{_0}")]
+    OptionalChain(Box<Expr>),
+    /// The target of an optional property access or call within an optional chain, i.e. `foo` in `foo?.bar`
+    #[display(fmt = "{_0}?.")]
+    Optional(Box<Expr>),
     /// Compiled bytecode
     #[display(fmt = "<compiled>")]
     Compiled(Vec<u8>),
@@ -212,6 +226,24 @@ impl ExprKind {
         Self::Compiled(c)
     }
 
+    /// Creates a tagged template expression
+    pub fn tagged_template(tag: Expr, template: TemplateLiteral) -> Self {
+        Self::TaggedTemplate(TaggedTemplate {
+            tag: Box::new(tag),
+            template,
+        })
+    }
+
+    /// Creates an optional chain expression
+    pub fn optional_chain(expr: Expr) -> Self {
+        Self::OptionalChain(Box::new(expr))
+    }
+
+    /// Creates the target of an optional property access or call
+    pub fn optional(expr: Expr) -> Self {
+        Self::Optional(Box::new(expr))
+    }
+
     /// Tries to convert an expression into a list of arrow function parameters
     ///
     /// We only know whether a value is an arrow function after parsing
@@ -279,6 +311,40 @@ impl fmt::Display for ArrayLiteral {
     }
 }
 
+/// A template literal expression (`` `foo${expr}bar` ``)
+///
+/// The strings and embedded expressions alternate, starting and ending with a (possibly empty) string,
+/// so there is always one more string than there are expressions
+#[derive(Debug, Clone)]
+pub struct TemplateLiteral {
+    /// The strings, with escape sequences processed
+    pub strings: Vec<Symbol>,
+    /// The strings as they appear in the source code, without processing escape sequences
+    pub raw_strings: Vec<Symbol>,
+    pub expressions: Vec<Expr>,
+}
+
+impl fmt::Display for TemplateLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`")?;
+        for (index, string) in self.raw_strings.iter().enumerate() {
+            if index > 0 {
+                write!(f, "${{{}}}", self.expressions[index - 1])?;
+            }
+            write!(f, "{string}")?;
+        }
+        write!(f, "`")
+    }
+}
+
+/// A tagged template expression (`` tag`foo${expr}bar` ``)
+#[derive(Debug, Clone)]
+pub struct TaggedTemplate {
+    /// The function that is called with the strings and values of the template
+    pub tag: Box<Expr>,
+    pub template: TemplateLiteral,
+}
+
 #[derive(Debug, Clone)]
 pub enum ObjectMemberKind {
     Getter(Symbol),
@@ -288,6 +354,8 @@ pub enum ObjectMemberKind {
     Static(Symbol),
     Spread,
     Dynamic(Expr),
+    /// A non-computed `__proto__: value` member, which sets the prototype of the object
+    Prototype,
 }
 
 impl fmt::Display for ObjectMemberKind {
@@ -300,6 +368,7 @@ impl fmt::Display for ObjectMemberKind {
             Self::Static(name) => write!(f, "{name}"),
             Self::Dynamic(expr) => write!(f, "[{expr}]"),
             Self::Spread => f.write_str("...<expression unavailable>"), // TODO: figure out a way to display it here
+            Self::Prototype => f.write_str("__proto__"),
         }
     }
 }
@@ -341,6 +410,9 @@ impl fmt::Display for PropertyAccessExpr {
 
         if self.computed {
             write!(f, "[{}]", self.property)?;
+        } else if let ExprKind::Optional(_) = self.target.kind {
+            // The `?.` is already part of the target
+            write!(f, "{}", self.property)?;
         } else {
             write!(f, ".{}", self.property)?;
         }
@@ -401,12 +473,49 @@ impl fmt::Display for FunctionCall {
 }
 
 /// The target of an assignment
-#[derive(Debug, Clone, Display)]
+#[derive(Debug, Clone)]
 pub enum AssignmentTarget {
     /// Assignment to an expression-place
     Expr(Box<Expr>),
     /// Assignment to a local id (i.e. previously allocated stack space)
     LocalId(u16),
+    /// Array destructuring assignment to existing places: `[a, b.c] = [1, 2]`
+    ///
+    /// Elided elements (`[a, , b]`) are `None`
+    ArrayDestructuring(Vec<Option<AssignmentTarget>>),
+    /// Object destructuring assignment to existing places: `({ a, b: c.d } = { a: 1, b: 2 })`
+    ObjectDestructuring(Vec<(Symbol, AssignmentTarget)>),
+}
+
+impl fmt::Display for AssignmentTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Expr(expr) => fmt::Display::fmt(expr, f),
+            Self::LocalId(id) => write!(f, "{id}"),
+            Self::ArrayDestructuring(fields) => {
+                write!(f, "[")?;
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    if let Some(field) = field {
+                        write!(f, "{field}")?;
+                    }
+                }
+                write!(f, "]")
+            }
+            Self::ObjectDestructuring(fields) => {
+                write!(f, "{{ ")?;
+                for (i, (name, target)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}: {target}")?;
+                }
+                write!(f, " }}")
+            }
+        }
+    }
 }
 
 impl AssignmentTarget {
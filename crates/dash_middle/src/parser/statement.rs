@@ -9,7 +9,7 @@ use crate::lexer::token::TokenType;
 use crate::sourcemap::Span;
 use crate::tree::TreeToken;
 
-use super::expr::{Expr, ExprKind};
+use super::expr::{Expr, ExprKind, LiteralExpr};
 use super::types::TypeSegment;
 
 /// A JavaScript statement
@@ -44,12 +44,15 @@ pub enum StatementKind {
     Class(Class),
     /// A switch statement
     Switch(SwitchStatement),
-    /// Continue loop statement
-    #[display(fmt = "continue;")]
-    Continue,
-    /// Break loop statement
-    #[display(fmt = "break;")]
-    Break,
+    /// Continue loop statement, with an optional label of the loop to continue
+    #[display(fmt = "continue{};", "_0.map(|label| format!(\" {label}\")).unwrap_or_default()")]
+    Continue(Option<Symbol>),
+    /// Break loop statement, with an optional label of the statement to break out of
+    #[display(fmt = "break{};", "_0.map(|label| format!(\" {label}\")).unwrap_or_default()")]
+    Break(Option<Symbol>),
+    /// A labeled statement, e.g. `outer: for (;;) {}`
+    #[display(fmt = "{_0}: {_1}")]
+    Labeled(Symbol, Box<Statement>),
     /// Debugger statement
     #[display(fmt = "debugger;")]
     Debugger,
@@ -147,6 +150,8 @@ pub enum ExportKind {
     Named(Vec<Symbol>),
     /// export let foo = "bar"
     NamedVar(VariableDeclarations),
+    /// export * from "foo"
+    All(Symbol),
 }
 
 impl fmt::Display for ExportKind {
@@ -163,6 +168,7 @@ impl fmt::Display for ExportKind {
                 fmt_list(f, &nv.0, ",")?;
                 write!(f, " }}")
             }
+            Self::All(path) => write!(f, "export * from \"{path}\""),
         }
     }
 }
@@ -179,21 +185,29 @@ impl ImportKind {
 }
 
 /// A catch statement
-#[derive(Debug, Clone, Display)]
-#[display(fmt = "catch ({}) {{ {} }}", "ident.unwrap_or(sym::empty)", "body")]
+#[derive(Debug, Clone)]
 pub struct Catch {
     /// The body of a catch statement
     pub body: Box<Statement>,
-    /// The identifier of the variable that receives the thrown error
-    pub ident: Option<Symbol>,
+    /// The binding that receives the thrown error, either an identifier or a destructuring pattern
+    pub binding: Option<VariableBinding>,
+}
+
+impl fmt::Display for Catch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.binding {
+            Some(binding) => write!(f, "catch ({}) {{ {} }}", binding.name, self.body),
+            None => write!(f, "catch {{ {} }}", self.body),
+        }
+    }
 }
 
 impl Catch {
     /// Creates a new catch statement
-    pub fn new(body: Statement, ident: Option<Symbol>) -> Self {
+    pub fn new(body: Statement, binding: Option<VariableBinding>) -> Self {
         Self {
             body: Box::new(body),
-            ident,
+            binding,
         }
     }
 }
@@ -293,8 +307,7 @@ impl DoWhileLoop {
 }
 
 /// A for..of loop
-#[derive(Debug, Clone, Display)]
-#[display(fmt = "for ({binding} of {expr}) {{ {body} }}")]
+#[derive(Debug, Clone)]
 pub struct ForOfLoop {
     /// The binding of this loop
     pub binding: VariableBinding,
@@ -302,6 +315,22 @@ pub struct ForOfLoop {
     pub expr: Expr,
     /// The body of this loop
     pub body: Box<Statement>,
+    /// Whether this is a `for await..of` loop
+    pub is_await: bool,
+}
+
+impl fmt::Display for ForOfLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            binding,
+            expr,
+            body,
+            is_await,
+        } = self;
+
+        let await_ = if *is_await { " await" } else { "" };
+        write!(f, "for{await_} ({binding} of {expr}) {{ {body} }}")
+    }
 }
 
 /// A for loop
@@ -392,9 +421,9 @@ pub enum FunctionKind {
     /// A normal function
     Function(Asyncness),
     /// A generator function
-    Generator,
+    Generator(Asyncness),
     /// An arrow function
-    Arrow,
+    Arrow(Asyncness),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -466,6 +495,8 @@ pub struct FunctionDeclaration {
         Option<Expr>,
         // Type segment
         Option<TypeSegment>,
+        // Span of the parameter name
+        Span,
     )>,
     /// Function body
     pub statements: Vec<Statement>,
@@ -475,6 +506,10 @@ pub struct FunctionDeclaration {
     /// If this function is a desugared class constructor,
     /// then this contains all the instance members that need to be initialized.
     pub constructor_initializers: Option<Vec<ClassMember>>,
+    /// Whether the function body starts with a `"use strict"` directive.
+    ///
+    /// This is determined when parsing, as the directive itself may be removed as dead code later.
+    pub has_use_strict_directive: bool,
 }
 
 impl fmt::Display for FunctionDeclaration {
@@ -489,7 +524,7 @@ impl fmt::Display for FunctionDeclaration {
 
         write!(f, "(")?;
 
-        for (id, (param, default, ty)) in self.parameters.iter().enumerate() {
+        for (id, (param, default, ty, _)) in self.parameters.iter().enumerate() {
             if id > 0 {
                 write!(f, ",")?;
             }
@@ -534,7 +569,7 @@ impl FunctionDeclaration {
     pub fn new(
         name: Option<Symbol>,
         id: FuncId,
-        parameters: Vec<(Parameter, Option<Expr>, Option<TypeSegment>)>,
+        parameters: Vec<(Parameter, Option<Expr>, Option<TypeSegment>, Span)>,
         statements: Vec<Statement>,
         ty: FunctionKind,
         ty_segment: Option<TypeSegment>,
@@ -544,6 +579,7 @@ impl FunctionDeclaration {
             id,
             name,
             parameters,
+            has_use_strict_directive: has_use_strict_directive(&statements),
             statements,
             ty,
             ty_segment,
@@ -552,6 +588,20 @@ impl FunctionDeclaration {
     }
 }
 
+/// Checks if the directive prologue of a function body or script contains a `"use strict"` directive
+pub fn has_use_strict_directive(statements: &[Statement]) -> bool {
+    statements
+        .iter()
+        .map_while(|stmt| match &stmt.kind {
+            StatementKind::Expression(Expr {
+                kind: ExprKind::Literal(LiteralExpr::String(directive)),
+                ..
+            }) => Some(*directive),
+            _ => None,
+        })
+        .any(|directive| directive == sym::use_strict)
+}
+
 /// A block statement, primarily used to enter a new scope
 #[derive(Debug, Clone)]
 pub struct BlockStatement(pub Vec<Statement>);
@@ -679,8 +729,8 @@ pub enum VariableDeclarationName {
     },
     /// Array destructuring: [ a ] = [ 1 ]
     ArrayDestructuring {
-        /// Elements to destructure
-        fields: Vec<Symbol>,
+        /// Elements to destructure, or `None` for holes, as in `[, b] = [1, 2]`
+        fields: Vec<Option<Symbol>>,
         /// The rest element, if present
         rest: Option<Symbol>,
     },
@@ -719,7 +769,9 @@ impl fmt::Display for VariableDeclarationName {
                         write!(f, ", ")?;
                     }
 
-                    write!(f, "{name}")?;
+                    if let Some(name) = name {
+                        write!(f, "{name}")?;
+                    }
                 }
 
                 if let Some(rest) = rest {
@@ -1,7 +1,7 @@
 use crate::interner::Symbol;
 use crate::parser::expr::{
     ArrayLiteral, AssignmentExpr, BinaryExpr, ConditionalExpr, Expr, ExprKind, FunctionCall, GroupingExpr, LiteralExpr,
-    ObjectLiteral, Postfix, Prefix, PropertyAccessExpr, Seq, UnaryExpr,
+    ObjectLiteral, Postfix, Prefix, PropertyAccessExpr, Seq, TaggedTemplate, TemplateLiteral, UnaryExpr,
 };
 use crate::parser::statement::{
     BlockStatement, Class, DoWhileLoop, ExportKind, ForInLoop, ForLoop, ForOfLoop, FunctionDeclaration, IfStatement,
@@ -96,6 +96,18 @@ pub trait Visitor<V> {
     /// Visits an object literal
     fn visit_object_literal(&mut self, span: Span, o: ObjectLiteral) -> V;
 
+    /// Visits a template literal
+    fn visit_template_literal(&mut self, span: Span, t: TemplateLiteral) -> V;
+
+    /// Visits a tagged template
+    fn visit_tagged_template(&mut self, span: Span, t: TaggedTemplate) -> V;
+
+    /// Visits an optional chain
+    fn visit_optional_chain(&mut self, span: Span, e: Box<Expr>) -> V;
+
+    /// Visits the target of an optional property access or call
+    fn visit_optional(&mut self, span: Span, e: Box<Expr>) -> V;
+
     /// Visits a try catch statement
     fn visit_try_catch(&mut self, span: Span, t: TryCatch) -> V;
 
@@ -121,10 +133,13 @@ pub trait Visitor<V> {
     fn visit_empty_statement(&mut self) -> V;
 
     /// Visits a break statement
-    fn visit_break(&mut self, span: Span) -> V;
+    fn visit_break(&mut self, span: Span, label: Option<Symbol>) -> V;
 
     /// Visits a continue statement
-    fn visit_continue(&mut self, span: Span) -> V;
+    fn visit_continue(&mut self, span: Span, label: Option<Symbol>) -> V;
+
+    /// Visits a labeled statement
+    fn visit_labeled(&mut self, span: Span, label: Symbol, body: Box<Statement>) -> V;
 
     /// Visits a debugger statement
     fn visit_debugger(&mut self, span: Span) -> V;
@@ -157,8 +172,9 @@ pub fn accept_default<T, V: Visitor<T>>(this: &mut V, Statement { kind, span }:
         StatementKind::Import(i) => this.visit_import_statement(span, i),
         StatementKind::Export(e) => this.visit_export_statement(span, e),
         StatementKind::Class(c) => this.visit_class_declaration(span, c),
-        StatementKind::Continue => this.visit_continue(span),
-        StatementKind::Break => this.visit_break(span),
+        StatementKind::Continue(label) => this.visit_continue(span, label),
+        StatementKind::Break(label) => this.visit_break(span, label),
+        StatementKind::Labeled(label, body) => this.visit_labeled(span, label, body),
         StatementKind::Debugger => this.visit_debugger(span),
         StatementKind::Empty => this.visit_empty_statement(),
         StatementKind::Switch(s) => this.visit_switch_statement(span, s),
@@ -186,6 +202,10 @@ where
         ExprKind::Class(e) => this.visit_class_expr(span, e),
         ExprKind::Array(e) => this.visit_array_literal(span, e),
         ExprKind::Object(e) => this.visit_object_literal(span, e),
+        ExprKind::Template(e) => this.visit_template_literal(span, e),
+        ExprKind::TaggedTemplate(e) => this.visit_tagged_template(span, e),
+        ExprKind::OptionalChain(e) => this.visit_optional_chain(span, e),
+        ExprKind::Optional(e) => this.visit_optional(span, e),
         ExprKind::Compiled(..) => on_empty(this),
         ExprKind::Empty => this.visit_empty_expr(),
     }
@@ -195,7 +195,7 @@ pub trait AstWalker<'a> {
             ExportKind::NamedVar(v) => {
                 self.accept(Statement::Variable(v));
             }
-            ExportKind::Named(..) => {}
+            ExportKind::Named(..) | ExportKind::All(..) => {}
         }
     }
 
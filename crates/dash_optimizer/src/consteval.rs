@@ -4,7 +4,7 @@ use dash_middle::lexer::token::TokenType;
 use dash_middle::parser::expr::{
     ArrayLiteral, ArrayMemberKind, AssignmentExpr, AssignmentTarget, BinaryExpr, CallArgumentKind, ConditionalExpr,
     Expr, ExprKind, FunctionCall, GroupingExpr, LiteralExpr, ObjectLiteral, ObjectMemberKind, PropertyAccessExpr,
-    UnaryExpr,
+    TaggedTemplate, TemplateLiteral, UnaryExpr,
 };
 use dash_middle::parser::statement::{
     BlockStatement, Class, ClassMemberValue, DoWhileLoop, ExportKind, ForInLoop, ForLoop, ForOfLoop, FuncId,
@@ -64,12 +64,13 @@ impl<'b, 'interner> ConstFunctionEvalCtx<'b, 'interner> {
             StatementKind::Export(ExportKind::Default(expr)) => {
                 self.visit(expr, func_id);
             }
-            StatementKind::Export(ExportKind::Named(..)) => {}
+            StatementKind::Export(ExportKind::Named(..) | ExportKind::All(..)) => {}
             StatementKind::Export(ExportKind::NamedVar(stmt)) => self.visit_variable_declaration(stmt, func_id),
             StatementKind::Class(stmt) => self.visit_class_statement(stmt, func_id),
             StatementKind::Switch(stmt) => self.visit_switch_statement(stmt, func_id),
-            StatementKind::Continue => {}
-            StatementKind::Break => {}
+            StatementKind::Labeled(_, stmt) => self.visit_statement(stmt, func_id),
+            StatementKind::Continue(_) => {}
+            StatementKind::Break(_) => {}
             StatementKind::Debugger => {}
             StatementKind::Empty => {}
         };
@@ -161,7 +162,9 @@ impl<'b, 'interner> ConstFunctionEvalCtx<'b, 'interner> {
                 self.visit_maybe_expr(finalizer.as_mut(), func_id);
                 self.visit_statement(body, func_id);
             }
-            Loop::ForOf(ForOfLoop { expr, body, binding }) => {
+            Loop::ForOf(ForOfLoop {
+                expr, body, binding, ..
+            }) => {
                 self.visit_variable_binding(binding, None, func_id);
                 self.visit(expr, func_id);
                 self.visit_statement(body, func_id);
@@ -235,6 +238,14 @@ impl<'b, 'interner> ConstFunctionEvalCtx<'b, 'interner> {
             ExprKind::Class(class) => self.visit_class_statement(class, func_id),
             ExprKind::Array(..) => self.visit_array_expression(expression, func_id),
             ExprKind::Object(..) => self.visit_object_expression(expression, func_id),
+            ExprKind::Template(TemplateLiteral { expressions, .. }) => {
+                expressions.iter_mut().for_each(|e| self.visit(e, func_id))
+            }
+            ExprKind::TaggedTemplate(TaggedTemplate { tag, template }) => {
+                self.visit(tag, func_id);
+                template.expressions.iter_mut().for_each(|e| self.visit(e, func_id))
+            }
+            ExprKind::OptionalChain(expr) | ExprKind::Optional(expr) => self.visit(expr, func_id),
             ExprKind::Compiled(..) => {}
             ExprKind::Empty => {}
         }
@@ -469,7 +480,7 @@ impl<'b, 'interner> ConstFunctionEvalCtx<'b, 'interner> {
     ) {
         let sub_func_id = *id;
 
-        for (param, expr, _) in parameters {
+        for (param, expr, ..) in parameters {
             match param {
                 Parameter::Identifier(ident) | Parameter::Spread(ident) => {
                     // TODO: handle this error, somehow
@@ -494,7 +505,7 @@ impl<'b, 'interner> ConstFunctionEvalCtx<'b, 'interner> {
 fn stmt_has_side_effects(stmt: &Statement) -> bool {
     match &stmt.kind {
         StatementKind::Block(BlockStatement(block)) => block.iter().any(stmt_has_side_effects),
-        StatementKind::Break => true,
+        StatementKind::Break(_) => true,
         StatementKind::Class(Class { .. }) => true, // TODO: can possibly be SE-free
         StatementKind::Empty => false,
         StatementKind::Expression(expr) => expr_has_side_effects(expr),
@@ -5,7 +5,7 @@ use dash_middle::lexer::token::TokenType;
 use dash_middle::parser::expr::{
     ArrayLiteral, ArrayMemberKind, AssignmentExpr, AssignmentTarget, BinaryExpr, CallArgumentKind, ConditionalExpr,
     Expr, ExprKind, FunctionCall, GroupingExpr, LiteralExpr, ObjectLiteral, ObjectMemberKind, PropertyAccessExpr,
-    UnaryExpr,
+    TaggedTemplate, TemplateLiteral, UnaryExpr,
 };
 use dash_middle::parser::statement::{
     BlockStatement, Class, ClassMemberValue, DoWhileLoop, ExportKind, ForInLoop, ForLoop, ForOfLoop, FuncId,
@@ -77,12 +77,13 @@ impl TypeInferCtx {
             StatementKind::Import(ImportKind::Dynamic(expr)) => drop(self.visit(expr, func_id)),
             StatementKind::Import(ImportKind::DefaultAs(SpecifierKind::Ident(..), ..)) => {}
             StatementKind::Export(ExportKind::Default(expr)) => drop(self.visit(expr, func_id)),
-            StatementKind::Export(ExportKind::Named(..)) => {}
+            StatementKind::Export(ExportKind::Named(..) | ExportKind::All(..)) => {}
             StatementKind::Export(ExportKind::NamedVar(stmt)) => self.visit_variable_declaration(stmt, func_id),
             StatementKind::Class(stmt) => self.visit_class_statement(stmt, func_id),
             StatementKind::Switch(stmt) => self.visit_switch_statement(stmt, func_id),
-            StatementKind::Continue => {}
-            StatementKind::Break => {}
+            StatementKind::Labeled(_, stmt) => self.visit_statement(stmt, func_id),
+            StatementKind::Continue(_) => {}
+            StatementKind::Break(_) => {}
             StatementKind::Debugger => {}
             StatementKind::Empty => {}
         }
@@ -180,7 +181,9 @@ impl TypeInferCtx {
                 self.visit_maybe_expr(finalizer.as_ref(), func_id);
                 self.visit_statement(body, func_id);
             }
-            Loop::ForOf(ForOfLoop { expr, body, binding }) => {
+            Loop::ForOf(ForOfLoop {
+                expr, body, binding, ..
+            }) => {
                 self.visit_variable_binding(binding, None, func_id);
                 self.visit(expr, func_id);
                 self.visit_statement(body, func_id);
@@ -263,6 +266,25 @@ impl TypeInferCtx {
             ExprKind::Class(class) => self.visit_class_expression(class, func_id),
             ExprKind::Array(expr) => self.visit_array_expression(expr, func_id),
             ExprKind::Object(expr) => self.visit_object_expression(expr, func_id),
+            ExprKind::Template(TemplateLiteral { expressions, .. }) => {
+                for expr in expressions {
+                    self.visit(expr, func_id);
+                }
+                Some(CompileValueType::String)
+            }
+            ExprKind::TaggedTemplate(TaggedTemplate { tag, template }) => {
+                self.visit(tag, func_id);
+                for expr in &template.expressions {
+                    self.visit(expr, func_id);
+                }
+                None
+            }
+            ExprKind::OptionalChain(expr) => {
+                // The chain may short-circuit and evaluate to undefined
+                drop(self.visit(expr, func_id));
+                None
+            }
+            ExprKind::Optional(expr) => self.visit(expr, func_id),
             ExprKind::Compiled(..) => None,
             ExprKind::Empty => None,
         }
@@ -354,8 +376,13 @@ impl TypeInferCtx {
         AssignmentExpr { left, right, .. }: &AssignmentExpr,
         func_id: FuncId,
     ) -> Option<CompileValueType> {
-        let AssignmentTarget::Expr(left) = left else {
-            panic!("Cannot infer type for assignment place LocalId");
+        let left = match left {
+            AssignmentTarget::Expr(left) => left,
+            AssignmentTarget::LocalId(_) => panic!("Cannot infer type for assignment place LocalId"),
+            AssignmentTarget::ArrayDestructuring(..) | AssignmentTarget::ObjectDestructuring(..) => {
+                self.visit_destructuring_target(left, func_id);
+                return self.visit(right, func_id);
+            }
         };
 
         self.visit(left, func_id);
@@ -398,6 +425,33 @@ impl TypeInferCtx {
         right_type
     }
 
+    /// Visits the places of a destructuring assignment.
+    /// The values they receive are not known statically, so any local variable assigned to loses its inferred type.
+    fn visit_destructuring_target(&mut self, target: &AssignmentTarget, func_id: FuncId) {
+        match target {
+            AssignmentTarget::Expr(expr) => {
+                self.visit(expr, func_id);
+
+                if let ExprKind::Literal(LiteralExpr::Identifier(ident)) = &expr.kind {
+                    if let Some(local) = self.find_local(*ident, func_id) {
+                        *local.inferred_type().borrow_mut() = None;
+                    }
+                }
+            }
+            AssignmentTarget::LocalId(_) => {}
+            AssignmentTarget::ArrayDestructuring(fields) => {
+                for field in fields.iter().flatten() {
+                    self.visit_destructuring_target(field, func_id);
+                }
+            }
+            AssignmentTarget::ObjectDestructuring(fields) => {
+                for (_, field) in fields {
+                    self.visit_destructuring_target(field, func_id);
+                }
+            }
+        }
+    }
+
     pub fn visit_call_expression(
         &mut self,
         FunctionCall { target, arguments, .. }: &FunctionCall,
@@ -487,14 +541,21 @@ impl TypeInferCtx {
             }
         }
 
-        for (param, expr, _) in parameters {
+        for (index, (param, expr, ..)) in parameters.iter().enumerate() {
             match param {
                 Parameter::Identifier(ident) | Parameter::Spread(ident) => {
-                    if self
-                        .scope_mut(sub_func_id)
-                        .add_local(*ident, VariableDeclarationKind::Var, None)
-                        .is_err()
-                    {
+                    // Parameters that are shadowed by a later one with the same name cannot be referred to,
+                    // but still need their own local
+                    let is_shadowed = parameters[index + 1..]
+                        .iter()
+                        .any(|(Parameter::Identifier(other) | Parameter::Spread(other), ..)| other == ident);
+                    let kind = if is_shadowed {
+                        VariableDeclarationKind::Unnameable
+                    } else {
+                        VariableDeclarationKind::Var
+                    };
+
+                    if self.scope_mut(sub_func_id).add_local(*ident, kind, None).is_err() {
                         error!("failed to reserve space for parameter")
                     }
                 }
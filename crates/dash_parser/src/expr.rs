@@ -2,11 +2,13 @@ use dash_middle::interner::{sym, Symbol};
 use dash_middle::lexer::token::{Token, TokenType, ASSIGNMENT_TYPES};
 use dash_middle::parser::error::Error;
 use dash_middle::parser::expr::{
-    ArrayMemberKind, AssignmentExpr, AssignmentTarget, CallArgumentKind, Expr, ExprKind, LiteralExpr, ObjectMemberKind,
+    ArrayLiteral, ArrayMemberKind, AssignmentExpr, AssignmentTarget, CallArgumentKind, Expr, ExprKind, LiteralExpr,
+    ObjectLiteral, ObjectMemberKind, TemplateLiteral,
 };
 use dash_middle::parser::statement::{
     Asyncness, BlockStatement, FunctionDeclaration, FunctionKind, Parameter, ReturnStatement, Statement, StatementKind,
 };
+use dash_middle::parser::types::TypeSegment;
 use dash_middle::sourcemap::Span;
 use dash_regex::Flags;
 
@@ -53,12 +55,72 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         if self.expect_token_type_and_skip(ASSIGNMENT_TYPES, false) {
             let operator = self.previous()?.ty;
             let rval = self.parse_yield()?;
-            expr = Expr::assignment(expr, rval, operator);
+
+            expr = match expr.kind {
+                ExprKind::Array(..) | ExprKind::Object(..) if operator == TokenType::Assignment => {
+                    // `[a, b] = [b, a]` or `({ a } = b)`: reinterpret the literal as a destructuring pattern
+                    let span = expr.span.to(rval.span);
+                    let target = self.expr_to_assignment_target(expr)?;
+                    Expr {
+                        span,
+                        kind: ExprKind::Assignment(AssignmentExpr::new(target, rval, operator)),
+                    }
+                }
+                _ => Expr::assignment(expr, rval, operator),
+            };
         }
 
         Some(expr)
     }
 
+    /// Converts the lefthand side of an assignment into an assignment target,
+    /// turning array and object literals into (possibly nested) destructuring patterns
+    fn expr_to_assignment_target(&mut self, expr: Expr) -> Option<AssignmentTarget> {
+        match expr.kind {
+            ExprKind::Array(ArrayLiteral(items)) => {
+                let mut fields = Vec::with_capacity(items.len());
+
+                for item in items {
+                    match item {
+                        ArrayMemberKind::Item(item) => fields.push(Some(self.expr_to_assignment_target(item)?)),
+                        ArrayMemberKind::Empty => fields.push(None),
+                        ArrayMemberKind::Spread(..) => {
+                            self.create_error(Error::Unimplemented(
+                                expr.span,
+                                "rest operator in destructuring assignment".into(),
+                            ));
+                            return None;
+                        }
+                    }
+                }
+
+                Some(AssignmentTarget::ArrayDestructuring(fields))
+            }
+            ExprKind::Object(ObjectLiteral(items)) => {
+                let mut fields = Vec::with_capacity(items.len());
+
+                for (key, value) in items {
+                    match key {
+                        ObjectMemberKind::Static(name) => fields.push((name, self.expr_to_assignment_target(value)?)),
+                        ObjectMemberKind::Prototype => {
+                            fields.push((sym::__proto__, self.expr_to_assignment_target(value)?))
+                        }
+                        _ => {
+                            self.create_error(Error::Unimplemented(
+                                expr.span,
+                                "non-identifier keys in destructuring assignment".into(),
+                            ));
+                            return None;
+                        }
+                    }
+                }
+
+                Some(AssignmentTarget::ObjectDestructuring(fields))
+            }
+            _ => Some(AssignmentTarget::Expr(Box::new(expr))),
+        }
+    }
+
     fn parse_ternary(&mut self) -> Option<Expr> {
         let mut expr = self.parse_nullish_coalescing()?;
 
@@ -263,6 +325,14 @@ impl<'a, 'interner> Parser<'a, 'interner> {
             let rval = self.parse_unary()?;
             let span = span.to(rval.span);
 
+            if ty == TokenType::Delete
+                && self.mode.is_strict()
+                && matches!(rval.kind, ExprKind::Literal(LiteralExpr::Identifier(_)))
+            {
+                self.create_error(Error::DeleteOfIdentifierInStrictMode(span));
+                return None;
+            }
+
             if [TokenType::Increment, TokenType::Decrement].contains(&ty) {
                 Some(Expr {
                     span,
@@ -292,81 +362,210 @@ impl<'a, 'interner> Parser<'a, 'interner> {
     }
 
     fn parse_field_access(&mut self) -> Option<Expr> {
-        if self.expect_token_type_and_skip(&[TokenType::New], false) {
-            self.new_level_stack
-                .inc_level()
-                .expect("Failed to increment `new` stack level");
+        let mut expr = if self.expect_token_type_and_skip(&[TokenType::New], false) {
+            self.parse_new_expression()?
+        } else {
+            self.parse_primary_expr()?
+        };
 
-            let rval = self.parse_field_access()?;
+        let mut is_optional_chain = false;
 
-            return Some(rval);
-        }
+        loop {
+            if let Some(head) = self.expect_template_literal(false) {
+                // Tagged template: foo`bar`
+                let template = self.parse_template_literal(head)?;
+                expr = Expr {
+                    span: expr.span.to(self.previous()?.span),
+                    kind: ExprKind::tagged_template(expr, template),
+                };
+                continue;
+            }
 
-        let mut expr = self.parse_primary_expr()?;
+            if !self.expect_token_type_and_skip(
+                &[
+                    TokenType::LeftParen,
+                    TokenType::Dot,
+                    TokenType::LeftSquareBrace,
+                    TokenType::OptionalChaining,
+                ],
+                false,
+            ) {
+                break;
+            }
 
-        while self.expect_token_type_and_skip(
-            &[TokenType::LeftParen, TokenType::Dot, TokenType::LeftSquareBrace],
-            false,
-        ) {
-            let previous = self.previous()?.ty;
+            let mut previous = self.previous()?.ty;
 
-            match previous {
-                TokenType::LeftParen => {
-                    let mut arguments = Vec::new();
-
-                    // Disassociate any new expressions in arguments such that `new x(() => x());`
-                    // is parsed as having the `new` operator only apply to the `x` identifier.
-                    self.new_level_stack.add_level();
-                    // TODO: refactor to `parse_expr_list`
-                    while !self.expect_token_type_and_skip(&[TokenType::RightParen], false) {
-                        self.expect_token_type_and_skip(&[TokenType::Comma], false);
-
-                        if let Some(spread) = self.parse_spread_operator(false) {
-                            arguments.push(CallArgumentKind::Spread(spread));
-                        } else {
-                            arguments.push(CallArgumentKind::Normal(self.parse_yield()?));
-                        }
-                    }
-                    self.new_level_stack.pop_level().expect("Missing `new` level stack");
+            if previous == TokenType::OptionalChaining {
+                // foo?.bar, foo?.[bar] or foo?.()
+                is_optional_chain = true;
+                expr = Expr {
+                    span: expr.span,
+                    kind: ExprKind::optional(expr),
+                };
 
-                    // End of function call.
-                    let level = self.new_level_stack.cur_level().expect("Missing `new` level stack");
-                    let is_constructor_call = level > 0;
-                    if is_constructor_call {
-                        self.new_level_stack.dec_level().expect("Missing `new` level stack");
-                    }
+                previous =
+                    if self.expect_token_type_and_skip(&[TokenType::LeftParen, TokenType::LeftSquareBrace], false) {
+                        self.previous()?.ty
+                    } else {
+                        TokenType::Dot
+                    };
+            }
 
+            match previous {
+                TokenType::LeftParen => {
+                    let arguments = self.parse_arguments()?;
                     expr = Expr {
                         span: expr.span.to(self.previous()?.span),
-                        kind: ExprKind::function_call(expr, arguments, is_constructor_call),
-                    };
-                }
-                TokenType::Dot => {
-                    let ident = self.expect_identifier_or_reserved_kw(true)?;
-                    let property = Expr {
-                        span: self.previous()?.span,
-                        kind: ExprKind::identifier(ident),
-                    };
-                    expr = Expr {
-                        span: expr.span.to(property.span),
-                        kind: ExprKind::property_access(false, expr, property),
-                    };
-                }
-                TokenType::LeftSquareBrace => {
-                    let property = self.parse_expression()?;
-                    self.expect_token_type_and_skip(&[TokenType::RightSquareBrace], true);
-                    expr = Expr {
-                        span: expr.span.to(property.span),
-                        kind: ExprKind::property_access(true, expr, property),
+                        kind: ExprKind::function_call(expr, arguments, false),
                     };
                 }
+                TokenType::Dot | TokenType::LeftSquareBrace => expr = self.parse_member_access(expr, previous)?,
                 _ => unreachable!(),
             }
         }
 
+        if is_optional_chain {
+            expr = Expr {
+                span: expr.span,
+                kind: ExprKind::optional_chain(expr),
+            };
+        }
+
         Some(expr)
     }
 
+    /// Parses the rest of a `new` expression, after the `new` keyword.
+    ///
+    /// The callee can be any member expression (including another `new` expression), e.g. `new a.b.C()` or `new (f())()`.
+    /// The argument list is optional, so `new C` is the same as `new C()`.
+    fn parse_new_expression(&mut self) -> Option<Expr> {
+        let new_span = self.previous()?.span;
+
+        let mut target = if self.expect_token_type_and_skip(&[TokenType::New], false) {
+            self.parse_new_expression()?
+        } else {
+            self.parse_primary_expr()?
+        };
+
+        while self.expect_token_type_and_skip(&[TokenType::Dot, TokenType::LeftSquareBrace], false) {
+            let previous = self.previous()?.ty;
+            target = self.parse_member_access(target, previous)?;
+        }
+
+        let arguments = if self.expect_token_type_and_skip(&[TokenType::LeftParen], false) {
+            self.parse_arguments()?
+        } else {
+            Vec::new()
+        };
+
+        Some(Expr {
+            span: new_span.to(self.previous()?.span),
+            kind: ExprKind::function_call(target, arguments, true),
+        })
+    }
+
+    /// Parses a call argument list, after the opening parenthesis
+    fn parse_arguments(&mut self) -> Option<Vec<CallArgumentKind>> {
+        let mut arguments = Vec::new();
+
+        // TODO: refactor to `parse_expr_list`
+        while !self.expect_token_type_and_skip(&[TokenType::RightParen], false) {
+            if !arguments.is_empty() {
+                if !self.expect_token_type_and_skip(&[TokenType::Comma], true) {
+                    return None;
+                }
+
+                // Trailing comma: f(a, b,)
+                if self.expect_token_type_and_skip(&[TokenType::RightParen], false) {
+                    break;
+                }
+            }
+
+            if let Some(spread) = self.parse_spread_operator(false) {
+                arguments.push(CallArgumentKind::Spread(spread));
+            } else {
+                arguments.push(CallArgumentKind::Normal(self.parse_yield()?));
+            }
+        }
+
+        Some(arguments)
+    }
+
+    /// Parses the rest of a template literal, after its first string segment
+    fn parse_template_literal(&mut self, head: Symbol) -> Option<TemplateLiteral> {
+        let mut strings = vec![head];
+        let mut raw_strings = vec![self.template_literal_raw_string()?];
+        let mut expressions = Vec::new();
+
+        // Every `${expr}` is followed by the next string segment, which may be empty
+        while self.expect_token_type_and_skip(&[TokenType::Dollar], false) {
+            self.expect_token_type_and_skip(&[TokenType::LeftBrace], true);
+            expressions.push(self.parse_expression()?);
+            self.expect_token_type_and_skip(&[TokenType::RightBrace], true);
+            strings.push(self.expect_template_literal(true)?);
+            raw_strings.push(self.template_literal_raw_string()?);
+        }
+
+        Some(TemplateLiteral {
+            strings,
+            raw_strings,
+            expressions,
+        })
+    }
+
+    /// Returns the source code of the template literal segment that was just consumed, without processing escape sequences
+    fn template_literal_raw_string(&mut self) -> Option<Symbol> {
+        let span = self.previous()?.span;
+
+        // The segment starts after the opening "`" or the "}" of the previous substitution,
+        // and ends either at the "${" of the next substitution or before the closing "`"
+        let is_substitution_next = matches!(
+            self.current(),
+            Some(Token {
+                ty: TokenType::Dollar,
+                ..
+            })
+        );
+        let hi = if is_substitution_next { span.hi } else { span.hi - 1 };
+        let raw = self.source.resolve(Span { lo: span.lo + 1, hi });
+
+        // Line terminators are normalized to \n in raw strings as well
+        let raw = raw.replace("\r\n", "\n").replace('\r', "\n");
+        Some(self.interner.intern(raw))
+    }
+
+    /// Parses a `.property` or `[property]` access on `expr`, after the `.` or `[` token
+    fn parse_member_access(&mut self, expr: Expr, previous: TokenType) -> Option<Expr> {
+        match previous {
+            TokenType::Dot => {
+                // `o.#x` is a computed access using the private symbol bound to `#x`
+                let is_private = self.expect_token_type_and_skip(&[TokenType::Hash], false);
+                let ident = if is_private {
+                    self.expect_private_name()?
+                } else {
+                    self.expect_identifier_or_reserved_kw(true)?
+                };
+                let property = Expr {
+                    span: self.previous()?.span,
+                    kind: ExprKind::identifier(ident),
+                };
+                Some(Expr {
+                    span: expr.span.to(property.span),
+                    kind: ExprKind::property_access(is_private, expr, property),
+                })
+            }
+            TokenType::LeftSquareBrace => {
+                let property = self.parse_expression()?;
+                self.expect_token_type_and_skip(&[TokenType::RightSquareBrace], true);
+                Some(Expr {
+                    span: expr.span.to(property.span),
+                    kind: ExprKind::property_access(true, expr, property),
+                })
+            }
+            _ => unreachable!(),
+        }
+    }
+
     /// Tries to parse a spread operator (...<expr>). The argument specifies if it's required.
     fn parse_spread_operator(&mut self, must_parse: bool) -> Option<Expr> {
         if self.expect_token_type_and_skip(&[TokenType::Dot], must_parse) {
@@ -392,27 +591,11 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         let expr = match current.ty {
             // removed to resolve #58
             TokenType::TemplateLiteral(sym) => {
-                let mut left = Expr {
-                    span: current.span,
-                    kind: ExprKind::string_literal(sym),
-                };
-                while !self.is_eof() {
-                    if self.expect_token_type_and_skip(&[TokenType::Dollar], false) {
-                        self.expect_token_type_and_skip(&[TokenType::LeftBrace], true);
-                        let right = self.parse_expression()?;
-                        self.expect_token_type_and_skip(&[TokenType::RightBrace], true);
-                        left = Expr::binary(left, right, TokenType::Plus);
-                    } else if let Some(sym) = self.expect_template_literal(false) {
-                        let right = Expr {
-                            span: self.previous()?.span,
-                            kind: ExprKind::string_literal(sym),
-                        };
-                        left = Expr::binary(left, right, TokenType::Plus);
-                    } else {
-                        break;
-                    }
+                let template = self.parse_template_literal(sym)?;
+                Expr {
+                    span: current.span.to(self.previous()?.span),
+                    kind: ExprKind::Template(template),
                 }
-                left
             }
             TokenType::FalseLit => Expr {
                 span: current.span,
@@ -434,6 +617,21 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                 span: current.span,
                 kind: ExprKind::string_literal(sym),
             },
+            TokenType::Hash => {
+                // A private name on its own is only valid as a brand check: `#x in obj`
+                let name = self.expect_private_name()?;
+                let span = current.span.to(self.previous()?.span);
+
+                if !self.current().is_some_and(|tok| tok.ty == TokenType::In) {
+                    self.create_error(Error::Unexpected(span, "private name outside of an `in` expression"));
+                    return None;
+                }
+
+                Expr {
+                    span,
+                    kind: ExprKind::identifier(name),
+                }
+            }
             TokenType::LeftSquareBrace => {
                 let mut items = Vec::new();
                 while !self.expect_token_type_and_skip(&[TokenType::RightSquareBrace], false) {
@@ -446,7 +644,15 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                     } else {
                         items.push(ArrayMemberKind::Item(self.parse_yield()?));
                     }
-                    self.expect_token_type_and_skip(&[TokenType::Comma], false);
+
+                    // An element must be followed by a comma (which may be trailing) or the end of the array
+                    if !self.expect_token_type_and_skip(&[TokenType::Comma], false)
+                        && !self.expect_token_type_and_skip(&[TokenType::RightSquareBrace], true)
+                    {
+                        return None;
+                    } else if self.previous()?.ty == TokenType::RightSquareBrace {
+                        break;
+                    }
                 }
                 let rbrace_span = self.previous()?.span;
                 Expr {
@@ -457,11 +663,15 @@ impl<'a, 'interner> Parser<'a, 'interner> {
             TokenType::LeftBrace => {
                 let mut items = Vec::new();
                 while !self.expect_token_type_and_skip(&[TokenType::RightBrace], false) {
-                    self.expect_token_type_and_skip(&[TokenType::Comma], false);
+                    if !items.is_empty() {
+                        if !self.expect_token_type_and_skip(&[TokenType::Comma], true) {
+                            return None;
+                        }
 
-                    // Allow trailing comma in object literal {f:1,}
-                    if self.expect_token_type_and_skip(&[TokenType::RightBrace], false) {
-                        break;
+                        // Allow trailing comma in object literal {f:1,}
+                        if self.expect_token_type_and_skip(&[TokenType::RightBrace], false) {
+                            break;
+                        }
                     }
 
                     let token = self.next()?.clone();
@@ -528,6 +738,17 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                             if self.expect_token_type_and_skip(&[TokenType::Colon], false) {
                                 // Normal property.
                                 let value = self.parse_yield()?;
+                                let key = match key {
+                                    // Only the non-shorthand, non-computed form sets the prototype
+                                    ObjectMemberKind::Static(sym::__proto__) => {
+                                        if items.iter().any(|(k, _)| matches!(k, ObjectMemberKind::Prototype)) {
+                                            self.create_error(Error::DuplicateProto(token.span));
+                                            return None;
+                                        }
+                                        ObjectMemberKind::Prototype
+                                    }
+                                    key => key,
+                                };
                                 items.push((key, value));
                             } else if self.expect_token_type_and_skip(&[TokenType::LeftParen], false) {
                                 // Method.
@@ -618,7 +839,9 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                                 },
                             ));
                         }
-                        ObjectMemberKind::DynamicGetter(_) | ObjectMemberKind::DynamicSetter(_) => {
+                        ObjectMemberKind::DynamicGetter(_)
+                        | ObjectMemberKind::DynamicSetter(_)
+                        | ObjectMemberKind::Prototype => {
                             unreachable!("never created")
                         }
                     }
@@ -648,12 +871,22 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                     return self.parse_arrow_function_end(current.span, Vec::new(), None);
                 }
 
-                self.new_level_stack.add_level();
                 let mut exprs = Vec::new();
                 let mut rest_binding = None;
+                let mut trailing_comma = None;
 
                 while !self.expect_token_type_and_skip(&[TokenType::RightParen], false) {
-                    self.expect_token_type_and_skip(&[TokenType::Comma], false);
+                    if !exprs.is_empty() {
+                        if !self.expect_token_type_and_skip(&[TokenType::Comma], true) {
+                            return None;
+                        }
+
+                        // Trailing commas are only allowed if this turns out to be an arrow function's parameter list
+                        if self.expect_token_type_and_skip(&[TokenType::RightParen], false) {
+                            trailing_comma = Some(self.previous()?.span);
+                            break;
+                        }
+                    }
 
                     if self.expect_token_type_and_skip(&[TokenType::Dot], false) {
                         for _ in 0..2 {
@@ -675,14 +908,18 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                     // rewriting the arrow AST transformation to recursively fold sequences
                     exprs.push(self.parse_yield()?);
                 }
-                self.new_level_stack.pop_level();
 
                 // This is an arrow function if the next token is an arrow (`=>`)
                 if self.expect_token_type_and_skip(&[TokenType::FatArrow], false) {
-                    return self.parse_arrow_function_end(current.span, exprs, rest_binding.map(|v| v.0));
+                    return self.parse_arrow_function_end(current.span, exprs, rest_binding);
                 }
 
                 // If it's not an arrow function, then it is a group
+                if let Some(span) = trailing_comma {
+                    self.create_error(Error::Unexpected(span, "trailing comma in parenthesized expression"));
+                    return None;
+                }
+
                 if let Some((sym, span)) = rest_binding {
                     self.create_error(Error::UnknownToken(Token {
                         span,
@@ -699,36 +936,15 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                         span,
                         kind: ExprKind::function(f),
                     })?
-                } else if self.expect_token_type_and_skip(&[TokenType::LeftParen], true) {
+                } else if self.expect_token_type_and_skip(&[TokenType::LeftParen], false) {
                     let params = self.parse_parameter_list()?;
-                    self.expect_token_type_and_skip(&[TokenType::FatArrow], true);
-                    let statement = if self.expect_token_type_and_skip(&[TokenType::LeftBrace], false) {
-                        self.advance_back();
-                        self.parse_statement()?
-                    } else {
-                        let expr = self.parse_expression_no_comma()?;
-                        Statement {
-                            span: expr.span,
-                            kind: StatementKind::Return(ReturnStatement(expr)),
-                        }
-                    };
-
-                    Expr {
-                        span: current.span.to(statement.span),
-                        kind: ExprKind::function(FunctionDeclaration::new(
-                            None,
-                            self.function_counter.inc(),
-                            params,
-                            vec![statement],
-                            // FIXME: this isn't correct -- we're currently desugaring async closures
-                            // as if they're simply async functions
-                            FunctionKind::Function(Asyncness::Yes),
-                            None,
-                            None,
-                        )),
-                    }
+                    return self.parse_async_arrow_function_end(current.span, params);
                 } else {
-                    return None;
+                    // `async x => ...`
+                    let span = self.current()?.span;
+                    let ident = self.expect_identifier(true)?;
+                    let params = vec![(Parameter::Identifier(ident), None, None, span)];
+                    return self.parse_async_arrow_function_end(current.span, params);
                 }
             }
             TokenType::Function => self.parse_function(false).map(|(f, span)| Expr {
@@ -793,13 +1009,7 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         let is_generator = self.expect_token_type_and_skip(&[TokenType::Star], false);
 
         let ty = if is_generator {
-            if is_async {
-                let star_span = self.previous().unwrap().span;
-                self.create_error(Error::Unimplemented(star_span, "async generator".into()));
-                return None;
-            }
-
-            FunctionKind::Generator
+            FunctionKind::Generator(is_async.into())
         } else {
             FunctionKind::Function(is_async.into())
         };
@@ -833,12 +1043,8 @@ impl<'a, 'interner> Parser<'a, 'interner> {
             return None;
         }
 
-        self.new_level_stack.add_level();
-
         let BlockStatement(statements) = self.parse_block()?;
 
-        self.new_level_stack.pop_level().unwrap();
-
         let func_id = self.function_counter.inc();
         Some((
             FunctionDeclaration::new(name, func_id, arguments, statements, ty, ty_seg, None),
@@ -859,7 +1065,7 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         &mut self,
         pre_span: Span,
         prec: Vec<Expr>,
-        rest_binding: Option<Symbol>,
+        rest_binding: Option<(Symbol, Span)>,
     ) -> Option<Expr> {
         let mut list = Vec::with_capacity(prec.len());
 
@@ -869,13 +1075,13 @@ impl<'a, 'interner> Parser<'a, 'interner> {
             // e.g. (a: number) => {}
             // we need to properly convert types here too
 
-            let (ident, value) = match expr.kind {
-                ExprKind::Literal(LiteralExpr::Identifier(ident)) => (ident, None),
+            let (ident, value, span) = match expr.kind {
+                ExprKind::Literal(LiteralExpr::Identifier(ident)) => (ident, None, expr.span),
                 ExprKind::Assignment(AssignmentExpr {
                     left: AssignmentTarget::Expr(left),
                     right,
                     ..
-                }) => (left.kind.as_identifier()?, Some(*right)),
+                }) => (left.kind.as_identifier()?, Some(*right), left.span),
                 _ => {
                     self.create_error(Error::Unimplemented(
                         expr.span,
@@ -886,11 +1092,11 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                 }
             };
 
-            list.push((Parameter::Identifier(ident), value, None));
+            list.push((Parameter::Identifier(ident), value, None, span));
         }
 
-        if let Some(rest_binding) = rest_binding {
-            list.push((Parameter::Spread(rest_binding), None, None));
+        if let Some((rest_binding, span)) = rest_binding {
+            list.push((Parameter::Spread(rest_binding), None, None, span));
         }
 
         let is_statement = self.expect_token_type_and_skip(&[TokenType::LeftBrace], false);
@@ -918,7 +1124,48 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                 func_id,
                 list,
                 vec![body],
-                FunctionKind::Arrow,
+                FunctionKind::Arrow(Asyncness::No),
+                None,
+                None,
+            )),
+        })
+    }
+
+    /// Parses the `=>` and body of an async arrow function, after its parameter list has been parsed.
+    ///
+    /// Unlike non-async arrow functions, async arrow functions are not ambiguous with groupings,
+    /// so the parameter list can be parsed directly
+    fn parse_async_arrow_function_end(
+        &mut self,
+        pre_span: Span,
+        parameters: Vec<(Parameter, Option<Expr>, Option<TypeSegment>, Span)>,
+    ) -> Option<Expr> {
+        if !self.expect_token_type_and_skip(&[TokenType::FatArrow], true) {
+            return None;
+        }
+
+        let body = if self.expect_token_type_and_skip(&[TokenType::LeftBrace], false) {
+            self.advance_back();
+            self.parse_statement()?
+        } else {
+            let lo_span = self.current()?.span;
+            let expr = self.parse_yield()?;
+            let hi_span = self.previous()?.span;
+            Statement {
+                kind: StatementKind::Return(ReturnStatement(expr)),
+                span: lo_span.to(hi_span),
+            }
+        };
+
+        let func_id = self.function_counter.inc();
+        Some(Expr {
+            span: pre_span.to(body.span),
+            kind: ExprKind::function(FunctionDeclaration::new(
+                None,
+                func_id,
+                parameters,
+                vec![body],
+                FunctionKind::Arrow(Asyncness::Yes),
                 None,
                 None,
             )),
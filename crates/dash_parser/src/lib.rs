@@ -5,12 +5,33 @@ use dash_middle::parser::error::Error;
 use dash_middle::parser::expr::{Expr, ExprKind};
 use dash_middle::parser::statement::{FuncId, Statement};
 use dash_middle::sourcemap::{SourceMap, Span};
-use dash_middle::util::{Counter, LevelStack};
+use dash_middle::util::Counter;
 
 mod expr;
 mod stmt;
 mod types;
 
+/// Whether source code is parsed as a module or as a classic script
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Classic script: `import` and `export` declarations are not allowed
+    #[default]
+    Script,
+    /// ES module: `import` and `export` declarations are allowed and the code is always in strict mode
+    Module,
+}
+
+impl ParseMode {
+    pub fn is_module(self) -> bool {
+        matches!(self, Self::Module)
+    }
+
+    /// Whether code parsed in this mode is in strict mode without requiring a directive
+    pub fn is_strict(self) -> bool {
+        self.is_module()
+    }
+}
+
 /// A JavaScript source code parser
 pub struct Parser<'a, 'interner> {
     tokens: Box<[Token]>,
@@ -18,10 +39,9 @@ pub struct Parser<'a, 'interner> {
     error_sync: bool,
     idx: usize,
     interner: &'interner mut StringInterner,
-    // TODO: remove, not needed anymore
-    _source: SourceMap<'a>,
-    new_level_stack: LevelStack,
+    source: SourceMap<'a>,
     function_counter: Counter<FuncId>,
+    mode: ParseMode,
 }
 
 impl<'a, 'interner> Parser<'a, 'interner> {
@@ -35,22 +55,30 @@ impl<'a, 'interner> Parser<'a, 'interner> {
 
     /// Creates a new parser from tokens generated by a [Lexer]
     pub fn new(interner: &'interner mut StringInterner, input: &'a str, tokens: Vec<Token>) -> Self {
-        let mut level_stack = LevelStack::new();
-        level_stack.add_level();
-
         Self {
             tokens: tokens.into_boxed_slice(),
             errors: Vec::new(),
             error_sync: false,
             idx: 0,
-            _source: SourceMap::new(input),
-            new_level_stack: level_stack,
+            source: SourceMap::new(input),
             interner,
             // FuncId::ROOT (0) is reserved for the root function, so the counter for new functions has to start at 1
             function_counter: Counter::with(FuncId::FIRST_NON_ROOT),
+            mode: ParseMode::default(),
         }
     }
 
+    /// Sets the mode to parse the source code in. Defaults to [`ParseMode::Script`].
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns the mode this parser parses the source code in
+    pub fn mode(&self) -> ParseMode {
+        self.mode
+    }
+
     /// Attempts to parse a single statement
     /// If an error occurs, `None` is returned and an error is added to
     /// an internal errors vec
@@ -68,8 +96,11 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         let mut stmts = Vec::new();
 
         while !self.is_eof() {
+            let start = self.idx;
             if let Some(stmt) = self.parse() {
                 stmts.push(stmt);
+            } else {
+                self.synchronize(start);
             }
         }
 
@@ -159,6 +190,44 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         ok
     }
 
+    /// Skips tokens until the likely start of the next statement after a parse error,
+    /// so that the rest of the statement does not produce follow-up errors.
+    ///
+    /// `start` is the token index at which the failed statement began.
+    fn synchronize(&mut self, start: usize) {
+        // Always make progress, or a statement that failed without consuming any tokens would be parsed forever
+        if self.idx == start {
+            self.advance();
+        }
+
+        while let Some(token) = self.current() {
+            match token.ty {
+                TokenType::Semicolon => {
+                    self.advance();
+                    return;
+                }
+                // Leave the closing brace to the enclosing block
+                TokenType::RightBrace => return,
+                TokenType::Let
+                | TokenType::Const
+                | TokenType::Var
+                | TokenType::Function
+                | TokenType::Class
+                | TokenType::If
+                | TokenType::For
+                | TokenType::While
+                | TokenType::Do
+                | TokenType::Try
+                | TokenType::Throw
+                | TokenType::Return
+                | TokenType::Switch
+                | TokenType::Import
+                | TokenType::Export => return,
+                _ => self.advance(),
+            }
+        }
+    }
+
     fn create_error(&mut self, err: Error) {
         debug!("got error {:?}, recovering", err);
         if !self.error_sync {
@@ -226,6 +295,17 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         }
     }
 
+    /// Parses the identifier of a private name, assuming the `#` has already been consumed.
+    ///
+    /// Private names are lowered to `#`-prefixed bindings, which the compiler declares for each class that has private members
+    /// and which hold a private symbol that is unique to the class. `#` cannot appear in identifiers,
+    /// so these never collide with user bindings.
+    pub fn expect_private_name(&mut self) -> Option<Symbol> {
+        let ident = self.expect_identifier_or_reserved_kw(true)?;
+        let name = format!("#{}", self.interner.resolve(ident));
+        Some(self.interner.intern(name))
+    }
+
     pub fn expect_identifier_or_reserved_kw(&mut self, emit_error: bool) -> Option<Symbol> {
         // TODO: this isn't quite right, it should always skip, even if it didn't match. also the argument is useless, we always call it with false
         if self.expect_token_and_skip(
@@ -13,22 +13,25 @@ use dash_middle::sourcemap::Span;
 
 use crate::Parser;
 
-type ParameterList = Option<Vec<(Parameter, Option<Expr>, Option<TypeSegment>)>>;
+type ParameterList = Option<Vec<(Parameter, Option<Expr>, Option<TypeSegment>, Span)>>;
 
 impl<'a, 'interner> Parser<'a, 'interner> {
     pub fn parse_statement(&mut self) -> Option<Statement> {
         self.error_sync = false;
         let lo_span = self.current()?.span;
-        let kind = match self.next()?.ty {
+        let ty = self.next()?.ty;
+        let kind = match ty {
             TokenType::Let | TokenType::Const | TokenType::Var => self.parse_variable().map(StatementKind::Variable),
             TokenType::If => self.parse_if(true).map(StatementKind::If),
             TokenType::Function => self.parse_function(false).map(|(k, _)| StatementKind::Function(k)),
             TokenType::Async => {
-                // async must be followed by function (todo: or async () => {})
-                if !self.expect_token_type_and_skip(&[TokenType::Function], true) {
-                    return None;
+                if self.expect_token_type_and_skip(&[TokenType::Function], false) {
+                    self.parse_function(true).map(|(k, _)| StatementKind::Function(k))
+                } else {
+                    // Not an async function declaration, so it must be an expression statement (e.g. `async () => {}`)
+                    self.advance_back();
+                    self.parse_expression().map(StatementKind::Expression)
                 }
-                self.parse_function(true).map(|(k, _)| StatementKind::Function(k))
             }
             TokenType::LeftBrace => self.parse_block().map(StatementKind::Block),
             TokenType::While => self.parse_while_loop().map(StatementKind::Loop),
@@ -41,10 +44,16 @@ impl<'a, 'interner> Parser<'a, 'interner> {
             TokenType::Export => self.parse_export().map(StatementKind::Export),
             TokenType::Class => self.parse_class().map(StatementKind::Class),
             TokenType::Switch => self.parse_switch().map(StatementKind::Switch),
-            TokenType::Continue => Some(StatementKind::Continue),
-            TokenType::Break => Some(StatementKind::Break),
+            TokenType::Continue => Some(StatementKind::Continue(self.expect_identifier(false))),
+            TokenType::Break => Some(StatementKind::Break(self.expect_identifier(false))),
             TokenType::Debugger => Some(StatementKind::Debugger),
             TokenType::Semicolon => Some(StatementKind::Empty),
+            ty if ty.is_identifier() && self.expect_token_type_and_skip(&[TokenType::Colon], false) => {
+                // Labeled statement, e.g. `outer: for (;;) {}`
+                let label = ty.as_identifier().unwrap();
+                let body = self.parse_statement()?;
+                Some(StatementKind::Labeled(label, Box::new(body)))
+            }
             _ => {
                 // We've skipped the current character because of the statement cases that skip the current token
                 // So we go back, as the skipped token belongs to this expression
@@ -85,7 +94,6 @@ impl<'a, 'interner> Parser<'a, 'interner> {
             }
 
             let is_static = self.expect_token_type_and_skip(&[TokenType::Static], false);
-            let is_private = self.expect_token_type_and_skip(&[TokenType::Hash], false);
             let asyncness = match self.expect_token_type_and_skip(&[TokenType::Async], false) {
                 true => Asyncness::Yes,
                 false => Asyncness::No,
@@ -100,6 +108,9 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                 Kind::Normal
             };
 
+            // The `#` of a private name comes right before the name, e.g. `get #x()` or `async *#gen()`
+            let is_private = self.expect_token_type_and_skip(&[TokenType::Hash], false);
+
             let key = if self.expect_token_type_and_skip(&[TokenType::LeftSquareBrace], false) {
                 let expr = self.parse_expression()?;
                 self.expect_token_type_and_skip(&[TokenType::RightSquareBrace], true);
@@ -117,6 +128,8 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                     };
                     property_kind = Kind::Normal;
                     ClassMemberKey::Named(key)
+                } else if is_private {
+                    ClassMemberKey::Named(self.expect_private_name()?)
                 } else {
                     ClassMemberKey::Named(self.expect_identifier_or_reserved_kw(true)?)
                 }
@@ -148,7 +161,7 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                     arguments,
                     vec![body],
                     match is_generator {
-                        true => FunctionKind::Generator,
+                        true => FunctionKind::Generator(asyncness),
                         false => FunctionKind::Function(asyncness),
                     },
                     ty_seg,
@@ -199,6 +212,19 @@ impl<'a, 'interner> Parser<'a, 'interner> {
     }
 
     fn parse_export(&mut self) -> Option<ExportKind> {
+        if !self.mode.is_module() {
+            let span = self.previous()?.span;
+            self.create_error(Error::ModuleSyntaxInScript(span));
+            return None;
+        }
+
+        // `export *` re-exports all named exports of another module
+        if self.expect_token_type_and_skip(&[TokenType::Star], false) {
+            self.expect_identifier(true); // TODO: enforce == from
+            let specifier = self.expect_string(true)?;
+            return Some(ExportKind::All(specifier));
+        }
+
         let is_named = self.expect_token_type_and_skip(&[TokenType::LeftBrace], false);
 
         if is_named {
@@ -239,6 +265,13 @@ impl<'a, 'interner> Parser<'a, 'interner> {
             return Some(ImportKind::Dynamic(specifier));
         }
 
+        // Dynamic imports are fine anywhere, but static import declarations require a module
+        if !self.mode.is_module() {
+            let span = self.previous()?.span;
+            self.create_error(Error::ModuleSyntaxInScript(span));
+            return None;
+        }
+
         // `import` followed by a `*` imports all exported values
         let is_import_all = self.expect_token_type_and_skip(&[TokenType::Star], false);
         if is_import_all {
@@ -272,15 +305,15 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         let try_ = self.parse_statement()?;
 
         let catch = if self.expect_token_type_and_skip(&[TokenType::Catch], false) {
-            let capture_ident = if self.expect_token_type_and_skip(&[TokenType::LeftParen], false) {
-                let ident = self.expect_identifier(true)?;
+            let binding = if self.expect_token_type_and_skip(&[TokenType::LeftParen], false) {
+                let binding = self.parse_variable_binding_with_kind(VariableDeclarationKind::Var)?;
                 self.expect_token_type_and_skip(&[TokenType::RightParen], true);
-                Some(ident)
+                Some(binding)
             } else {
                 None
             };
 
-            Some(Catch::new(self.parse_statement()?, capture_ident))
+            Some(Catch::new(self.parse_statement()?, binding))
         } else {
             None
         };
@@ -308,6 +341,13 @@ impl<'a, 'interner> Parser<'a, 'interner> {
     }
 
     fn parse_for_loop(&mut self) -> Option<Loop> {
+        let await_span = if self.expect_token_type_and_skip(&[TokenType::Await], false) {
+            Some(self.previous()?.span)
+        } else {
+            None
+        };
+        let is_await = await_span.is_some();
+
         self.expect_token_type_and_skip(&[TokenType::LeftParen], true);
 
         let init = if self.expect_token_type_and_skip(&[TokenType::Semicolon], false) {
@@ -323,6 +363,11 @@ impl<'a, 'interner> Parser<'a, 'interner> {
 
                 if is_of_or_in {
                     let ty = self.previous()?.ty;
+                    if let (Some(await_span), TokenType::In) = (await_span, ty) {
+                        self.create_error(Error::Unexpected(await_span, "`await` in a for..in loop"));
+                        return None;
+                    }
+
                     let expr = self.parse_expression()?;
 
                     self.expect_token_type_and_skip(&[TokenType::RightParen], true);
@@ -331,7 +376,12 @@ impl<'a, 'interner> Parser<'a, 'interner> {
 
                     return Some(match ty {
                         TokenType::In => Loop::ForIn(ForInLoop { binding, expr, body }),
-                        TokenType::Of => Loop::ForOf(ForOfLoop { binding, expr, body }),
+                        TokenType::Of => Loop::ForOf(ForOfLoop {
+                            binding,
+                            expr,
+                            body,
+                            is_await,
+                        }),
                         _ => unreachable!(),
                     });
                 } else {
@@ -360,6 +410,12 @@ impl<'a, 'interner> Parser<'a, 'interner> {
             }
         };
 
+        // Only for..of loops can be `for await` loops, which have returned by now
+        if let Some(await_span) = await_span {
+            self.create_error(Error::Unexpected(await_span, "`await` in a for loop"));
+            return None;
+        }
+
         let cond = if self.expect_token_type_and_skip(&[TokenType::Semicolon], false) {
             None
         } else {
@@ -417,8 +473,11 @@ impl<'a, 'interner> Parser<'a, 'interner> {
                 return None;
             }
 
+            let start = self.idx;
             if let Some(stmt) = self.parse_statement() {
                 stmts.push(stmt);
+            } else {
+                self.synchronize(start);
             }
         }
         Some(BlockStatement(stmts))
@@ -483,9 +542,20 @@ impl<'a, 'interner> Parser<'a, 'interner> {
         let mut parameters = Vec::new();
 
         while !self.expect_token_type_and_skip(&[TokenType::RightParen], false) {
+            if !parameters.is_empty() {
+                if !self.expect_token_type_and_skip(&[TokenType::Comma], true) {
+                    return None;
+                }
+
+                // Trailing comma: function(a, b,) {}
+                if self.expect_token_type_and_skip(&[TokenType::RightParen], false) {
+                    break;
+                }
+            }
+
             let tok = self.next().cloned()?;
 
-            let parameter = match tok.ty {
+            let (parameter, span) = match tok.ty {
                 TokenType::Dot => {
                     // Begin of spread operator
                     for _ in 0..2 {
@@ -494,11 +564,10 @@ impl<'a, 'interner> Parser<'a, 'interner> {
 
                     let ident = self.expect_identifier(true)?;
 
-                    Parameter::Spread(ident)
+                    (Parameter::Spread(ident), self.previous()?.span)
                 }
-                TokenType::Comma => continue,
                 // TODO: refactor to if let guards once stable
-                other if other.is_identifier() => Parameter::Identifier(other.as_identifier().unwrap()),
+                other if other.is_identifier() => (Parameter::Identifier(other.as_identifier().unwrap()), tok.span),
                 _ => {
                     self.create_error(Error::UnexpectedToken(tok.clone(), TokenType::Comma));
                     return None;
@@ -521,7 +590,7 @@ impl<'a, 'interner> Parser<'a, 'interner> {
 
             let is_spread = matches!(parameter, Parameter::Spread(..));
 
-            parameters.push((parameter, default, ty));
+            parameters.push((parameter, default, ty, span));
 
             if is_spread {
                 // Must be followed by )
@@ -610,16 +679,13 @@ impl<'a, 'interner> Parser<'a, 'interner> {
             let mut rest = None;
 
             while !self.expect_token_type_and_skip(&[TokenType::RightSquareBrace], false) {
-                if !fields.is_empty() {
-                    self.expect_token_type_and_skip(&[TokenType::Comma], true);
-                }
-
                 let cur = self.current()?.clone();
                 match cur.ty {
-                    TokenType::RightSquareBrace => {
-                        // Trailing comma.
+                    TokenType::Comma => {
+                        // Hole, e.g. the first element in `[, b]`
                         self.advance();
-                        break;
+                        fields.push(None);
+                        continue;
                     }
                     TokenType::Dot => {
                         // Skip the dot
@@ -631,29 +697,37 @@ impl<'a, 'interner> Parser<'a, 'interner> {
 
                         let name = self.current()?.clone();
                         if let Some(sym) = name.ty.as_identifier() {
-                            if rest.is_some() {
-                                // Only allow one rest operator
-                                self.create_error(Error::MultipleRestInDestructuring(name));
-                                return None;
-                            }
-
                             rest = Some(sym);
                             self.advance();
                         } else {
                             self.create_error(Error::UnexpectedToken(name, TokenType::DUMMY_IDENTIFIER));
                             return None;
                         }
+
+                        // The rest element must be the last element
+                        if !self.expect_token_type_and_skip(&[TokenType::RightSquareBrace], true) {
+                            return None;
+                        }
+                        break;
                     }
                     other if other.is_identifier() => {
                         let name = other.as_identifier().unwrap();
                         self.advance();
-                        fields.push(name);
+                        fields.push(Some(name));
                     }
                     _ => {
                         self.create_error(Error::UnexpectedToken(cur, TokenType::DUMMY_IDENTIFIER));
                         return None;
                     }
                 }
+
+                // Elements are separated by commas, a trailing comma is allowed
+                if !self.expect_token_type_and_skip(&[TokenType::Comma], false) {
+                    if !self.expect_token_type_and_skip(&[TokenType::RightSquareBrace], true) {
+                        return None;
+                    }
+                    break;
+                }
             }
 
             VariableDeclarationName::ArrayDestructuring { fields, rest }
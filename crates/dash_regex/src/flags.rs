@@ -10,6 +10,7 @@ bitflags! {
     pub struct Flags: u8 {
         const GLOBAL = 1;
         const IGNORE_CASE = 2;
+        const MULTILINE = 4;
     }
 }
 
@@ -27,6 +28,7 @@ impl FromStr for Flags {
             match c {
                 'g' => flags |= Flags::GLOBAL,
                 'i' => flags |= Flags::IGNORE_CASE,
+                'm' => flags |= Flags::MULTILINE,
                 o => return Err(Error::UnknownFlag(o)),
             }
         }
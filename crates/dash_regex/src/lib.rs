@@ -20,16 +20,17 @@ pub fn test() {
     use parser::Parser;
 
     use crate::matcher::Matcher;
+    use crate::Flags;
 
     fn matches(regex: &str, input: &str) -> bool {
         let nodes = Parser::new(regex.as_bytes()).parse_all().unwrap();
-        let mut matcher = Matcher::new(&nodes, input.as_bytes());
+        let mut matcher = Matcher::new(&nodes, input.as_bytes(), Flags::empty());
         matcher.matches()
     }
 
     fn matches_groups(regex: &str, input: &str, groups: &[&str]) -> bool {
         let nodes = Parser::new(regex.as_bytes()).parse_all().unwrap();
-        let mut matcher = Matcher::new(&nodes, input.as_bytes());
+        let mut matcher = Matcher::new(&nodes, input.as_bytes(), Flags::empty());
         matcher.matches()
             && nodes.group_count - 1 == groups.len()
             && matcher
@@ -52,4 +53,12 @@ pub fn test() {
     const RGB: &str = r"rgb[\s|\(]+((?:[-\+]?\d*\.\d+%?)|(?:[-\+]?\d+%?))[,|\s]+((?:[-\+]?\d*\.\d+%?)|(?:[-\+]?\d+%?))[,|\s]+((?:[-\+]?\d*\.\d+%?)|(?:[-\+]?\d+%?))\s*\)?";
     assert!(matches(RGB, "rgb(255, 255, 255)"));
     assert!(matches_groups(RGB, "rgb(144, 17, 9)", &["144", "17", "9"]));
+
+    assert!(matches("^$", ""));
+    assert!(!matches("a.", "a"));
+
+    // `.` matches entire characters, not single bytes
+    assert!(matches("^.$", "é"));
+    assert!(matches("^a.b$", "a€b"));
+    assert!(!matches("^..$", "é"));
 }
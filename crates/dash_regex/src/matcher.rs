@@ -2,6 +2,7 @@ use std::ops::Range;
 
 use smallvec::{smallvec, SmallVec};
 
+use crate::flags::Flags;
 use crate::node::Node;
 use crate::parser::ParsedRegex;
 use crate::stream::BorrowedStream;
@@ -10,6 +11,7 @@ use crate::visitor::Visit;
 pub struct Matcher<'a> {
     nodes: BorrowedStream<'a, Node>,
     text: BorrowedStream<'a, u8>,
+    flags: Flags,
     pub groups: Groups,
 }
 
@@ -35,20 +37,32 @@ impl Groups {
 }
 
 impl<'a> Matcher<'a> {
-    pub fn new(parsed_regex: &'a ParsedRegex, text: &'a [u8]) -> Self {
+    pub fn new(parsed_regex: &'a ParsedRegex, text: &'a [u8], flags: Flags) -> Self {
         Self {
             nodes: BorrowedStream::new(parsed_regex.nodes.as_slice()),
             text: BorrowedStream::new(text),
+            flags,
             groups: Groups::new(parsed_regex.group_count),
         }
     }
 
     pub fn matches(&mut self) -> bool {
-        let mut index = self.text.index();
+        self.matches_from(0)
+    }
 
-        // TODO: what if text.len() == 0?
+    /// Searches for the first match that starts at or after the given byte offset.
+    /// Matches only start at character boundaries of the UTF-8 encoded text.
+    ///
+    /// Unlike matching against a subslice of the text, anchors still see the full text and group ranges are absolute.
+    pub fn matches_from(&mut self, start: usize) -> bool {
+        self.groups = Groups::new(self.groups.0.len());
+        self.nodes.set_index(0);
+        self.text.set_index(start);
+        self.text.skip_continuation_bytes();
+        let mut index = self.text.index();
 
-        while index < self.text.len() {
+        // Trying to match at the end of the text is required for patterns that can match the empty string
+        while index <= self.text.len() {
             if self.nodes.is_eof() {
                 // all regex nodes matched
                 self.groups.set(0, index..self.text.index());
@@ -56,9 +70,10 @@ impl<'a> Matcher<'a> {
             }
 
             if !self.matches_single() {
-                index += 1;
                 self.nodes.set_index(0);
                 self.text.set_index(index);
+                self.text.advance_char();
+                index = self.text.index();
             }
         }
 
@@ -67,6 +82,6 @@ impl<'a> Matcher<'a> {
 
     pub fn matches_single(&mut self) -> bool {
         let node = self.nodes.next().unwrap();
-        node.matches(&mut self.text, &mut self.groups)
+        node.matches(&mut self.text, &mut self.groups, self.flags)
     }
 }
@@ -22,6 +22,10 @@ impl<'a, T> BorrowedStream<'a, T> {
         self.source.get(self.index)
     }
 
+    pub fn previous(&self) -> Option<&T> {
+        self.index.checked_sub(1).and_then(|i| self.source.get(i))
+    }
+
     pub fn index(&self) -> usize {
         self.index
     }
@@ -38,3 +42,18 @@ impl<'a, T> BorrowedStream<'a, T> {
         self.index >= self.source.len()
     }
 }
+
+impl BorrowedStream<'_, u8> {
+    /// Advances past the UTF-8 encoded character at the current position
+    pub fn advance_char(&mut self) {
+        self.advance();
+        self.skip_continuation_bytes();
+    }
+
+    /// Advances to the next character boundary, if the current position is in the middle of a UTF-8 encoded character
+    pub fn skip_continuation_bytes(&mut self) {
+        while self.current().is_some_and(|&b| b & 0xC0 == 0x80) {
+            self.advance();
+        }
+    }
+}
@@ -1,22 +1,25 @@
+use crate::flags::Flags;
 use crate::matcher::Groups;
 use crate::node::{Anchor, CharacterClassItem, GroupCaptureMode, MetaSequence, Node};
 use crate::stream::BorrowedStream;
 
 pub trait Visit<'a> {
-    fn matches(&self, s: &mut BorrowedStream<'a, u8>, groups: &mut Groups) -> bool;
+    fn matches(&self, s: &mut BorrowedStream<'a, u8>, groups: &mut Groups, flags: Flags) -> bool;
 }
 
 impl<'a> Visit<'a> for Anchor {
-    fn matches(&self, s: &mut BorrowedStream<'a, u8>, _: &mut Groups) -> bool {
+    fn matches(&self, s: &mut BorrowedStream<'a, u8>, _: &mut Groups, flags: Flags) -> bool {
+        // In multiline mode, anchors also match at line boundaries
+        let multiline = flags.contains(Flags::MULTILINE);
         match self {
-            Anchor::StartOfString => s.index() == 0,
-            Anchor::EndOfString => s.is_eof(),
+            Anchor::StartOfString => s.index() == 0 || (multiline && s.previous() == Some(&b'\n')),
+            Anchor::EndOfString => s.is_eof() || (multiline && s.current() == Some(&b'\n')),
         }
     }
 }
 
 impl<'a> Visit<'a> for MetaSequence {
-    fn matches(&self, s: &mut BorrowedStream<'a, u8>, _: &mut Groups) -> bool {
+    fn matches(&self, s: &mut BorrowedStream<'a, u8>, _: &mut Groups, _: Flags) -> bool {
         match self {
             Self::Digit => {
                 let is_digit = s.current().map(|c| c.is_ascii_digit()).unwrap_or(false);
@@ -47,22 +50,26 @@ impl<'a> Visit<'a> for MetaSequence {
 }
 
 impl<'a> Visit<'a> for Node {
-    fn matches(&self, s: &mut BorrowedStream<'a, u8>, groups: &mut Groups) -> bool {
+    fn matches(&self, s: &mut BorrowedStream<'a, u8>, groups: &mut Groups, flags: Flags) -> bool {
+        let ignore_case = flags.contains(Flags::IGNORE_CASE);
         match self {
             Node::LiteralCharacter(lit) => {
-                let matches = s.current().map(|c| c == lit).unwrap_or(false);
+                let matches = s
+                    .current()
+                    .map(|c| c == lit || (ignore_case && c.eq_ignore_ascii_case(lit)))
+                    .unwrap_or(false);
                 if matches {
                     s.advance();
                 }
                 matches
             }
             Node::Optional(node) => {
-                node.matches(s, groups);
+                node.matches(s, groups, flags);
                 true
             }
             Node::Group(capture, group) => {
                 let before = s.index();
-                let all_matched = group.iter().all(|node| node.matches(s, groups));
+                let all_matched = group.iter().all(|node| node.matches(s, groups, flags));
 
                 match capture {
                     GroupCaptureMode::Id(id) if all_matched => {
@@ -74,15 +81,15 @@ impl<'a> Visit<'a> for Node {
             }
             Node::Or(left, right) => {
                 let left_index = s.index();
-                let left_matches = left.iter().all(|node| node.matches(s, groups));
+                let left_matches = left.iter().all(|node| node.matches(s, groups, flags));
                 if left_matches {
                     return true;
                 }
                 s.set_index(left_index);
-                right.iter().all(|node| node.matches(s, groups))
+                right.iter().all(|node| node.matches(s, groups, flags))
             }
-            Node::Anchor(anchor) => anchor.matches(s, groups),
-            Node::MetaSequence(seq) => seq.matches(s, groups),
+            Node::Anchor(anchor) => anchor.matches(s, groups, flags),
+            Node::MetaSequence(seq) => seq.matches(s, groups, flags),
             Node::Repetition { node, min, max } => {
                 let mut count = 0;
                 while !s.is_eof() {
@@ -92,7 +99,7 @@ impl<'a> Visit<'a> for Node {
                         }
                     }
 
-                    if !node.matches(s, groups) {
+                    if !node.matches(s, groups, flags) {
                         break;
                     }
                     count += 1;
@@ -103,16 +110,23 @@ impl<'a> Visit<'a> for Node {
                 true
             }
             Node::AnyCharacter => {
-                s.next();
-                true
+                let matches = !s.is_eof();
+                if matches {
+                    s.advance_char();
+                }
+                matches
             }
             Node::CharacterClass(nodes) => {
                 let Some(&cur) = s.current() else { return false };
 
                 nodes.iter().any(|node| match *node {
-                    CharacterClassItem::Node(ref node) => node.matches(s, groups),
+                    CharacterClassItem::Node(ref node) => node.matches(s, groups, flags),
                     CharacterClassItem::Range(start, end) => {
-                        let matches = (start..=end).contains(&cur);
+                        let range = start..=end;
+                        let matches = range.contains(&cur)
+                            || (ignore_case
+                                && (range.contains(&cur.to_ascii_lowercase())
+                                    || range.contains(&cur.to_ascii_uppercase())));
                         if matches {
                             s.advance();
                         }
@@ -3,6 +3,7 @@ use std::future::Future;
 
 use dash_compiler::FunctionCompiler;
 use dash_middle::compiler::CompileResult;
+use dash_parser::ParseMode;
 use dash_vm::frame::{Exports, Frame};
 use dash_vm::localscope::LocalScope;
 use dash_vm::value::function::native::CallContext;
@@ -75,11 +76,12 @@ pub fn format_value<'s>(value: Value, scope: &'s mut LocalScope) -> Result<&'s s
 
     let inspect_bc = INSPECT_BC.with(|tls| {
         let inspect = tls.get_or_init(|| {
-            FunctionCompiler::compile_str(
+            FunctionCompiler::compile_str_with_mode(
                 // TODO: can reuse a string interner if worth it
                 &mut scope.interner,
                 include_str!("../js/inspect.js"),
                 Default::default(),
+                ParseMode::Module,
             )
             .unwrap()
         });
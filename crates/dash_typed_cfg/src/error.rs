@@ -5,4 +5,12 @@ use thiserror::Error;
 pub enum Error {
     #[error("unsupported instruction")]
     UnsupportedInstruction { instr: Instruction },
+    #[error("incompatible type stored in local {index}")]
+    IncompatibleLocalType { index: u16 },
+    #[error("local {index} has a type that is not supported")]
+    UnsupportedLocalType { index: u16 },
+    #[error("constant {index} has a type that is not supported")]
+    UnsupportedConstantType { index: u16 },
+    #[error("values on the stack at the end of a basic block are not supported")]
+    NonEmptyStack,
 }
@@ -2,7 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use error::Error;
 use passes::bb_generation::{BBGenerationCtxt, BBGenerationQuery, BasicBlockMap, Labels};
-use passes::type_infer::{TypeInferCtxt, TypeInferQuery, TypeMap, TypeStack};
+use passes::type_infer::{TypeInferCtxt, TypeInferQuery, TypeMap};
 
 pub mod error;
 pub mod passes;
@@ -17,7 +17,7 @@ pub struct TypedCfg {
 }
 
 pub fn lower<Q: TypedCfgQuery>(bytecode: &[u8], query: &mut Q) -> Result<TypedCfg, Error> {
-    let Labels(labels) = passes::bb_generation::find_labels(bytecode).unwrap();
+    let Labels(labels) = passes::bb_generation::find_labels(bytecode)?;
 
     let mut bcx = BBGenerationCtxt {
         bytecode,
@@ -26,7 +26,7 @@ pub fn lower<Q: TypedCfgQuery>(bytecode: &[u8], query: &mut Q) -> Result<TypedCf
         query,
     };
     bcx.find_bbs();
-    bcx.resolve_edges();
+    bcx.resolve_edges()?;
 
     let mut tycx = TypeInferCtxt {
         bbs: bcx.bbs,
@@ -35,7 +35,7 @@ pub fn lower<Q: TypedCfgQuery>(bytecode: &[u8], query: &mut Q) -> Result<TypedCf
         query,
         visited: HashSet::new(),
     };
-    tycx.resolve_types(TypeStack::default(), 0)?;
+    tycx.resolve_types_fixpoint()?;
 
     Ok(TypedCfg {
         bb_map: tycx.bbs,
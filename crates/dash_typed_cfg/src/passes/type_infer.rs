@@ -17,8 +17,10 @@ pub enum Type {
 }
 
 pub trait TypeInferQuery {
-    fn type_of_local(&self, index: u16) -> Type;
-    fn type_of_constant(&self, index: u16) -> Type;
+    /// Returns the type of a local, or `None` if its value cannot be used in JIT compiled code
+    fn type_of_local(&self, index: u16) -> Option<Type>;
+    /// Returns the type of a constant, or `None` if it cannot be used in JIT compiled code
+    fn type_of_constant(&self, index: u16) -> Option<Type>;
 }
 
 #[derive(Clone, Default)]
@@ -37,6 +39,15 @@ impl TypeStack {
     fn push(&mut self, ty: Type) {
         self.0.push(ty);
     }
+
+    /// Values are not carried over to other basic blocks, so the stack needs to be empty when leaving one
+    fn ensure_empty(&self) -> Result<(), Error> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::NonEmptyStack)
+        }
+    }
 }
 
 pub struct TypeInferCtxt<'a, 'q, Q> {
@@ -51,16 +62,35 @@ pub struct TypeInferCtxt<'a, 'q, Q> {
 }
 
 impl<'a, 'q, Q: TypeInferQuery> TypeInferCtxt<'a, 'q, Q> {
-    fn get_or_insert_local_ty(&mut self, index: u16) -> Type {
+    fn get_or_insert_local_ty(&mut self, index: u16) -> Result<Type, Error> {
         match self.local_tys.get(&index) {
-            Some(ty) => ty.clone(),
+            Some(ty) => Ok(ty.clone()),
             None => {
-                let ty = self.query.type_of_local(index);
+                let ty = self
+                    .query
+                    .type_of_local(index)
+                    .ok_or(Error::UnsupportedLocalType { index })?;
                 self.local_tys.insert(index, ty.clone());
-                ty
+                Ok(ty)
             }
         }
     }
+    /// Resolves the types of all reachable basic blocks, starting at the first one,
+    /// repeating until the types of locals no longer change.
+    ///
+    /// Locals can only ever be widened from I64 to F64, so this terminates after at most one round per local.
+    pub fn resolve_types_fixpoint(&mut self) -> Result<(), Error> {
+        loop {
+            let local_tys = self.local_tys.clone();
+            self.visited.clear();
+            self.resolve_types(TypeStack::default(), 0)?;
+
+            if self.local_tys == local_tys {
+                return Ok(());
+            }
+        }
+    }
+
     pub fn resolve_types(&mut self, mut ty_stack: TypeStack, bbk: BasicBlockKey) -> Result<(), Error> {
         // If this BB is in the list of visited BBs
         // do not resolve it again
@@ -86,12 +116,12 @@ impl<'a, 'q, Q: TypeInferQuery> TypeInferCtxt<'a, 'q, Q> {
                     (Type::F64, Type::I64) => ty_stack.push(Type::F64),
                     (Type::F64, Type::F64) => ty_stack.push(Type::F64),
                     (Type::Boolean, Type::Boolean) => ty_stack.push(Type::Boolean),
-                    _ => todo!(),
+                    _ => return Err(Error::UnsupportedInstruction { instr }),
                 },
                 Instruction::Div | Instruction::Rem | Instruction::Pow => match ty_stack.pop_two() {
                     (Type::F64 | Type::I64, Type::F64 | Type::I64) => ty_stack.push(Type::F64),
                     (Type::Boolean, Type::Boolean) => ty_stack.push(Type::Boolean),
-                    _ => todo!(),
+                    _ => return Err(Error::UnsupportedInstruction { instr }),
                 },
                 Instruction::Gt
                 | Instruction::Ge
@@ -110,7 +140,7 @@ impl<'a, 'q, Q: TypeInferQuery> TypeInferCtxt<'a, 'q, Q> {
                         _ => unreachable!(),
                     };
 
-                    let ty = self.get_or_insert_local_ty(index);
+                    let ty = self.get_or_insert_local_ty(index)?;
                     ty_stack.push(ty);
                 }
                 Instruction::Constant | Instruction::ConstantW => {
@@ -120,7 +150,10 @@ impl<'a, 'q, Q: TypeInferQuery> TypeInferCtxt<'a, 'q, Q> {
                         _ => unreachable!(),
                     };
 
-                    let ty = self.query.type_of_constant(index);
+                    let ty = self
+                        .query
+                        .type_of_constant(index)
+                        .ok_or(Error::UnsupportedConstantType { index })?;
                     ty_stack.push(ty);
                 }
                 Instruction::StoreLocal | Instruction::StoreLocalW => {
@@ -132,20 +165,30 @@ impl<'a, 'q, Q: TypeInferQuery> TypeInferCtxt<'a, 'q, Q> {
                     let _kind = dcx.next_byte();
 
                     let ty = ty_stack.pop();
-                    let ty_local = self.get_or_insert_local_ty(index);
-                    assert_eq!(ty, ty_local, "type must not change");
-                    ty_stack.push(ty);
-                    // Do nothing (for now); types cannot (must not) change in JIT
+                    let ty_local = self.get_or_insert_local_ty(index)?;
+                    match (&ty_local, &ty) {
+                        _ if ty_local == ty => {}
+                        // An integer stored in a float local is converted on store
+                        (Type::F64, Type::I64) => {}
+                        // A float stored in an integer local (e.g. an accumulator that starts out as `0`)
+                        // widens the local. Loads that were already inferred as I64 are fixed up
+                        // by the next round in `resolve_types_fixpoint`
+                        (Type::I64, Type::F64) => {
+                            self.local_tys.insert(index, Type::F64);
+                        }
+                        _ => return Err(Error::IncompatibleLocalType { index }),
+                    }
+                    ty_stack.push(self.local_tys[&index].clone());
                 }
                 Instruction::Pos => match ty_stack.pop() {
                     Type::I64 => ty_stack.push(Type::I64),
                     Type::F64 => ty_stack.push(Type::F64),
-                    _ => todo!(),
+                    _ => return Err(Error::UnsupportedInstruction { instr }),
                 },
                 Instruction::Neg => match ty_stack.pop() {
                     Type::I64 => ty_stack.push(Type::I64),
                     Type::F64 => ty_stack.push(Type::F64),
-                    _ => todo!(),
+                    _ => return Err(Error::UnsupportedInstruction { instr }),
                 },
                 Instruction::Not => {
                     ty_stack.pop();
@@ -154,11 +197,17 @@ impl<'a, 'q, Q: TypeInferQuery> TypeInferCtxt<'a, 'q, Q> {
                 Instruction::Ret => {
                     dcx.next_wide();
                     ty_stack.pop();
+                    ty_stack.ensure_empty()?;
+
+                    // Anything after a return in the same basic block is unreachable
+                    return Ok(());
                 }
                 Instruction::Jmp => {
                     let count = dcx.next_wide_signed();
                     let _target_ip = usize::try_from(index as i16 + count + 3).unwrap();
 
+                    ty_stack.ensure_empty()?;
+
                     let bb = &self.bbs[&bbk];
                     let Some(BasicBlockSuccessor::Unconditional(succ)) = bb.successor else {
                         panic!("unmatched basic block successor");
@@ -166,8 +215,6 @@ impl<'a, 'q, Q: TypeInferQuery> TypeInferCtxt<'a, 'q, Q> {
                     self.resolve_types(ty_stack.clone(), succ)?;
                     return Ok(());
                 }
-                Instruction::StrictEq => todo!(),
-                Instruction::StrictNe => todo!(),
                 Instruction::JmpFalseP
                 | Instruction::JmpFalseNP
                 | Instruction::JmpTrueP
@@ -185,6 +232,8 @@ impl<'a, 'q, Q: TypeInferQuery> TypeInferCtxt<'a, 'q, Q> {
                         }
                         _ => {}
                     }
+                    ty_stack.ensure_empty()?;
+
                     let count = dcx.next_wide_signed();
                     let _target_ip = usize::try_from(index as i16 + count + 3).unwrap();
 
@@ -259,7 +308,7 @@ impl<'a, 'q, Q: TypeInferQuery> TypeInferCtxt<'a, 'q, Q> {
                         | IntrinsicOperation::PrefixIncLocalNum
                         | IntrinsicOperation::PrefixDecLocalNum => {
                             let id = dcx.next_byte();
-                            let ty = self.get_or_insert_local_ty(id.into());
+                            let ty = self.get_or_insert_local_ty(id.into())?;
                             ty_stack.push(ty);
                         }
 
@@ -295,9 +344,92 @@ impl<'a, 'q, Q: TypeInferQuery> TypeInferCtxt<'a, 'q, Q> {
             let BasicBlockSuccessor::Unconditional(target) = succ else {
                 panic!("mismatching basic block successor");
             };
+            ty_stack.ensure_empty()?;
             self.resolve_types(ty_stack.clone(), target)?;
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dash_compiler::FunctionCompiler;
+    use dash_middle::compiler::constant::Constant;
+    use dash_middle::interner::StringInterner;
+    use dash_optimizer::OptLevel;
+
+    use crate::error::Error;
+    use crate::passes::bb_generation::{BBGenerationQuery, ConditionalBranchAction};
+    use crate::passes::type_infer::{Type, TypeInferQuery};
+    use crate::TypedCfgQuery;
+
+    /// Types every local as I64, as if all of them were initialized to `0`,
+    /// and assumes that every conditional jump (i.e. the loop condition) is not taken
+    struct LoopQuery {
+        constants: Vec<Constant>,
+    }
+
+    impl BBGenerationQuery for LoopQuery {
+        fn conditional_branch_at(&self, _: usize) -> Option<ConditionalBranchAction> {
+            Some(ConditionalBranchAction::NotTaken)
+        }
+    }
+
+    impl TypeInferQuery for LoopQuery {
+        fn type_of_local(&self, _: u16) -> Option<Type> {
+            Some(Type::I64)
+        }
+
+        fn type_of_constant(&self, index: u16) -> Option<Type> {
+            match self.constants[usize::from(index)] {
+                Constant::Number(n) if n.fract() == 0.0 => Some(Type::I64),
+                Constant::Number(_) => Some(Type::F64),
+                Constant::Boolean(_) => Some(Type::Boolean),
+                _ => None,
+            }
+        }
+    }
+
+    impl TypedCfgQuery for LoopQuery {}
+
+    fn lower(source: &str) -> Result<crate::TypedCfg, Error> {
+        let cr = FunctionCompiler::compile_str(&mut StringInterner::new(), source, OptLevel::None).unwrap();
+        let mut query = LoopQuery {
+            constants: cr.cp.to_vec(),
+        };
+        crate::lower(&cr.instructions, &mut query)
+    }
+
+    #[test]
+    fn fixpoint_widens_accumulator() {
+        let tcfg = lower("let sum = 0; for (let i = 1; i <= 1000; i++) { sum = sum + i / 4; }").unwrap();
+        assert_eq!(tcfg.ty_map[&0], Type::F64);
+        assert_eq!(tcfg.ty_map[&1], Type::I64);
+    }
+
+    #[test]
+    fn fixpoint_keeps_integer_locals() {
+        let tcfg = lower("let sum = 0; for (let i = 1; i <= 1000; i++) { sum = sum + i * 2; }").unwrap();
+        assert_eq!(tcfg.ty_map[&0], Type::I64);
+        assert_eq!(tcfg.ty_map[&1], Type::I64);
+    }
+
+    #[test]
+    fn incompatible_store_is_an_error() {
+        let res = lower("let x = 0; for (let i = 1; i <= 1000; i++) { x = true; }");
+        assert!(matches!(res, Err(Error::IncompatibleLocalType { index: 0 })));
+    }
+
+    #[test]
+    fn values_across_basic_blocks_are_an_error() {
+        let res = lower("let x = 0; for (let i = 1; i <= 1000; i++) { x = i > 500 ? 1 : 2; }");
+        assert!(matches!(res, Err(Error::NonEmptyStack)));
+    }
+
+    #[test]
+    fn unsupported_constant_is_an_error() {
+        let res = lower(r#"let x = 0; for (let i = 1; i <= 1000; i++) { x = "a"; }"#);
+        assert!(matches!(res, Err(Error::UnsupportedConstantType { .. })));
+    }
+}
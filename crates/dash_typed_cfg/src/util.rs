@@ -3,6 +3,8 @@ use std::slice::Iter;
 
 use dash_middle::compiler::instruction::{Instruction, IntrinsicOperation};
 
+use crate::error::Error;
+
 #[derive(Debug)]
 pub struct DecodeCtxt<'a> {
     iter: Enumerate<Iter<'a, u8>>,
@@ -50,7 +52,9 @@ impl<'a> DecodeCtxt<'a> {
     /// Decodes an instruction and does nothing with it apart from advancing the iterator.
     /// Useful for passes that are only interested in a few instructions
     /// and do not care about the rest. For the other instructions, they can call this method.
-    pub fn decode_ignore(&mut self, instr: Instruction) {
+    ///
+    /// Returns an error for instructions that the JIT does not support.
+    pub fn decode_ignore(&mut self, instr: Instruction) -> Result<(), Error> {
         match instr {
             Instruction::Add
             | Instruction::Sub
@@ -71,6 +75,8 @@ impl<'a> DecodeCtxt<'a> {
                 self.next_byte();
             }
             Instruction::Not
+            | Instruction::Neg
+            | Instruction::Pos
             | Instruction::Lt
             | Instruction::Le
             | Instruction::Gt
@@ -118,12 +124,13 @@ impl<'a> DecodeCtxt<'a> {
                     | IntrinsicOperation::GeNumLConstR32
                     | IntrinsicOperation::LtNumLConstR32
                     | IntrinsicOperation::LeNumLConstR32 => drop(self.next_u32()),
-                    other => todo!("{other:?}"),
+                    _ => return Err(Error::UnsupportedInstruction { instr }),
                 }
             }
             Instruction::Pop => {}
             Instruction::Ret => drop(self.next_wide()),
-            other => todo!("{other:?}"),
+            _ => return Err(Error::UnsupportedInstruction { instr }),
         }
+        Ok(())
     }
 }
@@ -12,7 +12,7 @@ function fib(n) {
     if (n <= 1) return n;
     return fib(n - 1) + fib(n - 2);
 }
-return fib(12);
+fib(12);
 ";
 const FIBONACCI_ITERATIVE: &str = r"
 function fib(n) {
@@ -26,7 +26,41 @@ function fib(n) {
     }
     return fib;
 }
-return fib(12);
+fib(12);
+";
+const PROPERTY_ACCESS: &str = r"
+class Point {
+    constructor(x, y) { this.x = x; this.y = y; }
+    sum() { return this.x + this.y; }
+}
+const points = [];
+for (let i = 0; i < 100; i++) points.push(new Point(i, i));
+let total = 0;
+for (let j = 0; j < 500; j++) {
+    for (let i = 0; i < points.length; i++) {
+        const p = points[i];
+        total += p.x + p.y + p.sum();
+    }
+}
+total;
+";
+const ARRAY_INDEX_OF: &str = r"
+const numbers = [];
+for (let i = 0; i < 10000; i++) numbers.push(i * 2);
+let found = 0;
+for (let i = 0; i < 100; i++) {
+    found += numbers.indexOf(19998) + numbers.indexOf(-1);
+}
+found;
+";
+const ARRAY_JOIN: &str = r"
+const strings = [];
+for (let i = 0; i < 10000; i++) strings.push('item' + i);
+let length = 0;
+for (let i = 0; i < 10; i++) {
+    length += strings.join(', ').length + strings.toString().length;
+}
+length;
 ";
 
 pub fn benchmark(cr: &mut Criterion) {
@@ -48,6 +82,24 @@ pub fn benchmark(cr: &mut Criterion) {
             vm.eval(FIBONACCI_ITERATIVE, OptLevel::Aggressive).unwrap();
         })
     });
+    cr.bench_function("property_access(50000)", |b| {
+        b.iter(|| {
+            let mut vm = Vm::new(Default::default());
+            vm.eval(PROPERTY_ACCESS, OptLevel::Aggressive).unwrap();
+        })
+    });
+    cr.bench_function("array_index_of(10000)", |b| {
+        b.iter(|| {
+            let mut vm = Vm::new(Default::default());
+            vm.eval(ARRAY_INDEX_OF, OptLevel::Aggressive).unwrap();
+        })
+    });
+    cr.bench_function("array_join(10000)", |b| {
+        b.iter(|| {
+            let mut vm = Vm::new(Default::default());
+            vm.eval(ARRAY_JOIN, OptLevel::Aggressive).unwrap();
+        })
+    });
     let mut tinycolor2 = ureq::get("https://www.unpkg.com/tinycolor2@1.6.0/cjs/tinycolor.js")
         .call()
         .unwrap()
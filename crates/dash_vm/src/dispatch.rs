@@ -200,7 +200,6 @@ mod extract {
 
     use crate::gc::handle::Handle;
     use crate::value::object::{PropertyKey, PropertyValue};
-    use crate::value::ops::conversions::ValueConversion;
     use crate::value::string::JsString;
     use crate::value::{Unrooted, Value};
 
@@ -309,6 +308,7 @@ mod extract {
         Getter { key: PropertyKey, value: Handle },
         Setter { key: PropertyKey, value: Handle },
         Spread(Value),
+        Prototype(Value),
     }
 
     pub struct IdentW(pub JsString);
@@ -453,6 +453,7 @@ mod extract {
                     }
                 }
                 ObjectMemberKind::Spread => Self::Spread(extract(cx)),
+                ObjectMemberKind::Prototype => Self::Prototype(extract(cx)),
             })
         }
     }
@@ -460,7 +461,7 @@ mod extract {
     #[derive(Debug)]
     pub enum ArrayElement {
         Single(Value),
-        Spread(Value, usize),
+        Spread(Vec<Value>),
         Hole(usize),
     }
 
@@ -475,9 +476,8 @@ mod extract {
                 ArrayMemberKind::Item => ArrayElement::Single(extract_front(seq, cx)),
                 ArrayMemberKind::Spread => {
                     let value: Value = extract_front(seq, cx);
-                    // TODO: make this work for array-like values, not just arrays, by calling @@iterator on it
-                    let len = value.length_of_array_like(cx.scope)?;
-                    ArrayElement::Spread(value, len)
+                    let values = cx.scope.iterate(value)?.collect::<Result<Vec<_>, _>>()?;
+                    ArrayElement::Spread(values)
                 }
                 ArrayMemberKind::Empty => {
                     let count = cx.fetch_and_inc_ip();
@@ -553,25 +553,31 @@ mod extract {
 }
 
 mod handlers {
+    use dash_middle::compiler::constant::InlineCache;
     use dash_middle::compiler::instruction::{AssignKind, IntrinsicOperation};
     use dash_middle::compiler::{FunctionCallMetadata, StaticImportKind};
     use dash_middle::interner::sym;
     use dash_middle::iterator_with::{InfallibleIteratorWith, IteratorWith};
     use handlers::extract::{extract, ForwardSequence, FrontIteratorWith};
-    use hashbrown::hash_map::Entry;
     use if_chain::if_chain;
+    use rustc_hash::FxHashSet;
     use smallvec::SmallVec;
     use std::ops::{Add, ControlFlow, Div, Mul, Rem, Sub};
 
     use crate::frame::{FrameState, TryBlock};
+    use crate::gc::handle::Handle;
     use crate::throw;
     use crate::util::unlikely;
     use crate::value::array::{Array, ArrayIterator, Element};
     use crate::value::function::user::UserFunction;
     use crate::value::function::{adjust_stack_from_flat_call, Function, FunctionKind};
-    use crate::value::object::{NamedObject, Object, ObjectMap, PropertyKey, PropertyValue, PropertyValueKind};
+    use crate::value::object::{
+        NamedObject, Object, PropertyDataDescriptor, PropertyKey, PropertyValue, PropertyValueKind,
+    };
     use crate::value::ops::conversions::ValueConversion;
     use crate::value::ops::equality;
+    use crate::value::primitive::Symbol;
+    use crate::value::root_ext::RootErrExt;
 
     use self::extract::{ArrayElement, BackwardSequence, ExportProperty, IdentW, NumberWConstant, ObjectProperty};
 
@@ -654,13 +660,13 @@ mod handlers {
 
     pub fn objin<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
         cx.evaluate_binary_with_scope(|property, target, sc| {
-            let property = property.to_js_string(sc)?;
+            let property = PropertyKey::from_value(sc, property.clone())?;
             let found = target
                 .for_each_prototype(sc, |sc, target| {
                     let contains = target
-                        .own_keys(sc)?
-                        .iter()
-                        .any(|v| matches!(v, Value::String(s) if *s == property));
+                        .get_own_property_descriptor(sc, property.clone())
+                        .root_err(sc)?
+                        .is_some();
 
                     if contains {
                         Ok(ControlFlow::Break(()))
@@ -735,6 +741,80 @@ mod handlers {
         Ok(None)
     }
 
+    pub fn to_string<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
+        let value = cx.pop_stack_rooted();
+        let string = value.to_js_string(cx.scope)?;
+        cx.stack.push(Value::String(string));
+        Ok(None)
+    }
+
+    pub fn template_object<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
+        let raw = cx.pop_stack_rooted();
+        let strings = cx.pop_stack_rooted();
+
+        strings.set_property(cx.scope, sym::raw.into(), PropertyValue::static_empty(raw.clone()))?;
+
+        for array in [&raw, &strings] {
+            if let Some(array) = array.downcast_ref::<Array>() {
+                array.freeze();
+            }
+        }
+
+        cx.stack.push(strings);
+        Ok(None)
+    }
+
+    pub fn private_symbol<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
+        let description = cx.pop_stack_rooted().to_js_string(cx.scope)?;
+        cx.stack.push(Value::Symbol(Symbol::private(description)));
+        Ok(None)
+    }
+
+    /// Calls the superclass constructor in a derived class constructor and binds its result as `this`.
+    ///
+    /// The frame of a derived constructor starts out with an instance whose prototype is `new.target.prototype`,
+    /// which user-defined superclass constructors initialize in place. Any other constructor creates its own object,
+    /// which then needs to inherit from `new.target.prototype` instead.
+    pub fn super_call<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
+        let (superclass, arguments) = cx.pop_stack2_rooted();
+
+        let mut args = Vec::new();
+        for i in 0..arguments.length_of_array_like(cx.scope)? {
+            let i = cx.scope.intern_usize(i);
+            let arg = arguments.get_property(&mut cx, i.into())?.root(cx.scope);
+            args.push(arg);
+        }
+
+        let Some(Value::Object(instance)) = cx.active_frame().this.clone() else {
+            throw!(
+                cx,
+                ReferenceError,
+                "super() can only be called in a derived class constructor"
+            )
+        };
+
+        let this = match superclass.downcast_ref::<Function>().map(Function::kind) {
+            Some(FunctionKind::User(fun)) => {
+                let HandleResult::Return(this) =
+                    fun.handle_function_call(&mut cx, Value::Object(instance), args, true)?
+                else {
+                    unreachable!() // UserFunction cannot `yield`/`await`
+                };
+                this.root(cx.scope)
+            }
+            _ => {
+                let prototype = instance.get_prototype(&mut cx)?;
+                let this = superclass.construct(&mut cx, Value::undefined(), args)?.root(cx.scope);
+                this.set_prototype(&mut cx, prototype)?;
+                this
+            }
+        };
+
+        cx.active_frame_mut().this = Some(this.clone());
+        cx.stack.push(this);
+        Ok(None)
+    }
+
     pub fn pop<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
         cx.pop_stack();
         Ok(None)
@@ -901,6 +981,18 @@ mod handlers {
             AssignKind::Assignment => {
                 let value = cx.pop_stack_rooted();
 
+                // In strict mode, assigning to an undeclared variable does not implicitly create a global
+                if cx.active_frame().function.is_strict
+                    && cx
+                        .global
+                        .clone()
+                        .get_property_descriptor(&mut cx, name.into())?
+                        .is_none()
+                {
+                    let name = name.res(cx.scope).to_owned();
+                    throw!(&mut cx, ReferenceError, "{} is not defined", name)
+                }
+
                 cx.global
                     .clone()
                     .set_property(&mut cx, name.into(), PropertyValue::static_default(value.clone()))?;
@@ -934,6 +1026,7 @@ mod handlers {
         this: Value,
         function: &Function,
         user_function: &UserFunction,
+        lexical_arguments: Option<Handle>,
         argc: usize,
         is_constructor: bool,
     ) -> Result<Option<HandleResult>, Unrooted> {
@@ -980,7 +1073,7 @@ mod handlers {
         // reference list since they stay on the VM stack
         // and are reachable from there
 
-        let arguments = adjust_stack_from_flat_call(&mut cx, user_function, sp, argc);
+        let arguments = adjust_stack_from_flat_call(&mut cx, user_function, sp, argc).or(lexical_arguments);
 
         let mut frame = Frame::from_function(Some(this), user_function, is_constructor, true, arguments);
         frame.set_sp(sp);
@@ -1071,10 +1164,20 @@ mod handlers {
 
         if let Some(function) = callee.downcast_ref::<Function>() {
             match function.kind() {
-                FunctionKind::User(user) => call_flat(cx, &callee, this, function, user, argc, is_constructor),
+                FunctionKind::User(user) => call_flat(cx, &callee, this, function, user, None, argc, is_constructor),
                 FunctionKind::Closure(closure) => {
                     let bound_this = closure.this.clone();
-                    call_flat(cx, &callee, bound_this, function, &closure.fun, argc, is_constructor)
+                    let arguments = closure.arguments.clone();
+                    call_flat(
+                        cx,
+                        &callee,
+                        bound_this,
+                        function,
+                        &closure.fun,
+                        arguments,
+                        argc,
+                        is_constructor,
+                    )
                 }
                 _ => call_generic(cx, &callee, this, argc, is_constructor, call_ip),
             }
@@ -1090,7 +1193,7 @@ mod handlers {
         let offset = cx.fetchw_and_inc_ip() as i16;
         let value = cx.pop_stack_rooted();
 
-        let jump = !value.is_truthy(cx.scope);
+        let jump = !value.to_boolean(cx.scope)?;
 
         #[cfg(feature = "jit")]
         cx.record_conditional_jump(ip, jump);
@@ -1100,6 +1203,7 @@ mod handlers {
 
             if offset.is_negative() {
                 frame.ip -= -offset as usize;
+                cx.check_interrupt()?;
             } else {
                 frame.ip += offset as usize;
             }
@@ -1114,7 +1218,7 @@ mod handlers {
         let offset = cx.fetchw_and_inc_ip() as i16;
         let value = cx.peek_stack();
 
-        let jump = !value.is_truthy(cx.scope);
+        let jump = !value.to_boolean(cx.scope)?;
 
         #[cfg(feature = "jit")]
         cx.record_conditional_jump(ip, jump);
@@ -1124,6 +1228,7 @@ mod handlers {
 
             if offset.is_negative() {
                 frame.ip -= -offset as usize;
+                cx.check_interrupt()?;
             } else {
                 frame.ip += offset as usize;
             }
@@ -1139,7 +1244,7 @@ mod handlers {
         let offset = cx.fetchw_and_inc_ip() as i16;
         let value = cx.pop_stack_rooted();
 
-        let jump = value.is_truthy(cx.scope);
+        let jump = value.to_boolean(cx.scope)?;
 
         #[cfg(feature = "jit")]
         cx.record_conditional_jump(ip, jump);
@@ -1149,6 +1254,7 @@ mod handlers {
 
             if offset.is_negative() {
                 frame.ip -= -offset as usize;
+                cx.check_interrupt()?;
             } else {
                 frame.ip += offset as usize;
             }
@@ -1163,7 +1269,7 @@ mod handlers {
         let offset = cx.fetchw_and_inc_ip() as i16;
         let value = cx.peek_stack();
 
-        let jump = value.is_truthy(cx.scope);
+        let jump = value.to_boolean(cx.scope)?;
 
         #[cfg(feature = "jit")]
         cx.record_conditional_jump(ip, jump);
@@ -1173,6 +1279,7 @@ mod handlers {
 
             if offset.is_negative() {
                 frame.ip -= -offset as usize;
+                cx.check_interrupt()?;
             } else {
                 frame.ip += offset as usize;
             }
@@ -1197,6 +1304,7 @@ mod handlers {
 
             if offset.is_negative() {
                 frame.ip -= -offset as usize;
+                cx.check_interrupt()?;
             } else {
                 frame.ip += offset as usize;
             }
@@ -1221,6 +1329,7 @@ mod handlers {
 
             if offset.is_negative() {
                 frame.ip -= -offset as usize;
+                cx.check_interrupt()?;
             } else {
                 frame.ip += offset as usize;
             }
@@ -1250,6 +1359,7 @@ mod handlers {
 
             if offset.is_negative() {
                 frame.ip -= -offset as usize;
+                cx.check_interrupt()?;
             } else {
                 frame.ip += offset as usize;
             }
@@ -1279,6 +1389,7 @@ mod handlers {
 
             if offset.is_negative() {
                 frame.ip -= -offset as usize;
+                cx.check_interrupt()?;
             } else {
                 frame.ip += offset as usize;
             }
@@ -1298,6 +1409,7 @@ mod handlers {
             let old_ip = frame.ip;
 
             frame.ip -= -offset as usize;
+            cx.check_interrupt()?;
 
             // Negative jumps are (currently) always also a marker for the end of a loop
             // and we want to JIT compile loops that run often
@@ -1392,11 +1504,8 @@ mod handlers {
         while let Some(element) = iter.next_front(cx) {
             match element? {
                 ArrayElement::Single(value) => fun(Element::Value(PropertyValue::static_default(value))),
-                ArrayElement::Spread(source, len) => {
-                    for i in 0..len {
-                        let i = cx.scope.intern_usize(i);
-
-                        let value = source.get_property(cx.scope, i.into())?.root(cx.scope);
+                ArrayElement::Spread(values) => {
+                    for value in values {
                         fun(Element::Value(PropertyValue::static_default(value)));
                     }
                 }
@@ -1444,38 +1553,67 @@ mod handlers {
     pub fn objlit<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
         let mut iter = BackwardSequence::<ObjectProperty>::new_u16(&mut cx);
 
-        let mut obj = ObjectMap::default();
+        let mut properties = Vec::new();
         while let Some(property) = iter.next(&mut cx) {
-            match property? {
-                ObjectProperty::Static { key, value } => drop(obj.insert(key, value)),
-                ObjectProperty::Getter { key, value } => match obj.entry(key) {
-                    Entry::Occupied(mut entry) => match &mut entry.get_mut().kind {
-                        PropertyValueKind::Static(_) => drop(entry.insert(PropertyValue::getter_default(value))),
-                        PropertyValueKind::Trap { get, .. } => *get = Some(value),
-                    },
-                    Entry::Vacant(entry) => drop(entry.insert(PropertyValue::getter_default(value))),
+            properties.push(property?);
+        }
+
+        // Properties are popped off the stack in reverse order.
+        // Defining them in source order makes later properties override earlier ones, and gives objects created
+        // by the same literal the same shape.
+        let obj = NamedObject::new(&cx);
+        for property in properties.into_iter().rev() {
+            match property {
+                ObjectProperty::Static { key, value } => obj.set_raw_property(key, value),
+                ObjectProperty::Getter { key, value } => match obj.get_raw_property(key.clone()) {
+                    Some(PropertyValue {
+                        kind: PropertyValueKind::Trap { set, .. },
+                        descriptor,
+                    }) => obj.set_raw_property(
+                        key,
+                        PropertyValue::new(PropertyValueKind::Trap { get: Some(value), set }, descriptor),
+                    ),
+                    _ => obj.set_raw_property(key, PropertyValue::getter_default(value)),
                 },
-                ObjectProperty::Setter { key, value } => match obj.entry(key) {
-                    Entry::Occupied(mut entry) => match &mut entry.get_mut().kind {
-                        PropertyValueKind::Static(_) => drop(entry.insert(PropertyValue::setter_default(value))),
-                        PropertyValueKind::Trap { set, .. } => *set = Some(value),
-                    },
-                    Entry::Vacant(entry) => drop(entry.insert(PropertyValue::setter_default(value))),
+                ObjectProperty::Setter { key, value } => match obj.get_raw_property(key.clone()) {
+                    Some(PropertyValue {
+                        kind: PropertyValueKind::Trap { get, .. },
+                        descriptor,
+                    }) => obj.set_raw_property(
+                        key,
+                        PropertyValue::new(PropertyValueKind::Trap { get, set: Some(value) }, descriptor),
+                    ),
+                    _ => obj.set_raw_property(key, PropertyValue::setter_default(value)),
                 },
                 ObjectProperty::Spread(value) => {
-                    if let Value::Object(object) = value {
-                        for key in object.own_keys(cx.scope)? {
-                            let key = PropertyKey::from_value(cx.scope, key)?;
-                            let value = object.get_property(&mut cx, key.clone())?.root(cx.scope);
-                            obj.insert(key, PropertyValue::static_default(value));
+                    // Spreading `null` or `undefined` is a no-op
+                    if matches!(value, Value::Undefined(_) | Value::Null(_)) {
+                        continue;
+                    }
+
+                    let source = value.to_object(cx.scope)?;
+                    for key in source.own_keys(cx.scope)? {
+                        let key = PropertyKey::from_value(cx.scope, key)?;
+                        let is_enumerable = source
+                            .get_own_property_descriptor(cx.scope, key.clone())?
+                            .is_some_and(|desc| desc.descriptor.contains(PropertyDataDescriptor::ENUMERABLE));
+
+                        // Only own enumerable properties are copied, and getters are evaluated to their value
+                        if is_enumerable {
+                            let value = source.get_property(&mut cx, key.clone())?.root(cx.scope);
+                            obj.set_raw_property(key, PropertyValue::static_default(value));
                         }
                     }
                 }
+                ObjectProperty::Prototype(value) => {
+                    // Values other than objects and `null` are ignored
+                    if matches!(value, Value::Object(_) | Value::Null(_)) {
+                        obj.set_prototype(cx.scope, value)?;
+                    }
+                }
             }
         }
 
-        let obj = NamedObject::with_values(&cx, obj);
-
         let handle = cx.gc.register(obj);
         cx.stack.push(handle.into());
 
@@ -1517,6 +1655,7 @@ mod handlers {
                     target.set_property(cx.scope, key, prop)?;
                 }
                 ObjectProperty::Spread(_) => unimplemented!("spread operator in AssignProperties"),
+                ObjectProperty::Prototype(_) => unimplemented!("__proto__ in AssignProperties"),
             }
         }
 
@@ -1528,6 +1667,7 @@ mod handlers {
         let ident = cx.identifier_constant(id.into());
 
         let preserve_this = cx.fetch_and_inc_ip() == 1;
+        let cache_id = cx.fetchw_and_inc_ip();
 
         let target = if preserve_this {
             cx.peek_stack()
@@ -1535,11 +1675,78 @@ mod handlers {
             cx.pop_stack_rooted()
         };
 
-        let value = target.get_property(&mut cx, ident.into())?;
+        let function = cx.active_frame().function.clone();
+        let cache = &function.inline_caches[usize::from(cache_id)];
+
+        if let Some(property) = inline_cache_lookup(&target, cache.get()) {
+            let value = property.get_or_apply(&mut cx, target)?;
+            cx.push_stack(value);
+            return Ok(None);
+        }
+
+        let key = PropertyKey::from(ident);
+        let value = target.get_property(&mut cx, key.clone())?;
+        if let Some(entry) = inline_cache_entry(&target, &key) {
+            cache.set(entry);
+        }
+
         cx.push_stack(value);
         Ok(None)
     }
 
+    fn named_object(value: &Value) -> Option<&NamedObject> {
+        match value {
+            Value::Object(handle) => handle.as_any().downcast_ref::<NamedObject>(),
+            _ => None,
+        }
+    }
+
+    /// Looks up the property described by a populated inline cache, if the receiver matches it
+    fn inline_cache_lookup(target: &Value, cache: InlineCache) -> Option<PropertyValue> {
+        let object = named_object(target)?;
+        if cache.shape != object.shape_id() {
+            return None;
+        }
+
+        if cache.holder == 0 {
+            return object.get_slot(cache.slot);
+        }
+
+        let prototype = object.raw_prototype()?;
+        if prototype.as_erased_ptr() as usize != cache.holder {
+            return None;
+        }
+
+        let holder = prototype.as_any().downcast_ref::<NamedObject>()?;
+        if holder.shape_id() != cache.holder_shape {
+            return None;
+        }
+        holder.get_slot(cache.slot)
+    }
+
+    /// Computes the inline cache entry for a property that is either an own property of the receiver or of its direct prototype
+    fn inline_cache_entry(target: &Value, key: &PropertyKey) -> Option<InlineCache> {
+        let object = named_object(target)?;
+        if let Some(slot) = object.lookup_slot(key) {
+            return Some(InlineCache {
+                shape: object.shape_id(),
+                holder: 0,
+                holder_shape: 0,
+                slot,
+            });
+        }
+
+        let prototype = object.raw_prototype()?;
+        let holder = prototype.as_any().downcast_ref::<NamedObject>()?;
+        let slot = holder.lookup_slot(key)?;
+        Some(InlineCache {
+            shape: object.shape_id(),
+            holder: prototype.as_erased_ptr() as usize,
+            holder_shape: holder.shape_id(),
+            slot,
+        })
+    }
+
     pub fn staticpropertyassign<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
         let kind = AssignKind::from_repr(cx.fetch_and_inc_ip()).unwrap();
         let id = cx.fetchw_and_inc_ip();
@@ -1620,7 +1827,7 @@ mod handlers {
 
         macro_rules! op {
             ($op:expr) => {{
-                let (target, value, key) = cx.pop_stack3_rooted();
+                let (target, key, value) = cx.pop_stack3_rooted();
 
                 let key = PropertyKey::from_value(&mut cx, key)?;
                 let prop = target.get_property(&mut cx, key.clone())?.root(cx.scope);
@@ -1660,7 +1867,7 @@ mod handlers {
 
         match kind {
             AssignKind::Assignment => {
-                let (target, value, key) = cx.pop_stack3_rooted();
+                let (target, key, value) = cx.pop_stack3_rooted();
 
                 let key = PropertyKey::from_value(&mut cx, key)?;
 
@@ -1905,6 +2112,36 @@ mod handlers {
         Ok(None)
     }
 
+    pub fn export_all<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
+        let path_id = cx.fetchw_and_inc_ip();
+        let path = cx.string_constant(path_id.into());
+
+        let module = match cx.params.static_import_callback() {
+            Some(cb) => cb(&mut cx, StaticImportKind::All, path)?,
+            None => throw!(cx, Error, "Static imports are disabled for this context."),
+        };
+        let module = module.root(cx.scope);
+
+        let mut named = Vec::new();
+        for key in module.own_keys(cx.scope)? {
+            // The default export is never forwarded
+            let Value::String(key) = key else { continue };
+            if key.sym() == sym::default {
+                continue;
+            }
+
+            let value = module.get_property(cx.scope, key.into())?;
+            named.push((key, value));
+        }
+
+        match &mut cx.active_frame_mut().state {
+            FrameState::Module(exports) => exports.named.extend(named),
+            _ => throw!(cx, Error, "Export is only available at the top level in modules"),
+        }
+
+        Ok(None)
+    }
+
     pub fn debugger<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
         if let Some(cb) = cx.params().debugger_callback() {
             cb(&mut cx)?;
@@ -1918,9 +2155,13 @@ mod handlers {
             .frames
             .iter()
             .rev()
-            .find_map(|f| f.this.as_ref())
-            .cloned()
-            .unwrap_or_else(|| Value::Object(cx.global.clone()));
+            .find_map(|f| f.this.as_ref().map(|this| (this, f.function.is_strict)));
+
+        let this = match this {
+            // In sloppy mode, functions called without a receiver get the global object as `this`
+            Some((Value::Undefined(_) | Value::Null(_), false)) | None => Value::Object(cx.global.clone()),
+            Some((this, _)) => this.clone(),
+        };
 
         cx.stack.push(this);
         Ok(None)
@@ -1962,17 +2203,49 @@ mod handlers {
         Ok(None)
     }
 
-    pub fn call_for_in_iterator<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
+    pub fn call_async_iterator<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
         let value = cx.pop_stack_rooted();
+        let symbol_async_iterator = cx.statics.symbol_async_iterator.clone();
+        let mut iterable = value
+            .get_property(&mut cx, PropertyKey::Symbol(symbol_async_iterator))?
+            .root(cx.scope);
+
+        if iterable.is_nullish() {
+            // Not an async iterable, fall back to the sync iterator.
+            // Its results are awaited by the loop, so they behave the same as an async iterator
+            let symbol_iterator = cx.statics.symbol_iterator.clone();
+            iterable = value
+                .get_property(&mut cx, PropertyKey::Symbol(symbol_iterator))?
+                .root(cx.scope);
+        }
+
+        let iterator = iterable.apply(&mut cx, value, Vec::new())?;
+        cx.push_stack(iterator);
+        Ok(None)
+    }
+
+    pub fn call_for_in_iterator<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
+        let mut value = cx.pop_stack_rooted();
+
+        // Enumerable string keys of the object and its prototype chain.
+        // A key that was already seen further down the chain is skipped, even if that property is not enumerable.
+        let mut seen = FxHashSet::default();
+        let mut keys = Vec::new();
+        while let Value::Object(_) | Value::External(_) = value {
+            for key in value.own_keys(cx.scope)? {
+                let Value::String(key) = key else { continue };
+                if !seen.insert(key) {
+                    continue;
+                }
+
+                let descriptor = value.get_own_property_descriptor(cx.scope, key.into())?;
+                if descriptor.is_some_and(|d| d.descriptor.contains(PropertyDataDescriptor::ENUMERABLE)) {
+                    keys.push(PropertyValue::static_default(Value::String(key)));
+                }
+            }
 
-        let keys = match value {
-            Value::Object(obj) => obj.own_keys(cx.scope)?,
-            Value::External(obj) => obj.own_keys(cx.scope)?,
-            _ => Vec::new(),
+            value = value.get_prototype(cx.scope)?;
         }
-        .into_iter()
-        .map(PropertyValue::static_default)
-        .collect();
 
         let keys = Array::from_vec(&cx, keys);
         let keys = cx.register(keys);
@@ -2041,6 +2314,14 @@ mod handlers {
             let rest = NamedObject::new(cx.scope);
             let rest = cx.scope.register(rest);
             for key in keys {
+                // Only own enumerable properties that were not destructured are copied to the rest object
+                let is_enumerable = obj
+                    .get_own_property_descriptor(cx.scope, key.into())?
+                    .is_some_and(|desc| desc.descriptor.contains(PropertyDataDescriptor::ENUMERABLE));
+                if !is_enumerable {
+                    continue;
+                }
+
                 let value = obj.get_property(cx.scope, key.into())?.root(cx.scope);
                 rest.set_property(cx.scope, key.into(), PropertyValue::static_default(value))?;
             }
@@ -2052,15 +2333,36 @@ mod handlers {
     }
 
     pub fn arraydestruct<'sc, 'vm>(mut cx: DispatchContext<'sc, 'vm>) -> Result<Option<HandleResult>, Unrooted> {
+        let rest_id = match cx.fetchw_and_inc_ip() {
+            u16::MAX => None,
+            n => Some(n),
+        };
         let array = cx.pop_stack_rooted();
 
         let mut iter = BackwardSequence::<NumberWConstant>::new_u16(&mut cx).enumerate();
+        let mut count = 0;
 
         while let Some((i, NumberWConstant(id))) = iter.next_infallible(&mut cx) {
             let id = id as usize;
             let key = cx.scope.intern_usize(i);
             let prop = array.get_property(cx.scope, key.into())?;
             cx.set_local(id, prop);
+            count += 1;
+        }
+
+        if let Some(rest_id) = rest_id {
+            // The rest element collects all remaining elements after the named ones
+            let len = array.length_of_array_like(cx.scope)?;
+            let mut rest = Vec::with_capacity(len.saturating_sub(count));
+            for i in count..len {
+                let key = cx.scope.intern_usize(i);
+                let value = array.get_property(cx.scope, key.into())?.root(cx.scope);
+                rest.push(PropertyValue::static_default(value));
+            }
+
+            let rest = Array::from_vec(&cx, rest);
+            let rest = cx.scope.register(rest);
+            cx.set_local(rest_id.into(), Value::Object(rest).into());
         }
 
         Ok(None)
@@ -2331,6 +2633,7 @@ pub fn handle(vm: &mut Vm, instruction: Instruction) -> Result<Option<HandleResu
         Instruction::ImportStatic => handlers::import_static(cx),
         Instruction::ExportDefault => handlers::export_default(cx),
         Instruction::ExportNamed => handlers::export_named(cx),
+        Instruction::ExportAll => handlers::export_all(cx),
         Instruction::Debugger => handlers::debugger(cx),
         Instruction::Global => handlers::global_this(cx),
         Instruction::Super => handlers::super_(cx),
@@ -2342,12 +2645,17 @@ pub fn handle(vm: &mut Vm, instruction: Instruction) -> Result<Option<HandleResu
         Instruction::IntrinsicOp => handlers::intrinsic_op(cx),
         Instruction::CallSymbolIterator => handlers::call_symbol_iterator(cx),
         Instruction::CallForInIterator => handlers::call_for_in_iterator(cx),
+        Instruction::CallAsyncIterator => handlers::call_async_iterator(cx),
         Instruction::DeletePropertyStatic => handlers::delete_property_static(cx),
         Instruction::DeletePropertyDynamic => handlers::delete_property_dynamic(cx),
         Instruction::ObjDestruct => handlers::objdestruct(cx),
         Instruction::ArrayDestruct => handlers::arraydestruct(cx),
         Instruction::AssignProperties => handlers::assign_properties(cx),
         Instruction::DelayedReturn => handlers::delayed_ret(cx),
+        Instruction::ToString => handlers::to_string(cx),
+        Instruction::TemplateObject => handlers::template_object(cx),
+        Instruction::PrivateSymbol => handlers::private_symbol(cx),
+        Instruction::SuperCall => handlers::super_call(cx),
         Instruction::Nop => Ok(None),
         _ => unimplemented!("{:?}", instruction),
     }
@@ -3,7 +3,7 @@ use dash_lexer::Lexer;
 use dash_middle::compiler::StaticImportKind;
 use dash_optimizer::type_infer::TypeInferCtx;
 use dash_optimizer::OptLevel;
-use dash_parser::Parser;
+use dash_parser::{ParseMode, Parser};
 
 use crate::frame::Frame;
 use crate::gc::interner::sym;
@@ -19,16 +19,26 @@ pub enum EvalError {
 }
 
 impl Vm {
+    /// Evaluates the given source code as a script
     pub fn eval(&mut self, input: &str, opt: OptLevel) -> Result<Unrooted, EvalError> {
+        self.eval_with_mode(input, opt, ParseMode::Script)
+    }
+
+    /// Evaluates the given source code, parsing it either as a module or as a script.
+    ///
+    /// Scripts may not contain `import` or `export` declarations, while modules are always in strict mode.
+    pub fn eval_with_mode(&mut self, input: &str, opt: OptLevel, mode: ParseMode) -> Result<Unrooted, EvalError> {
         let tokens = Lexer::new(&mut self.interner, input)
             .scan_all()
             .map_err(EvalError::Middle)?;
         let (ast, counter) = Parser::new(&mut self.interner, input, tokens)
+            .with_mode(mode)
             .parse_all()
             .map_err(EvalError::Middle)?;
 
         let tcx = TypeInferCtx::new(counter);
         let cr = FunctionCompiler::new(input, opt, tcx, &mut self.interner)
+            .with_strict(mode.is_strict())
             .compile_ast(ast, true)
             .map_err(|err| EvalError::Middle(vec![err]))?;
         let mut frame = Frame::from_compile_result(cr);
@@ -37,15 +47,36 @@ impl Vm {
         Ok(val.into_value())
     }
 
+    /// Parses the given source code and returns every error that was found, without compiling or executing it.
+    ///
+    /// Unlike [`Vm::eval`], this does not stop at the first syntax error. An empty vector means the code is valid.
+    pub fn parse_and_validate(&mut self, input: &str) -> Vec<dash_middle::parser::error::Error> {
+        let tokens = match Lexer::new(&mut self.interner, input).scan_all() {
+            Ok(tokens) => tokens,
+            Err(errors) => return errors,
+        };
+
+        match Parser::new(&mut self.interner, input, tokens).parse_all() {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors,
+        }
+    }
+
     pub fn evaluate_module(
         sc: &mut LocalScope,
         input: &str,
         import_ty: StaticImportKind,
         opt: OptLevel,
     ) -> Result<Unrooted, Unrooted> {
-        let re = match FunctionCompiler::compile_str(&mut sc.interner, input, opt) {
+        let re = match FunctionCompiler::compile_str_with_mode(&mut sc.interner, input, opt, ParseMode::Module) {
             Ok(re) => re,
-            Err(err) => throw!(sc, SyntaxError, "Middle error: {:?}", err),
+            Err(errs) => {
+                let message = errs
+                    .first()
+                    .map(|err| err.syntax_error_message(input))
+                    .unwrap_or_default();
+                throw!(sc, SyntaxError, "{}", message)
+            }
         };
 
         let frame = Frame::from_compile_result(re);
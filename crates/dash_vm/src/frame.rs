@@ -179,6 +179,8 @@ impl Frame {
             source: cr.source,
             debug_symbols: cr.debug_symbols,
             references_arguments: false,
+            inline_caches: (0..cr.inline_caches).map(|_| Cell::default()).collect(),
+            is_strict: cr.is_strict,
         };
 
         Self {
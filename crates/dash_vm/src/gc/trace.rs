@@ -1,6 +1,6 @@
 use std::any::TypeId;
 use std::cell::{Cell, OnceCell, RefCell};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
@@ -70,6 +70,14 @@ unsafe impl<T: Trace> Trace for Vec<T> {
     }
 }
 
+unsafe impl<T: Trace> Trace for VecDeque<T> {
+    fn trace(&self, cx: &mut TraceCtxt<'_>) {
+        for v in self {
+            v.trace(cx);
+        }
+    }
+}
+
 unsafe impl<A: Trace, B: Trace> Trace for (A, B) {
     fn trace(&self, cx: &mut TraceCtxt<'_>) {
         self.0.trace(cx);
@@ -167,6 +175,8 @@ unsafe impl Trace for dash_middle::compiler::constant::Function {
             source: _,
             debug_symbols: _,
             references_arguments: _,
+            inline_caches: _,
+            is_strict: _,
         } = self;
         name.trace(cx);
         constants.trace(cx);
@@ -0,0 +1,40 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A handle that can be used to interrupt code running in a [`Vm`](crate::Vm), possibly from another thread.
+///
+/// Handles are cheap to clone and all clones obtained from the same VM share the same flag.
+/// The VM checks the flag whenever it jumps backwards (i.e. at the end of every loop iteration),
+/// so even tight loops can be interrupted, but straight-line code and native functions run to completion.
+/// JIT compiled code does not check the flag, so loops are not JIT compiled while a handle exists.
+///
+/// Once the VM notices the interrupt, it throws an `Error` that cannot be caught by the script:
+/// `catch` and `finally` blocks are skipped and all frames are unwound, so the error is returned to the host.
+/// The flag stays set until [`InterruptHandle::reset`] is called, which means that any code that runs in the meantime,
+/// including async tasks, is interrupted at its first backwards jump as well.
+#[derive(Debug, Clone, Default)]
+pub struct InterruptHandle {
+    flag: Arc<AtomicBool>,
+}
+
+impl InterruptHandle {
+    /// Requests that the running script is terminated
+    pub fn interrupt(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Clears the interrupt flag, allowing code to run again
+    pub fn reset(&self) {
+        self.flag.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    /// Whether a handle other than the VM's own one exists, i.e. whether anyone can interrupt the VM
+    #[cfg(feature = "jit")]
+    pub(crate) fn has_other_handles(&self) -> bool {
+        Arc::strong_count(&self.flag) > 1
+    }
+}
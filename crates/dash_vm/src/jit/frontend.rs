@@ -1,14 +1,16 @@
 use std::collections::HashMap;
+use std::rc::{Rc, Weak};
 
-use dash_llvm_jit_backend::codegen::JitFunction;
+use dash_llvm_jit_backend::codegen::{JitFunction, NumericMapFunction};
 use dash_llvm_jit_backend::error::Error;
 use dash_llvm_jit_backend::{codegen, init};
+use dash_log::debug;
 use dash_middle::compiler::constant::Function;
 use dash_typed_cfg::TypedCfg;
 
 use crate::Vm;
 
-use super::query::QueryProvider;
+use super::query::{MapQueryProvider, QueryProvider};
 pub use dash_llvm_jit_backend::Trace;
 
 pub struct Frontend {
@@ -16,6 +18,28 @@ pub struct Frontend {
     /// this will contain metadata such as the pc of the loop header and its end
     trace: Option<Trace>,
     cache: HashMap<CacheKey, (TypedCfg, JitFunction)>,
+    /// Callbacks to `Array.prototype.map` over arrays of numbers, see [`numeric_map_kernel`]
+    map_kernels: HashMap<*const Function, MapKernel>,
+}
+
+/// The number of elements a callback needs to have mapped in the interpreter before it is JIT compiled
+const HOT_MAP_ELEMENTS: usize = 100;
+
+struct MapKernel {
+    /// Only a weak reference, so that caching a kernel doesn't keep the function alive.
+    /// The allocation of the function stays around as long as this exists, so its address cannot be reused
+    /// by another function while it is cached.
+    function: Weak<Function>,
+    /// The number of elements this callback has mapped in the interpreter
+    elements: usize,
+    state: MapKernelState,
+}
+
+enum MapKernelState {
+    Cold,
+    Compiled(NumericMapFunction),
+    /// Compiling the callback failed, so don't bother trying again
+    Poisoned,
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -40,6 +64,7 @@ impl Frontend {
         Self {
             trace: None,
             cache: HashMap::new(),
+            map_kernels: HashMap::new(),
         }
     }
 
@@ -58,6 +83,15 @@ impl Frontend {
     pub fn set_recording_trace(&mut self, trace: Trace) {
         self.trace = Some(trace);
     }
+
+    /// Returns the number of map callbacks that have been compiled into a numeric map kernel
+    #[cfg(test)]
+    pub fn compiled_map_kernels(&self) -> usize {
+        self.map_kernels
+            .values()
+            .filter(|kernel| matches!(kernel.state, MapKernelState::Compiled(_)))
+            .count()
+    }
 }
 
 pub fn compile_current_trace(vm: &mut Vm) -> Result<(Trace, JitFunction), Error> {
@@ -87,3 +121,54 @@ pub fn compile_current_trace(vm: &mut Vm) -> Result<(Trace, JitFunction), Error>
 
     Ok((trace, fun))
 }
+
+/// Returns a numeric map kernel for `function`, which is about to map `len` numbers.
+///
+/// Like loops, callbacks only get compiled once they are hot, i.e. once they have mapped enough elements.
+pub fn numeric_map_kernel(vm: &mut Vm, function: &Rc<Function>, len: usize) -> Option<NumericMapFunction> {
+    let kernels = &mut vm.jit.map_kernels;
+    let key = Rc::as_ptr(function);
+    if !kernels.contains_key(&key) {
+        // Forget the kernels of functions that have been dropped in the meantime
+        kernels.retain(|_, kernel| kernel.function.strong_count() > 0);
+    }
+
+    let kernel = kernels.entry(key).or_insert_with(|| MapKernel {
+        function: Rc::downgrade(function),
+        elements: 0,
+        state: MapKernelState::Cold,
+    });
+
+    match kernel.state {
+        MapKernelState::Compiled(fun) => return Some(fun),
+        MapKernelState::Poisoned => return None,
+        MapKernelState::Cold => {}
+    }
+
+    kernel.elements += len;
+    if kernel.elements <= HOT_MAP_ELEMENTS {
+        return None;
+    }
+
+    match compile_numeric_map(function) {
+        Ok(fun) => {
+            kernel.state = MapKernelState::Compiled(fun);
+            Some(fun)
+        }
+        Err(err) => {
+            debug!("cannot compile map callback: {err:?}");
+            kernel.state = MapKernelState::Poisoned;
+            None
+        }
+    }
+}
+
+fn compile_numeric_map(function: &Function) -> Result<NumericMapFunction, Error> {
+    let bytecode = function.buffer.with(|buf| buf.to_vec());
+    // Only the element and the index are passed to the kernel, not the array itself
+    let params = if function.params > 1 { 2 } else { function.params as u16 };
+
+    let mut query = MapQueryProvider { function };
+    let tcfg = dash_typed_cfg::lower(&bytecode, &mut query)?;
+    codegen::compile_numeric_map(&bytecode, &tcfg, params, &mut query)
+}
@@ -7,6 +7,11 @@ use dash_typed_cfg::passes::bb_generation::ConditionalBranchAction;
 pub use frontend::Frontend;
 use frontend::Trace;
 
+use crate::value::array::{Array, ArrayInner};
+use crate::value::function::{Function, FunctionKind};
+use crate::value::object::{PropertyValue, PropertyValueKind};
+use crate::value::primitive::Number;
+use crate::value::Value;
 use crate::Vm;
 
 fn handle_loop_trace(vm: &mut Vm, jmp_instr_ip: usize) {
@@ -47,6 +52,12 @@ fn handle_loop_trace(vm: &mut Vm, jmp_instr_ip: usize) {
 pub fn handle_loop_end(vm: &mut Vm, loop_end_ip: usize) {
     // We are jumping back to a loop header
 
+    // JIT compiled code does not count executed instructions or check for interrupts,
+    // so loops need to stay in the interpreter if either of them can stop the script
+    if vm.remaining_instructions().is_some() || vm.interrupt.has_other_handles() {
+        return;
+    }
+
     if vm.jit.recording_trace().is_some() {
         handle_loop_trace(vm, loop_end_ip);
     } else {
@@ -80,20 +91,66 @@ fn handle_loop_counter_inc(vm: &mut Vm, loop_end_ip: usize, parent_ip: Option<us
     }
 }
 
+/// Maps an array of numbers using a JIT compiled version of `callback`, if it only does arithmetic on its arguments.
+///
+/// Returns `None` if either the array or the callback are not supported,
+/// in which case the caller needs to fall back to calling the callback for every element.
+pub fn numeric_map(vm: &mut Vm, array: &Value, callback: &Value) -> Option<Array> {
+    // Like loops, kernels do not count executed instructions or check for interrupts
+    if vm.remaining_instructions().is_some() || vm.interrupt.has_other_handles() {
+        return None;
+    }
+
+    let function = match callback.downcast_ref::<Function>()?.kind() {
+        FunctionKind::User(fun) => fun.inner().clone(),
+        FunctionKind::Closure(closure) => closure.fun.inner().clone(),
+        _ => return None,
+    };
+
+    let input = {
+        let array = array.downcast_ref::<Array>()?;
+        let items = array.items.borrow();
+        let ArrayInner::NonHoley(items) = &*items else {
+            return None;
+        };
+
+        items
+            .iter()
+            .map(|item| match item.kind() {
+                PropertyValueKind::Static(Value::Number(Number(n))) => Some(*n),
+                _ => None,
+            })
+            .collect::<Option<Vec<f64>>>()?
+    };
+
+    let kernel = frontend::numeric_map_kernel(vm, &function, input.len())?;
+    let mut output = vec![0.0; input.len()];
+    unsafe { kernel(input.as_ptr(), output.as_mut_ptr(), input.len() as u64) };
+
+    let items = output
+        .into_iter()
+        .map(|n| PropertyValue::static_default(Value::number(n)))
+        .collect();
+    Some(Array::from_vec(vm, items))
+}
+
 #[cfg(all(test, feature = "jit"))]
 mod tests {
 
     use dash_compiler::FunctionCompiler;
     use dash_llvm_jit_backend::codegen;
     use dash_llvm_jit_backend::codegen::{CodegenQuery, JitConstant};
+    use dash_middle::compiler::constant::Constant;
     use dash_middle::interner::StringInterner;
+    use dash_middle::util::is_integer;
     use dash_optimizer::OptLevel;
     use dash_typed_cfg::passes::bb_generation::{BBGenerationQuery, ConditionalBranchAction};
     use dash_typed_cfg::passes::type_infer::{Type, TypeInferQuery};
     use dash_typed_cfg::TypedCfgQuery;
 
     use crate::value::primitive::Number;
-    use crate::value::Value;
+    use crate::value::{Root, Value};
+    use crate::Vm;
 
     #[derive(Debug)]
     struct TestQueryProvider {}
@@ -107,30 +164,30 @@ mod tests {
     }
 
     impl TypeInferQuery for TestQueryProvider {
-        fn type_of_constant(&self, index: u16) -> dash_typed_cfg::passes::type_infer::Type {
+        fn type_of_constant(&self, index: u16) -> Option<Type> {
             match index {
                 #[allow(clippy::manual_range_patterns)]
-                0 | 1 | 2 => Type::I64,
-                _ => todo!("{index}"),
+                0 | 1 | 2 => Some(Type::I64),
+                _ => None,
             }
         }
 
-        fn type_of_local(&self, index: u16) -> Type {
+        fn type_of_local(&self, index: u16) -> Option<Type> {
             match index {
-                0 => Type::I64,
-                1 => Type::Boolean,
-                o => todo!("{o}"),
+                0 => Some(Type::I64),
+                1 => Some(Type::Boolean),
+                _ => None,
             }
         }
     }
 
     impl CodegenQuery for TestQueryProvider {
-        fn get_constant(&self, cid: u16) -> JitConstant {
+        fn get_constant(&self, cid: u16) -> Option<JitConstant> {
             match cid {
-                0 => JitConstant::I64(0),
-                1 => JitConstant::I64(10),
-                2 => JitConstant::I64(3),
-                _ => todo!(),
+                0 => Some(JitConstant::I64(0)),
+                1 => Some(JitConstant::I64(10)),
+                2 => Some(JitConstant::I64(3)),
+                _ => None,
             }
         }
     }
@@ -163,4 +220,158 @@ mod tests {
         unsafe { fun(s.as_mut_ptr().cast(), 0, &mut x) };
         dbg!(x, s);
     }
+
+    /// Answers queries from the compile result's constant pool and the initial values of the locals,
+    /// and assumes that every conditional jump (i.e. the loop condition) is not taken
+    struct LoopQueryProvider<'a> {
+        constants: &'a [Constant],
+        locals: &'a [Value],
+    }
+    impl BBGenerationQuery for LoopQueryProvider<'_> {
+        fn conditional_branch_at(&self, _: usize) -> Option<ConditionalBranchAction> {
+            Some(ConditionalBranchAction::NotTaken)
+        }
+    }
+
+    impl TypeInferQuery for LoopQueryProvider<'_> {
+        fn type_of_constant(&self, index: u16) -> Option<Type> {
+            match self.constants[usize::from(index)] {
+                Constant::Number(n) if is_integer(n) => Some(Type::I64),
+                Constant::Number(_) => Some(Type::F64),
+                _ => None,
+            }
+        }
+
+        fn type_of_local(&self, index: u16) -> Option<Type> {
+            match self.locals[usize::from(index)] {
+                Value::Number(Number(n)) if is_integer(n) => Some(Type::I64),
+                Value::Number(_) => Some(Type::F64),
+                _ => None,
+            }
+        }
+    }
+
+    impl CodegenQuery for LoopQueryProvider<'_> {
+        fn get_constant(&self, cid: u16) -> Option<JitConstant> {
+            match self.constants[usize::from(cid)] {
+                Constant::Number(n) if is_integer(n) => Some(JitConstant::I64(n as i64)),
+                Constant::Number(n) => Some(JitConstant::F64(n)),
+                _ => None,
+            }
+        }
+    }
+
+    impl TypedCfgQuery for LoopQueryProvider<'_> {}
+
+    /// JIT compiles `source`, which must declare `sum` as its first local, and
+    /// checks that the value of `sum` after running it matches the interpreter
+    fn assert_sum_matches_interpreter(source: &str) {
+        let cr = FunctionCompiler::compile_str(&mut StringInterner::new(), source, OptLevel::None).unwrap();
+        let bytecode = &cr.instructions;
+
+        let mut locals = [Value::Number(Number(0.0)), Value::Number(Number(0.0))];
+        let mut query = LoopQueryProvider {
+            constants: &cr.cp,
+            locals: &locals,
+        };
+        let tcfg = dash_typed_cfg::lower(bytecode, &mut query).unwrap();
+
+        dash_llvm_jit_backend::init();
+
+        let fun = codegen::compile_typed_cfg(bytecode, &tcfg, &mut query).unwrap();
+        let mut ip = 0;
+        unsafe { fun(locals.as_mut_ptr().cast(), 0, &mut ip) };
+
+        let mut vm = Vm::new(Default::default());
+        let mut scope = vm.scope();
+        let expected = scope
+            .eval(&format!("{source}; sum"), OptLevel::None)
+            .unwrap()
+            .root(&mut scope);
+        assert_eq!(locals[0], expected);
+    }
+
+    #[test]
+    pub fn integer_sum_loop() {
+        assert_sum_matches_interpreter("let sum = 0; for (let i = 1; i <= 1000; i++) { sum = sum + i; }");
+    }
+
+    /// Maps a large array of numbers with `callback` and compares the result with
+    /// calling the callback from a loop in the interpreter.
+    /// Returns the number of callbacks that were compiled into numeric map kernels
+    fn assert_map_matches_interpreter(callback: &str) -> usize {
+        let source = format!(
+            r"
+            const input = [];
+            for (let i = 0; i < 10000; i++) {{ input.push(i * 1.5 - 3000); }}
+            const f = {callback};
+
+            const mapped = input.map(f);
+            const expected = [];
+            for (let i = 0; i < input.length; i++) {{ expected.push(f(input[i], i, input)); }}
+
+            const same = (a, b) => a === b ? (a !== 0 || 1 / a === 1 / b) : (a !== a && b !== b);
+            mapped.length === expected.length && mapped.every((value, i) => same(value, expected[i]));
+            "
+        );
+
+        let mut vm = Vm::new(Default::default());
+        let value = vm.eval(&source, OptLevel::Basic).unwrap().root(&mut vm.scope());
+        assert_eq!(value, Value::Boolean(true), "{callback}");
+        vm.jit.compiled_map_kernels()
+    }
+
+    #[test]
+    pub fn numeric_map() {
+        for callback in [
+            "x => x * 2 + 1",
+            "(x, i) => x / (i + 1) - i % 7",
+            "x => { if (x > 500) { return x % 7; } return -x; }",
+            "function(x) { while (x > 10) { x = x / 3; } return x; }",
+        ] {
+            assert_eq!(
+                assert_map_matches_interpreter(callback),
+                1,
+                "{callback} was not compiled"
+            );
+        }
+
+        // Callbacks that don't return numbers or use anything but their arguments fall back to the interpreter
+        for callback in [
+            "x => x > 0",
+            "x => x > 0 ? x : 0",
+            "(x, i, arr) => x + arr.length",
+            "x => x + input.length",
+        ] {
+            assert_eq!(assert_map_matches_interpreter(callback), 0, "{callback} was compiled");
+        }
+    }
+
+    #[test]
+    pub fn map_kernels_of_dropped_functions_are_forgotten() {
+        let source = |callback: &str| {
+            format!(
+                r"
+                const input = [];
+                for (let i = 0; i < 1000; i++) {{ input.push(i * 0.5); }}
+                input.map({callback}).length;
+                "
+            )
+        };
+
+        let mut vm = Vm::new(Default::default());
+        vm.eval(&source("x => x * 2"), OptLevel::Basic).unwrap();
+        assert_eq!(vm.jit.compiled_map_kernels(), 1);
+
+        // Once the first callback is collected, compiling another one drops its kernel
+        vm.perform_gc();
+        vm.eval(&source("x => x * 3"), OptLevel::Basic).unwrap();
+        assert_eq!(vm.jit.compiled_map_kernels(), 1);
+    }
+
+    #[test]
+    pub fn float_accumulator_loop() {
+        // `sum` starts out as an integer and becomes a float on the first iteration
+        assert_sum_matches_interpreter("let sum = 0; for (let i = 1; i <= 1000; i++) { sum = sum + i / 4; }");
+    }
 }
@@ -1,6 +1,6 @@
 use dash_llvm_jit_backend::codegen::{CodegenQuery, JitConstant};
 use dash_llvm_jit_backend::Trace;
-use dash_middle::compiler::constant::Constant;
+use dash_middle::compiler::constant::{Constant, Function};
 use dash_middle::util::is_integer;
 use dash_typed_cfg::passes::bb_generation::{BBGenerationQuery, ConditionalBranchAction};
 use dash_typed_cfg::passes::type_infer::{Type, TypeInferQuery};
@@ -24,48 +24,90 @@ impl<'a> BBGenerationQuery for QueryProvider<'a> {
 }
 
 impl<'a> TypeInferQuery for QueryProvider<'a> {
-    fn type_of_constant(&self, index: u16) -> Type {
+    fn type_of_constant(&self, index: u16) -> Option<Type> {
         let constant = &self.vm.active_frame().function.constants[usize::from(index)];
         match constant {
-            Constant::Boolean(..) => Type::Boolean,
+            Constant::Boolean(..) => Some(Type::Boolean),
             Constant::Number(n) => {
                 if is_integer(*n) {
-                    Type::I64
+                    Some(Type::I64)
                 } else {
-                    Type::F64
+                    Some(Type::F64)
                 }
             }
-            _ => panic!("invalid jit type"),
+            _ => None,
         }
     }
-    fn type_of_local(&self, index: u16) -> Type {
+    fn type_of_local(&self, index: u16) -> Option<Type> {
         match self.vm.get_local(index.into()).unwrap() {
-            Value::Boolean(..) => Type::Boolean,
+            Value::Boolean(..) => Some(Type::Boolean),
             Value::Number(Number(n)) => {
                 if is_integer(n) {
-                    Type::I64
+                    Some(Type::I64)
                 } else {
-                    Type::F64
+                    Some(Type::F64)
                 }
             }
-            _ => panic!("invalid jit type"),
+            _ => None,
         }
     }
 }
 
 impl<'a> CodegenQuery for QueryProvider<'a> {
-    fn get_constant(&self, id: u16) -> JitConstant {
+    fn get_constant(&self, id: u16) -> Option<JitConstant> {
         let constant = &self.vm.active_frame().function.constants[usize::from(id)];
         match constant {
-            Constant::Boolean(b) => JitConstant::Boolean(*b),
+            Constant::Boolean(b) => Some(JitConstant::Boolean(*b)),
             Constant::Number(n) => {
                 if is_integer(*n) {
-                    JitConstant::I64(*n as i64)
+                    Some(JitConstant::I64(*n as i64))
                 } else {
-                    JitConstant::F64(*n)
+                    Some(JitConstant::F64(*n))
                 }
             }
-            _ => todo!(),
+            _ => None,
+        }
+    }
+}
+
+/// Answers queries for compiling the body of a callback to `Array.prototype.map` over an array of numbers
+pub struct MapQueryProvider<'a> {
+    pub function: &'a Function,
+}
+
+impl<'a> TypedCfgQuery for MapQueryProvider<'a> {}
+
+impl<'a> BBGenerationQuery for MapQueryProvider<'a> {
+    fn conditional_branch_at(&self, _: usize) -> Option<ConditionalBranchAction> {
+        // Unlike a trace, the whole function body is compiled
+        Some(ConditionalBranchAction::Either)
+    }
+}
+
+impl<'a> TypeInferQuery for MapQueryProvider<'a> {
+    fn type_of_constant(&self, index: u16) -> Option<Type> {
+        match self.function.constants[usize::from(index)] {
+            Constant::Boolean(..) => Some(Type::Boolean),
+            Constant::Number(n) if is_integer(n) => Some(Type::I64),
+            Constant::Number(_) => Some(Type::F64),
+            _ => None,
+        }
+    }
+
+    fn type_of_local(&self, index: u16) -> Option<Type> {
+        // The element and its index are passed as floats, so that arithmetic on them behaves like in the interpreter.
+        // Other locals start out as `undefined`
+        (usize::from(index) < self.function.params.min(2)).then_some(Type::F64)
+    }
+}
+
+impl<'a> CodegenQuery for MapQueryProvider<'a> {
+    fn get_constant(&self, id: u16) -> Option<JitConstant> {
+        match self.function.constants[usize::from(id)] {
+            Constant::Boolean(b) => Some(JitConstant::Boolean(b)),
+            Constant::Number(n) if is_integer(n) => Some(JitConstant::I64(n as i64)),
+            Constant::Number(n) => Some(JitConstant::F64(n)),
+            _ => None,
         }
     }
 }
@@ -1,27 +1,51 @@
 use std::ops::Range;
 
 use crate::gc::interner::sym;
+use crate::js_std::object;
 use crate::localscope::LocalScope;
 use crate::throw;
-use crate::value::array::{Array, ArrayIterator};
+use crate::value::array::{Array, ArrayInner, ArrayIterator, Element};
 use crate::value::function::native::CallContext;
-use crate::value::object::PropertyValue;
+use crate::value::object::{Object, PropertyValue, PropertyValueKind};
 use crate::value::ops::conversions::ValueConversion;
-use crate::value::ops::equality::strict_eq;
+use crate::value::ops::equality::{same_value_zero, strict_eq};
+use crate::value::primitive::Number;
 use crate::value::root_ext::RootErrExt;
 use crate::value::string::JsString;
-use crate::value::{array, Root, Value, ValueContext};
+use crate::value::{array, Root, Typeof, Value, ValueContext};
 
+/// Array(...) behaves the same whether or not it is called with `new`
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
-    let size = cx.args.first().unwrap_or_undefined().to_length_u(cx.scope)?;
-    let array = Array::with_hole(cx.scope, size);
+    let array = match cx.args[..] {
+        [Value::Number(Number(len))] => {
+            if len < 0.0 || len > u32::MAX as f64 || len.trunc() != len {
+                throw!(cx.scope, RangeError, "Invalid array length");
+            }
+            Array::with_hole(cx.scope, len as usize)
+        }
+        _ => Array::from_vec(
+            cx.scope,
+            cx.args.into_iter().map(PropertyValue::static_default).collect(),
+        ),
+    };
     Ok(cx.scope.register(array).into())
 }
 
+/// The maximum number of bytes `join` reserves upfront
+const MAX_JOIN_CAPACITY: usize = 1 << 20;
+
 fn join_inner(sc: &mut LocalScope, array: Value, separator: JsString) -> Result<Value, Value> {
     let length = array.length_of_array_like(sc)?;
 
-    let mut result = String::new();
+    // All elements are pushed into a single buffer, which is pre-sized for the separators
+    // (and at least one byte per element) so that joining stays linear in the length of the result.
+    // The length of array-like objects cannot be trusted, so the initial capacity is capped.
+    let separator_len = separator.res(sc).len();
+    let capacity = length
+        .saturating_sub(1)
+        .saturating_mul(separator_len)
+        .saturating_add(length);
+    let mut result = String::with_capacity(capacity.min(MAX_JOIN_CAPACITY));
 
     for i in 0..length {
         if i > 0 {
@@ -30,19 +54,34 @@ fn join_inner(sc: &mut LocalScope, array: Value, separator: JsString) -> Result<
 
         let i = sc.intern_usize(i);
         let element = array.get_property(sc, i.into()).root(sc)?;
-        let s = element.to_js_string(sc)?;
-        result.push_str(s.res(sc));
+        // `null` and `undefined` are joined as empty strings
+        if !matches!(element, Value::Undefined(_) | Value::Null(_)) {
+            let s = element.to_js_string(sc)?;
+            result.push_str(s.res(sc));
+        }
     }
 
     Ok(Value::String(sc.intern(result).into()))
 }
 
 pub fn to_string(cx: CallContext) -> Result<Value, Value> {
-    join_inner(cx.scope, cx.this, sym::comma.into())
+    let this = Value::Object(cx.this.to_object(cx.scope)?);
+    let join = this.get_property(cx.scope, sym::join.into()).root(cx.scope)?;
+
+    match join {
+        Value::Object(join) if matches!(join.type_of(), Typeof::Function) => {
+            join.apply(cx.scope, this, Vec::new()).root(cx.scope)
+        }
+        // Fall back to Object.prototype.toString if `join` is not callable
+        _ => object::to_string(CallContext { this, ..cx }),
+    }
 }
 
 pub fn join(cx: CallContext) -> Result<Value, Value> {
-    let sep = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let sep = match cx.args.first() {
+        None | Some(Value::Undefined(_)) => sym::comma.into(),
+        Some(sep) => sep.to_js_string(cx.scope)?,
+    };
     join_inner(cx.scope, cx.this, sep)
 }
 
@@ -95,19 +134,46 @@ pub fn keys(cx: CallContext) -> Result<Value, Value> {
     throw!(cx.scope, Error, "Not implemented")
 }
 
+/// Calls the callback of an array method with the element, its index and the array itself as arguments,
+/// and the optional `thisArg` as its `this` value
+fn call_callback(
+    scope: &mut LocalScope,
+    callback: &Value,
+    this_arg: &Value,
+    element: Value,
+    index: usize,
+    array: &Value,
+) -> Result<Value, Value> {
+    let args = vec![element, Value::number(index as f64), array.clone()];
+    callback.apply(scope, this_arg.clone(), args).root(scope)
+}
+
+/// Reads the element at the given index, or returns `None` if the array does not have it (i.e. it is a hole).
+///
+/// This is `HasProperty` followed by `Get` in the spec, which iteration methods like `forEach` use to skip holes.
+fn get_element_if_present(scope: &mut LocalScope, array: &Value, index: usize) -> Result<Option<Value>, Value> {
+    let Value::Object(object) = array else {
+        return Ok(None);
+    };
+
+    let key = scope.intern_usize(index);
+    match object.get_property_descriptor(scope, key.into()).root_err(scope)? {
+        Some(descriptor) => Ok(Some(descriptor.get_or_apply(scope, array.clone()).root(scope)?)),
+        None => Ok(None),
+    }
+}
+
 pub fn every(cx: CallContext) -> Result<Value, Value> {
     let this = Value::Object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
     let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
 
     for k in 0..len {
-        let pk = cx.scope.intern_usize(k);
-        let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
-        let args = vec![pkv, Value::number(k as f64)];
-        let test = callback
-            .apply(cx.scope, Value::undefined(), args)
-            .root(cx.scope)?
-            .to_boolean(cx.scope)?;
+        let Some(pkv) = get_element_if_present(cx.scope, &this, k)? else {
+            continue;
+        };
+        let test = call_callback(cx.scope, &callback, &this_arg, pkv, k, &this)?.to_boolean(cx.scope)?;
 
         if !test {
             return Ok(false.into());
@@ -121,15 +187,13 @@ pub fn some(cx: CallContext) -> Result<Value, Value> {
     let this = Value::Object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
     let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
 
     for k in 0..len {
-        let pk = cx.scope.intern_usize(k);
-        let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
-        let args = vec![pkv, Value::number(k as f64)];
-        let test = callback
-            .apply(cx.scope, Value::undefined(), args)
-            .root(cx.scope)?
-            .to_boolean(cx.scope)?;
+        let Some(pkv) = get_element_if_present(cx.scope, &this, k)? else {
+            continue;
+        };
+        let test = call_callback(cx.scope, &callback, &this_arg, pkv, k, &this)?.to_boolean(cx.scope)?;
 
         if test {
             return Ok(true.into());
@@ -160,16 +224,14 @@ pub fn filter(cx: CallContext) -> Result<Value, Value> {
     let this = Value::Object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
     let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
     let mut values = Vec::new();
 
     for k in 0..len {
-        let pk = cx.scope.intern_usize(k);
-        let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
-        let args = vec![pkv.clone(), Value::number(k as f64)];
-        let test = callback
-            .apply(cx.scope, Value::undefined(), args)
-            .root(cx.scope)?
-            .to_boolean(cx.scope)?;
+        let Some(pkv) = get_element_if_present(cx.scope, &this, k)? else {
+            continue;
+        };
+        let test = call_callback(cx.scope, &callback, &this_arg, pkv.clone(), k, &this)?.to_boolean(cx.scope)?;
 
         if test {
             values.push(PropertyValue::static_default(pkv));
@@ -185,29 +247,28 @@ pub fn reduce(cx: CallContext) -> Result<Value, Value> {
     let this = Value::Object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
     let callback = cx.args.first().unwrap_or_undefined();
-    let initial_value = cx.args.get(1);
-
-    let (start, mut accumulator) = match (len, initial_value) {
-        (0, None) => throw!(cx.scope, TypeError, "Reduce of empty array with no initial value"),
-        (0, Some(_)) => return Ok(initial_value.unwrap().clone()),
-        (_, Some(initial)) => (0, initial.clone()),
-        (1, None) => {
-            let pkv = this.get_property(cx.scope, sym::zero.into()).root(cx.scope)?;
-            return Ok(pkv);
-        }
-        (_, None) => {
-            let pkv = this.get_property(cx.scope, sym::zero.into()).root(cx.scope)?;
-            let pkv2 = this.get_property(cx.scope, sym::one.into()).root(cx.scope)?;
-            let args = vec![pkv, pkv2, Value::number(1_f64)];
-            (2, callback.apply(cx.scope, Value::undefined(), args).root(cx.scope)?)
-        }
+
+    let mut start = 0;
+    let mut accumulator = match cx.args.get(1) {
+        Some(initial) => initial.clone(),
+        // Without an initial value, the first element that is not a hole is used
+        None => loop {
+            if start >= len {
+                throw!(cx.scope, TypeError, "Reduce of empty array with no initial value");
+            }
+            let element = get_element_if_present(cx.scope, &this, start)?;
+            start += 1;
+            if let Some(element) = element {
+                break element;
+            }
+        },
     };
 
     for k in start..len {
-        let pk = cx.scope.intern_usize(k);
-        let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
-        let args = vec![accumulator, pkv, Value::number(k as f64)];
-        accumulator = callback.apply(cx.scope, Value::undefined(), args).root(cx.scope)?;
+        if let Some(pkv) = get_element_if_present(cx.scope, &this, k)? {
+            let args = vec![accumulator, pkv, Value::number(k as f64), this.clone()];
+            accumulator = callback.apply(cx.scope, Value::undefined(), args).root(cx.scope)?;
+        }
     }
 
     Ok(accumulator)
@@ -217,15 +278,12 @@ pub fn find(cx: CallContext) -> Result<Value, Value> {
     let this = Value::Object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
     let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
 
     for k in 0..len {
         let pk = cx.scope.intern_usize(k);
         let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
-        let args = vec![pkv.clone(), Value::number(k as f64)];
-        let test = callback
-            .apply(cx.scope, Value::undefined(), args)
-            .root(cx.scope)?
-            .to_boolean(cx.scope)?;
+        let test = call_callback(cx.scope, &callback, &this_arg, pkv.clone(), k, &this)?.to_boolean(cx.scope)?;
 
         if test {
             return Ok(pkv);
@@ -239,15 +297,50 @@ pub fn find_index(cx: CallContext) -> Result<Value, Value> {
     let this = Value::Object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
     let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
 
     for k in 0..len {
         let pk = cx.scope.intern_usize(k);
         let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
-        let args = vec![pkv, Value::number(k as f64)];
-        let test = callback
-            .apply(cx.scope, Value::undefined(), args)
-            .root(cx.scope)?
-            .to_boolean(cx.scope)?;
+        let test = call_callback(cx.scope, &callback, &this_arg, pkv, k, &this)?.to_boolean(cx.scope)?;
+
+        if test {
+            return Ok(Value::number(k as f64));
+        }
+    }
+
+    Ok(Value::number(-1.0))
+}
+
+pub fn find_last(cx: CallContext) -> Result<Value, Value> {
+    let this = Value::Object(cx.this.to_object(cx.scope)?);
+    let len = this.length_of_array_like(cx.scope)?;
+    let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
+
+    for k in (0..len).rev() {
+        let pk = cx.scope.intern_usize(k);
+        let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
+        let test = call_callback(cx.scope, &callback, &this_arg, pkv.clone(), k, &this)?.to_boolean(cx.scope)?;
+
+        if test {
+            return Ok(pkv);
+        }
+    }
+
+    Ok(Value::undefined())
+}
+
+pub fn find_last_index(cx: CallContext) -> Result<Value, Value> {
+    let this = Value::Object(cx.this.to_object(cx.scope)?);
+    let len = this.length_of_array_like(cx.scope)?;
+    let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
+
+    for k in (0..len).rev() {
+        let pk = cx.scope.intern_usize(k);
+        let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
+        let test = call_callback(cx.scope, &callback, &this_arg, pkv, k, &this)?.to_boolean(cx.scope)?;
 
         if test {
             return Ok(Value::number(k as f64));
@@ -265,26 +358,78 @@ pub fn for_each(cx: CallContext) -> Result<Value, Value> {
     let this = Value::Object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
     let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
 
     for k in 0..len {
-        let pk = cx.scope.intern_usize(k);
-        let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
-        let args = vec![pkv, Value::number(k as f64)];
-        callback.apply(cx.scope, Value::undefined(), args).root_err(cx.scope)?;
+        if let Some(pkv) = get_element_if_present(cx.scope, &this, k)? {
+            call_callback(cx.scope, &callback, &this_arg, pkv, k, &this)?;
+        }
     }
 
     Ok(Value::undefined())
 }
 
+/// Resolves the `fromIndex` argument of `indexOf` and `includes` to the index at which to start searching
+fn search_start_index(scope: &mut LocalScope, from_index: Value, len: usize) -> Result<usize, Value> {
+    let from_index = from_index.to_integer_or_infinity(scope)?;
+    if from_index >= 0.0 {
+        Ok((from_index as usize).min(len))
+    } else {
+        let k = len as isize + from_index as isize;
+        Ok(usize::try_from(k).unwrap_or_default())
+    }
+}
+
+/// Searches for a number in an array without going through the generic property lookup,
+/// comparing elements using `eq` (strict equality or SameValueZero).
+///
+/// Returns `None` if the fast path cannot be used for this array. Accessor elements could have side effects,
+/// so this also bails out as soon as it reaches one, in which case the caller needs to fall back to the generic path.
+fn index_of_number_fast_path(
+    this: &Value,
+    search_element: f64,
+    from_index: usize,
+    eq: fn(&Value, &Value) -> bool,
+) -> Option<Option<usize>> {
+    let array = this.downcast_ref::<Array>()?;
+    let items = array.items.borrow();
+    let ArrayInner::NonHoley(items) = &*items else {
+        return None;
+    };
+
+    let search_element = Value::number(search_element);
+    for (k, item) in items.iter().enumerate().skip(from_index) {
+        match item.kind() {
+            PropertyValueKind::Static(value) if eq(value, &search_element) => return Some(Some(k)),
+            PropertyValueKind::Static(_) => {}
+            PropertyValueKind::Trap { .. } => return None,
+        }
+    }
+
+    Some(None)
+}
+
 pub fn includes(cx: CallContext) -> Result<Value, Value> {
     let this = Value::Object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
+    if len == 0 {
+        return Ok(false.into());
+    }
+
+    // `includes` uses SameValueZero, so unlike `indexOf` it can find `NaN`
     let search_element = cx.args.first().unwrap_or_undefined();
+    let from_index = search_start_index(cx.scope, cx.args.get(1).unwrap_or_undefined(), len)?;
 
-    for k in 0..len {
+    if let Value::Number(Number(n)) = search_element {
+        if let Some(found) = index_of_number_fast_path(&this, n, from_index, same_value_zero) {
+            return Ok(found.is_some().into());
+        }
+    }
+
+    for k in from_index..len {
         let pk = cx.scope.intern_usize(k);
         let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
-        if strict_eq(&pkv, &search_element) {
+        if same_value_zero(&pkv, &search_element) {
             return Ok(true.into());
         }
     }
@@ -300,22 +445,18 @@ pub fn index_of(cx: CallContext) -> Result<Value, Value> {
     }
 
     let search_element = cx.args.first().unwrap_or_undefined();
-    let from_index = cx.args.get(1).unwrap_or_undefined().to_integer_or_infinity(cx.scope)?;
-    if from_index == f64::INFINITY {
-        return Ok(Value::number(-1.));
-    } else if from_index == f64::NEG_INFINITY {
-        return Ok(Value::number(0.));
+    let from_index = search_start_index(cx.scope, cx.args.get(1).unwrap_or_undefined(), len)?;
+
+    if let Value::Number(Number(n)) = search_element {
+        if let Some(found) = index_of_number_fast_path(&this, n, from_index, strict_eq) {
+            return Ok(Value::number(found.map_or(-1.0, |k| k as f64)));
+        }
     }
-    let from_index = if from_index.is_sign_positive() {
-        from_index as usize
-    } else {
-        let k = len as isize + from_index as isize;
-        usize::try_from(k).unwrap_or_default()
-    };
 
     for k in from_index..len {
-        let pk = cx.scope.intern_usize(k);
-        let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
+        let Some(pkv) = get_element_if_present(cx.scope, &this, k)? else {
+            continue;
+        };
         if strict_eq(&pkv, &search_element) {
             return Ok(Value::number(k as f64));
         }
@@ -346,8 +487,9 @@ pub fn last_index_of(cx: CallContext) -> Result<Value, Value> {
     };
 
     for k in (0..=from_index).rev() {
-        let pk = cx.scope.intern_usize(k);
-        let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
+        let Some(pkv) = get_element_if_present(cx.scope, &this, k)? else {
+            continue;
+        };
         if strict_eq(&pkv, &search_element) {
             return Ok(Value::number(k as f64));
         }
@@ -360,18 +502,30 @@ pub fn map(cx: CallContext) -> Result<Value, Value> {
     let this = Value::Object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
     let callback = cx.args.first().unwrap_or_undefined();
+    let this_arg = cx.args.get(1).unwrap_or_undefined();
+
+    #[cfg(feature = "jit")]
+    if let Some(values) = crate::jit::numeric_map(cx.scope, &this, &callback) {
+        return Ok(cx.scope.register(values).into());
+    }
+
     let mut values = Vec::new();
 
     for k in 0..len {
-        let pk = cx.scope.intern_usize(k);
-        let pkv = this.get_property(cx.scope, pk.into()).root(cx.scope)?;
-        let args = vec![pkv.clone(), Value::number(k as f64)];
-        let value = callback.apply(cx.scope, Value::undefined(), args).root(cx.scope)?;
+        // Holes are preserved in the resulting array
+        let element = match get_element_if_present(cx.scope, &this, k)? {
+            Some(pkv) => {
+                let value = call_callback(cx.scope, &callback, &this_arg, pkv, k, &this)?;
+                Element::Value(PropertyValue::static_default(value))
+            }
+            None => Element::Hole { count: 1 },
+        };
 
-        values.push(PropertyValue::static_default(value));
+        values.push(element);
     }
 
-    let values = Array::from_vec(cx.scope, values);
+    let values = Array::from_possibly_holey(cx.scope, values);
+    values.try_convert_to_non_holey();
 
     Ok(cx.scope.register(values).into())
 }
@@ -385,8 +539,9 @@ pub fn pop(cx: CallContext) -> Result<Value, Value> {
     }
 
     let new_len = len - 1;
-    let new_len_sym = cx.scope.intern_usize(len - 1);
-    let value = this.delete_property(cx.scope, new_len_sym.into())?.root(cx.scope);
+    let new_len_sym = cx.scope.intern_usize(new_len);
+    let value = this.get_property(cx.scope, new_len_sym.into()).root(cx.scope)?;
+    this.delete_property(cx.scope, new_len_sym.into())?;
     this.set_property(
         cx.scope,
         sym::length.into(),
@@ -399,20 +554,20 @@ pub fn pop(cx: CallContext) -> Result<Value, Value> {
 pub fn push(cx: CallContext) -> Result<Value, Value> {
     let this = Value::Object(cx.this.to_object(cx.scope)?);
     let len = this.length_of_array_like(cx.scope)?;
-
-    let mut last = Value::undefined();
-
-    if cx.args.is_empty() {
-        let len = cx.scope.intern_usize(len);
-        this.set_property(cx.scope, len.into(), PropertyValue::static_default(Value::undefined()))?;
-    }
+    let new_len = len + cx.args.len();
 
     for (idx, arg) in cx.args.into_iter().enumerate() {
-        last = arg.clone();
         array::spec_array_set_property(cx.scope, &this, idx + len, PropertyValue::static_default(arg))?;
     }
 
-    Ok(last)
+    let new_len = Value::number(new_len as f64);
+    this.set_property(
+        cx.scope,
+        sym::length.into(),
+        PropertyValue::static_default(new_len.clone()),
+    )?;
+
+    Ok(new_len)
 }
 
 pub fn reverse(cx: CallContext) -> Result<Value, Value> {
@@ -440,7 +595,7 @@ pub fn shift(cx: CallContext) -> Result<Value, Value> {
         return Ok(Value::undefined());
     }
 
-    let prop = this.delete_property(cx.scope, sym::zero.into())?.root(cx.scope);
+    let prop = this.get_property(cx.scope, sym::zero.into()).root(cx.scope)?;
 
     for k in 1..len {
         let pk = cx.scope.intern_usize(k);
@@ -449,6 +604,8 @@ pub fn shift(cx: CallContext) -> Result<Value, Value> {
         this.set_property(cx.scope, prev_pk.into(), PropertyValue::static_default(pkv))?;
     }
 
+    let last = cx.scope.intern_usize(len - 1);
+    this.delete_property(cx.scope, last.into())?;
     this.set_property(
         cx.scope,
         sym::length.into(),
@@ -481,8 +638,14 @@ fn shift_array(
         )?;
     }
 
-    // Start shifting the elements by the shift_by (can be either negative or positive) amount
-    for k in range {
+    // Start shifting the elements by the shift_by (can be either negative or positive) amount.
+    // When shifting to the right, iterate from the end so that elements are read before they are overwritten
+    let indices: Box<dyn Iterator<Item = isize>> = if shift_by > 0 {
+        Box::new(range.rev())
+    } else {
+        Box::new(range)
+    };
+    for k in indices {
         let pk = scope.intern_isize(k);
         let shift_pk = scope.intern_isize(k + shift_by);
         let pkv = arr.get_property(scope, pk.into()).root(scope)?;
@@ -515,7 +678,14 @@ pub fn unshift(cx: CallContext) -> Result<Value, Value> {
         this.set_property(cx.scope, idx.into(), PropertyValue::static_default(arg))?;
     }
 
-    Ok(Value::number(new_len as f64))
+    let new_len = Value::number(new_len as f64);
+    this.set_property(
+        cx.scope,
+        sym::length.into(),
+        PropertyValue::static_default(new_len.clone()),
+    )?;
+
+    Ok(new_len)
 }
 
 fn to_slice_index(index: isize, len: usize) -> usize {
@@ -5,6 +5,10 @@ use crate::value::ops::conversions::ValueConversion;
 use crate::value::Value;
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if !cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Constructor ArrayBuffer requires 'new'");
+    }
+
     let length = match cx.args.first() {
         Some(length) => length.to_number(cx.scope)? as usize,
         None => 0,
@@ -14,19 +14,28 @@ pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     }
 }
 
+/// Returns the boolean value of `this`, unwrapping it if it is a boxed boolean
+fn this_boolean_value(value: &Value) -> Option<bool> {
+    match value {
+        Value::Boolean(b) => Some(*b),
+        _ => value.downcast_ref::<boxed::Boolean>().map(|b| *b.value()),
+    }
+}
+
 pub fn to_string(cx: CallContext) -> Result<Value, Value> {
-    if let Value::Boolean(b) = cx.this {
-        let s = b.then(|| sym::true_.into()).unwrap_or_else(|| sym::false_.into());
+    match this_boolean_value(&cx.this) {
+        Some(b) => {
+            let s = b.then(|| sym::true_.into()).unwrap_or_else(|| sym::false_.into());
 
-        Ok(Value::String(s))
-    } else {
-        todo!()
+            Ok(Value::String(s))
+        }
+        None => throw!(cx.scope, TypeError, "Boolean.prototype.toString called on non-boolean"),
     }
 }
 
 pub fn value_of(cx: CallContext) -> Result<Value, Value> {
-    match cx.this {
-        Value::Boolean(b) => Ok(Value::Boolean(b)),
-        _ => throw!(cx.scope, TypeError, "Boolean.valueOf called on non-boolean"),
+    match this_boolean_value(&cx.this) {
+        Some(b) => Ok(Value::Boolean(b)),
+        None => throw!(cx.scope, TypeError, "Boolean.valueOf called on non-boolean"),
     }
 }
@@ -1,8 +1,18 @@
 use crate::throw;
+use crate::value::date::Date;
 use crate::value::function::native::CallContext;
+use crate::value::ops::conversions::ValueConversion;
 use crate::value::root_ext::RootErrExt;
 use crate::value::Value;
 
+const MS_PER_SECOND: f64 = 1000.0;
+const MS_PER_MINUTE: f64 = 60.0 * MS_PER_SECOND;
+const MS_PER_HOUR: f64 = 60.0 * MS_PER_MINUTE;
+const MS_PER_DAY: f64 = 24.0 * MS_PER_HOUR;
+
+/// The largest absolute time value that a date can hold (±100,000,000 days around the epoch)
+const MAX_TIME: f64 = 8.64e15;
+
 pub fn time_millis(cx: &mut CallContext) -> Result<u64, Value> {
     let callback = match cx.scope.params().time_millis_callback() {
         Some(c) => c,
@@ -12,11 +22,161 @@ pub fn time_millis(cx: &mut CallContext) -> Result<u64, Value> {
     callback(cx.scope).root_err(cx.scope)
 }
 
-pub fn constructor(cx: CallContext) -> Result<Value, Value> {
-    throw!(cx.scope, Error, "Date constructor is currently unimplemented")
+/// Implements the TimeClip abstract operation, turning out of range times into `NaN`
+fn time_clip(time: f64) -> f64 {
+    if !time.is_finite() || time.abs() > MAX_TIME {
+        f64::NAN
+    } else {
+        // Adding zero turns -0 into +0
+        time.trunc() + 0.0
+    }
+}
+
+/// Converts a number of days since the epoch into a (year, month, day) triple, with months starting at 1.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// The components of a time value. Dates have no notion of time zones yet, so local time is always UTC.
+struct DateComponents {
+    year: i64,
+    /// Zero-based, like `getMonth()`
+    month: u32,
+    day: u32,
+    hours: u32,
+    minutes: u32,
+    seconds: u32,
+    millis: u32,
+}
+
+impl DateComponents {
+    /// Splits a time value into its components, or returns `None` for invalid dates
+    fn from_time(time: f64) -> Option<Self> {
+        if time.is_nan() {
+            return None;
+        }
+
+        let days = (time / MS_PER_DAY).floor();
+        let time_in_day = time - days * MS_PER_DAY;
+        let (year, month, day) = civil_from_days(days as i64);
+
+        Some(Self {
+            year,
+            month: month - 1,
+            day,
+            hours: (time_in_day / MS_PER_HOUR) as u32,
+            minutes: (time_in_day % MS_PER_HOUR / MS_PER_MINUTE) as u32,
+            seconds: (time_in_day % MS_PER_MINUTE / MS_PER_SECOND) as u32,
+            millis: (time_in_day % MS_PER_SECOND) as u32,
+        })
+    }
+}
+
+pub fn constructor(mut cx: CallContext) -> Result<Value, Value> {
+    if !cx.is_constructor_call {
+        throw!(cx.scope, Error, "Calling Date as a function is currently unimplemented");
+    }
+
+    let time = match &cx.args[..] {
+        [] => time_millis(&mut cx)? as f64,
+        [value] => match value.downcast_ref::<Date>() {
+            Some(date) => date.time(),
+            None => match value.to_primitive(cx.scope, None)? {
+                Value::String(_) => throw!(cx.scope, Error, "Parsing date strings is currently unimplemented"),
+                value => time_clip(value.to_number(cx.scope)?),
+            },
+        },
+        _ => throw!(
+            cx.scope,
+            Error,
+            "Date constructor with multiple arguments is currently unimplemented"
+        ),
+    };
+
+    let date = Date::new(cx.scope, time);
+    Ok(Value::Object(cx.scope.register(date)))
 }
 
 pub fn now(mut cx: CallContext) -> Result<Value, Value> {
     let time = time_millis(&mut cx)?;
     Ok(Value::number(time as f64))
 }
+
+fn this_time(cx: &mut CallContext) -> Result<f64, Value> {
+    match cx.this.downcast_ref::<Date>() {
+        Some(date) => Ok(date.time()),
+        None => throw!(&mut cx.scope, TypeError, "this is not a Date object"),
+    }
+}
+
+/// Returns one of the components of the date, or `NaN` for invalid dates
+fn get_component(mut cx: CallContext, component: impl Fn(&DateComponents) -> f64) -> Result<Value, Value> {
+    let time = this_time(&mut cx)?;
+    let value = DateComponents::from_time(time).map_or(f64::NAN, |c| component(&c));
+    Ok(Value::number(value))
+}
+
+pub fn get_time(mut cx: CallContext) -> Result<Value, Value> {
+    let time = this_time(&mut cx)?;
+    Ok(Value::number(time))
+}
+
+pub fn get_full_year(cx: CallContext) -> Result<Value, Value> {
+    get_component(cx, |c| c.year as f64)
+}
+
+pub fn get_month(cx: CallContext) -> Result<Value, Value> {
+    get_component(cx, |c| f64::from(c.month))
+}
+
+pub fn get_date(cx: CallContext) -> Result<Value, Value> {
+    get_component(cx, |c| f64::from(c.day))
+}
+
+pub fn get_hours(cx: CallContext) -> Result<Value, Value> {
+    get_component(cx, |c| f64::from(c.hours))
+}
+
+pub fn get_minutes(cx: CallContext) -> Result<Value, Value> {
+    get_component(cx, |c| f64::from(c.minutes))
+}
+
+pub fn get_seconds(cx: CallContext) -> Result<Value, Value> {
+    get_component(cx, |c| f64::from(c.seconds))
+}
+
+pub fn to_iso_string(mut cx: CallContext) -> Result<Value, Value> {
+    let time = this_time(&mut cx)?;
+    let Some(c) = DateComponents::from_time(time) else {
+        throw!(cx.scope, RangeError, "Invalid time value")
+    };
+
+    // Years outside of 0..=9999 use the extended six digit format with a sign
+    let year = if (0..=9999).contains(&c.year) {
+        format!("{:04}", c.year)
+    } else {
+        format!("{}{:06}", if c.year < 0 { '-' } else { '+' }, c.year.abs())
+    };
+
+    let iso = format!(
+        "{year}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        c.month + 1,
+        c.day,
+        c.hours,
+        c.minutes,
+        c.seconds,
+        c.millis
+    );
+    Ok(Value::String(cx.scope.intern(iso).into()))
+}
@@ -0,0 +1,77 @@
+use crate::throw;
+use crate::value::finalization_registry::FinalizationRegistry;
+use crate::value::function::native::CallContext;
+use crate::value::object::Object;
+use crate::value::{Typeof, Value, ValueContext};
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if !cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Constructor FinalizationRegistry requires 'new'");
+    }
+
+    let cleanup = match cx.args.first().unwrap_or_undefined() {
+        Value::Object(cleanup) if matches!(cleanup.type_of(), Typeof::Function) => cleanup,
+        _ => throw!(cx.scope, TypeError, "FinalizationRegistry: cleanup must be callable"),
+    };
+
+    let registry = FinalizationRegistry::new(cx.scope, cleanup);
+    let registry = cx.scope.register(registry);
+    cx.scope.add_finalization_registry(registry.clone());
+
+    Ok(Value::Object(registry))
+}
+
+pub fn register(cx: CallContext) -> Result<Value, Value> {
+    let this = match cx.this.downcast_ref::<FinalizationRegistry>() {
+        Some(registry) => registry,
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    let target = match cx.args.first().unwrap_or_undefined() {
+        Value::Object(target) => target,
+        _ => throw!(
+            cx.scope,
+            TypeError,
+            "FinalizationRegistry.prototype.register: invalid target"
+        ),
+    };
+    let held_value = cx.args.get(1).unwrap_or_undefined();
+    if held_value == Value::Object(target.clone()) {
+        throw!(
+            cx.scope,
+            TypeError,
+            "FinalizationRegistry.prototype.register: target and holdings must not be same"
+        );
+    }
+
+    let unregister_token = match cx.args.get(2).unwrap_or_undefined() {
+        Value::Object(token) => Some(token),
+        Value::Undefined(_) => None,
+        _ => throw!(
+            cx.scope,
+            TypeError,
+            "FinalizationRegistry.prototype.register: invalid unregister token"
+        ),
+    };
+
+    this.register(target, held_value, unregister_token);
+    Ok(Value::undefined())
+}
+
+pub fn unregister(cx: CallContext) -> Result<Value, Value> {
+    let this = match cx.this.downcast_ref::<FinalizationRegistry>() {
+        Some(registry) => registry,
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    let token = match cx.args.first().unwrap_or_undefined() {
+        Value::Object(token) => token,
+        _ => throw!(
+            cx.scope,
+            TypeError,
+            "FinalizationRegistry.prototype.unregister: invalid unregister token"
+        ),
+    };
+
+    Ok(Value::Boolean(this.unregister(&token)))
+}
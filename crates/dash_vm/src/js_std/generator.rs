@@ -1,24 +1,83 @@
 use std::mem;
 
 use crate::dispatch::HandleResult;
-use crate::frame::Frame;
+use crate::frame::{Frame, TryBlock};
 use crate::gc::interner::sym;
 use crate::localscope::LocalScope;
 use crate::throw;
+use crate::value::function::async_generator::AsyncGeneratorIterator;
 use crate::value::function::generator::{as_generator, GeneratorState};
 use crate::value::function::native::CallContext;
 use crate::value::function::{Function, FunctionKind};
 use crate::value::object::{NamedObject, Object, PropertyValue};
+use crate::value::promise::Promise;
 use crate::value::root_ext::RootErrExt;
 use crate::value::{Root, Value, ValueContext};
 
 pub fn next(cx: CallContext) -> Result<Value, Value> {
     let arg = cx.args.first().unwrap_or_undefined();
+    match resume(cx.scope, &cx.this, GeneratorResumeKind::Next(arg))? {
+        GeneratorResumeResult::Return(value) => create_generator_value(cx.scope, true, Some(value)),
+        // Async functions are desugared to generators, so `await` is treated equivalent to `yield`, for now...
+        GeneratorResumeResult::Yield(value) | GeneratorResumeResult::Await(value) => {
+            create_generator_value(cx.scope, false, Some(value))
+        }
+    }
+}
+
+pub fn async_next(cx: CallContext) -> Result<Value, Value> {
+    let arg = cx.args.first().unwrap_or_undefined();
+    let this = match &cx.this {
+        Value::Object(this) if this.as_any().is::<AsyncGeneratorIterator>() => this.clone(),
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    let promise = Promise::new(cx.scope);
+    let promise = cx.scope.register(promise);
+
+    let generator = this.as_any().downcast_ref::<AsyncGeneratorIterator>().unwrap();
+    generator.enqueue(cx.scope, this.clone(), arg, promise.clone())?;
+
+    Ok(Value::Object(promise))
+}
+
+/// The point at which a generator stopped after being resumed
+pub(crate) enum GeneratorResumeResult {
+    Return(Value),
+    Yield(Value),
+    Await(Value),
+}
+
+/// How the suspended `yield` or `await` expression of a generator completes when it is resumed
+pub(crate) enum GeneratorResumeKind {
+    /// The expression evaluates to the value
+    Next(Value),
+    /// The value is thrown at the expression, as if by a `throw` statement
+    Throw(Value),
+}
+
+/// Runs a generator iterator until it returns or reaches the next `yield` or `await`.
+pub(crate) fn resume(
+    scope: &mut LocalScope,
+    this: &Value,
+    kind: GeneratorResumeKind,
+) -> Result<GeneratorResumeResult, Value> {
     let frame = {
-        let generator = as_generator(cx.scope, &cx.this)?;
+        let generator = as_generator(scope, this)?;
+
+        if let GeneratorResumeKind::Throw(value) = &kind {
+            if !generator.did_run() {
+                // There is no suspended expression to throw at yet, so the exception completes the generator
+                generator.state().replace(GeneratorState::Finished);
+                return Err(value.clone());
+            }
+        }
 
         let (ip, old_stack, arguments, try_blocks) = match &mut *generator.state().borrow_mut() {
-            GeneratorState::Finished => return create_generator_value(cx.scope, true, None),
+            GeneratorState::Finished => match kind {
+                GeneratorResumeKind::Next(_) => return Ok(GeneratorResumeResult::Return(Value::undefined())),
+                GeneratorResumeKind::Throw(value) => return Err(value),
+            },
             GeneratorState::Running {
                 ip,
                 stack,
@@ -31,82 +90,87 @@ pub fn next(cx: CallContext) -> Result<Value, Value> {
         let function = match function.as_any().downcast_ref::<Function>().map(|fun| fun.kind()) {
             Some(FunctionKind::Generator(gen)) => gen.function(),
             Some(FunctionKind::Async(fun)) => fun.inner().function(),
-            _ => throw!(cx.scope, TypeError, "Incompatible generator function"),
+            Some(FunctionKind::AsyncGenerator(fun)) => fun.inner().function(),
+            _ => throw!(scope, TypeError, "Incompatible generator function"),
         };
 
-        cx.scope.try_blocks.extend(try_blocks);
-        let current_sp = cx.scope.stack_size();
-        cx.scope.try_extend_stack(old_stack).root_err(cx.scope)?;
+        // The generator may be resumed at a different call depth than the one it was suspended at,
+        // so the try blocks need to refer to the frame it is about to be executed in
+        let fp = scope.frames.len() + 1;
+        scope
+            .try_blocks
+            .extend(try_blocks.into_iter().map(|tb| TryBlock { frame_ip: fp, ..tb }));
+        let current_sp = scope.stack_size();
+        scope.try_extend_stack(old_stack).root_err(scope)?;
 
-        let mut frame = Frame::from_function(None, function, false, false, arguments);
+        let mut frame = Frame::from_function(Some(generator.this()), function, false, false, arguments);
         frame.set_ip(ip);
         frame.set_sp(current_sp);
 
         if !generator.did_run() {
             // If it hasn't run before, do the stack space management initially (push undefined values for locals)
             // We only want to do this if the generator hasn't run already, because the locals are already in `old_stack`
-            cx.scope.pad_stack_for_frame(&frame);
-        } else {
+            scope.pad_stack_for_frame(&frame);
+        } else if let GeneratorResumeKind::Next(arg) = &kind {
             // Generator did run before. Push the yielded value onto the stack, which will be what the yield expression
             // evaluates to.
-            cx.scope.stack.push(arg);
+            scope.stack.push(arg.clone());
         }
 
         frame
     };
 
     // Generators work a bit different from normal functions, so we do the stack padding management ourselves here
-    let result = match cx.scope.execute_frame_raw(frame) {
+    let result = match kind {
+        GeneratorResumeKind::Next(_) => scope.execute_frame_raw(frame),
+        GeneratorResumeKind::Throw(value) => scope.execute_frame_raw_with_throw(frame, value.into()),
+    };
+    let result = match result {
         Ok(v) => v,
-        // TODO: this should not early return because we never reset the temporarily broken generator state
-        Err(v) => return Err(v.root(cx.scope)),
+        Err(v) => {
+            // An uncaught exception completes the generator
+            as_generator(scope, this)?.state().replace(GeneratorState::Finished);
+            return Err(v.root(scope));
+        }
     };
-    let generator = as_generator(cx.scope, &cx.this)?;
+    let generator = as_generator(scope, this)?;
 
-    match result {
+    let (value, is_yield) = match result {
         HandleResult::Return(value) => {
             generator.state().replace(GeneratorState::Finished);
-            let value = value.root(cx.scope);
-
-            create_generator_value(cx.scope, true, Some(value))
+            return Ok(GeneratorResumeResult::Return(value.root(scope)));
         }
-        HandleResult::Yield(value) | HandleResult::Await(value) => {
-            // Async functions are desugared to generators, so `await` is treated equivalent to `yield`, for now...
-            let value = value.root(cx.scope);
-
-            let fp = cx.scope.frames.len();
-            let frame = cx.scope.pop_frame().expect("Generator frame is missing");
-            let stack = cx.scope.drain_stack(frame.sp..).collect::<Vec<_>>();
-
-            // Save any try blocks part of this frame
-            let frame_try_blocks = cx
-                .scope
-                .try_blocks
-                .iter()
-                .rev()
-                .take_while(|b| b.frame_ip == fp)
-                .count();
-
-            let total_try_blocks = cx.scope.try_blocks.len();
-            let try_blocks = cx
-                .scope
-                .try_blocks
-                .drain(total_try_blocks - frame_try_blocks..)
-                .collect::<Vec<_>>();
-
-            generator.state().replace(GeneratorState::Running {
-                ip: frame.ip,
-                stack,
-                arguments: frame.arguments,
-                try_blocks,
-            });
+        HandleResult::Yield(value) => (value.root(scope), true),
+        HandleResult::Await(value) => (value.root(scope), false),
+    };
 
-            create_generator_value(cx.scope, false, Some(value))
-        }
+    let fp = scope.frames.len();
+    let frame = scope.pop_frame().expect("Generator frame is missing");
+    let stack = scope.drain_stack(frame.sp..).collect::<Vec<_>>();
+
+    // Save any try blocks part of this frame
+    let frame_try_blocks = scope.try_blocks.iter().rev().take_while(|b| b.frame_ip == fp).count();
+
+    let total_try_blocks = scope.try_blocks.len();
+    let try_blocks = scope
+        .try_blocks
+        .drain(total_try_blocks - frame_try_blocks..)
+        .collect::<Vec<_>>();
+
+    generator.state().replace(GeneratorState::Running {
+        ip: frame.ip,
+        stack,
+        arguments: frame.arguments,
+        try_blocks,
+    });
+
+    match is_yield {
+        true => Ok(GeneratorResumeResult::Yield(value)),
+        false => Ok(GeneratorResumeResult::Await(value)),
     }
 }
 
-fn create_generator_value(scope: &mut LocalScope, done: bool, value: Option<Value>) -> Result<Value, Value> {
+pub(crate) fn create_generator_value(scope: &mut LocalScope, done: bool, value: Option<Value>) -> Result<Value, Value> {
     let obj = NamedObject::new(scope);
     obj.set_property(scope, sym::done.into(), PropertyValue::static_default(done.into()))?;
     obj.set_property(
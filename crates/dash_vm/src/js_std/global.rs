@@ -1,10 +1,22 @@
-use dash_middle::parser::error::IntoFormattableErrors;
+use dash_parser::ParseMode;
+
+use rustc_hash::FxHashMap;
 
 use crate::eval::EvalError;
+use crate::gc::handle::Handle;
+use crate::localscope::LocalScope;
 use crate::throw;
+use crate::value::arguments::Arguments;
+use crate::value::array::Array;
+use crate::value::arraybuffer::ArrayBuffer;
+use crate::value::date::Date;
 use crate::value::function::native::CallContext;
+use crate::value::map::Map;
+use crate::value::object::{NamedObject, Object, PropertyKey, PropertyValue};
 use crate::value::ops::conversions::ValueConversion;
-use crate::value::{Root, Value, ValueContext};
+use crate::value::set::Set;
+use crate::value::typedarray::TypedArray;
+use crate::value::{Root, Typeof, Value, ValueContext};
 
 pub fn is_nan(cx: CallContext) -> Result<Value, Value> {
     // 1. Let num be ? ToNumber(number).
@@ -20,13 +32,24 @@ pub fn eval(cx: CallContext) -> Result<Value, Value> {
         other => return Ok(other),
     };
 
-    match cx.scope.eval(&source, Default::default()) {
+    match cx.scope.eval_with_mode(&source, Default::default(), ParseMode::Script) {
         Ok(v) => Ok(v.root(cx.scope)),
         Err(EvalError::Exception(ex)) => Err(ex.root(cx.scope)),
-        Err(EvalError::Middle(err)) => throw!(cx.scope, SyntaxError, "{}", err.formattable(&source, true)),
+        Err(EvalError::Middle(errs)) => {
+            let message = errs
+                .first()
+                .map(|err| err.syntax_error_message(&source))
+                .unwrap_or_default();
+            throw!(cx.scope, SyntaxError, "{}", message)
+        }
     }
 }
 
+pub fn collect_garbage(cx: CallContext) -> Result<Value, Value> {
+    cx.scope.perform_gc();
+    Ok(Value::undefined())
+}
+
 pub fn log(cx: CallContext) -> Result<Value, Value> {
     for arg in cx.args {
         let tstr = arg.to_js_string(cx.scope)?;
@@ -57,24 +80,267 @@ pub fn parse_float(cx: CallContext) -> Result<Value, Value> {
 }
 
 pub fn parse_int(cx: CallContext) -> Result<Value, Value> {
+    // 1. Let inputString be ? ToString(string).
     let input_string = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
-    let radix = cx
-        .args
-        .get(1)
-        .cloned()
-        .map(|v| v.to_number(cx.scope))
-        .transpose()?
-        .map(|r| r as u32)
-        .unwrap_or(10);
+    // 6. Let R be ℝ(? ToInt32(radix)).
+    // (Step 6 is performed early because the string is borrowed from the scope afterwards)
+    let mut radix = cx.args.get(1).unwrap_or_undefined().to_int32(cx.scope)?;
 
-    let trimmed_string = input_string.res(cx.scope).trim();
+    // 2. Let S be ! TrimString(inputString, start).
+    let mut s = input_string.res(cx.scope).trim_start();
 
-    // TODO: follow spec
-    let num = Value::number(
-        i32::from_str_radix(trimmed_string, radix)
-            .map(|n| n as f64)
-            .unwrap_or(f64::NAN),
-    );
+    // 3. Let sign be 1.
+    // 4. If S is not empty and the first code unit of S is the code unit 0x002D (HYPHEN-MINUS), set sign to -1.
+    let sign = if s.starts_with('-') { -1.0 } else { 1.0 };
 
-    Ok(num)
+    // 5. If S is not empty and the first code unit of S is either the code unit 0x002B (PLUS SIGN) or the code unit 0x002D (HYPHEN-MINUS), set S to the substring of S from index 1.
+    if let Some(rest) = s.strip_prefix(['+', '-']) {
+        s = rest;
+    }
+
+    // 7. Let stripPrefix be true.
+    let mut strip_prefix = true;
+
+    // 8. If R ≠ 0, then
+    if radix != 0 {
+        // a. If R < 2 or R > 36, return NaN.
+        if !(2..=36).contains(&radix) {
+            return Ok(Value::number(f64::NAN));
+        }
+
+        // b. If R ≠ 16, set stripPrefix to false.
+        if radix != 16 {
+            strip_prefix = false;
+        }
+    } else {
+        // 9. Else, set R to 10.
+        radix = 10;
+    }
+
+    // 10. If stripPrefix is true, then
+    if strip_prefix {
+        // a. If the length of S is at least 2 and the first two code units of S are either "0x" or "0X", then
+        if let Some(rest) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            // i. Set S to the substring of S from index 2.
+            s = rest;
+            // ii. Set R to 16.
+            radix = 16;
+        }
+    }
+
+    // 11. If S contains a code unit that is not a radix-R digit, let end be the index within S of the first such code unit; otherwise, let end be the length of S.
+    // 12. Let Z be the substring of S from 0 to end.
+    let radix = radix as u32;
+    let digits = s.chars().map_while(|c| c.to_digit(radix));
+
+    // 13. If Z is empty, return NaN.
+    // 14. Let mathInt be the integer value that is represented by Z in radix-R notation.
+    let mut math_int = None;
+    for digit in digits {
+        math_int = Some(math_int.unwrap_or(0.0) * f64::from(radix) + f64::from(digit));
+    }
+    let Some(math_int) = math_int else {
+        return Ok(Value::number(f64::NAN));
+    };
+
+    // 15. If mathInt = 0, then
+    //   a. If sign = -1, return -0𝔽.
+    //   b. Return +0𝔽.
+    // 16. Return 𝔽(sign × mathInt).
+    Ok(Value::number(sign * math_int))
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes a binary string as base64, where every character represents a single byte
+pub fn btoa(cx: CallContext) -> Result<Value, Value> {
+    let input = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+
+    let mut bytes = Vec::new();
+    for c in input.res(cx.scope).chars() {
+        match u8::try_from(c) {
+            Ok(b) => bytes.push(b),
+            Err(_) => throw!(
+                cx.scope,
+                Error,
+                "String contains characters outside of the Latin1 range"
+            ),
+        }
+    }
+
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0, |n, (i, &b)| n | (u32::from(b) << (16 - i * 8)));
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (n >> (18 - i * 6)) & 0x3f;
+                output.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    Ok(Value::String(cx.scope.intern(output).into()))
+}
+
+/// Decodes a base64 string into a binary string, where every character represents a single byte
+pub fn atob(cx: CallContext) -> Result<Value, Value> {
+    let input = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+
+    let mut data = input
+        .res(cx.scope)
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect::<Vec<_>>();
+
+    if data.len() % 4 == 0 {
+        for _ in 0..2 {
+            if data.last() == Some(&b'=') {
+                data.pop();
+            }
+        }
+    }
+
+    if data.len() % 4 == 1 {
+        throw!(cx.scope, Error, "The string to be decoded is not correctly encoded");
+    }
+
+    let mut output = String::with_capacity(data.len() / 4 * 3);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for b in data {
+        let Some(index) = BASE64_ALPHABET.iter().position(|&c| c == b) else {
+            throw!(cx.scope, Error, "The string to be decoded is not correctly encoded");
+        };
+
+        buffer = (buffer << 6) | index as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            output.push(char::from((buffer >> bits) as u8));
+        }
+    }
+
+    Ok(Value::String(cx.scope.intern(output).into()))
+}
+
+pub fn structured_clone(cx: CallContext) -> Result<Value, Value> {
+    let value = cx.args.first().unwrap_or_undefined();
+    structured_clone_value(cx.scope, value, &mut FxHashMap::default())
+}
+
+pub fn queue_microtask(cx: CallContext) -> Result<Value, Value> {
+    let callback = match cx.args.first() {
+        Some(Value::Object(obj)) if matches!(obj.type_of(), Typeof::Function) => obj.clone(),
+        _ => throw!(cx.scope, TypeError, "queueMicrotask callback must be a function"),
+    };
+
+    cx.scope.add_async_task(callback);
+    Ok(Value::undefined())
+}
+
+/// Deep-copies a value, following the structured clone algorithm.
+///
+/// `memory` maps already cloned objects to their copy, so that shared references and cycles are preserved in the copy.
+fn structured_clone_value(
+    scope: &mut LocalScope,
+    value: Value,
+    memory: &mut FxHashMap<Handle, Handle>,
+) -> Result<Value, Value> {
+    let object = match value {
+        Value::Object(object) => object,
+        Value::Symbol(_) => throw!(scope, Error, "DataCloneError: Symbol could not be cloned"),
+        Value::External(external) => return structured_clone_value(scope, external.inner().clone(), memory),
+        other => return Ok(other),
+    };
+
+    if let Some(copy) = memory.get(&object) {
+        return Ok(Value::Object(copy.clone()));
+    }
+
+    if let Some(date) = object.as_any().downcast_ref::<Date>() {
+        let copy = scope.register(Date::new(scope, date.time()));
+        memory.insert(object, copy.clone());
+        return Ok(Value::Object(copy));
+    }
+
+    if let Some(buffer) = object.as_any().downcast_ref::<ArrayBuffer>() {
+        let storage = buffer.storage().to_vec();
+        let copy = scope.register(ArrayBuffer::from_storage(scope, storage));
+        memory.insert(object, copy.clone());
+        return Ok(Value::Object(copy));
+    }
+
+    if let Some(array) = object.as_any().downcast_ref::<TypedArray>() {
+        let (kind, buffer) = (array.kind(), array.buffer());
+        let Value::Object(buffer) = structured_clone_value(scope, Value::Object(buffer), memory)? else {
+            unreachable!("cloned ArrayBuffer must be an object")
+        };
+        let copy = scope.register(TypedArray::new(scope, buffer, kind));
+        memory.insert(object, copy.clone());
+        return Ok(Value::Object(copy));
+    }
+
+    if let Some(map) = object.as_any().downcast_ref::<Map>() {
+        let entries = map.entries();
+        let copy = scope.register(Map::new(scope));
+        memory.insert(object, copy.clone());
+        for (key, value) in entries {
+            let key = structured_clone_value(scope, key, memory)?;
+            let value = structured_clone_value(scope, value, memory)?;
+            copy.as_any().downcast_ref::<Map>().unwrap().set(key, value);
+        }
+        return Ok(Value::Object(copy));
+    }
+
+    if let Some(set) = object.as_any().downcast_ref::<Set>() {
+        let values = set.values();
+        let copy = scope.register(Set::new(scope));
+        memory.insert(object, copy.clone());
+        for value in values {
+            let value = structured_clone_value(scope, value, memory)?;
+            copy.as_any().downcast_ref::<Set>().unwrap().add(value);
+        }
+        return Ok(Value::Object(copy));
+    }
+
+    let is_array = object.as_any().downcast_ref::<Array>().is_some();
+    // `arguments` objects are cloned into plain objects
+    let is_plain_object = object.as_any().downcast_ref::<NamedObject>().is_some()
+        || object.as_any().downcast_ref::<Arguments>().is_some();
+    if !is_array && !is_plain_object {
+        let what = match object.type_of() {
+            Typeof::Function => "Function",
+            _ => "Object",
+        };
+        throw!(scope, Error, "DataCloneError: {} could not be cloned", what);
+    }
+
+    let source = Value::Object(object.clone());
+    let copy = if is_array {
+        let len = source.length_of_array_like(scope)?;
+        let copy = scope.register(Array::with_hole(scope, len));
+        memory.insert(object, copy.clone());
+        copy
+    } else {
+        let copy = scope.register(NamedObject::new(scope));
+        memory.insert(object, copy.clone());
+        copy
+    };
+
+    for key in source.own_keys(scope)? {
+        // Only string keyed properties are cloned, symbol keys are skipped
+        let Value::String(key) = key else { continue };
+        let key = PropertyKey::String(key);
+        let value = source.get_property(scope, key.clone()).root(scope)?;
+        let value = structured_clone_value(scope, value, memory)?;
+        copy.set_property(scope, key, PropertyValue::static_default(value))?;
+    }
+
+    Ok(Value::Object(copy))
 }
@@ -1,6 +1,15 @@
+use std::fmt::Write;
+
+use crate::gc::interner::sym;
+use crate::localscope::LocalScope;
+use crate::value::array::Array;
+use crate::value::boxed::{Boolean as BoxedBoolean, Number as BoxedNumber, String as BoxedString};
 use crate::value::function::native::CallContext;
+use crate::value::object::{NamedObject, Object, PropertyDataDescriptor, PropertyValue};
 use crate::value::ops::conversions::ValueConversion;
-use crate::value::{Value, ValueContext};
+use crate::value::root_ext::RootErrExt;
+use crate::value::string::JsString;
+use crate::value::{Root, Typeof, Value, ValueContext};
 use crate::{json, throw};
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
@@ -10,11 +19,258 @@ pub fn constructor(cx: CallContext) -> Result<Value, Value> {
 pub fn parse(cx: CallContext) -> Result<Value, Value> {
     let value = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
     let bytes = value.res(cx.scope).as_bytes().to_owned();
-    let parse = match json::parser::Parser::new(&bytes, cx.scope).parse() {
+    let max_depth = cx
+        .scope
+        .params()
+        .max_json_depth()
+        .unwrap_or(json::parser::DEFAULT_MAX_DEPTH);
+    let parse = match json::parser::Parser::new(&bytes, cx.scope)
+        .with_max_depth(max_depth)
+        .parse()
+    {
         Ok(v) => v,
         Err(e) => {
             throw!(cx.scope, SyntaxError, "{}", e.to_string())
         }
     };
-    Ok(parse)
+
+    match cx.args.get(1) {
+        Some(reviver) if matches!(reviver.type_of(), Typeof::Function) => {
+            let root = NamedObject::new(cx.scope);
+            root.set_property(cx.scope, sym::empty.into(), PropertyValue::static_default(parse))?;
+            let root = Value::Object(cx.scope.register(root));
+            internalize(cx.scope, reviver, &root, sym::empty.into())
+        }
+        _ => Ok(parse),
+    }
+}
+
+/// Returns the enumerable, string-keyed own properties of an object
+fn enumerable_own_keys(sc: &mut LocalScope, value: &Value) -> Result<Vec<JsString>, Value> {
+    let mut keys = Vec::new();
+
+    for key in Object::own_keys(value, sc)? {
+        let Value::String(key) = key else { continue };
+
+        let descriptor = value.get_own_property_descriptor(sc, key.into()).root_err(sc)?;
+        if descriptor.is_some_and(|d| d.descriptor.contains(PropertyDataDescriptor::ENUMERABLE)) {
+            keys.push(key);
+        }
+    }
+
+    Ok(keys)
+}
+
+/// Walks the parsed structure bottom-up, replacing every property with the return value of the reviver
+fn internalize(sc: &mut LocalScope, reviver: &Value, holder: &Value, key: JsString) -> Result<Value, Value> {
+    let value = holder.get_property(sc, key.into()).root(sc)?;
+
+    if let Value::Object(_) = value {
+        let keys = if value.downcast_ref::<Array>().is_some() {
+            let len = value.length_of_array_like(sc)?;
+            (0..len).map(|i| sc.intern_usize(i).into()).collect()
+        } else {
+            enumerable_own_keys(sc, &value)?
+        };
+
+        for key in keys {
+            let element = internalize(sc, reviver, &value, key)?;
+            if let Value::Undefined(_) = element {
+                value.delete_property(sc, key.into())?;
+            } else {
+                value.set_property(sc, key.into(), PropertyValue::static_default(element))?;
+            }
+        }
+    }
+
+    reviver
+        .apply(sc, holder.clone(), vec![Value::String(key), value])
+        .root(sc)
+}
+
+struct Stringifier {
+    replacer: Option<Value>,
+    property_list: Option<Vec<JsString>>,
+    /// Objects that are currently being serialized, used for detecting cycles
+    stack: Vec<Value>,
+    out: String,
+}
+
+impl Stringifier {
+    fn new(sc: &mut LocalScope, replacer: Value) -> Result<Self, Value> {
+        let mut this = Self {
+            replacer: None,
+            property_list: None,
+            stack: Vec::new(),
+            out: String::new(),
+        };
+
+        if matches!(replacer.type_of(), Typeof::Function) {
+            this.replacer = Some(replacer);
+        } else if replacer.downcast_ref::<Array>().is_some() {
+            let len = replacer.length_of_array_like(sc)?;
+            let mut list = Vec::new();
+
+            for i in 0..len {
+                let i = sc.intern_usize(i);
+                let item = replacer.get_property(sc, i.into()).root(sc)?;
+
+                let item = match &item {
+                    Value::String(s) => Some(*s),
+                    Value::Number(_) => Some(item.to_js_string(sc)?),
+                    _ if item.downcast_ref::<BoxedString>().is_some()
+                        || item.downcast_ref::<BoxedNumber>().is_some() =>
+                    {
+                        Some(item.to_js_string(sc)?)
+                    }
+                    _ => None,
+                };
+
+                if let Some(item) = item {
+                    if !list.contains(&item) {
+                        list.push(item);
+                    }
+                }
+            }
+
+            this.property_list = Some(list);
+        }
+
+        Ok(this)
+    }
+
+    /// Serializes `holder[key]` and returns whether anything was written
+    fn serialize_property(&mut self, sc: &mut LocalScope, holder: &Value, key: JsString) -> Result<bool, Value> {
+        let mut value = holder.get_property(sc, key.into()).root(sc)?;
+
+        if let Value::Object(_) = value {
+            let to_json = value.get_property(sc, sym::toJSON.into()).root(sc)?;
+            if matches!(to_json.type_of(), Typeof::Function) {
+                value = to_json.apply(sc, value, vec![Value::String(key)]).root(sc)?;
+            }
+        }
+
+        if let Some(replacer) = &self.replacer {
+            value = replacer
+                .apply(sc, holder.clone(), vec![Value::String(key), value])
+                .root(sc)?;
+        }
+
+        if value.downcast_ref::<BoxedNumber>().is_some() {
+            value = Value::number(value.to_number(sc)?);
+        } else if value.downcast_ref::<BoxedString>().is_some() {
+            value = Value::String(value.to_js_string(sc)?);
+        } else if let Some(b) = value.downcast_ref::<BoxedBoolean>() {
+            value = Value::Boolean(*b.value());
+        }
+
+        match &value {
+            Value::Null(_) => self.out.push_str("null"),
+            Value::Boolean(b) => write!(self.out, "{b}").unwrap(),
+            Value::String(s) => quote(s.res(sc), &mut self.out),
+            Value::Number(n) if n.0.is_finite() => {
+                let s = value.to_js_string(sc)?;
+                self.out.push_str(s.res(sc));
+            }
+            Value::Number(_) => self.out.push_str("null"),
+            Value::Object(_) if !matches!(value.type_of(), Typeof::Function) => {
+                if self.stack.contains(&value) {
+                    throw!(sc, TypeError, "Converting circular structure to JSON");
+                }
+
+                self.stack.push(value.clone());
+                if value.downcast_ref::<Array>().is_some() {
+                    self.serialize_array(sc, &value)?;
+                } else {
+                    self.serialize_object(sc, &value)?;
+                }
+                self.stack.pop();
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn serialize_object(&mut self, sc: &mut LocalScope, value: &Value) -> Result<(), Value> {
+        let keys = match &self.property_list {
+            Some(list) => list.clone(),
+            None => enumerable_own_keys(sc, value)?,
+        };
+
+        self.out.push('{');
+        let mut first = true;
+
+        for key in keys {
+            let mark = self.out.len();
+            if !first {
+                self.out.push(',');
+            }
+            quote(key.res(sc), &mut self.out);
+            self.out.push(':');
+
+            if self.serialize_property(sc, value, key)? {
+                first = false;
+            } else {
+                // undefined, functions and symbols are omitted from objects
+                self.out.truncate(mark);
+            }
+        }
+
+        self.out.push('}');
+        Ok(())
+    }
+
+    fn serialize_array(&mut self, sc: &mut LocalScope, value: &Value) -> Result<(), Value> {
+        let len = value.length_of_array_like(sc)?;
+
+        self.out.push('[');
+        for i in 0..len {
+            if i > 0 {
+                self.out.push(',');
+            }
+
+            let key = sc.intern_usize(i);
+            if !self.serialize_property(sc, value, key.into())? {
+                // ... but serialized as null in arrays
+                self.out.push_str("null");
+            }
+        }
+        self.out.push(']');
+        Ok(())
+    }
+}
+
+fn quote(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+pub fn stringify(cx: CallContext) -> Result<Value, Value> {
+    let value = cx.args.first().unwrap_or_undefined();
+    let replacer = cx.args.get(1).unwrap_or_undefined();
+    let mut stringifier = Stringifier::new(cx.scope, replacer)?;
+
+    let wrapper = NamedObject::new(cx.scope);
+    wrapper.set_property(cx.scope, sym::empty.into(), PropertyValue::static_default(value))?;
+    let wrapper = Value::Object(cx.scope.register(wrapper));
+
+    if stringifier.serialize_property(cx.scope, &wrapper, sym::empty.into())? {
+        Ok(Value::String(cx.scope.intern(stringifier.out).into()))
+    } else {
+        Ok(Value::undefined())
+    }
 }
@@ -1,27 +1,36 @@
 use crate::gc::interner::sym;
 use crate::throw;
+use crate::value::array::Array;
 use crate::value::function::native::CallContext;
 use crate::value::map::Map;
 use crate::value::object::PropertyKey;
-use crate::value::ops::conversions::ValueConversion;
+use crate::value::primitive::Number;
 use crate::value::{Root, Value, ValueContext};
 
+use super::object::collect_groups;
+
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if !cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Constructor Map requires 'new'");
+    }
+
     let map = Map::new(cx.scope);
-    if let Some(iter) = cx.args.first() {
-        let len = iter.length_of_array_like(cx.scope)?;
-
-        for i in 0..len {
-            let i = cx.scope.intern_usize(i);
-            let item = iter
-                .get_property(cx.scope, PropertyKey::String(i.into()))
-                .root(cx.scope)?;
-            let k = item
-                .get_property(cx.scope, PropertyKey::String(sym::zero.into()))
-                .root(cx.scope)?;
-            let v = item
-                .get_property(cx.scope, PropertyKey::String(sym::one.into()))
-                .root(cx.scope)?;
+    let iterable = cx.args.first().unwrap_or_undefined();
+    if !matches!(iterable, Value::Undefined(_) | Value::Null(_)) {
+        let mut entries = cx.scope.iterate(iterable)?;
+        while let Some(entry) = entries.next() {
+            let entry = entry?;
+            let scope = entries.scope();
+            if !matches!(entry, Value::Object(_) | Value::External(_)) {
+                throw!(scope, TypeError, "Iterator value is not an entry object");
+            }
+
+            let k = entry
+                .get_property(scope, PropertyKey::String(sym::zero.into()))
+                .root(scope)?;
+            let v = entry
+                .get_property(scope, PropertyKey::String(sym::one.into()))
+                .root(scope)?;
             map.set(k, v);
         }
     }
@@ -93,3 +102,21 @@ pub fn size(cx: CallContext) -> Result<Value, Value> {
 
     Ok(Value::number(this.size() as f64))
 }
+
+pub fn group_by(cx: CallContext) -> Result<Value, Value> {
+    let items = cx.args.first().unwrap_or_undefined();
+    let callback = cx.args.get(1).unwrap_or_undefined();
+    let groups = collect_groups(cx.scope, items, callback, |_, key| match key {
+        // -0 is normalized to +0, as keys are compared using SameValueZero
+        Value::Number(Number(0.0)) => Ok(Value::number(0.0)),
+        other => Ok(other),
+    })?;
+
+    let map = Map::new(cx.scope);
+    for (key, values) in groups {
+        let values = Array::from_vec(cx.scope, values);
+        map.set(key, Value::Object(cx.scope.register(values)));
+    }
+
+    Ok(Value::Object(cx.scope.register(map)))
+}
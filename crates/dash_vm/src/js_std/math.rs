@@ -100,7 +100,10 @@ pub fn ceil(cx: CallContext) -> Result<Value, Value> {
 }
 
 pub fn clz32(cx: CallContext) -> Result<Value, Value> {
-    let n = cx.args.first().unwrap_or_undefined().to_number(cx.scope)? as u32;
+    // 1. Let n be ? ToUint32(x).
+    let n = cx.args.first().unwrap_or_undefined().to_uint32(cx.scope)?;
+    // 2. Let p be the number of leading zero bits in the unsigned 32-bit binary representation of n.
+    // 3. Return 𝔽(p).
     Ok(Value::number(n.leading_zeros() as f64))
 }
 
@@ -197,6 +200,23 @@ pub fn trunc(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::number(n.trunc()))
 }
 
+pub fn imul(cx: CallContext) -> Result<Value, Value> {
+    // 1. Let a be ℝ(? ToUint32(x)).
+    let a = cx.args.first().unwrap_or_undefined().to_uint32(cx.scope)?;
+    // 2. Let b be ℝ(? ToUint32(y)).
+    let b = cx.args.get(1).unwrap_or_undefined().to_uint32(cx.scope)?;
+    // 3. Let product be (a × b) modulo 2^32.
+    // 4. If product ≥ 2^31, return 𝔽(product - 2^32); otherwise return 𝔽(product).
+    Ok(Value::number(a.wrapping_mul(b) as i32 as f64))
+}
+
+pub fn fround(cx: CallContext) -> Result<Value, Value> {
+    // 1. Let n be ? ToNumber(x).
+    let n = cx.args.first().unwrap_or_undefined().to_number(cx.scope)?;
+    // 2.-5. Convert to IEEE 754-2019 binary32 using roundTiesToEven, and back to binary64.
+    Ok(Value::number(n as f32 as f64))
+}
+
 pub fn floor(cx: CallContext) -> Result<Value, Value> {
     // 1. Let n be ? ToNumber(x).
     let n = cx.args.first().unwrap_or_undefined().to_number(cx.scope)?;
@@ -227,13 +247,13 @@ pub fn pow(cx: CallContext) -> Result<Value, Value> {
 pub fn max(cx: CallContext) -> Result<Value, Value> {
     let mut max = -f64::INFINITY;
 
+    // Every argument must be converted to a number, even if an earlier one was NaN
     for arg in cx.args.iter() {
         let n = arg.to_number(cx.scope)?;
-        if n.is_nan() {
-            return Ok(Value::number(f64::NAN));
-        }
-
-        if n > max {
+        if n.is_nan() || max.is_nan() {
+            max = f64::NAN;
+        } else if n > max || (n == 0.0 && max == 0.0 && max.is_sign_negative()) {
+            // +0 is considered larger than -0
             max = n;
         }
     }
@@ -246,11 +266,10 @@ pub fn min(cx: CallContext) -> Result<Value, Value> {
 
     for arg in cx.args.iter() {
         let n = arg.to_number(cx.scope)?;
-        if n.is_nan() {
-            return Ok(Value::number(f64::NAN));
-        }
-
-        if n < min {
+        if n.is_nan() || min.is_nan() {
+            min = f64::NAN;
+        } else if n < min || (n == 0.0 && min == 0.0 && n.is_sign_negative()) {
+            // -0 is considered smaller than +0
             min = n;
         }
     }
@@ -7,6 +7,7 @@ pub mod arraybuffer;
 pub mod boolean;
 pub mod date;
 pub mod error;
+pub mod finalization_registry;
 pub mod function;
 pub mod generator;
 pub mod global;
@@ -16,11 +17,18 @@ pub mod math;
 pub mod number;
 pub mod object;
 pub mod promise;
+pub mod reflect;
 pub mod regex;
 pub mod set;
 pub mod string;
+pub mod string_iterator;
 pub mod symbol;
+pub mod text_decoder;
+pub mod text_encoder;
 pub mod typedarray;
+pub mod weakmap;
+pub mod weakref;
+pub mod weakset;
 
 pub fn identity_this(cx: CallContext) -> Result<Value, Value> {
     Ok(cx.this)
@@ -3,10 +3,13 @@ use crate::util::intern_f64;
 use crate::value::function::native::CallContext;
 use crate::value::ops::conversions::ValueConversion;
 use crate::value::primitive::{Number, MAX_SAFE_INTEGERF, MIN_SAFE_INTEGERF};
-use crate::value::{boxed, Value, ValueContext};
+use crate::value::{boxed, Value};
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
-    let value = cx.args.first().unwrap_or_undefined().to_number(cx.scope)?;
+    let value = match cx.args.first() {
+        Some(arg) => arg.to_number(cx.scope)?,
+        None => 0.0,
+    };
     if cx.is_constructor_call {
         let value = boxed::Number::new(cx.scope, value);
         Ok(Value::Object(cx.scope.register(value)))
@@ -15,30 +18,115 @@ pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     }
 }
 
-pub fn to_string(cx: CallContext) -> Result<Value, Value> {
-    let radix = cx
-        .args
-        .first()
-        .cloned()
-        .map(|v| v.to_number(cx.scope))
-        .transpose()?
-        .map(|n| n as u8)
-        .unwrap_or(10);
+/// Returns the number value of `this`, unwrapping it if it is a boxed number
+fn this_number_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(Number(n)) => Some(*n),
+        _ => value.downcast_ref::<boxed::Number>().map(|n| *n.value()),
+    }
+}
 
-    let Value::Number(Number(num)) = cx.this else {
+pub fn to_string(cx: CallContext) -> Result<Value, Value> {
+    let Some(num) = this_number_value(&cx.this) else {
         throw!(cx.scope, TypeError, "Number.prototype.toString called on non-number")
     };
 
-    let re = match radix {
-        2 => cx.scope.intern(format!("{:b}", num as u64).as_ref()),
+    let radix = match cx.args.first() {
+        Some(Value::Undefined(_)) | None => 10.0,
+        Some(radix) => radix.to_integer_or_infinity(cx.scope)?,
+    };
+
+    if !(2.0..=36.0).contains(&radix) {
+        throw!(cx.scope, RangeError, "toString() radix must be between 2 and 36")
+    }
+
+    let re = match radix as u32 {
         10 => intern_f64(cx.scope, num),
-        16 => cx.scope.intern(format!("{:x}", num as u64)),
-        _ => throw!(cx.scope, RangeError, "Invalid radix: {}", radix),
+        radix => cx.scope.intern(format_radix(num, radix)),
     };
 
     Ok(Value::String(re.into()))
 }
 
+/// Formats a number in the given radix, using the shortest fractional part that uniquely identifies the number
+fn format_radix(num: f64, radix: u32) -> String {
+    if num.is_nan() {
+        return String::from("NaN");
+    }
+    if num.is_infinite() {
+        return String::from(if num < 0.0 { "-Infinity" } else { "Infinity" });
+    }
+
+    let radix_f = f64::from(radix);
+    let value = num.abs();
+    let mut integer = value.floor();
+    let mut fraction = value - integer;
+
+    // Half the distance to the next representable number: any digits past this point are just noise
+    let next_double = |n: f64| f64::from_bits(n.to_bits() + 1);
+    let mut delta = (0.5 * (next_double(value) - value)).max(next_double(0.0));
+
+    let mut fraction_digits = Vec::new();
+    if fraction >= delta {
+        loop {
+            fraction *= radix_f;
+            delta *= radix_f;
+            let digit = fraction as u32;
+            fraction_digits.push(digit);
+            fraction -= f64::from(digit);
+
+            if (fraction > 0.5 || (fraction == 0.5 && digit & 1 == 1)) && fraction + delta > 1.0 {
+                // Round up, propagating the carry into the integer part if necessary
+                loop {
+                    match fraction_digits.pop() {
+                        Some(digit) if digit + 1 < radix => {
+                            fraction_digits.push(digit + 1);
+                            break;
+                        }
+                        Some(_) => {}
+                        None => {
+                            integer += 1.0;
+                            break;
+                        }
+                    }
+                }
+                break;
+            }
+
+            if fraction < delta {
+                break;
+            }
+        }
+    }
+
+    // Digits of numbers that are too large to be represented exactly are filled with zeros
+    let mut integer_digits = Vec::new();
+    while integer / radix_f >= 2f64.powi(53) {
+        integer /= radix_f;
+        integer_digits.push(0);
+    }
+    loop {
+        let remainder = integer % radix_f;
+        integer_digits.push(remainder as u32);
+        integer = (integer - remainder) / radix_f;
+        if integer <= 0.0 {
+            break;
+        }
+    }
+
+    let to_char = |digit: u32| char::from_digit(digit, radix).unwrap();
+    let mut out = String::with_capacity(integer_digits.len() + fraction_digits.len() + 2);
+    if num < 0.0 {
+        out.push('-');
+    }
+    out.extend(integer_digits.into_iter().rev().map(to_char));
+    if !fraction_digits.is_empty() {
+        out.push('.');
+        out.extend(fraction_digits.into_iter().map(to_char));
+    }
+    out
+}
+
 pub fn is_finite(cx: CallContext) -> Result<Value, Value> {
     let num = match cx.args.first() {
         Some(Value::Number(Number(n))) => n,
@@ -89,3 +177,10 @@ pub fn to_fixed(cx: CallContext) -> Result<Value, Value> {
 
     Ok(Value::String(cx.scope.intern(re.as_ref()).into()))
 }
+
+pub fn value_of(cx: CallContext) -> Result<Value, Value> {
+    match this_number_value(&cx.this) {
+        Some(n) => Ok(Value::number(n)),
+        None => throw!(cx.scope, TypeError, "Number.prototype.valueOf called on non-number"),
+    }
+}
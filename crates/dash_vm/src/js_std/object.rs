@@ -11,10 +11,14 @@ use crate::value::ops::conversions::ValueConversion;
 use crate::value::root_ext::RootErrExt;
 use crate::value::{Root, Typeof, Value, ValueContext};
 
+/// Object(...) behaves the same whether or not it is called with `new`:
+/// primitives are boxed, objects are returned as is, and null or undefined create a new empty object
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     match cx.args.first() {
+        Some(Value::Undefined(_) | Value::Null(_)) | None => {
+            Ok(Value::Object(cx.scope.register(NamedObject::new(cx.scope))))
+        }
         Some(v) => v.to_object(cx.scope).map(Value::Object),
-        None => Ok(Value::Object(cx.scope.register(NamedObject::new(cx.scope)))),
     }
 }
 
@@ -31,9 +35,19 @@ pub fn create(cx: CallContext) -> Result<Value, Value> {
 
 pub fn keys(cx: CallContext) -> Result<Value, Value> {
     let obj = cx.args.first().unwrap_or_undefined().to_object(cx.scope)?;
-    // FIXME: own_keys should probably takes an `enumerable: bool`
-    let keys = obj.own_keys(cx.scope)?;
-    let array = Array::from_vec(cx.scope, keys.into_iter().map(PropertyValue::static_default).collect());
+    let mut keys = Vec::new();
+    for key in obj.own_keys(cx.scope)? {
+        // Only own enumerable properties are listed
+        let property = PropertyKey::from_value(cx.scope, key.clone())?;
+        let is_enumerable = obj
+            .get_own_property_descriptor(cx.scope, property)
+            .root_err(cx.scope)?
+            .is_some_and(|desc| desc.descriptor.contains(PropertyDataDescriptor::ENUMERABLE));
+        if is_enumerable {
+            keys.push(PropertyValue::static_default(key));
+        }
+    }
+    let array = Array::from_vec(cx.scope, keys);
     Ok(cx.scope.register(array).into())
 }
 
@@ -127,6 +141,14 @@ pub fn has_own_property(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::Boolean(desc.is_some()))
 }
 
+pub fn has_own(cx: CallContext) -> Result<Value, Value> {
+    let o = cx.args.first().unwrap_or_undefined().to_object(cx.scope)?;
+    let key = cx.args.get(1).unwrap_or_undefined();
+    let key = PropertyKey::from_value(cx.scope, key)?;
+    let desc = o.get_own_property_descriptor(cx.scope, key).root_err(cx.scope)?;
+    Ok(Value::Boolean(desc.is_some()))
+}
+
 pub fn define_property(cx: CallContext) -> Result<Value, Value> {
     let object = match cx.args.first() {
         Some(Value::Object(o)) => o,
@@ -209,6 +231,60 @@ pub fn entries(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::Object(cx.scope.register(entries)))
 }
 
+/// Groups the values of an iterable by the return value of the callback, in insertion order.
+///
+/// `coerce_key` is applied to each key before grouping, so that `Object.groupBy` and `Map.groupBy`
+/// can share this implementation.
+pub(crate) fn collect_groups(
+    scope: &mut LocalScope,
+    items: Value,
+    callback: Value,
+    coerce_key: fn(&mut LocalScope, Value) -> Result<Value, Value>,
+) -> Result<Vec<(Value, Vec<PropertyValue>)>, Value> {
+    if !matches!(callback.type_of(), Typeof::Function) {
+        throw!(scope, TypeError, "groupBy callback must be a function");
+    }
+
+    let mut groups: Vec<(Value, Vec<PropertyValue>)> = Vec::new();
+    let mut items = scope.iterate(items)?;
+    let mut k = 0;
+    while let Some(value) = items.next() {
+        let value = value?;
+        let scope = items.scope();
+
+        let key = callback
+            .apply(scope, Value::undefined(), vec![value.clone(), Value::number(k as f64)])
+            .root(scope)?;
+        let key = coerce_key(scope, key)?;
+
+        match groups.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, group)) => group.push(PropertyValue::static_default(value)),
+            None => groups.push((key, vec![PropertyValue::static_default(value)])),
+        }
+        k += 1;
+    }
+
+    Ok(groups)
+}
+
+pub fn group_by(cx: CallContext) -> Result<Value, Value> {
+    let items = cx.args.first().unwrap_or_undefined();
+    let callback = cx.args.get(1).unwrap_or_undefined();
+    let groups = collect_groups(cx.scope, items, callback, |scope, key| {
+        PropertyKey::from_value(scope, key).map(|key| key.as_value())
+    })?;
+
+    let obj = NamedObject::null();
+    for (key, values) in groups {
+        let key = PropertyKey::from_value(cx.scope, key)?;
+        let values = Array::from_vec(cx.scope, values);
+        let values = Value::Object(cx.scope.register(values));
+        obj.set_property(cx.scope, key, PropertyValue::static_default(values))?;
+    }
+
+    Ok(Value::Object(cx.scope.register(obj)))
+}
+
 pub fn get_prototype_of(cx: CallContext) -> Result<Value, Value> {
     let obj = cx.args.first().unwrap_or_undefined().to_object(cx.scope)?;
     obj.get_prototype(cx.scope)
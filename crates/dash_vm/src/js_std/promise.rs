@@ -1,16 +1,25 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
 use dash_proc_macro::Trace;
 
 use crate::gc::handle::Handle;
 use crate::gc::interner::sym;
+use crate::localscope::LocalScope;
+use crate::value::array::Array;
 use crate::value::function::bound::BoundFunction;
 use crate::value::function::native::CallContext;
-use crate::value::object::{NamedObject, Object, PropertyKey};
+use crate::value::object::{NamedObject, Object, PropertyKey, PropertyValue};
 use crate::value::promise::{Promise, PromiseRejecter, PromiseResolver, PromiseState};
 use crate::value::root_ext::RootErrExt;
 use crate::value::{Root, Typeof, Unrooted, Value, ValueContext};
-use crate::{delegate, throw, Vm};
+use crate::{delegate, throw, PromiseAction, Vm};
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if !cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Constructor Promise requires 'new'");
+    }
+
     let initiator = match cx.args.first() {
         Some(v) if matches!(v.type_of(), Typeof::Function) => v,
         _ => throw!(cx.scope, TypeError, "Promise callback must be a function"),
@@ -47,10 +56,135 @@ pub fn resolve(cx: CallContext) -> Result<Value, Value> {
 
 pub fn reject(cx: CallContext) -> Result<Value, Value> {
     let value = cx.args.first().unwrap_or_undefined();
-    let promise = Promise::resolved(cx.scope, value);
+    let promise = Promise::rejected(cx.scope, value);
     Ok(Value::Object(cx.scope.register(promise)))
 }
 
+/// Collects the values of an iterable, throwing if the value is not iterable
+fn iterable_to_vec(scope: &mut LocalScope, iterable: Value) -> Result<Vec<Value>, Value> {
+    let iterator = scope.statics.symbol_iterator.clone();
+    let iterator = iterable.get_property(scope, iterator.into()).root(scope)?;
+    if !matches!(iterator.type_of(), Typeof::Function) {
+        throw!(scope, TypeError, "Promise combinator argument is not iterable");
+    }
+
+    let iterator = iterator.apply(scope, iterable, Vec::new()).root(scope)?;
+    let next = iterator.get_property(scope, sym::next.into()).root(scope)?;
+
+    let mut values = Vec::new();
+    loop {
+        let item = next.apply(scope, iterator.clone(), Vec::new()).root(scope)?;
+        let done = item.get_property(scope, sym::done.into()).root(scope)?.is_truthy(scope);
+        if done {
+            break;
+        }
+        values.push(item.get_property(scope, sym::value.into()).root(scope)?);
+    }
+
+    Ok(values)
+}
+
+/// Schedules `on_resolve` or `on_reject` to be called with the settled value of `value`.
+/// Values that are not promises are treated as already resolved.
+pub(crate) fn subscribe(scope: &mut LocalScope, value: Value, on_resolve: Handle, on_reject: Handle) {
+    let (task, value) = match value.downcast_ref::<Promise>() {
+        Some(promise) => {
+            let mut state = promise.state().borrow_mut();
+            match &mut *state {
+                PromiseState::Pending { resolve, reject } => {
+                    resolve.push(on_resolve);
+                    reject.push(on_reject);
+                    return;
+                }
+                PromiseState::Resolved(value) => (on_resolve, value.clone()),
+                PromiseState::Rejected(value) => (on_reject, value.clone()),
+            }
+        }
+        None => (on_resolve, value),
+    };
+
+    let bf = BoundFunction::new(scope, task, None, Some(vec![value]));
+    let bf = scope.register(bf);
+    scope.add_async_task(bf);
+}
+
+/// Settles a promise, unless it has already been settled
+fn settle(scope: &mut LocalScope, promise: &Handle, action: PromiseAction, value: Value) {
+    let promise = promise.as_any().downcast_ref::<Promise>().unwrap();
+    if matches!(*promise.state().borrow(), PromiseState::Pending { .. }) {
+        scope.drive_promise(action, promise, vec![value]);
+    }
+}
+
+/// Shared implementation of `Promise.all` and `Promise.allSettled`
+fn all_inner(cx: CallContext, wrap_settled: bool) -> Result<Value, Value> {
+    let values = iterable_to_vec(cx.scope, cx.args.first().unwrap_or_undefined())?;
+
+    let promise = {
+        let p = Promise::new(cx.scope);
+        cx.scope.register(p)
+    };
+
+    if values.is_empty() {
+        let array = Array::new(cx.scope);
+        let array = Value::Object(cx.scope.register(array));
+        settle(cx.scope, &promise, PromiseAction::Resolve, array);
+        return Ok(Value::Object(promise));
+    }
+
+    let state = Rc::new(CombinatorState {
+        promise: promise.clone(),
+        values: RefCell::new(vec![Value::undefined(); values.len()]),
+        remaining: Cell::new(values.len()),
+    });
+
+    for (index, value) in values.into_iter().enumerate() {
+        let on_resolve = {
+            let t = ElementTask::new(cx.scope, state.clone(), index, wrap_settled, false);
+            cx.scope.register(t)
+        };
+        let on_reject = if wrap_settled {
+            let t = ElementTask::new(cx.scope, state.clone(), index, wrap_settled, true);
+            cx.scope.register(t)
+        } else {
+            let t = SettleTask::new(cx.scope, promise.clone(), true);
+            cx.scope.register(t)
+        };
+        subscribe(cx.scope, value, on_resolve, on_reject);
+    }
+
+    Ok(Value::Object(promise))
+}
+
+pub fn all(cx: CallContext) -> Result<Value, Value> {
+    all_inner(cx, false)
+}
+
+pub fn all_settled(cx: CallContext) -> Result<Value, Value> {
+    all_inner(cx, true)
+}
+
+pub fn race(cx: CallContext) -> Result<Value, Value> {
+    let values = iterable_to_vec(cx.scope, cx.args.first().unwrap_or_undefined())?;
+
+    let promise = {
+        let p = Promise::new(cx.scope);
+        cx.scope.register(p)
+    };
+
+    // An empty iterable results in a promise that stays pending forever
+    for value in values {
+        let (on_resolve, on_reject) = {
+            let t1 = SettleTask::new(cx.scope, promise.clone(), false);
+            let t2 = SettleTask::new(cx.scope, promise.clone(), true);
+            (cx.scope.register(t1), cx.scope.register(t2))
+        };
+        subscribe(cx.scope, value, on_resolve, on_reject);
+    }
+
+    Ok(Value::Object(promise))
+}
+
 pub fn then(cx: CallContext) -> Result<Value, Value> {
     let promise = match cx.this.downcast_ref::<Promise>() {
         Some(promise) => promise,
@@ -92,6 +226,34 @@ pub fn then(cx: CallContext) -> Result<Value, Value> {
 
 // TODO: Promise.prototype.catch
 
+pub fn finally(cx: CallContext) -> Result<Value, Value> {
+    if cx.this.downcast_ref::<Promise>().is_none() {
+        throw!(cx.scope, TypeError, "Receiver must be a promise");
+    }
+
+    let finally_promise = {
+        let p = Promise::new(cx.scope);
+        cx.scope.register(p)
+    };
+
+    let (on_resolve, on_reject) = match cx.args.first() {
+        Some(Value::Object(handler)) if matches!(handler.type_of(), Typeof::Function) => {
+            let t1 = FinallyTask::new(cx.scope, finally_promise.clone(), handler.clone(), false);
+            let t2 = FinallyTask::new(cx.scope, finally_promise.clone(), handler.clone(), true);
+            (cx.scope.register(t1), cx.scope.register(t2))
+        }
+        _ => {
+            // A handler that is not callable passes the outcome through unchanged
+            let t1 = SettleTask::new(cx.scope, finally_promise.clone(), false);
+            let t2 = SettleTask::new(cx.scope, finally_promise.clone(), true);
+            (cx.scope.register(t1), cx.scope.register(t2))
+        }
+    };
+    subscribe(cx.scope, cx.this, on_resolve, on_reject);
+
+    Ok(Value::Object(finally_promise))
+}
+
 #[derive(Debug, Trace)]
 struct ThenTask {
     // TODO: make a type like CastHandle<Promise> that implements Deref by downcasting
@@ -159,3 +321,214 @@ impl Object for ThenTask {
         Ok(Value::undefined().into())
     }
 }
+
+/// Calls the `finally` handler and, once the promise it returns (if any) resolves, settles the returned promise
+/// the same way as the original one. If the handler throws or its promise rejects, the returned promise is
+/// rejected with that reason instead.
+#[derive(Debug, Trace)]
+struct FinallyTask {
+    finally_promise: Handle,
+    handler: Handle,
+    rejected: bool,
+    obj: NamedObject,
+}
+
+impl FinallyTask {
+    pub fn new(vm: &Vm, finally_promise: Handle, handler: Handle, rejected: bool) -> Self {
+        Self {
+            finally_promise,
+            handler,
+            rejected,
+            obj: NamedObject::new(vm),
+        }
+    }
+}
+
+impl Object for FinallyTask {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        as_any,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        scope: &mut crate::localscope::LocalScope,
+        _callee: Handle,
+        _this: Value,
+        args: Vec<Value>,
+    ) -> Result<Unrooted, Unrooted> {
+        let settled = args.first().unwrap_or_undefined();
+
+        let ret = match self.handler.apply(scope, Value::undefined(), Vec::new()) {
+            Ok(ret) => ret.root(scope),
+            Err(err) => {
+                let err = err.root(scope);
+                settle(scope, &self.finally_promise, PromiseAction::Reject, err);
+                return Ok(Value::undefined().into());
+            }
+        };
+
+        // Wait for the promise returned by the handler before passing the original outcome through
+        let (on_resolve, on_reject) = {
+            let pass_through = SettleTask::new(scope, self.finally_promise.clone(), self.rejected);
+            let pass_through = scope.register(pass_through);
+            let t1 = BoundFunction::new(scope, pass_through, None, Some(vec![settled]));
+            let t2 = SettleTask::new(scope, self.finally_promise.clone(), true);
+            (scope.register(t1), scope.register(t2))
+        };
+        subscribe(scope, ret, on_resolve, on_reject);
+
+        Ok(Value::undefined().into())
+    }
+}
+
+/// Settles a promise with the value it is called with
+#[derive(Debug, Trace)]
+struct SettleTask {
+    promise: Handle,
+    rejected: bool,
+    obj: NamedObject,
+}
+
+impl SettleTask {
+    pub fn new(vm: &Vm, promise: Handle, rejected: bool) -> Self {
+        Self {
+            promise,
+            rejected,
+            obj: NamedObject::new(vm),
+        }
+    }
+}
+
+impl Object for SettleTask {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        as_any,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        scope: &mut crate::localscope::LocalScope,
+        _callee: Handle,
+        _this: Value,
+        args: Vec<Value>,
+    ) -> Result<Unrooted, Unrooted> {
+        let action = match self.rejected {
+            true => PromiseAction::Reject,
+            false => PromiseAction::Resolve,
+        };
+        settle(scope, &self.promise, action, args.first().unwrap_or_undefined());
+
+        Ok(Value::undefined().into())
+    }
+}
+
+/// State shared between the element tasks of a `Promise.all` or `Promise.allSettled` call
+#[derive(Debug, Trace)]
+struct CombinatorState {
+    promise: Handle,
+    values: RefCell<Vec<Value>>,
+    remaining: Cell<usize>,
+}
+
+/// Stores the settled value of one input of `Promise.all` or `Promise.allSettled`,
+/// and resolves the combined promise once all inputs have settled.
+#[derive(Debug, Trace)]
+struct ElementTask {
+    state: Rc<CombinatorState>,
+    index: usize,
+    wrap_settled: bool,
+    rejected: bool,
+    obj: NamedObject,
+}
+
+impl ElementTask {
+    pub fn new(vm: &Vm, state: Rc<CombinatorState>, index: usize, wrap_settled: bool, rejected: bool) -> Self {
+        Self {
+            state,
+            index,
+            wrap_settled,
+            rejected,
+            obj: NamedObject::new(vm),
+        }
+    }
+}
+
+impl Object for ElementTask {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        as_any,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        scope: &mut crate::localscope::LocalScope,
+        _callee: Handle,
+        _this: Value,
+        args: Vec<Value>,
+    ) -> Result<Unrooted, Unrooted> {
+        let value = args.first().unwrap_or_undefined();
+
+        let value = if self.wrap_settled {
+            let (status, key) = match self.rejected {
+                true => (sym::rejected, sym::reason),
+                false => (sym::fulfilled, sym::value),
+            };
+
+            let obj = NamedObject::new(scope);
+            obj.set_property(
+                scope,
+                sym::status.into(),
+                PropertyValue::static_default(Value::String(status.into())),
+            )?;
+            obj.set_property(scope, key.into(), PropertyValue::static_default(value))?;
+            Value::Object(scope.register(obj))
+        } else {
+            value
+        };
+
+        self.state.values.borrow_mut()[self.index] = value;
+
+        let remaining = self.state.remaining.get() - 1;
+        self.state.remaining.set(remaining);
+
+        if remaining == 0 {
+            let values = self
+                .state
+                .values
+                .borrow()
+                .iter()
+                .map(|v| PropertyValue::static_default(v.clone()))
+                .collect();
+            let array = Array::from_vec(scope, values);
+            let array = Value::Object(scope.register(array));
+            settle(scope, &self.state.promise, PromiseAction::Resolve, array);
+        }
+
+        Ok(Value::undefined().into())
+    }
+}
@@ -0,0 +1,179 @@
+use std::ops::ControlFlow;
+
+use crate::gc::handle::Handle;
+use crate::gc::interner::sym;
+use crate::localscope::LocalScope;
+use crate::throw;
+use crate::value::function::native::CallContext;
+use crate::value::function::{Function, FunctionKind};
+use crate::value::object::{Object, PropertyDataDescriptor, PropertyKey, PropertyValue, PropertyValueKind};
+use crate::value::ops::conversions::ValueConversion;
+use crate::value::ops::equality::same_value;
+use crate::value::root_ext::RootErrExt;
+use crate::value::{Root, Typeof, Value, ValueContext};
+
+/// Returns the target object of a `Reflect` function, which must not be a primitive
+fn target_object(scope: &mut LocalScope, value: Option<&Value>, function: &str) -> Result<Handle, Value> {
+    match value {
+        Some(Value::Object(target)) => Ok(target.clone()),
+        _ => throw!(scope, TypeError, "Reflect.{} called on non-object", function),
+    }
+}
+
+/// Whether a value can be called with `new`. Arrow functions, async functions and generators cannot.
+fn is_constructor(value: &Handle) -> bool {
+    matches!(value.type_of(), Typeof::Function)
+        && !matches!(
+            value.as_any().downcast_ref::<Function>().map(Function::kind),
+            Some(
+                FunctionKind::Closure(_)
+                    | FunctionKind::Async(_)
+                    | FunctionKind::Generator(_)
+                    | FunctionKind::AsyncGenerator(_)
+            )
+        )
+}
+
+pub fn construct(cx: CallContext) -> Result<Value, Value> {
+    let target = match cx.args.first() {
+        Some(Value::Object(target)) if is_constructor(target) => target.clone(),
+        _ => throw!(cx.scope, TypeError, "Reflect.construct: target is not a constructor"),
+    };
+    let new_target = match cx.args.get(2) {
+        Some(Value::Object(new_target)) if is_constructor(new_target) => new_target.clone(),
+        None => target.clone(),
+        _ => throw!(cx.scope, TypeError, "Reflect.construct: newTarget is not a constructor"),
+    };
+
+    let args_array = match cx.args.get(1) {
+        Some(args @ Value::Object(_)) => args.clone(),
+        _ => throw!(
+            cx.scope,
+            TypeError,
+            "Reflect.construct: arguments list must be an object"
+        ),
+    };
+    let mut args = Vec::new();
+    for i in 0..args_array.length_of_array_like(cx.scope)? {
+        let key = cx.scope.intern_usize(i);
+        args.push(args_array.get_property(cx.scope, key.into()).root(cx.scope)?);
+    }
+
+    let instance = target.construct(cx.scope, Value::undefined(), args).root(cx.scope)?;
+
+    if new_target != target {
+        // There is no `new.target`, so the instance is created by `target` as usual
+        // and its prototype is changed to that of `newTarget` afterwards
+        if let Value::Object(instance) = &instance {
+            let prototype = new_target
+                .get_property(cx.scope, sym::prototype.into())
+                .root(cx.scope)?;
+            if let Value::Object(_) = prototype {
+                instance.set_prototype(cx.scope, prototype)?;
+            }
+        }
+    }
+
+    Ok(instance)
+}
+
+pub fn define_property(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "defineProperty")?;
+    let key = PropertyKey::from_value(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+    let descriptor = match cx.args.get(2) {
+        Some(descriptor @ Value::Object(_)) => descriptor.clone(),
+        _ => throw!(cx.scope, TypeError, "Property descriptor must be an object"),
+    };
+    let value = PropertyValue::from_descriptor_value(cx.scope, descriptor)?;
+
+    if let Some(current) = target
+        .get_own_property_descriptor(cx.scope, key.clone())
+        .root_err(cx.scope)?
+    {
+        if !current.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE) {
+            if !is_compatible_redefinition(&current, &value) {
+                return Ok(Value::Boolean(false));
+            }
+        } else if !current.descriptor.contains(PropertyDataDescriptor::WRITABLE) {
+            // Setting a non-writable property has no effect, so it needs to be removed before it can be redefined
+            target.delete_property(cx.scope, key.clone())?;
+        }
+    }
+
+    target.set_property(cx.scope, key, value)?;
+    Ok(Value::Boolean(true))
+}
+
+/// Checks if a non-configurable property can be redefined with the given property value.
+///
+/// Such a property can only be made non-writable or get a new value if it is writable, but it cannot be
+/// made configurable, change its enumerability or turn from a data property into an accessor (or vice versa).
+fn is_compatible_redefinition(current: &PropertyValue, new: &PropertyValue) -> bool {
+    if new.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE)
+        || new.descriptor.contains(PropertyDataDescriptor::ENUMERABLE)
+            != current.descriptor.contains(PropertyDataDescriptor::ENUMERABLE)
+    {
+        return false;
+    }
+
+    match (&current.kind, &new.kind) {
+        (PropertyValueKind::Static(_), PropertyValueKind::Static(_))
+            if current.descriptor.contains(PropertyDataDescriptor::WRITABLE) =>
+        {
+            true
+        }
+        (PropertyValueKind::Static(current_value), PropertyValueKind::Static(new_value)) => {
+            !new.descriptor.contains(PropertyDataDescriptor::WRITABLE) && same_value(current_value, new_value)
+        }
+        (PropertyValueKind::Trap { .. }, PropertyValueKind::Trap { .. }) => current.kind == new.kind,
+        _ => false,
+    }
+}
+
+pub fn delete_property(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "deleteProperty")?;
+    let key = PropertyKey::from_value(cx.scope, cx.args.get(1).unwrap_or_undefined())?;
+
+    if let Some(current) = target
+        .get_own_property_descriptor(cx.scope, key.clone())
+        .root_err(cx.scope)?
+    {
+        if !current.descriptor.contains(PropertyDataDescriptor::CONFIGURABLE) {
+            return Ok(Value::Boolean(false));
+        }
+    }
+
+    target.delete_property(cx.scope, key)?;
+    Ok(Value::Boolean(true))
+}
+
+pub fn get_prototype_of(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "getPrototypeOf")?;
+    target.get_prototype(cx.scope)
+}
+
+pub fn set_prototype_of(cx: CallContext) -> Result<Value, Value> {
+    let target = target_object(cx.scope, cx.args.first(), "setPrototypeOf")?;
+    let prototype = match cx.args.get(1) {
+        Some(prototype @ (Value::Object(_) | Value::Null(_))) => prototype.clone(),
+        _ => throw!(cx.scope, TypeError, "Object prototype may only be an Object or null"),
+    };
+
+    // Setting the prototype fails if it would create a cycle, i.e. if the target is part of the new prototype chain
+    let target = Value::Object(target);
+    let is_cyclic = prototype
+        .for_each_prototype(cx.scope, |_, proto| {
+            if proto == &target {
+                Ok(ControlFlow::Break(()))
+            } else {
+                Ok(ControlFlow::Continue(()))
+            }
+        })?
+        .is_break();
+    if is_cyclic {
+        return Ok(Value::Boolean(false));
+    }
+
+    target.set_prototype(cx.scope, prototype)?;
+    Ok(Value::Boolean(true))
+}
@@ -1,14 +1,17 @@
 use crate::gc::interner::sym;
+use crate::localscope::LocalScope;
 use crate::throw;
 use crate::value::array::Array;
 use crate::value::function::native::CallContext;
 use crate::value::object::PropertyValue;
 use crate::value::ops::conversions::ValueConversion;
 use crate::value::regex::{RegExp, RegExpInner};
+use crate::value::string::JsString;
 use crate::value::{Value, ValueContext};
 use dash_regex::matcher::Matcher as RegexMatcher;
 use dash_regex::parser::Parser as RegexParser;
 use dash_regex::Flags;
+use std::ops::Range;
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     let pattern = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
@@ -24,107 +27,151 @@ pub fn constructor(cx: CallContext) -> Result<Value, Value> {
         None => Flags::empty(),
     };
 
-    let nodes = match RegexParser::new(pattern.res(cx.scope).as_bytes()).parse_all() {
+    create_regexp(cx.scope, pattern, flags)
+}
+
+/// Parses the pattern and creates a new RegExp object from it
+fn create_regexp(scope: &mut LocalScope, pattern: JsString, flags: Flags) -> Result<Value, Value> {
+    let nodes = match RegexParser::new(pattern.res(scope).as_bytes()).parse_all() {
         Ok(nodes) => nodes,
-        Err(err) => throw!(cx.scope, SyntaxError, "Regex parser error: {}", err),
+        Err(err) => throw!(scope, SyntaxError, "Regex parser error: {}", err),
     };
 
-    let regex = RegExp::new(nodes, flags, pattern, cx.scope);
+    let regex = RegExp::new(nodes, flags, pattern, scope);
 
-    Ok(Value::Object(cx.scope.register(regex)))
+    Ok(Value::Object(scope.register(regex)))
 }
 
-pub fn test(cx: CallContext) -> Result<Value, Value> {
-    let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+/// Returns the value if it is a RegExp, and otherwise creates a new RegExp using its string value as the pattern,
+/// like `String.prototype.match` and `String.prototype.search` do
+pub fn to_regexp(scope: &mut LocalScope, value: &Value) -> Result<Value, Value> {
+    if value.downcast_ref::<RegExp>().is_some() {
+        return Ok(value.clone());
+    }
 
-    let regex = match cx.this.downcast_ref::<RegExp>() {
-        Some(regex) => regex,
-        None => throw!(cx.scope, TypeError, "Receiver must be a RegExp"),
+    let pattern = match value {
+        Value::Undefined(_) => sym::empty.into(),
+        _ => value.to_js_string(scope)?,
     };
+    create_regexp(scope, pattern, Flags::empty())
+}
 
-    let RegExpInner {
-        regex,
-        last_index,
-        flags,
-        ..
-    } = match regex.inner() {
-        Some(nodes) => nodes,
-        None => throw!(cx.scope, TypeError, "Receiver must be an initialized RegExp object"),
+/// Returns the inner regex of a RegExp object, throwing a TypeError if the value is not an initialized RegExp
+pub fn regex_inner<'a>(scope: &mut LocalScope, value: &'a Value) -> Result<&'a RegExpInner, Value> {
+    let regex = match value.downcast_ref::<RegExp>() {
+        Some(regex) => regex,
+        None => throw!(scope, TypeError, "Receiver must be a RegExp"),
     };
 
-    let text = text.res(cx.scope);
-    let is_global = flags.contains(Flags::GLOBAL);
-
-    if is_global && last_index.get() >= text.len() {
-        last_index.set(0);
-        return Ok(Value::Boolean(false));
+    match regex.inner() {
+        Some(inner) => Ok(inner),
+        None => throw!(scope, TypeError, "Receiver must be an initialized RegExp object"),
     }
+}
 
-    let mut matcher = RegexMatcher::new(regex, text[last_index.get()..].as_bytes());
-    if matcher.matches() {
-        if is_global {
-            last_index.set(last_index.get() + matcher.groups.get(0).unwrap().end);
-        }
-        Ok(Value::Boolean(true))
-    } else {
-        if is_global {
-            last_index.set(0);
+/// Searches for the first match of the regex in `text` that starts at or after the byte offset `start`.
+///
+/// On success, returns the byte ranges of all capture groups, where the first one is the entire match.
+/// Matches with ranges that are not on character boundaries are skipped, as they cannot be represented as substrings.
+pub fn find_match(regex: &RegExpInner, text: &str, start: usize) -> Option<Vec<Option<Range<usize>>>> {
+    let mut matcher = RegexMatcher::new(&regex.regex, text.as_bytes(), regex.flags);
+    let mut start = start;
+
+    while matcher.matches_from(start) {
+        let groups = matcher.groups.iter().collect::<Vec<_>>();
+        let on_char_boundaries = groups
+            .iter()
+            .flatten()
+            .all(|r| text.is_char_boundary(r.start) && text.is_char_boundary(r.end));
+
+        if on_char_boundaries {
+            return Some(groups);
         }
-        Ok(Value::Boolean(false))
+
+        let match_start = groups[0].as_ref().unwrap().start;
+        start = match_start + text[match_start..].chars().next().map_or(1, char::len_utf8);
     }
+
+    None
 }
 
-pub fn exec(cx: CallContext<'_, '_>) -> Result<Value, Value> {
-    let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+/// Converts a byte offset into `text` to the UTF-16 based index that JavaScript code sees
+pub fn byte_to_utf16_index(text: &str, index: usize) -> usize {
+    text[..index].encode_utf16().count()
+}
 
-    let regex = match cx.this.downcast_ref::<RegExp>() {
-        Some(regex) => regex,
-        None => throw!(cx.scope, TypeError, "Receiver must be a RegExp"),
-    };
+/// Converts a UTF-16 based index to a byte offset into `text`, or `None` if the index is out of bounds.
+///
+/// An index that points to the second half of a surrogate pair is rounded up to the end of the character.
+pub fn utf16_to_byte_index(text: &str, index: usize) -> Option<usize> {
+    let mut units = 0;
+    for (offset, c) in text.char_indices() {
+        if units >= index {
+            return Some(offset);
+        }
+        units += c.len_utf16();
+    }
 
-    let RegExpInner {
-        regex,
-        last_index,
-        flags,
-        ..
-    } = match regex.inner() {
-        Some(nodes) => nodes,
-        None => throw!(cx.scope, TypeError, "Receiver must be an initialized RegExp object"),
-    };
+    (units >= index).then_some(text.len())
+}
 
-    let text = text.res(cx.scope).to_owned();
-    let is_global = flags.contains(Flags::GLOBAL);
+/// Runs the regex against `text`, respecting and updating `lastIndex` if the regex is global
+pub fn exec_stateful(regex: &RegExpInner, text: &str) -> Option<Vec<Option<Range<usize>>>> {
+    let RegExpInner { last_index, flags, .. } = regex;
 
-    if is_global && last_index.get() >= text.len() {
-        last_index.set(0);
-        return Ok(Value::null());
+    if !flags.contains(Flags::GLOBAL) {
+        return find_match(regex, text, 0);
     }
 
-    let mut matcher = RegexMatcher::new(regex, text[last_index.get()..].as_bytes());
-    if matcher.matches() {
-        if is_global {
-            last_index.set(last_index.get() + matcher.groups.get(0).unwrap().end);
-        }
+    let Some(start) = utf16_to_byte_index(text, last_index.get()) else {
+        last_index.set(0);
+        return None;
+    };
 
-        let groups = matcher
-            .groups
-            .iter()
-            .map(|g| {
-                let sub = match g {
-                    Some(r) => cx.scope.intern(&text[r]).into(),
-                    None => sym::null.into(),
-                };
-                PropertyValue::static_default(Value::String(sub))
-            })
-            .collect();
-
-        let groups = Array::from_vec(cx.scope, groups);
-        Ok(Value::Object(cx.scope.register(groups)))
-    } else {
-        if is_global {
+    match find_match(regex, text, start) {
+        Some(groups) => {
+            last_index.set(byte_to_utf16_index(text, groups[0].as_ref().unwrap().end));
+            Some(groups)
+        }
+        None => {
             last_index.set(0);
+            None
         }
+    }
+}
 
-        Ok(Value::null())
+pub fn test(cx: CallContext) -> Result<Value, Value> {
+    let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let regex = regex_inner(cx.scope, &cx.this)?;
+
+    let text = text.res(cx.scope);
+    Ok(Value::Boolean(exec_stateful(regex, text).is_some()))
+}
+
+/// Creates the result array of `exec`, which contains the matched substrings of every group
+pub fn match_groups_to_array(scope: &mut LocalScope, text: &str, groups: &[Option<Range<usize>>]) -> Value {
+    let groups = groups
+        .iter()
+        .map(|g| {
+            let sub = match g {
+                Some(r) => Value::String(scope.intern(&text[r.clone()]).into()),
+                None => Value::undefined(),
+            };
+            PropertyValue::static_default(sub)
+        })
+        .collect();
+
+    let groups = Array::from_vec(scope, groups);
+    Value::Object(scope.register(groups))
+}
+
+pub fn exec(cx: CallContext<'_, '_>) -> Result<Value, Value> {
+    let text = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let regex = regex_inner(cx.scope, &cx.this)?;
+
+    let text = text.res(cx.scope).to_owned();
+    match exec_stateful(regex, &text) {
+        Some(groups) => Ok(match_groups_to_array(cx.scope, &text, &groups)),
+        None => Ok(Value::null()),
     }
 }
@@ -1,22 +1,18 @@
 use crate::throw;
 use crate::value::function::native::CallContext;
-use crate::value::object::PropertyKey;
-use crate::value::ops::conversions::ValueConversion;
 use crate::value::set::Set;
-use crate::value::{Root, Value, ValueContext};
+use crate::value::{Value, ValueContext};
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
-    let set = Set::new(cx.scope);
-    if let Some(iter) = cx.args.first() {
-        let len = iter.length_of_array_like(cx.scope)?;
-
-        for i in 0..len {
-            let i = cx.scope.intern_usize(i);
-            let item = iter
-                .get_property(cx.scope, PropertyKey::String(i.into()))
-                .root(cx.scope)?;
+    if !cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Constructor Set requires 'new'");
+    }
 
-            set.add(item);
+    let set = Set::new(cx.scope);
+    let iterable = cx.args.first().unwrap_or_undefined();
+    if !matches!(iterable, Value::Undefined(_) | Value::Null(_)) {
+        for value in cx.scope.iterate(iterable)? {
+            set.add(value?);
         }
     }
 
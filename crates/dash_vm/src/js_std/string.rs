@@ -1,17 +1,24 @@
 use dash_middle::interner::sym;
 
+use crate::js_std::regex;
 use crate::localscope::LocalScope;
 use crate::throw;
-use crate::value::array::{Array, ArrayIterator};
+use crate::value::array::Array;
 use crate::value::boxed::String as BoxedString;
 use crate::value::function::native::CallContext;
-use crate::value::object::PropertyValue;
+use crate::value::object::{Object, PropertyValue};
 use crate::value::ops::conversions::ValueConversion;
-use crate::value::{Value, ValueContext};
+use crate::value::regex::{RegExp, RegExpInner};
+use crate::value::string::{JsString, StringIterator};
+use crate::value::{Root, Typeof, Value, ValueContext};
+use dash_regex::Flags;
 use std::fmt::Write;
+use std::ops::Range;
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     let value = match cx.args.first() {
+        // String(symbol) is the one conversion that does not throw for symbols
+        Some(Value::Symbol(symbol)) if !cx.is_constructor_call => symbol.descriptive_string(cx.scope),
         Some(arg) => arg.to_js_string(cx.scope)?,
         None => sym::empty.into(),
     };
@@ -23,8 +30,26 @@ pub fn constructor(cx: CallContext) -> Result<Value, Value> {
     }
 }
 
+/// Returns the string value of `this`, unwrapping it if it is a boxed string
+fn this_string_value(value: &Value) -> Option<JsString> {
+    match value {
+        Value::String(s) => Some(*s),
+        _ => value.downcast_ref::<BoxedString>().map(|s| *s.value()),
+    }
+}
+
 pub fn to_string(cx: CallContext) -> Result<Value, Value> {
-    Ok(cx.this)
+    match this_string_value(&cx.this) {
+        Some(s) => Ok(Value::String(s)),
+        None => throw!(cx.scope, TypeError, "String.prototype.toString called on non-string"),
+    }
+}
+
+pub fn value_of(cx: CallContext) -> Result<Value, Value> {
+    match this_string_value(&cx.this) {
+        Some(s) => Ok(Value::String(s)),
+        None => throw!(cx.scope, TypeError, "String.prototype.valueOf called on non-string"),
+    }
 }
 
 fn create_html(
@@ -118,6 +143,28 @@ pub fn char_code_at(cx: CallContext) -> Result<Value, Value> {
     }
 }
 
+pub fn code_point_at(cx: CallContext) -> Result<Value, Value> {
+    let index = cx.args.first().unwrap_or_undefined().to_integer_or_infinity(cx.scope)?;
+    let this = cx.this.to_js_string(cx.scope)?.res(cx.scope);
+    if index < 0.0 {
+        return Ok(Value::undefined());
+    }
+
+    // The index refers to UTF-16 code units
+    let mut units = this.encode_utf16().skip(index as usize);
+    let Some(first) = units.next() else {
+        return Ok(Value::undefined());
+    };
+
+    let code_point = match char::decode_utf16([first].into_iter().chain(units.next())).next() {
+        Some(Ok(c)) => c as u32,
+        // Lone surrogates are returned as is
+        _ => u32::from(first),
+    };
+
+    Ok(Value::number(code_point as f64))
+}
+
 pub fn concat(cx: CallContext) -> Result<Value, Value> {
     let this = cx.this.to_js_string(cx.scope)?;
     let other = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
@@ -243,10 +290,16 @@ pub fn repeat(cx: CallContext) -> Result<Value, Value> {
 }
 
 pub fn replace(cx: CallContext) -> Result<Value, Value> {
-    // TODO: once we have regexp, we can properly implement this
     let string = cx.this.to_js_string(cx.scope)?;
 
-    let search_string = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
+    let pattern = cx.args.first().unwrap_or_undefined();
+    if pattern.downcast_ref::<RegExp>().is_some() {
+        let replace_value = cx.args.get(1).unwrap_or_undefined();
+        let string = string.res(cx.scope).to_owned();
+        return replace_regex(cx.scope, &string, &pattern, &replace_value);
+    }
+
+    let search_string = pattern.to_js_string(cx.scope)?;
 
     let replace_value = cx.args.get(1).unwrap_or_undefined().to_js_string(cx.scope)?;
 
@@ -257,6 +310,129 @@ pub fn replace(cx: CallContext) -> Result<Value, Value> {
     Ok(Value::String(cx.scope.intern(string).into()))
 }
 
+/// Replaces the first match of the regex, or all of them if it is global.
+/// The replacement can either be a function that is called with the match, or a string with `$` substitutions.
+fn replace_regex(scope: &mut LocalScope, string: &str, regexp: &Value, replace_value: &Value) -> Result<Value, Value> {
+    let regex = regex::regex_inner(scope, regexp)?;
+    let is_global = regex.flags.contains(Flags::GLOBAL);
+    let replace_fn = match replace_value {
+        Value::Object(o) if matches!(o.type_of(), Typeof::Function) => None,
+        _ => Some(replace_value.to_js_string(scope)?.res(scope).to_owned()),
+    };
+
+    let mut result = String::new();
+    let mut last_end = 0;
+    let mut search_start = 0;
+
+    while let Some(groups) = regex::find_match(regex, string, search_start) {
+        let range = groups[0].clone().unwrap();
+        result.push_str(&string[last_end..range.start]);
+
+        match &replace_fn {
+            Some(template) => expand_replacement(&mut result, template, string, &groups),
+            None => {
+                // The callback receives the match, all capture groups, the offset of the match and the whole string
+                let mut args: Vec<Value> = groups
+                    .iter()
+                    .map(|g| match g {
+                        Some(r) => Value::String(scope.intern(&string[r.clone()]).into()),
+                        None => Value::undefined(),
+                    })
+                    .collect();
+                args.push(Value::number(regex::byte_to_utf16_index(string, range.start) as f64));
+                args.push(Value::String(scope.intern(string).into()));
+
+                let replacement = replace_value.apply(scope, Value::undefined(), args).root(scope)?;
+                result.push_str(replacement.to_js_string(scope)?.res(scope));
+            }
+        }
+
+        last_end = range.end;
+        if !is_global {
+            break;
+        }
+
+        // Skip ahead after empty matches so that the same position is not matched forever
+        search_start = if range.is_empty() {
+            range.end + string[range.end..].chars().next().map_or(1, char::len_utf8)
+        } else {
+            range.end
+        };
+    }
+
+    if is_global {
+        regex.last_index.set(0);
+    }
+
+    result.push_str(&string[last_end..]);
+    Ok(Value::String(scope.intern(result).into()))
+}
+
+/// Appends the replacement string to `result`, expanding the `$` patterns `$$`, `$&`, `` $` ``, `$'` and `$n`
+fn expand_replacement(result: &mut String, template: &str, string: &str, groups: &[Option<Range<usize>>]) {
+    let matched = groups[0].clone().unwrap();
+    let group = |n: usize| Some(n).filter(|&n| n >= 1 && n < groups.len());
+
+    let mut rest = template;
+    while let Some(pos) = rest.find('$') {
+        result.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        let consumed = match after.as_bytes().first() {
+            Some(b'$') => {
+                result.push('$');
+                1
+            }
+            Some(b'&') => {
+                result.push_str(&string[matched.clone()]);
+                1
+            }
+            Some(b'`') => {
+                result.push_str(&string[..matched.start]);
+                1
+            }
+            Some(b'\'') => {
+                result.push_str(&string[matched.end..]);
+                1
+            }
+            Some(digit) if digit.is_ascii_digit() => {
+                // Two digit group references take precedence over one digit references if the group exists
+                let two_digits = after
+                    .get(..2)
+                    .filter(|s| s.bytes().all(|b| b.is_ascii_digit()))
+                    .and_then(|s| s.parse().ok())
+                    .and_then(group);
+
+                let reference = match two_digits {
+                    Some(n) => Some((n, 2)),
+                    None => group(usize::from(digit - b'0')).map(|n| (n, 1)),
+                };
+
+                match reference {
+                    Some((n, len)) => {
+                        if let Some(range) = &groups[n] {
+                            result.push_str(&string[range.clone()]);
+                        }
+                        len
+                    }
+                    None => {
+                        result.push('$');
+                        0
+                    }
+                }
+            }
+            _ => {
+                result.push('$');
+                0
+            }
+        };
+
+        rest = &after[consumed..];
+    }
+
+    result.push_str(rest);
+}
+
 pub fn replace_all(cx: CallContext) -> Result<Value, Value> {
     let string = cx.this.to_js_string(cx.scope)?;
 
@@ -273,30 +449,155 @@ pub fn replace_all(cx: CallContext) -> Result<Value, Value> {
 
 pub fn split(cx: CallContext) -> Result<Value, Value> {
     let string = cx.this.to_js_string(cx.scope)?.res(cx.scope).to_owned();
-    let separator = cx
-        .args
-        .first()
-        .unwrap_or_undefined()
-        .to_js_string(cx.scope)?
-        .res(cx.scope)
-        .to_owned();
+    let limit = match cx.args.get(1) {
+        None | Some(Value::Undefined(_)) => u32::MAX,
+        Some(limit) => limit.to_uint32(cx.scope)?,
+    } as usize;
+
+    if let Some(separator) = cx.args.first().filter(|s| s.downcast_ref::<RegExp>().is_some()) {
+        let regex = regex::regex_inner(cx.scope, separator)?;
+        let result = split_regex(cx.scope, &string, regex, limit);
+        let array = Array::from_vec(cx.scope, result);
+        return Ok(cx.scope.register(array).into());
+    }
 
-    let result = if separator.is_empty() {
-        string
+    let separator = match cx.args.first() {
+        None | Some(Value::Undefined(_)) => None,
+        Some(separator) => Some(separator.to_js_string(cx.scope)?.res(cx.scope).to_owned()),
+    };
+
+    let result = match separator {
+        _ if limit == 0 => Vec::new(),
+        None => vec![PropertyValue::static_default(Value::String(
+            cx.scope.intern(string).into(),
+        ))],
+        // An empty separator splits the string into its characters, so an empty string results in an empty array
+        Some(separator) if separator.is_empty() => string
             .chars()
+            .take(limit)
             .map(|c| PropertyValue::static_default(Value::String(cx.scope.intern_char(c).into())))
-            .collect()
-    } else {
-        string
+            .collect(),
+        Some(separator) => string
             .split(&separator)
+            .take(limit)
             .map(|s| PropertyValue::static_default(Value::String(cx.scope.intern(s).into())))
-            .collect()
+            .collect(),
     };
 
     let array = Array::from_vec(cx.scope, result);
     Ok(cx.scope.register(array).into())
 }
 
+/// Splits the string at every match of the regex. Capture groups of the separator are included in the result.
+fn split_regex(scope: &mut LocalScope, string: &str, regex: &RegExpInner, limit: usize) -> Vec<PropertyValue> {
+    let mut result = Vec::new();
+    let mut push = |scope: &mut LocalScope, value: Option<&str>| {
+        let value = match value {
+            Some(s) => Value::String(scope.intern(s).into()),
+            None => Value::undefined(),
+        };
+        result.push(PropertyValue::static_default(value));
+    };
+
+    if limit == 0 {
+        return result;
+    }
+
+    if string.is_empty() {
+        // An empty string is only split if the regex cannot match it
+        if regex::find_match(regex, string, 0).is_none() {
+            push(scope, Some(string));
+        }
+        return result;
+    }
+
+    let mut last_end = 0;
+    let mut search_start = 0;
+    let mut count = 0;
+
+    while let Some(groups) = regex::find_match(regex, string, search_start) {
+        let range = groups[0].clone().unwrap();
+        if range.start >= string.len() {
+            break;
+        }
+
+        if range.end == last_end {
+            // An empty match at the end of the previous separator does not split the string
+            search_start = range.start + string[range.start..].chars().next().map_or(1, char::len_utf8);
+            continue;
+        }
+
+        push(scope, Some(&string[last_end..range.start]));
+        count += 1;
+        if count == limit {
+            return result;
+        }
+
+        for group in &groups[1..] {
+            push(scope, group.clone().map(|r| &string[r]));
+            count += 1;
+            if count == limit {
+                return result;
+            }
+        }
+
+        last_end = range.end;
+        search_start = range.end.max(range.start + 1);
+    }
+
+    push(scope, Some(&string[last_end..]));
+    result
+}
+
+pub fn match_(cx: CallContext) -> Result<Value, Value> {
+    let string = cx.this.to_js_string(cx.scope)?.res(cx.scope).to_owned();
+    let regexp = regex::to_regexp(cx.scope, cx.args.first().unwrap_or(&Value::undefined()))?;
+    let regex = regex::regex_inner(cx.scope, &regexp)?;
+
+    if !regex.flags.contains(Flags::GLOBAL) {
+        return match regex::exec_stateful(regex, &string) {
+            Some(groups) => Ok(regex::match_groups_to_array(cx.scope, &string, &groups)),
+            None => Ok(Value::null()),
+        };
+    }
+
+    // Global regexes return all matched substrings, without capture groups
+    let mut matches = Vec::new();
+    let mut search_start = 0;
+    while let Some(groups) = regex::find_match(regex, &string, search_start) {
+        let range = groups[0].clone().unwrap();
+        search_start = if range.is_empty() {
+            range.end + string[range.end..].chars().next().map_or(1, char::len_utf8)
+        } else {
+            range.end
+        };
+
+        let matched = cx.scope.intern(&string[range]);
+        matches.push(PropertyValue::static_default(Value::String(matched.into())));
+    }
+    regex.last_index.set(0);
+
+    if matches.is_empty() {
+        return Ok(Value::null());
+    }
+
+    let array = Array::from_vec(cx.scope, matches);
+    Ok(cx.scope.register(array).into())
+}
+
+pub fn search(cx: CallContext) -> Result<Value, Value> {
+    let string = cx.this.to_js_string(cx.scope)?.res(cx.scope).to_owned();
+    let regexp = regex::to_regexp(cx.scope, cx.args.first().unwrap_or(&Value::undefined()))?;
+    let regex = regex::regex_inner(cx.scope, &regexp)?;
+
+    // search ignores the global flag and lastIndex, and always starts at the beginning of the string
+    let index = match regex::find_match(regex, &string, 0) {
+        Some(groups) => regex::byte_to_utf16_index(&string, groups[0].as_ref().unwrap().start) as f64,
+        None => -1.0,
+    };
+    Ok(Value::number(index))
+}
+
 pub fn to_uppercase(cx: CallContext) -> Result<Value, Value> {
     let string = cx.this.to_js_string(cx.scope)?;
     let result = string.res(cx.scope).to_uppercase();
@@ -328,9 +629,31 @@ pub fn trim_end(cx: CallContext) -> Result<Value, Value> {
 }
 
 pub fn from_char_code(cx: CallContext) -> Result<Value, Value> {
-    let code = cx.args.first().unwrap_or_undefined().to_int32(cx.scope)?;
-    let s = char::from_u32(code as u32).unwrap_or(char::REPLACEMENT_CHARACTER);
-    Ok(Value::String(cx.scope.intern_char(s).into()))
+    let mut units = Vec::with_capacity(cx.args.len());
+    for arg in &cx.args {
+        units.push(arg.to_int32(cx.scope)? as u16);
+    }
+
+    // Lone surrogates cannot be represented in our strings, so they are replaced
+    let s = char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect::<String>();
+
+    Ok(Value::String(cx.scope.intern(s).into()))
+}
+
+pub fn from_code_point(cx: CallContext) -> Result<Value, Value> {
+    let mut s = String::with_capacity(cx.args.len());
+    for arg in &cx.args {
+        let code = arg.to_number(cx.scope)?;
+        if code.fract() != 0.0 || !(0.0..=0x10FFFF as f64).contains(&code) {
+            throw!(cx.scope, RangeError, "Invalid code point {}", code);
+        }
+
+        s.push(char::from_u32(code as u32).unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+
+    Ok(Value::String(cx.scope.intern(s).into()))
 }
 
 pub fn substr(cx: CallContext) -> Result<Value, Value> {
@@ -389,17 +712,7 @@ pub fn substring(cx: CallContext) -> Result<Value, Value> {
 }
 
 pub fn iterator(cx: CallContext) -> Result<Value, Value> {
-    let string = cx.this.to_js_string(cx.scope)?.res(cx.scope).to_owned();
-    let chars = string
-        .chars()
-        .map(|c| cx.scope.intern_char(c).into())
-        .map(Value::String)
-        .map(PropertyValue::static_default)
-        .collect::<Vec<_>>();
-    let chars = Array::from_vec(cx.scope, chars);
-    let chars = cx.scope.register(chars);
-    let iter = ArrayIterator::new(cx.scope, Value::Object(chars))?;
-    let iter = cx.scope.register(iter);
-
-    Ok(Value::Object(iter))
+    let string = cx.this.to_js_string(cx.scope)?;
+    let iter = StringIterator::new(cx.scope, string);
+    Ok(Value::Object(cx.scope.register(iter)))
 }
@@ -0,0 +1,30 @@
+use crate::gc::interner::sym;
+use crate::throw;
+use crate::value::function::native::CallContext;
+use crate::value::object::{NamedObject, Object, PropertyValue};
+use crate::value::string::StringIterator;
+use crate::value::Value;
+
+pub fn next(cx: CallContext) -> Result<Value, Value> {
+    let iterator = match cx.this.downcast_ref::<StringIterator>() {
+        Some(it) => it,
+        None => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    let next = iterator.next(cx.scope);
+    let done = next.is_none();
+
+    let obj = NamedObject::new(cx.scope);
+    obj.set_property(
+        cx.scope,
+        sym::value.into(),
+        PropertyValue::static_default(next.map_or_else(Value::undefined, Value::String)),
+    )?;
+    obj.set_property(
+        cx.scope,
+        sym::done.into(),
+        PropertyValue::static_default(Value::Boolean(done)),
+    )?;
+
+    Ok(cx.scope.register(obj).into())
+}
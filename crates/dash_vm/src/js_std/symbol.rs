@@ -1,10 +1,47 @@
+use crate::throw;
+use crate::value::boxed::Symbol as BoxedSymbol;
 use crate::value::function::native::CallContext;
 use crate::value::ops::conversions::ValueConversion;
 use crate::value::primitive::Symbol;
-use crate::value::{Value, ValueContext};
+use crate::value::Value;
 
 pub fn constructor(cx: CallContext) -> Result<Value, Value> {
-    let description = cx.args.first().unwrap_or_undefined().to_js_string(cx.scope)?;
-    let symbol = Symbol::new(description);
-    Ok(symbol.into())
+    if cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Symbol is not a constructor");
+    }
+
+    let description = match cx.args.first() {
+        None | Some(Value::Undefined(_)) => None,
+        Some(description) => Some(description.to_js_string(cx.scope)?),
+    };
+    Ok(Symbol::with_description(description).into())
+}
+
+/// Returns the symbol value of `this`, unwrapping it if it is a boxed symbol
+fn this_symbol_value(value: &Value) -> Option<Symbol> {
+    match value {
+        Value::Symbol(symbol) => Some(symbol.clone()),
+        _ => value.downcast_ref::<BoxedSymbol>().map(|s| s.value().clone()),
+    }
+}
+
+pub fn to_string(cx: CallContext) -> Result<Value, Value> {
+    match this_symbol_value(&cx.this) {
+        Some(symbol) => Ok(Value::String(symbol.descriptive_string(cx.scope))),
+        None => throw!(cx.scope, TypeError, "Symbol.prototype.toString called on non-symbol"),
+    }
+}
+
+pub fn value_of(cx: CallContext) -> Result<Value, Value> {
+    match this_symbol_value(&cx.this) {
+        Some(symbol) => Ok(Value::Symbol(symbol)),
+        None => throw!(cx.scope, TypeError, "Symbol.prototype.valueOf called on non-symbol"),
+    }
+}
+
+pub fn description(cx: CallContext) -> Result<Value, Value> {
+    match this_symbol_value(&cx.this) {
+        Some(symbol) => Ok(symbol.description().map_or(Value::undefined(), Value::String)),
+        None => throw!(cx.scope, TypeError, "Symbol.prototype.description called on non-symbol"),
+    }
 }
@@ -0,0 +1,43 @@
+use crate::gc::interner::sym;
+use crate::throw;
+use crate::value::arraybuffer::ArrayBuffer;
+use crate::value::function::native::CallContext;
+use crate::value::object::{NamedObject, Object};
+use crate::value::typedarray::TypedArray;
+use crate::value::{Value, ValueContext};
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if !cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Constructor TextDecoder requires 'new'");
+    }
+
+    let decoder = NamedObject::with_prototype_and_constructor(
+        cx.scope.statics.text_decoder_prototype.clone(),
+        cx.scope.statics.text_decoder_ctor.clone(),
+    );
+    Ok(Value::Object(cx.scope.register(decoder)))
+}
+
+/// Decodes the bytes of a typed array or an array buffer as UTF-8,
+/// replacing invalid sequences with U+FFFD
+pub fn decode(cx: CallContext) -> Result<Value, Value> {
+    let input = cx.args.first().unwrap_or_undefined();
+
+    let buffer = if let Some(array) = input.downcast_ref::<TypedArray>() {
+        array.buffer()
+    } else if let Value::Object(buffer) = &input {
+        buffer.clone()
+    } else if let Value::Undefined(..) = input {
+        return Ok(Value::String(sym::empty.into()));
+    } else {
+        throw!(cx.scope, TypeError, "Expected an ArrayBuffer or a typed array");
+    };
+
+    let Some(buffer) = buffer.as_any().downcast_ref::<ArrayBuffer>() else {
+        throw!(cx.scope, TypeError, "Expected an ArrayBuffer or a typed array");
+    };
+
+    let bytes = buffer.storage().iter().map(|b| b.get()).collect::<Vec<_>>();
+    let string = String::from_utf8_lossy(&bytes);
+    Ok(Value::String(cx.scope.intern(string.as_ref()).into()))
+}
@@ -0,0 +1,32 @@
+use crate::throw;
+use crate::value::arraybuffer::ArrayBuffer;
+use crate::value::function::native::CallContext;
+use crate::value::object::NamedObject;
+use crate::value::ops::conversions::ValueConversion;
+use crate::value::typedarray::{TypedArray, TypedArrayKind};
+use crate::value::Value;
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if !cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Constructor TextEncoder requires 'new'");
+    }
+
+    let encoder = NamedObject::with_prototype_and_constructor(
+        cx.scope.statics.text_encoder_prototype.clone(),
+        cx.scope.statics.text_encoder_ctor.clone(),
+    );
+    Ok(Value::Object(cx.scope.register(encoder)))
+}
+
+/// Encodes a string as UTF-8 into a new Uint8Array
+pub fn encode(cx: CallContext) -> Result<Value, Value> {
+    let input = match cx.args.first() {
+        Some(Value::Undefined(..)) | None => String::new(),
+        Some(value) => value.to_js_string(cx.scope)?.res(cx.scope).to_owned(),
+    };
+
+    let buffer = ArrayBuffer::from_storage(cx.scope, input.bytes().map(Into::into).collect());
+    let buffer = cx.scope.register(buffer);
+    let array = TypedArray::new(cx.scope, buffer, TypedArrayKind::Uint8Array);
+    Ok(Value::Object(cx.scope.register(array)))
+}
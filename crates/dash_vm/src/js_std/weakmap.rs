@@ -0,0 +1,94 @@
+use crate::gc::interner::sym;
+use crate::throw;
+use crate::value::function::native::CallContext;
+use crate::value::object::{Object, PropertyKey};
+use crate::value::weakmap::WeakMap;
+use crate::value::{Root, Value, ValueContext};
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if !cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Constructor WeakMap requires 'new'");
+    }
+
+    // The map needs to be known to the VM before any entries are added, so that they are cleared if a GC happens
+    let map = WeakMap::new(cx.scope);
+    let map = cx.scope.register(map);
+    cx.scope.add_weak_map(map.clone());
+
+    let iterable = cx.args.first().unwrap_or_undefined();
+    if !matches!(iterable, Value::Undefined(_) | Value::Null(_)) {
+        let this = map.as_any().downcast_ref::<WeakMap>().unwrap();
+        let mut entries = cx.scope.iterate(iterable)?;
+        while let Some(entry) = entries.next() {
+            let entry = entry?;
+            let scope = entries.scope();
+            if !matches!(entry, Value::Object(_) | Value::External(_)) {
+                throw!(scope, TypeError, "Iterator value is not an entry object");
+            }
+
+            let k = entry
+                .get_property(scope, PropertyKey::String(sym::zero.into()))
+                .root(scope)?;
+            let v = entry
+                .get_property(scope, PropertyKey::String(sym::one.into()))
+                .root(scope)?;
+            match k {
+                Value::Object(k) => this.set(k, v),
+                _ => throw!(scope, TypeError, "Invalid value used as weak map key"),
+            }
+        }
+    }
+
+    Ok(Value::Object(map))
+}
+
+pub fn set(cx: CallContext) -> Result<Value, Value> {
+    let this = match cx.this.downcast_ref::<WeakMap>() {
+        Some(map) => map,
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    let k = match cx.args.first().unwrap_or_undefined() {
+        Value::Object(k) => k,
+        _ => throw!(cx.scope, TypeError, "Invalid value used as weak map key"),
+    };
+    let v = cx.args.get(1).unwrap_or_undefined();
+    this.set(k, v);
+
+    Ok(cx.this)
+}
+
+pub fn get(cx: CallContext) -> Result<Value, Value> {
+    let this = match cx.this.downcast_ref::<WeakMap>() {
+        Some(map) => map,
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    // Non-object keys can never be in the map, so there is no need to throw
+    let value = match cx.args.first() {
+        Some(Value::Object(k)) => this.get(k),
+        _ => None,
+    };
+
+    Ok(value.unwrap_or_undefined())
+}
+
+pub fn has(cx: CallContext) -> Result<Value, Value> {
+    let this = match cx.this.downcast_ref::<WeakMap>() {
+        Some(map) => map,
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    let has = matches!(cx.args.first(), Some(Value::Object(k)) if this.has(k));
+    Ok(Value::Boolean(has))
+}
+
+pub fn delete(cx: CallContext) -> Result<Value, Value> {
+    let this = match cx.this.downcast_ref::<WeakMap>() {
+        Some(map) => map,
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    let did_delete = matches!(cx.args.first(), Some(Value::Object(k)) if this.delete(k));
+    Ok(Value::Boolean(did_delete))
+}
@@ -0,0 +1,30 @@
+use crate::throw;
+use crate::value::function::native::CallContext;
+use crate::value::weakref::WeakRef;
+use crate::value::{Value, ValueContext};
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if !cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Constructor WeakRef requires 'new'");
+    }
+
+    let target = match cx.args.first().unwrap_or_undefined() {
+        Value::Object(target) => target,
+        _ => throw!(cx.scope, TypeError, "WeakRef: target must be an object"),
+    };
+
+    let weak_ref = WeakRef::new(cx.scope, target);
+    let weak_ref = cx.scope.register(weak_ref);
+    cx.scope.add_weak_ref(weak_ref.clone());
+
+    Ok(Value::Object(weak_ref))
+}
+
+pub fn deref(cx: CallContext) -> Result<Value, Value> {
+    let this = match cx.this.downcast_ref::<WeakRef>() {
+        Some(weak_ref) => weak_ref,
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    Ok(this.target().map_or_else(Value::undefined, Value::Object))
+}
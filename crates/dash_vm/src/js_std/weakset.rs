@@ -0,0 +1,64 @@
+use crate::throw;
+use crate::value::function::native::CallContext;
+use crate::value::object::Object;
+use crate::value::weakset::WeakSet;
+use crate::value::{Value, ValueContext};
+
+pub fn constructor(cx: CallContext) -> Result<Value, Value> {
+    if !cx.is_constructor_call {
+        throw!(cx.scope, TypeError, "Constructor WeakSet requires 'new'");
+    }
+
+    // The set needs to be known to the VM before any values are added, so that they are cleared if a GC happens
+    let set = WeakSet::new(cx.scope);
+    let set = cx.scope.register(set);
+    cx.scope.add_weak_set(set.clone());
+
+    let iterable = cx.args.first().unwrap_or_undefined();
+    if !matches!(iterable, Value::Undefined(_) | Value::Null(_)) {
+        let this = set.as_any().downcast_ref::<WeakSet>().unwrap();
+        let mut values = cx.scope.iterate(iterable)?;
+        while let Some(value) = values.next() {
+            match value? {
+                Value::Object(value) => this.add(value),
+                _ => throw!(values.scope(), TypeError, "Invalid value used in weak set"),
+            }
+        }
+    }
+
+    Ok(Value::Object(set))
+}
+
+pub fn add(cx: CallContext) -> Result<Value, Value> {
+    let this = match cx.this.downcast_ref::<WeakSet>() {
+        Some(set) => set,
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    match cx.args.first().unwrap_or_undefined() {
+        Value::Object(value) => this.add(value),
+        _ => throw!(cx.scope, TypeError, "Invalid value used in weak set"),
+    }
+
+    Ok(cx.this)
+}
+
+pub fn has(cx: CallContext) -> Result<Value, Value> {
+    let this = match cx.this.downcast_ref::<WeakSet>() {
+        Some(set) => set,
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    let has = matches!(cx.args.first(), Some(Value::Object(value)) if this.has(value));
+    Ok(Value::Boolean(has))
+}
+
+pub fn delete(cx: CallContext) -> Result<Value, Value> {
+    let this = match cx.this.downcast_ref::<WeakSet>() {
+        Some(set) => set,
+        _ => throw!(cx.scope, TypeError, "Incompatible receiver"),
+    };
+
+    let did_delete = matches!(cx.args.first(), Some(Value::Object(value)) if this.delete(value));
+    Ok(Value::Boolean(did_delete))
+}
@@ -6,9 +6,12 @@ use dash_middle::util;
 
 use crate::localscope::LocalScope;
 use crate::value::array::Array;
-use crate::value::object::{NamedObject, ObjectMap, PropertyKey, PropertyValue};
+use crate::value::object::{NamedObject, PropertyKey, PropertyValue};
 use crate::value::Value;
 
+/// The default maximum nesting depth of arrays and objects
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
 /// An error that occurred during parsing JSON
 ///
 /// If possible, variants carry an additional `usize` with them which is the offset
@@ -23,6 +26,8 @@ pub enum JsonParseError {
     Utf8Error(Utf8Error, usize),
     /// Failed to parse a number
     ParseFloatError(ParseFloatError, usize),
+    /// Arrays or objects are nested deeper than the configured maximum depth
+    MaxDepthExceeded(usize),
 }
 
 impl JsonParseError {
@@ -35,6 +40,7 @@ impl JsonParseError {
             }
             Self::Utf8Error(_, pos) => Cow::Owned(format!("Utf8 Error at position {}", pos)),
             Self::ParseFloatError(_, pos) => Cow::Owned(format!("Failed to parse number at position {}", pos)),
+            Self::MaxDepthExceeded(pos) => Cow::Owned(format!("Maximum nesting depth exceeded at position {}", pos)),
         }
     }
 }
@@ -102,13 +108,36 @@ impl From<Utf8Error> for ConversionError {
 pub struct Parser<'a, 'sc, 'vm> {
     source: &'a [u8],
     idx: usize,
+    depth: usize,
+    max_depth: usize,
     sc: &'sc mut LocalScope<'vm>,
 }
 
 impl<'a, 'sc, 'vm> Parser<'a, 'sc, 'vm> {
     /// Creates a new JSON parser
     pub fn new(source: &'a [u8], sc: &'sc mut LocalScope<'vm>) -> Self {
-        Self { source, idx: 0, sc }
+        Self {
+            source,
+            idx: 0,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            sc,
+        }
+    }
+
+    /// Sets the maximum nesting depth of arrays and objects
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Enters a nested array or object, failing if the maximum depth was exceeded
+    fn enter_nested(&mut self) -> Result<(), JsonParseError> {
+        if self.depth >= self.max_depth {
+            return Err(JsonParseError::MaxDepthExceeded(self.idx));
+        }
+        self.depth += 1;
+        Ok(())
     }
 
     /// Returns the current byte, if present
@@ -191,6 +220,7 @@ impl<'a, 'sc, 'vm> Parser<'a, 'sc, 'vm> {
 
         match cur {
             b'[' => {
+                self.enter_nested()?;
                 let mut arr = Vec::new();
 
                 self.idx += 1;
@@ -208,17 +238,22 @@ impl<'a, 'sc, 'vm> Parser<'a, 'sc, 'vm> {
                     arr.push(PropertyValue::static_default(self.parse()?));
                 }
 
+                self.depth -= 1;
                 let arr = Array::from_vec(self.sc, arr);
                 Ok(Value::Object(self.sc.register(arr)))
             }
             b'{' => {
-                let mut obj = ObjectMap::default();
+                self.enter_nested()?;
+                let mut obj = Vec::new();
 
                 self.idx += 1;
 
                 while let Some(cur) = self.skip_to_relevant_token() {
                     match cur {
-                        b'}' => break,
+                        b'}' => {
+                            self.idx += 1;
+                            break;
+                        }
                         b',' => self.idx += 1,
                         _ => {}
                     };
@@ -233,9 +268,10 @@ impl<'a, 'sc, 'vm> Parser<'a, 'sc, 'vm> {
                     self.skip_whitespaces(); // spaces
                     self.idx += 1; // :
                     let value = self.parse()?;
-                    obj.insert(PropertyKey::String(key.into()), PropertyValue::static_default(value));
+                    obj.push((PropertyKey::String(key.into()), PropertyValue::static_default(value)));
                 }
 
+                self.depth -= 1;
                 let obj = NamedObject::with_values(self.sc, obj);
 
                 Ok(Value::Object(self.sc.register(obj)))
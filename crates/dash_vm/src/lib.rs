@@ -1,16 +1,24 @@
 #![warn(clippy::redundant_clone)]
 #![deny(clippy::disallowed_methods)]
 
+use std::collections::VecDeque;
 use std::ops::RangeBounds;
 use std::vec::Drain;
 use std::fmt;
 
 use crate::gc::interner::{self, sym};
 use crate::gc::trace::{Trace, TraceCtxt};
+use crate::interrupt::InterruptHandle;
 use crate::util::cold_path;
-use crate::value::function::Function;
+use crate::value::finalization_registry::FinalizationRegistry;
+use crate::value::function::bound::BoundFunction;
+use crate::value::function::native::{NativeClosure, NativeFunction};
+use crate::value::function::{Function, FunctionKind};
 use crate::value::object::{PropertyDataDescriptor, PropertyValueKind};
 use crate::value::primitive::Symbol;
+use crate::value::weakmap::WeakMap;
+use crate::value::weakref::WeakRef;
+use crate::value::weakset::WeakSet;
 use crate::value::Root;
 
 use self::dispatch::HandleResult;
@@ -25,6 +33,7 @@ use dash_log::{debug, error, span, Level};
 use dash_middle::compiler::instruction::Instruction;
 use gc::handle::Handle;
 use gc::interner::StringInterner;
+use gc::persistent::Persistent;
 use gc::Gc;
 use localscope::{scope, LocalScopeList};
 use rustc_hash::FxHashSet;
@@ -39,11 +48,13 @@ pub mod eval;
 pub mod external;
 pub mod frame;
 pub mod gc;
+pub mod interrupt;
 pub mod js_std;
 pub mod json;
 pub mod localscope;
 mod macros;
 pub mod params;
+pub mod snapshot;
 pub mod statics;
 #[cfg(test)]
 mod test;
@@ -56,7 +67,7 @@ const DEFAULT_GC_OBJECT_COUNT_THRESHOLD: usize = 8192;
 
 pub struct Vm {
     frames: Vec<Frame>,
-    async_tasks: Vec<Handle>,
+    async_tasks: VecDeque<Handle>,
     // TODO: the inner vec of the stack should be private for soundness
     // popping from the stack must return `Unrooted`
     stack: Vec<Value>,
@@ -82,6 +93,20 @@ pub struct Vm {
     /// or adding a property to a builtin, will cause this to be set to `false`, which in turn
     /// will disable many optimizations such as specialized intrinsics.
     builtins_pure: bool,
+    /// `WeakRef` objects whose targets have not been collected yet.
+    /// These are not roots: a `WeakRef` that is not marked during the mark phase is removed from this list before sweeping.
+    weak_refs: Vec<Handle>,
+    /// Like `weak_refs`, the `FinalizationRegistry` objects that are still alive.
+    finalization_registries: Vec<Handle>,
+    /// Like `weak_refs`, the `WeakMap` objects that are still alive.
+    /// The values of their entries are only traced once their key has been marked.
+    weak_maps: Vec<Handle>,
+    /// Like `weak_refs`, the `WeakSet` objects that are still alive.
+    weak_sets: Vec<Handle>,
+    /// The number of instructions that may still be executed, if an instruction limit is set
+    instruction_budget: Option<u64>,
+    /// Set from the outside to terminate the running script
+    interrupt: InterruptHandle,
     #[cfg(feature = "jit")]
     jit: jit::Frontend,
 }
@@ -99,7 +124,7 @@ impl Vm {
 
         let mut vm = Self {
             frames: Vec::new(),
-            async_tasks: Vec::new(),
+            async_tasks: VecDeque::new(),
             stack: Vec::with_capacity(512),
             gc,
             interner: StringInterner::new(),
@@ -111,6 +136,12 @@ impl Vm {
             params,
             gc_object_threshold,
             builtins_pure: true,
+            weak_refs: Vec::new(),
+            finalization_registries: Vec::new(),
+            weak_maps: Vec::new(),
+            weak_sets: Vec::new(),
+            instruction_budget: None,
+            interrupt: InterruptHandle::default(),
 
             #[cfg(feature = "jit")]
             jit: jit::Frontend::new(),
@@ -127,6 +158,75 @@ impl Vm {
         self.global.clone()
     }
 
+    /// Defines a property on the global object, making the value accessible to evaluated code as a global variable.
+    ///
+    /// Fails if the global object rejects the value, e.g. when setting `constructor` to a non-object.
+    pub fn set_global(&mut self, name: &str, value: Value) -> Result<(), Value> {
+        let mut scope = self.scope();
+        let name = scope.intern(name);
+        let global = scope.global.clone();
+        global.set_property(&mut scope, name.into(), PropertyValue::static_default(value))
+    }
+
+    /// Registers a native function as a global function.
+    ///
+    /// The returned handle refers to the function object, which can be used to attach additional properties.
+    /// It is a [`Persistent`] handle, so the function stays alive for as long as the handle exists,
+    /// even if the global is overwritten.
+    ///
+    /// ```
+    /// use dash_vm::value::function::native::CallContext;
+    /// use dash_vm::value::{Root, Value};
+    /// use dash_vm::Vm;
+    ///
+    /// fn host_log(cx: CallContext) -> Result<Value, Value> {
+    ///     println!("{:?}", cx.args);
+    ///     Ok(Value::number(cx.args.len() as f64))
+    /// }
+    ///
+    /// let mut vm = Vm::new(Default::default());
+    /// vm.set_global_fn("hostLog", host_log).unwrap();
+    ///
+    /// let result = vm.eval("hostLog('hello', 'world')", Default::default()).unwrap();
+    /// let result = result.root(&mut vm.scope());
+    /// assert!(matches!(result, Value::Number(n) if n.0 == 2.0));
+    /// ```
+    pub fn set_global_fn(&mut self, name: &str, f: NativeFunction) -> Result<Persistent, Value> {
+        let mut scope = self.scope();
+        let name = scope.intern(name);
+        let fun = Function::new(&scope, Some(name.into()), FunctionKind::Native(f));
+        let fun = scope.register(fun);
+        let global = scope.global.clone();
+        global.set_property(
+            &mut scope,
+            name.into(),
+            PropertyValue::static_default(Value::Object(fun.clone())),
+        )?;
+        drop(scope);
+
+        Ok(Persistent::new(self, fun))
+    }
+
+    /// Defines an accessor on the global object whose getter is backed by a Rust closure.
+    ///
+    /// The closure is invoked every time evaluated code reads the global, so it can expose host state that changes
+    /// over time. Captured values are not traced by the garbage collector, so the closure must not hold on to handles.
+    ///
+    /// Fails if the global object rejects the accessor.
+    pub fn define_global_getter(
+        &mut self,
+        name: &str,
+        getter: impl Fn(&mut LocalScope<'_>) -> Result<Value, Value> + 'static,
+    ) -> Result<Handle, Value> {
+        let mut scope = self.scope();
+        let name = scope.intern(name);
+        let fun = NativeClosure::new(&scope, move |cx| getter(cx.scope));
+        let fun = scope.register(fun);
+        let global = scope.global.clone();
+        global.set_property(&mut scope, name.into(), PropertyValue::getter_default(fun.clone()))?;
+        Ok(fun)
+    }
+
     /// Prepare the VM for execution.
     #[rustfmt::skip]
     fn prepare(&mut self) {
@@ -244,9 +344,11 @@ impl Vm {
                 (sym::defineProperty, scope.statics.object_define_property.clone()),
                 (sym::defineProperties, scope.statics.object_define_properties.clone()),
                 (sym::entries, scope.statics.object_entries.clone()),
+                (sym::groupBy, scope.statics.object_group_by.clone()),
                 (sym::assign, scope.statics.object_assign.clone()),
                 (sym::getPrototypeOf, scope.statics.object_get_prototype_of.clone()),
                 (sym::setPrototypeOf, scope.statics.object_set_prototype_of.clone()),
+                (sym::hasOwn, scope.statics.object_has_own.clone()),
             ],
             [],
             [],
@@ -315,6 +417,8 @@ impl Vm {
                 (sym::tan, scope.statics.math_tan.clone()),
                 (sym::tanh, scope.statics.math_tanh.clone()),
                 (sym::trunc, scope.statics.math_trunc.clone()),
+                (sym::imul, scope.statics.math_imul.clone()),
+                (sym::fround, scope.statics.math_fround.clone()),
                 (sym::random, scope.statics.math_random.clone()),
                 (sym::max, scope.statics.math_max.clone()),
                 (sym::min, scope.statics.math_min.clone()),
@@ -360,6 +464,7 @@ impl Vm {
             [
                 (sym::toString, scope.statics.number_tostring.clone()),
                 (sym::toFixed, scope.statics.number_to_fixed.clone()),
+                (sym::valueOf, scope.statics.number_value_of.clone()),
             ],
             [],
             [],
@@ -398,6 +503,7 @@ impl Vm {
             function_ctor.clone(),
             [
                 (sym::fromCharCode, scope.statics.string_from_char_code.clone()),
+                (sym::fromCodePoint, scope.statics.string_from_code_point.clone()),
             ],
             [],
             [],
@@ -411,8 +517,10 @@ impl Vm {
            scope.statics.string_ctor.clone(),
            [
                 (sym::toString, scope.statics.string_tostring.clone()),
+                (sym::valueOf, scope.statics.string_value_of.clone()),
                 (sym::charAt, scope.statics.string_char_at.clone()),
                 (sym::charCodeAt, scope.statics.string_char_code_at.clone()),
+                (sym::codePointAt, scope.statics.string_code_point_at.clone()),
                 (sym::concat, scope.statics.string_concat.clone()),
                 (sym::endsWith, scope.statics.string_ends_with.clone()),
                 (sym::startsWith, scope.statics.string_starts_with.clone()),
@@ -425,6 +533,8 @@ impl Vm {
                 (sym::replace, scope.statics.string_replace.clone()),
                 (sym::replaceAll, scope.statics.string_replace_all.clone()),
                 (sym::split, scope.statics.string_split.clone()),
+                (sym::match_, scope.statics.string_match.clone()),
+                (sym::search, scope.statics.string_search.clone()),
                 (sym::toLowerCase, scope.statics.string_to_lowercase.clone()),
                 (sym::toUpperCase, scope.statics.string_to_uppercase.clone()),
                 (sym::big, scope.statics.string_big.clone()),
@@ -483,6 +593,8 @@ impl Vm {
                 (sym::reduce, scope.statics.array_reduce.clone()),
                 (sym::find, scope.statics.array_find.clone()),
                 (sym::findIndex, scope.statics.array_find_index.clone()),
+                (sym::findLast, scope.statics.array_find_last.clone()),
+                (sym::findLastIndex, scope.statics.array_find_last_index.clone()),
                 (sym::flat, scope.statics.array_flat.clone()),
                 (sym::forEach, scope.statics.array_for_each.clone()),
                 (sym::includes, scope.statics.array_includes.clone()),
@@ -518,6 +630,21 @@ impl Vm {
             &mut scope,
         );
         
+        register(
+            scope.statics.string_iterator_prototype.clone(),
+            object_proto.clone(),
+            object_ctor.clone(),
+            [
+                (sym::next, scope.statics.string_iterator_next.clone()),
+            ],
+            [
+                (scope.statics.symbol_iterator.clone(), scope.statics.identity_this.clone()),
+            ],
+            [],
+            None,
+            &mut scope,
+        );
+        
         register(
             scope.statics.generator_iterator_prototype.clone(),
             object_proto.clone(), // TODO: wrong
@@ -532,6 +659,21 @@ impl Vm {
             None,
             &mut scope,
         );
+
+        register(
+            scope.statics.async_generator_iterator_prototype.clone(),
+            object_proto.clone(),
+            function_ctor.clone(),
+            [
+                (sym::next, scope.statics.async_generator_iterator_next.clone()),
+            ],
+            [
+                (scope.statics.symbol_async_iterator.clone(), scope.statics.identity_this.clone()),
+            ],
+            [],
+            None,
+            &mut scope,
+        );
         
         let symbol_ctor = register(
             scope.statics.symbol_ctor.clone(),
@@ -556,6 +698,39 @@ impl Vm {
             Some((sym::JsSymbol, scope.statics.symbol_prototype.clone())),
             &mut scope,
         );
+
+        let symbol_proto = register(
+            scope.statics.symbol_prototype.clone(),
+            object_proto.clone(),
+            symbol_ctor.clone(),
+            [
+                (sym::toString, scope.statics.symbol_tostring.clone()),
+                (sym::valueOf, scope.statics.symbol_valueof.clone()),
+            ],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
+        // `description` is an accessor property, which `register` cannot set up
+        let symbol_description = register(
+            scope.statics.symbol_description.clone(),
+            function_proto.clone(),
+            function_ctor.clone(),
+            [],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+        symbol_proto
+            .set_property(
+                &mut scope,
+                sym::description.into(),
+                PropertyValue::new(PropertyValueKind::getter(symbol_description), PropertyDataDescriptor::CONFIGURABLE),
+            )
+            .unwrap();
         
         let error_ctor = register(
             scope.statics.error_ctor.clone(),
@@ -605,6 +780,58 @@ impl Vm {
             &mut scope,
         );
         
+        let text_encoder_ctor = register(
+            scope.statics.text_encoder_ctor.clone(),
+            function_proto.clone(),
+            function_ctor.clone(),
+            [],
+            [],
+            [],
+            Some((sym::TextEncoder, scope.statics.text_encoder_prototype.clone())),
+            &mut scope,
+        );
+        
+        register(
+            scope.statics.text_encoder_prototype.clone(),
+            object_proto.clone(),
+            text_encoder_ctor.clone(),
+            [
+                (sym::encode, scope.statics.text_encoder_encode.clone()),
+            ],
+            [],
+            [
+                (sym::encoding, Value::String(sym::utf8.into()), None),
+            ],
+            None,
+            &mut scope,
+        );
+        
+        let text_decoder_ctor = register(
+            scope.statics.text_decoder_ctor.clone(),
+            function_proto.clone(),
+            function_ctor.clone(),
+            [],
+            [],
+            [],
+            Some((sym::TextDecoder, scope.statics.text_decoder_prototype.clone())),
+            &mut scope,
+        );
+        
+        register(
+            scope.statics.text_decoder_prototype.clone(),
+            object_proto.clone(),
+            text_decoder_ctor.clone(),
+            [
+                (sym::decode, scope.statics.text_decoder_decode.clone()),
+            ],
+            [],
+            [
+                (sym::encoding, Value::String(sym::utf8.into()), None),
+            ],
+            None,
+            &mut scope,
+        );
+        
         let u8array_ctor = register(
             scope.statics.uint8array_ctor.clone(),
             function_proto.clone(),
@@ -804,6 +1031,9 @@ impl Vm {
             [
                 (sym::resolve, scope.statics.promise_resolve.clone()),
                 (sym::reject, scope.statics.promise_reject.clone()),
+                (sym::all, scope.statics.promise_all.clone()),
+                (sym::race, scope.statics.promise_race.clone()),
+                (sym::allSettled, scope.statics.promise_all_settled.clone()),
             ],
             [],
             [],
@@ -817,6 +1047,7 @@ impl Vm {
             promise_ctor.clone(),
             [
                 (sym::then, scope.statics.promise_then.clone()),
+                (sym::finally, scope.statics.promise_finally.clone()),
             ],
             [],
             [],
@@ -856,7 +1087,9 @@ impl Vm {
             scope.statics.map_constructor.clone(),
             function_proto.clone(),
             function_ctor.clone(),
-            [],
+            [
+                (sym::groupBy, scope.statics.map_group_by.clone()),
+            ],
             [],
             [],
             Some((sym::Map, scope.statics.map_prototype.clone())),
@@ -880,7 +1113,126 @@ impl Vm {
             None,
             &mut scope,
         );
+
+        let reflect = register(
+            scope.statics.reflect.clone(),
+            object_proto.clone(),
+            object_ctor.clone(),
+            [
+                (sym::construct, scope.statics.reflect_construct.clone()),
+                (sym::defineProperty, scope.statics.reflect_define_property.clone()),
+                (sym::deleteProperty, scope.statics.reflect_delete_property.clone()),
+                (sym::getPrototypeOf, scope.statics.reflect_get_prototype_of.clone()),
+                (sym::setPrototypeOf, scope.statics.reflect_set_prototype_of.clone()),
+            ],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
+        let weakref_ctor = register(
+            scope.statics.weakref_ctor.clone(),
+            function_proto.clone(),
+            function_ctor.clone(),
+            [],
+            [],
+            [],
+            Some((sym::WeakRef, scope.statics.weakref_prototype.clone())),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.weakref_prototype.clone(),
+            object_proto.clone(),
+            weakref_ctor.clone(),
+            [
+                (sym::deref, scope.statics.weakref_deref.clone()),
+            ],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
+        let finalization_registry_ctor = register(
+            scope.statics.finalization_registry_ctor.clone(),
+            function_proto.clone(),
+            function_ctor.clone(),
+            [],
+            [],
+            [],
+            Some((sym::FinalizationRegistry, scope.statics.finalization_registry_prototype.clone())),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.finalization_registry_prototype.clone(),
+            object_proto.clone(),
+            finalization_registry_ctor.clone(),
+            [
+                (sym::register, scope.statics.finalization_registry_register.clone()),
+                (sym::unregister, scope.statics.finalization_registry_unregister.clone()),
+            ],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
         
+        let weakmap_ctor = register(
+            scope.statics.weakmap_ctor.clone(),
+            function_proto.clone(),
+            function_ctor.clone(),
+            [],
+            [],
+            [],
+            Some((sym::WeakMap, scope.statics.weakmap_prototype.clone())),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.weakmap_prototype.clone(),
+            object_proto.clone(),
+            weakmap_ctor.clone(),
+            [
+                (sym::set, scope.statics.weakmap_set.clone()),
+                (sym::get, scope.statics.weakmap_get.clone()),
+                (sym::has, scope.statics.weakmap_has.clone()),
+                (sym::delete, scope.statics.weakmap_delete.clone()),
+            ],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
+        let weakset_ctor = register(
+            scope.statics.weakset_ctor.clone(),
+            function_proto.clone(),
+            function_ctor.clone(),
+            [],
+            [],
+            [],
+            Some((sym::WeakSet, scope.statics.weakset_prototype.clone())),
+            &mut scope,
+        );
+
+        register(
+            scope.statics.weakset_prototype.clone(),
+            object_proto.clone(),
+            weakset_ctor.clone(),
+            [
+                (sym::add, scope.statics.weakset_add.clone()),
+                (sym::has, scope.statics.weakset_has.clone()),
+                (sym::delete, scope.statics.weakset_delete.clone()),
+            ],
+            [],
+            [],
+            None,
+            &mut scope,
+        );
+
         let regexp_ctor = register(
             scope.statics.regexp_ctor.clone(),
             function_proto.clone(),
@@ -1091,7 +1443,16 @@ impl Vm {
             scope.statics.date_prototype.clone(),
             object_proto.clone(),
             date_ctor.clone(),
-            [],
+            [
+                (sym::getTime, scope.statics.date_get_time.clone()),
+                (sym::getFullYear, scope.statics.date_get_full_year.clone()),
+                (sym::getMonth, scope.statics.date_get_month.clone()),
+                (sym::getDate, scope.statics.date_get_date.clone()),
+                (sym::getHours, scope.statics.date_get_hours.clone()),
+                (sym::getMinutes, scope.statics.date_get_minutes.clone()),
+                (sym::getSeconds, scope.statics.date_get_seconds.clone()),
+                (sym::toISOString, scope.statics.date_to_iso_string.clone()),
+            ],
             [],
             [],
             None,
@@ -1104,6 +1465,7 @@ impl Vm {
             function_ctor,
             [
                 (sym::parse, scope.statics.json_parse.clone()),
+                (sym::stringify, scope.statics.json_stringify.clone()),
             ],
             [],
             [],
@@ -1121,10 +1483,16 @@ impl Vm {
                 (sym::isFinite, scope.statics.is_finite.clone()),
                 (sym::parseFloat, scope.statics.parse_float.clone()),
                 (sym::parseInt, scope.statics.parse_int.clone()),
+                (sym::btoa, scope.statics.btoa.clone()),
+                (sym::atob, scope.statics.atob.clone()),
+                (sym::structuredClone, scope.statics.structured_clone.clone()),
+                (sym::queueMicrotask, scope.statics.queue_microtask.clone()),
                 (sym::RegExp, regexp_ctor),
                 (sym::JsSymbol, symbol_ctor),
                 (sym::Date, date_ctor),
                 (sym::ArrayBuffer, arraybuffer_ctor),
+                (sym::TextEncoder, text_encoder_ctor),
+                (sym::TextDecoder, text_decoder_ctor),
                 (sym::Uint8Array, u8array_ctor),
                 (sym::Int8Array, i8array_ctor),
                 (sym::Uint16Array, u16array_ctor),
@@ -1146,18 +1514,38 @@ impl Vm {
                 (sym::Object, object_ctor),
                 (sym::Set, set_ctor),
                 (sym::Map, map_ctor),
+                (sym::WeakRef, weakref_ctor),
+                (sym::FinalizationRegistry, finalization_registry_ctor),
+                (sym::WeakMap, weakmap_ctor),
+                (sym::WeakSet, weakset_ctor),
                 (sym::console, console),
                 (sym::Math, math),
                 (sym::Number, number_ctor),
                 (sym::Boolean, boolean_ctor),
                 (sym::Promise, promise_ctor),
                 (sym::JSON, json_ctor),
+                (sym::Reflect, reflect),
             ],
             [],
             [],
             None,
             &mut scope
         );
+
+        if scope.params.expose_gc() {
+            let global = scope.global.clone();
+            let collect_garbage = register(
+                scope.statics.collect_garbage.clone(),
+                scope.statics.function_proto.clone(),
+                scope.statics.function_ctor.clone(),
+                [],
+                [],
+                [],
+                None,
+                &mut scope,
+            );
+            global.set_property(&mut scope, sym::gc.into(), PropertyValue::static_non_enumerable(collect_garbage.into())).unwrap();
+        }
     }
 
     pub(crate) fn active_frame(&self) -> &Frame {
@@ -1244,6 +1632,27 @@ impl Vm {
         Ok(())
     }
 
+    /// Consumes one instruction from the instruction budget, throwing if it is exhausted
+    #[inline]
+    fn consume_instruction_budget(&mut self) -> Result<(), Unrooted> {
+        if let Some(budget) = &mut self.instruction_budget {
+            if util::unlikely(*budget == 0) {
+                throw!(&mut self.scope(), RangeError, "Instruction limit exceeded");
+            }
+            *budget -= 1;
+        }
+        Ok(())
+    }
+
+    /// Throws an uncatchable error if the VM has been interrupted through an [`InterruptHandle`]
+    #[inline]
+    pub(crate) fn check_interrupt(&mut self) -> Result<(), Unrooted> {
+        if util::unlikely(self.interrupt.is_interrupted()) {
+            throw!(&mut self.scope(), Error, "Execution interrupted");
+        }
+        Ok(())
+    }
+
     pub(crate) fn try_extend_stack<I>(&mut self, other: I) -> Result<(), Unrooted>
     where
         I: IntoIterator<Item = Value>,
@@ -1289,6 +1698,15 @@ impl Vm {
 
     fn handle_rt_error(&mut self, err: Unrooted, max_fp: usize) -> Result<(), Unrooted> {
         debug!("handling rt error @{max_fp}");
+        if util::unlikely(self.interrupt.is_interrupted()) {
+            // Interrupts cannot be caught, so discard the try blocks of this frame and keep unwinding
+            while self.try_blocks.last().is_some_and(|tb| tb.frame_ip >= max_fp) {
+                self.try_blocks.pop();
+            }
+            self.frames.pop();
+            return Err(err);
+        }
+
         // Using .last() here instead of .pop() because there is a possibility that we
         // can't use this block (read the comment above the if statement try_fp < max_fp)
         if let Some(&TryBlock { catch_ip, finally_ip, frame_ip: try_fp }) = self.try_blocks.last() {
@@ -1354,19 +1772,20 @@ impl Vm {
 
     /// Adds a function to the async task queue.
     pub fn add_async_task(&mut self, fun: Handle) {
-        self.async_tasks.push(fun);
+        self.async_tasks.push_back(fun);
     }
 
     pub fn has_async_tasks(&self) -> bool {
         !self.async_tasks.is_empty()
     }
 
-    /// Processes all queued async tasks
+    /// Processes all queued async tasks in the order they were added to the queue,
+    /// including tasks that are queued while processing
     pub fn process_async_tasks(&mut self) {
         debug!("process async tasks");
         debug!(async_task_count = %self.async_tasks.len());
 
-        while let Some(task) = self.async_tasks.pop() {
+        while let Some(task) = self.async_tasks.pop_front() {
             let mut scope = self.scope();
 
             scope.add_ref(task.clone());
@@ -1410,6 +1829,14 @@ impl Vm {
         self.handle_instruction_loop()
     }
 
+    /// Executes a frame in this VM by throwing an exception at its current instruction,
+    /// without doing any sort of stack management
+    fn execute_frame_raw_with_throw(&mut self, frame: Frame, err: Unrooted) -> Result<HandleResult, Unrooted> {
+        self.try_push_frame(frame)?;
+        self.handle_rt_error(err, self.frames.len())?;
+        self.handle_instruction_loop()
+    }
+
     fn handle_instruction_loop(&mut self) -> Result<HandleResult, Unrooted> {
         let fp = self.frames.len();
 
@@ -1425,6 +1852,11 @@ impl Vm {
                 }
             }
 
+            if let Err(err) = self.consume_instruction_budget() {
+                self.handle_rt_error(err, fp)?;
+                continue;
+            }
+
             let instruction = Instruction::from_repr(self.fetch_and_inc_ip()).unwrap();
 
             match dispatch::handle(self, instruction) {
@@ -1457,8 +1889,12 @@ impl Vm {
 
         let trace_roots = span!(Level::TRACE, "gc trace");
         trace_roots.in_scope(|| self.trace_roots());
+        self.trace_weak_map_values();
 
         // All reachable roots are marked.
+        debug!("clear weak references");
+        let finalizers = self.clear_weak_refs();
+
         debug!("object count before sweep: {}", self.gc.node_count());
         let sweep = span!(Level::TRACE, "gc sweep");
         sweep.in_scope(|| unsafe { self.gc.sweep() });
@@ -1467,12 +1903,109 @@ impl Vm {
         debug!("sweep interner");
         self.interner.sweep();
 
+        // Cleanup callbacks are called later as async tasks, like promise reactions
+        for (cleanup, held_value) in finalizers {
+            let task = BoundFunction::new(self, cleanup, None, Some(vec![held_value]));
+            let task = self.gc.register(task);
+            self.add_async_task(task);
+        }
+
         // Adjust GC threshold
         let new_object_count = self.gc.node_count();
         self.gc_object_threshold = new_object_count * 2;
         debug!("new threshold: {}", self.gc_object_threshold);
     }
 
+    /// Clears weak references to objects that were not marked in the last mark phase.
+    ///
+    /// Returns the cleanup callbacks of finalization registries that need to be called, along with their held value.
+    /// These are still marked, so they survive the following sweep.
+    fn clear_weak_refs(&mut self) -> Vec<(Handle, Value)> {
+        // Weak references that were not marked themselves are about to be collected along with their target
+        self.weak_refs.retain(|weak_ref| {
+            weak_ref.interior_flags().is_marked()
+                && weak_ref
+                    .as_any()
+                    .downcast_ref::<WeakRef>()
+                    .is_some_and(WeakRef::clear_if_collected)
+        });
+
+        self.weak_maps.retain(|map| {
+            if !map.interior_flags().is_marked() {
+                return false;
+            }
+
+            map.as_any().downcast_ref::<WeakMap>().unwrap().remove_collected();
+            true
+        });
+
+        self.weak_sets.retain(|set| {
+            if !set.interior_flags().is_marked() {
+                return false;
+            }
+
+            set.as_any().downcast_ref::<WeakSet>().unwrap().remove_collected();
+            true
+        });
+
+        let mut finalizers = Vec::new();
+        self.finalization_registries.retain(|registry| {
+            if !registry.interior_flags().is_marked() {
+                return false;
+            }
+
+            let registry = registry.as_any().downcast_ref::<FinalizationRegistry>().unwrap();
+            if let Some(cleanup) = registry.cleanup() {
+                for held_value in registry.take_collected() {
+                    finalizers.push((cleanup.clone(), held_value));
+                }
+            }
+            true
+        });
+
+        finalizers
+    }
+
+    /// Traces the values of `WeakMap` entries whose key is alive.
+    ///
+    /// A value can itself keep the key of another entry alive, so this needs to be repeated until no more entries are found.
+    fn trace_weak_map_values(&mut self) {
+        let mut cx = TraceCtxt::new(&mut self.interner);
+        let mut live_entries = 0;
+        loop {
+            let count = self
+                .weak_maps
+                .iter()
+                .filter(|map| map.interior_flags().is_marked())
+                .map(|map| {
+                    let map = map.as_any().downcast_ref::<WeakMap>().unwrap();
+                    map.trace_live_values(&mut cx)
+                })
+                .sum();
+
+            if count == live_entries {
+                break;
+            }
+            live_entries = count;
+        }
+    }
+
+    pub(crate) fn add_weak_ref(&mut self, weak_ref: Handle) {
+        self.weak_refs.push(weak_ref);
+    }
+
+    pub(crate) fn add_finalization_registry(&mut self, registry: Handle) {
+        self.finalization_registries.push(registry);
+    }
+
+    pub(crate) fn add_weak_map(&mut self, map: Handle) {
+        self.weak_maps.push(map);
+    }
+
+    pub(crate) fn add_weak_set(&mut self, set: Handle) {
+        self.weak_sets.push(set);
+    }
+
     fn trace_roots(&mut self) {
         let mut cx = TraceCtxt::new(&mut self.interner);
 
@@ -1534,6 +2067,28 @@ impl Vm {
         &mut self.params
     }
 
+    /// Limits the number of instructions that may be executed, or removes the limit if `None` is passed.
+    ///
+    /// The budget is shared by all code that runs in this VM and is *not* reset between `eval` calls,
+    /// only by calling this function again. Once it is exhausted, every further instruction throws a `RangeError`,
+    /// so the error cannot be caught by the script to continue running.
+    /// While a limit is set, loops are never JIT compiled.
+    pub fn set_instruction_limit(&mut self, limit: Option<u64>) {
+        self.instruction_budget = limit;
+    }
+
+    /// Returns the number of instructions that may still be executed, or `None` if there is no instruction limit.
+    pub fn remaining_instructions(&self) -> Option<u64> {
+        self.instruction_budget
+    }
+
+    /// Returns a handle that can be used to terminate running code, e.g. from another thread after a timeout.
+    ///
+    /// See [`InterruptHandle`] for when interrupts are noticed and how they unwind.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.interrupt.clone()
+    }
+
     pub(crate) fn builtins_purity(&self) -> bool {
         self.builtins_pure
     }
@@ -40,6 +40,8 @@ pub struct VmParams {
     debugger_callback: Option<DebuggerCallback>,
     unhandled_task_exception_callback: Option<UnhandledTaskException>,
     initial_gc_object_threshold: Option<usize>,
+    max_json_depth: Option<usize>,
+    expose_gc: bool,
     state: Option<Box<dyn State>>,
 }
 
@@ -131,4 +133,25 @@ impl VmParams {
     pub fn initial_gc_object_threshold(&self) -> Option<usize> {
         self.initial_gc_object_threshold
     }
+
+    pub fn set_max_json_depth(mut self, depth: usize) -> Self {
+        self.max_json_depth = Some(depth);
+        self
+    }
+
+    pub fn max_json_depth(&self) -> Option<usize> {
+        self.max_json_depth
+    }
+
+    /// Exposes a global `gc()` function that triggers a garbage collection cycle.
+    ///
+    /// This is intended for tests and debugging and should not be enabled for untrusted code.
+    pub fn set_expose_gc(mut self, expose: bool) -> Self {
+        self.expose_gc = expose;
+        self
+    }
+
+    pub fn expose_gc(&self) -> bool {
+        self.expose_gc
+    }
 }
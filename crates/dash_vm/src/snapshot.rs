@@ -0,0 +1,165 @@
+use crate::gc::handle::Handle;
+use crate::localscope::LocalScope;
+use crate::throw;
+use crate::value::array::Array;
+use crate::value::object::{NamedObject, Object, PropertyDataDescriptor, PropertyKey, PropertyValue};
+use crate::value::ops::conversions::ValueConversion;
+use crate::value::string::JsString;
+use crate::value::{Root, Unrooted, Value};
+use crate::Vm;
+
+/// A copy of the plain data global variables of a [`Vm`], i.e. the enumerable properties of its global object.
+///
+/// A snapshot is taken once from a base VM (e.g. after running some setup code) with [`Vm::snapshot_globals`]
+/// and can then be restored into any number of independent VMs with [`Vm::restore_globals`].
+/// Values are copied into the snapshot, so mutating a VM restored from it never affects the snapshot
+/// or any other VM.
+///
+/// Only primitive values and plain objects and arrays made up of them can be captured.
+/// This does not save the cost of creating a VM: builtins are not part of the snapshot,
+/// as every VM creates its own when it is constructed.
+#[derive(Debug, Clone)]
+pub struct GlobalsSnapshot {
+    globals: Vec<(String, SnapshotValue)>,
+}
+
+#[derive(Debug, Clone)]
+enum SnapshotValue {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<SnapshotValue>),
+    Object(Vec<(String, SnapshotValue)>),
+}
+
+impl SnapshotValue {
+    /// Copies a value into an owned representation that does not depend on the VM.
+    ///
+    /// `ancestors` contains the objects that are currently being copied, to detect cycles.
+    fn capture(scope: &mut LocalScope, value: Value, ancestors: &mut Vec<Handle>) -> Result<Self, Value> {
+        let object = match value {
+            Value::Undefined(_) => return Ok(Self::Undefined),
+            Value::Null(_) => return Ok(Self::Null),
+            Value::Boolean(b) => return Ok(Self::Boolean(b)),
+            Value::Number(n) => return Ok(Self::Number(n.0)),
+            Value::String(s) => return Ok(Self::String(s.res(scope).to_owned())),
+            Value::Object(o) => o,
+            Value::Symbol(_) | Value::External(_) => throw!(scope, TypeError, "Cannot snapshot value"),
+        };
+
+        if ancestors.contains(&object) {
+            throw!(scope, TypeError, "Cannot snapshot cyclic object");
+        }
+
+        let is_array = object.as_any().downcast_ref::<Array>().is_some();
+        if !is_array && object.as_any().downcast_ref::<NamedObject>().is_none() {
+            throw!(scope, TypeError, "Cannot snapshot non-plain object");
+        }
+
+        ancestors.push(object.clone());
+        let value = Value::Object(object);
+
+        let captured = if is_array {
+            let len = value.length_of_array_like(scope)?;
+            let mut elements = Vec::with_capacity(len);
+            for i in 0..len {
+                let key = scope.intern_usize(i);
+                let element = value.get_property(scope, PropertyKey::String(key.into())).root(scope)?;
+                elements.push(Self::capture(scope, element, ancestors)?);
+            }
+            Self::Array(elements)
+        } else {
+            let mut properties = Vec::new();
+            for key in value.own_keys(scope)? {
+                let Value::String(key) = key else {
+                    throw!(scope, TypeError, "Cannot snapshot symbol property");
+                };
+                let property = value.get_property(scope, PropertyKey::String(key)).root(scope)?;
+                let property = Self::capture(scope, property, ancestors)?;
+                properties.push((key.res(scope).to_owned(), property));
+            }
+            Self::Object(properties)
+        };
+
+        ancestors.pop();
+        Ok(captured)
+    }
+
+    fn restore(&self, scope: &mut LocalScope) -> Result<Value, Value> {
+        Ok(match self {
+            Self::Undefined => Value::undefined(),
+            Self::Null => Value::null(),
+            Self::Boolean(b) => Value::Boolean(*b),
+            Self::Number(n) => Value::number(*n),
+            Self::String(s) => Value::String(JsString::from(scope.intern(s.as_str()))),
+            Self::Array(elements) => {
+                let elements = elements
+                    .iter()
+                    .map(|element| element.restore(scope).map(PropertyValue::static_default))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let array = Array::from_vec(scope, elements);
+                Value::Object(scope.register(array))
+            }
+            Self::Object(properties) => {
+                let object = NamedObject::new(scope);
+                let object = Value::Object(scope.register(object));
+                for (key, value) in properties {
+                    let key = PropertyKey::String(scope.intern(key.as_str()).into());
+                    let value = value.restore(scope)?;
+                    object.set_property(scope, key, PropertyValue::static_default(value))?;
+                }
+                object
+            }
+        })
+    }
+}
+
+impl Vm {
+    /// Copies every enumerable property of the global object into a snapshot.
+    /// Builtins are non-enumerable and are skipped.
+    ///
+    /// Fails if any of these globals holds a value that cannot be captured, such as a function.
+    pub fn snapshot_globals(&mut self) -> Result<GlobalsSnapshot, Unrooted> {
+        let global = self.global();
+        let mut scope = self.scope();
+        let mut globals = Vec::new();
+
+        for key in global.own_keys(&mut scope)? {
+            let Value::String(key) = key else { continue };
+
+            let is_enumerable = global
+                .get_own_property_descriptor(&mut scope, PropertyKey::String(key))?
+                .is_some_and(|p| p.descriptor.contains(PropertyDataDescriptor::ENUMERABLE));
+            if !is_enumerable {
+                continue;
+            }
+
+            let value = global
+                .get_property(&mut scope, PropertyKey::String(key))
+                .root(&mut scope)?;
+            let value = SnapshotValue::capture(&mut scope, value, &mut Vec::new())?;
+            globals.push((key.res(&scope).to_owned(), value));
+        }
+
+        Ok(GlobalsSnapshot { globals })
+    }
+
+    /// Defines the global variables of a snapshot taken with [`Vm::snapshot_globals`] on this VM,
+    /// overwriting existing globals with the same name.
+    ///
+    /// Fails if setting one of the globals throws.
+    pub fn restore_globals(&mut self, snapshot: &GlobalsSnapshot) -> Result<(), Unrooted> {
+        let global = self.global();
+        let mut scope = self.scope();
+
+        for (name, value) in &snapshot.globals {
+            let key = PropertyKey::String(scope.intern(name.as_str()).into());
+            let value = value.restore(&mut scope)?;
+            global.set_property(&mut scope, key, PropertyValue::static_default(value))?;
+        }
+
+        Ok(())
+    }
+}
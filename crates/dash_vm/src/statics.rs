@@ -5,10 +5,13 @@ use crate::gc::interner::{self, sym};
 use crate::gc::Gc;
 use crate::js_std;
 use crate::value::error::{AggregateError, EvalError, RangeError, ReferenceError, SyntaxError, TypeError, URIError};
+use crate::value::finalization_registry::FinalizationRegistry;
 use crate::value::function::{Function, FunctionKind};
 use crate::value::map::Map;
 use crate::value::regex::RegExp;
 use crate::value::set::Set;
+use crate::value::string::StringIterator;
+use crate::value::weakref::WeakRef;
 use crate::value::PureBuiltin;
 
 use super::value::array::{Array, ArrayIterator};
@@ -32,9 +35,14 @@ pub struct Statics {
     pub function_to_string: Handle,
     pub is_nan: Handle,
     pub eval: Handle,
+    pub collect_garbage: Handle,
     pub is_finite: Handle,
     pub parse_float: Handle,
     pub parse_int: Handle,
+    pub btoa: Handle,
+    pub atob: Handle,
+    pub structured_clone: Handle,
+    pub queue_microtask: Handle,
     pub console: Handle,
     pub console_log: Handle,
     pub math: Handle,
@@ -65,6 +73,8 @@ pub struct Statics {
     pub math_tan: Handle,
     pub math_tanh: Handle,
     pub math_trunc: Handle,
+    pub math_imul: Handle,
+    pub math_fround: Handle,
     pub math_random: Handle,
     pub math_max: Handle,
     pub math_min: Handle,
@@ -77,10 +87,12 @@ pub struct Statics {
     pub object_get_own_property_descriptor: Handle,
     pub object_get_own_property_descriptors: Handle,
     pub object_has_own_property: Handle,
+    pub object_has_own: Handle,
     pub object_define_property: Handle,
     pub object_define_properties: Handle,
     pub object_assign: Handle,
     pub object_entries: Handle,
+    pub object_group_by: Handle,
     pub object_get_prototype_of: Handle,
     pub object_set_prototype_of: Handle,
     pub object_is_prototype_of: Handle,
@@ -92,6 +104,7 @@ pub struct Statics {
     pub number_is_nan: Handle,
     pub number_is_safe_integer: Handle,
     pub number_to_fixed: Handle,
+    pub number_value_of: Handle,
     pub boolean_ctor: Handle,
     pub boolean_tostring: Handle,
     pub boolean_prototype: Handle,
@@ -99,8 +112,10 @@ pub struct Statics {
     pub string_ctor: Handle,
     pub string_prototype: Handle,
     pub string_tostring: Handle,
+    pub string_value_of: Handle,
     pub string_char_at: Handle,
     pub string_char_code_at: Handle,
+    pub string_code_point_at: Handle,
     pub string_concat: Handle,
     pub string_ends_with: Handle,
     pub string_starts_with: Handle,
@@ -113,6 +128,8 @@ pub struct Statics {
     pub string_replace: Handle,
     pub string_replace_all: Handle,
     pub string_split: Handle,
+    pub string_match: Handle,
+    pub string_search: Handle,
     pub string_to_uppercase: Handle,
     pub string_to_lowercase: Handle,
     pub string_big: Handle,
@@ -130,6 +147,7 @@ pub struct Statics {
     pub string_trim_start: Handle,
     pub string_trim_end: Handle,
     pub string_from_char_code: Handle,
+    pub string_from_code_point: Handle,
     pub string_substr: Handle,
     pub string_substring: Handle,
     pub string_iterator: Handle,
@@ -140,6 +158,9 @@ pub struct Statics {
     pub array_values: Handle,
     pub symbol_ctor: Handle,
     pub symbol_prototype: Handle,
+    pub symbol_tostring: Handle,
+    pub symbol_valueof: Handle,
+    pub symbol_description: Handle,
     pub symbol_async_iterator: Symbol,
     pub symbol_has_instance: Symbol,
     pub symbol_is_concat_spreadable: Symbol,
@@ -155,6 +176,8 @@ pub struct Statics {
     pub symbol_unscopables: Symbol,
     pub array_iterator_prototype: Handle,
     pub array_iterator_next: Handle,
+    pub string_iterator_prototype: Handle,
+    pub string_iterator_next: Handle,
     pub identity_this: Handle,
     pub array_at: Handle,
     pub array_concat: Handle,
@@ -167,6 +190,8 @@ pub struct Statics {
     pub array_reduce: Handle,
     pub array_find: Handle,
     pub array_find_index: Handle,
+    pub array_find_last: Handle,
+    pub array_find_last_index: Handle,
     pub array_flat: Handle,
     pub array_for_each: Handle,
     pub array_includes: Handle,
@@ -184,6 +209,8 @@ pub struct Statics {
     pub array_is_array: Handle,
     pub generator_iterator_prototype: Handle,
     pub generator_iterator_next: Handle,
+    pub async_generator_iterator_prototype: Handle,
+    pub async_generator_iterator_next: Handle,
     pub error_ctor: Handle,
     pub error_prototype: Handle,
     pub error_to_string: Handle,
@@ -204,6 +231,12 @@ pub struct Statics {
     pub arraybuffer_ctor: Handle,
     pub arraybuffer_prototype: Handle,
     pub arraybuffer_byte_length: Handle,
+    pub text_encoder_ctor: Handle,
+    pub text_encoder_prototype: Handle,
+    pub text_encoder_encode: Handle,
+    pub text_decoder_ctor: Handle,
+    pub text_decoder_prototype: Handle,
+    pub text_decoder_decode: Handle,
     pub uint8array_ctor: Handle,
     pub uint8array_prototype: Handle,
     pub int8array_ctor: Handle,
@@ -225,7 +258,11 @@ pub struct Statics {
     pub promise_proto: Handle,
     pub promise_resolve: Handle,
     pub promise_reject: Handle,
+    pub promise_all: Handle,
+    pub promise_race: Handle,
+    pub promise_all_settled: Handle,
     pub promise_then: Handle,
+    pub promise_finally: Handle,
     pub set_constructor: Handle,
     pub set_prototype: Handle,
     pub set_add: Handle,
@@ -241,6 +278,31 @@ pub struct Statics {
     pub map_delete: Handle,
     pub map_clear: Handle,
     pub map_size: Handle,
+    pub map_group_by: Handle,
+    pub reflect: Handle,
+    pub reflect_construct: Handle,
+    pub reflect_define_property: Handle,
+    pub reflect_delete_property: Handle,
+    pub reflect_get_prototype_of: Handle,
+    pub reflect_set_prototype_of: Handle,
+    pub weakref_ctor: Handle,
+    pub weakref_prototype: Handle,
+    pub weakref_deref: Handle,
+    pub finalization_registry_ctor: Handle,
+    pub finalization_registry_prototype: Handle,
+    pub finalization_registry_register: Handle,
+    pub finalization_registry_unregister: Handle,
+    pub weakmap_ctor: Handle,
+    pub weakmap_prototype: Handle,
+    pub weakmap_set: Handle,
+    pub weakmap_get: Handle,
+    pub weakmap_has: Handle,
+    pub weakmap_delete: Handle,
+    pub weakset_ctor: Handle,
+    pub weakset_prototype: Handle,
+    pub weakset_add: Handle,
+    pub weakset_has: Handle,
+    pub weakset_delete: Handle,
     pub regexp_ctor: Handle,
     pub regexp_prototype: Handle,
     pub regexp_test: Handle,
@@ -248,8 +310,17 @@ pub struct Statics {
     pub date_ctor: Handle,
     pub date_prototype: Handle,
     pub date_now: Handle,
+    pub date_get_time: Handle,
+    pub date_get_full_year: Handle,
+    pub date_get_month: Handle,
+    pub date_get_date: Handle,
+    pub date_get_hours: Handle,
+    pub date_get_minutes: Handle,
+    pub date_get_seconds: Handle,
+    pub date_to_iso_string: Handle,
     pub json_ctor: Handle,
     pub json_parse: Handle,
+    pub json_stringify: Handle,
 }
 
 fn builtin_object<O: Object + 'static>(gc: &mut Gc, obj: O) -> Handle {
@@ -294,10 +365,12 @@ impl Statics {
                 js_std::object::get_own_property_descriptors,
             ),
             object_has_own_property: function(gc, sym::hasOwnProperty, js_std::object::has_own_property),
+            object_has_own: function(gc, sym::hasOwn, js_std::object::has_own),
             object_define_property: function(gc, sym::defineProperty, js_std::object::define_property),
             object_define_properties: function(gc, sym::defineProperties, js_std::object::define_properties),
             object_assign: function(gc, sym::assign, js_std::object::assign),
             object_entries: function(gc, sym::entries, js_std::object::entries),
+            object_group_by: function(gc, sym::groupBy, js_std::object::group_by),
             object_get_prototype_of: function(gc, sym::getPrototypeOf, js_std::object::get_prototype_of),
             object_set_prototype_of: function(gc, sym::setPrototypeOf, js_std::object::set_prototype_of),
             object_is_prototype_of: function(gc, sym::isPrototypeOf, js_std::object::is_prototype_of),
@@ -316,9 +389,14 @@ impl Statics {
             string_prototype: builtin_object(gc, BoxedString::with_obj(sym::empty.into(), NamedObject::null())),
             is_nan: function(gc, sym::isNaN, js_std::global::is_nan),
             eval: function(gc, sym::eval, js_std::global::eval),
+            collect_garbage: function(gc, sym::gc, js_std::global::collect_garbage),
             is_finite: function(gc, sym::isFinite, js_std::global::is_finite),
             parse_float: function(gc, sym::parseFloat, js_std::global::parse_float),
             parse_int: function(gc, sym::parseInt, js_std::global::parse_int),
+            btoa: function(gc, sym::btoa, js_std::global::btoa),
+            atob: function(gc, sym::atob, js_std::global::atob),
+            structured_clone: function(gc, sym::structuredClone, js_std::global::structured_clone),
+            queue_microtask: function(gc, sym::queueMicrotask, js_std::global::queue_microtask),
             math_abs: function(gc, sym::abs, js_std::math::abs),
             math_acos: function(gc, sym::acos, js_std::math::acos),
             math_acosh: function(gc, sym::acosh, js_std::math::acosh),
@@ -345,6 +423,8 @@ impl Statics {
             math_tan: function(gc, sym::tan, js_std::math::tan),
             math_tanh: function(gc, sym::tanh, js_std::math::tanh),
             math_trunc: function(gc, sym::trunc, js_std::math::trunc),
+            math_imul: function(gc, sym::imul, js_std::math::imul),
+            math_fround: function(gc, sym::fround, js_std::math::fround),
             math_random: function(gc, sym::random, js_std::math::random),
             math_max: function(gc, sym::max, js_std::math::max),
             math_min: function(gc, sym::min, js_std::math::min),
@@ -353,10 +433,13 @@ impl Statics {
             number_is_nan: function(gc, sym::isNaN, js_std::number::is_nan),
             number_is_safe_integer: function(gc, sym::isSafeInteger, js_std::number::is_safe_integer),
             number_to_fixed: function(gc, sym::toFixed, js_std::number::to_fixed),
+            number_value_of: function(gc, sym::valueOf, js_std::number::value_of),
             boolean_valueof: function(gc, sym::valueOf, js_std::boolean::value_of),
             string_tostring: function(gc, sym::toString, js_std::string::to_string),
+            string_value_of: function(gc, sym::valueOf, js_std::string::value_of),
             string_char_at: function(gc, sym::charAt, js_std::string::char_at),
             string_char_code_at: function(gc, sym::charCodeAt, js_std::string::char_code_at),
+            string_code_point_at: function(gc, sym::codePointAt, js_std::string::code_point_at),
             string_concat: function(gc, sym::concat, js_std::string::concat),
             string_ends_with: function(gc, sym::endsWith, js_std::string::ends_with),
             string_starts_with: function(gc, sym::startsWith, js_std::string::starts_with),
@@ -369,6 +452,8 @@ impl Statics {
             string_replace: function(gc, sym::replace, js_std::string::replace),
             string_replace_all: function(gc, sym::replaceAll, js_std::string::replace_all),
             string_split: function(gc, sym::split, js_std::string::split),
+            string_match: function(gc, sym::match_, js_std::string::match_),
+            string_search: function(gc, sym::search, js_std::string::search),
             string_to_uppercase: function(gc, sym::toUpperCase, js_std::string::to_uppercase),
             string_to_lowercase: function(gc, sym::toLowerCase, js_std::string::to_lowercase),
             string_big: function(gc, sym::big, js_std::string::big),
@@ -386,6 +471,7 @@ impl Statics {
             string_trim_start: function(gc, sym::trimStart, js_std::string::trim_start),
             string_trim_end: function(gc, sym::trimEnd, js_std::string::trim_end),
             string_from_char_code: function(gc, sym::fromCharCode, js_std::string::from_char_code),
+            string_from_code_point: function(gc, sym::fromCodePoint, js_std::string::from_code_point),
             string_substr: function(gc, sym::substr, js_std::string::substr),
             string_substring: function(gc, sym::substring, js_std::string::substring),
             string_iterator: function(gc, sym::iterator, js_std::string::iterator),
@@ -400,6 +486,9 @@ impl Statics {
                 gc,
                 BoxedSymbol::with_obj(Symbol::new(sym::empty.into()), NamedObject::null()),
             ),
+            symbol_tostring: function(gc, sym::toString, js_std::symbol::to_string),
+            symbol_valueof: function(gc, sym::valueOf, js_std::symbol::value_of),
+            symbol_description: function(gc, sym::description, js_std::symbol::description),
             symbol_async_iterator: Symbol::new(sym::asyncIterator.into()),
             symbol_has_instance: Symbol::new(sym::hasInstance.into()),
             symbol_is_concat_spreadable: Symbol::new(sym::isConcatSpreadable.into()),
@@ -415,6 +504,8 @@ impl Statics {
             symbol_unscopables: Symbol::new(sym::unscopables.into()),
             array_iterator_prototype: builtin_object(gc, ArrayIterator::empty()),
             array_iterator_next: function(gc, sym::next, js_std::array_iterator::next),
+            string_iterator_prototype: builtin_object(gc, StringIterator::empty()),
+            string_iterator_next: function(gc, sym::next, js_std::string_iterator::next),
             identity_this: function(gc, sym::iterator, js_std::identity_this),
             array_at: function(gc, sym::at, js_std::array::at),
             array_concat: function(gc, sym::concat, js_std::array::concat),
@@ -427,6 +518,8 @@ impl Statics {
             array_reduce: function(gc, sym::reduce, js_std::array::reduce),
             array_find: function(gc, sym::find, js_std::array::find),
             array_find_index: function(gc, sym::findIndex, js_std::array::find_index),
+            array_find_last: function(gc, sym::findLast, js_std::array::find_last),
+            array_find_last_index: function(gc, sym::findLastIndex, js_std::array::find_last_index),
             array_flat: function(gc, sym::flat, js_std::array::flat),
             array_for_each: function(gc, sym::forEach, js_std::array::for_each),
             array_includes: function(gc, sym::includes, js_std::array::includes),
@@ -446,6 +539,8 @@ impl Statics {
                 builtin_object(gc, GeneratorIterator::empty(obj))
             },
             generator_iterator_next: function(gc, sym::next, js_std::generator::next),
+            async_generator_iterator_prototype: empty_object(gc),
+            async_generator_iterator_next: function(gc, sym::next, js_std::generator::async_next),
             error_ctor: function(gc, sym::Error, js_std::error::error_constructor),
             error_prototype: builtin_object(gc, Error::empty()),
             error_to_string: function(gc, sym::toString, js_std::error::to_string),
@@ -466,6 +561,12 @@ impl Statics {
             arraybuffer_ctor: function(gc, sym::ArrayBuffer, js_std::arraybuffer::constructor),
             arraybuffer_prototype: builtin_object(gc, ArrayBuffer::empty()),
             arraybuffer_byte_length: function(gc, sym::byteLength, js_std::arraybuffer::byte_length),
+            text_encoder_ctor: function(gc, sym::TextEncoder, js_std::text_encoder::constructor),
+            text_encoder_prototype: empty_object(gc),
+            text_encoder_encode: function(gc, sym::encode, js_std::text_encoder::encode),
+            text_decoder_ctor: function(gc, sym::TextDecoder, js_std::text_decoder::constructor),
+            text_decoder_prototype: empty_object(gc),
+            text_decoder_decode: function(gc, sym::decode, js_std::text_decoder::decode),
             uint8array_ctor: function(gc, sym::Uint8Array, js_std::typedarray::u8array::constructor),
             uint8array_prototype: empty_object(gc),
             int8array_ctor: function(gc, sym::Int8Array, js_std::typedarray::i8array::constructor),
@@ -487,7 +588,11 @@ impl Statics {
             promise_proto: empty_object(gc),
             promise_resolve: function(gc, sym::resolve, js_std::promise::resolve),
             promise_reject: function(gc, sym::reject, js_std::promise::reject),
+            promise_all: function(gc, sym::all, js_std::promise::all),
+            promise_race: function(gc, sym::race, js_std::promise::race),
+            promise_all_settled: function(gc, sym::allSettled, js_std::promise::all_settled),
             promise_then: function(gc, sym::then, js_std::promise::then),
+            promise_finally: function(gc, sym::finally, js_std::promise::finally),
             set_constructor: function(gc, sym::Set, js_std::set::constructor),
             set_add: function(gc, sym::add, js_std::set::add),
             set_has: function(gc, sym::has, js_std::set::has),
@@ -503,6 +608,38 @@ impl Statics {
             map_prototype: builtin_object(gc, Map::with_obj(NamedObject::null())),
             map_clear: function(gc, sym::clear, js_std::map::clear),
             map_size: function(gc, sym::size, js_std::map::size),
+            map_group_by: function(gc, sym::groupBy, js_std::map::group_by),
+            reflect: empty_object(gc),
+            reflect_construct: function(gc, sym::construct, js_std::reflect::construct),
+            reflect_define_property: function(gc, sym::defineProperty, js_std::reflect::define_property),
+            reflect_delete_property: function(gc, sym::deleteProperty, js_std::reflect::delete_property),
+            reflect_get_prototype_of: function(gc, sym::getPrototypeOf, js_std::reflect::get_prototype_of),
+            reflect_set_prototype_of: function(gc, sym::setPrototypeOf, js_std::reflect::set_prototype_of),
+            weakref_ctor: function(gc, sym::WeakRef, js_std::weakref::constructor),
+            weakref_prototype: builtin_object(gc, WeakRef::with_obj(None, NamedObject::null())),
+            weakref_deref: function(gc, sym::deref, js_std::weakref::deref),
+            finalization_registry_ctor: function(
+                gc,
+                sym::FinalizationRegistry,
+                js_std::finalization_registry::constructor,
+            ),
+            finalization_registry_prototype: builtin_object(
+                gc,
+                FinalizationRegistry::with_obj(None, NamedObject::null()),
+            ),
+            finalization_registry_register: function(gc, sym::register, js_std::finalization_registry::register),
+            finalization_registry_unregister: function(gc, sym::unregister, js_std::finalization_registry::unregister),
+            weakmap_ctor: function(gc, sym::WeakMap, js_std::weakmap::constructor),
+            weakmap_prototype: builtin_object(gc, NamedObject::null()),
+            weakmap_set: function(gc, sym::set, js_std::weakmap::set),
+            weakmap_get: function(gc, sym::get, js_std::weakmap::get),
+            weakmap_has: function(gc, sym::has, js_std::weakmap::has),
+            weakmap_delete: function(gc, sym::delete, js_std::weakmap::delete),
+            weakset_ctor: function(gc, sym::WeakSet, js_std::weakset::constructor),
+            weakset_prototype: builtin_object(gc, NamedObject::null()),
+            weakset_add: function(gc, sym::add, js_std::weakset::add),
+            weakset_has: function(gc, sym::has, js_std::weakset::has),
+            weakset_delete: function(gc, sym::delete, js_std::weakset::delete),
             regexp_ctor: function(gc, sym::RegExp, js_std::regex::constructor),
             regexp_prototype: builtin_object(gc, RegExp::empty()),
             regexp_test: function(gc, sym::test, js_std::regex::test),
@@ -510,8 +647,17 @@ impl Statics {
             date_ctor: function(gc, sym::Date, js_std::date::constructor),
             date_prototype: builtin_object(gc, NamedObject::null()),
             date_now: function(gc, sym::now, js_std::date::now),
+            date_get_time: function(gc, sym::getTime, js_std::date::get_time),
+            date_get_full_year: function(gc, sym::getFullYear, js_std::date::get_full_year),
+            date_get_month: function(gc, sym::getMonth, js_std::date::get_month),
+            date_get_date: function(gc, sym::getDate, js_std::date::get_date),
+            date_get_hours: function(gc, sym::getHours, js_std::date::get_hours),
+            date_get_minutes: function(gc, sym::getMinutes, js_std::date::get_minutes),
+            date_get_seconds: function(gc, sym::getSeconds, js_std::date::get_seconds),
+            date_to_iso_string: function(gc, sym::toISOString, js_std::date::to_iso_string),
             json_ctor: function(gc, sym::JSON, js_std::json::constructor),
             json_parse: function(gc, sym::parse, js_std::json::parse),
+            json_stringify: function(gc, sym::stringify, js_std::json::stringify),
         }
     }
 }
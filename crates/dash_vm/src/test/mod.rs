@@ -1,12 +1,17 @@
 use std::ptr;
 
+use dash_middle::compiler::StaticImportKind;
 use dash_middle::interner::sym;
 use dash_optimizer::OptLevel;
 
 use crate::gc::persistent::Persistent;
-use crate::value::object::{NamedObject, Object, PropertyValue};
+use crate::params::VmParams;
+use crate::value::array::Array;
+use crate::value::object::{NamedObject, Object, PropertyKey, PropertyValue};
 use crate::value::primitive::Number;
-use crate::value::{Root, Value};
+use crate::value::promise::{Promise, PromiseState};
+use crate::value::string::JsString;
+use crate::value::{Root, Unrooted, Value};
 use crate::Vm;
 
 const INTERPRETER: &str = include_str!("interpreter.js");
@@ -132,6 +137,509 @@ fn async_tasks() {
     assert!(vm.stack.is_empty());
 }
 
+#[test]
+fn promise_finally() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.calls = 0;
+    globalThis.resolved = Promise.resolve(1).finally(() => { calls++; return 2; });
+    globalThis.rejected = new Promise((_, reject) => reject(3)).finally(() => { calls++; });
+    globalThis.overridden = Promise.resolve(4).finally(() => { calls++; throw 5; });
+    globalThis.passed = Promise.resolve(6).finally(undefined);
+    globalThis.log = [];
+    globalThis.waited = Promise.resolve(7).finally(() => new Promise(resolve => {
+        queueMicrotask(() => { log.push("handler"); resolve(8); });
+    }));
+    waited.then(v => log.push("waited " + v));
+    globalThis.handlerRejected = Promise.resolve(9).finally(() => new Promise((_, reject) => reject(10)));
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+    assert!(vm.async_tasks.is_empty());
+
+    let mut scope = vm.scope();
+    let mut settled = |name: &str| {
+        let value = scope.eval(name, Default::default()).unwrap().root(&mut scope);
+        let promise = value.downcast_ref::<Promise>().unwrap();
+        let state = match &*promise.state().borrow() {
+            PromiseState::Resolved(v) => Ok(v.clone()),
+            PromiseState::Rejected(v) => Err(v.clone()),
+            PromiseState::Pending { .. } => panic!("{name} is still pending"),
+        };
+        state
+    };
+    assert_eq!(settled("resolved"), Ok(Value::number(1.0)));
+    assert_eq!(settled("rejected"), Err(Value::number(3.0)));
+    assert_eq!(settled("overridden"), Err(Value::number(5.0)));
+    assert_eq!(settled("passed"), Ok(Value::number(6.0)));
+    assert_eq!(settled("waited"), Ok(Value::number(7.0)));
+    assert_eq!(settled("handlerRejected"), Err(Value::number(10.0)));
+    assert_eq!(settled("Promise.resolve(calls)"), Ok(Value::number(3.0)));
+
+    let log = scope.eval("log.join(',')", Default::default()).unwrap().root(&mut scope);
+    let Value::String(log) = log else {
+        panic!("log is not a string: {log:?}")
+    };
+    assert_eq!(log.res(&scope), "handler,waited 7");
+}
+
+#[test]
+fn queue_microtask_order() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.log = [];
+    queueMicrotask(() => log.push(1));
+    Promise.resolve().then(() => log.push(2));
+    queueMicrotask(() => {
+        log.push(3);
+        queueMicrotask(() => log.push(5));
+    });
+    Promise.resolve().then(() => log.push(4));
+    log.push(0);
+    try {
+        queueMicrotask(5);
+    } catch (e) {
+        log.push(e instanceof TypeError);
+    }
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+    assert!(vm.async_tasks.is_empty());
+
+    let mut scope = vm.scope();
+    let result = scope.eval("log.join(',')", Default::default()).unwrap().root(&mut scope);
+    let Value::String(result) = result else {
+        panic!("result is not a string: {result:?}")
+    };
+    assert_eq!(result.res(&scope), "0,true,1,2,3,4,5");
+}
+
+#[test]
+fn async_function_rejections() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.caught = (async function() {
+        try { await Promise.reject(1); } catch {}
+        try { await Promise.reject(1); } catch (e) { return e + 1; }
+    })();
+    globalThis.afterAwait = (async function() { await null; throw 3; })();
+    // Exceptions before the first `await` must not be thrown synchronously
+    globalThis.beforeAwait = (async () => { throw 4; })();
+    globalThis.uncaught = (async function() { await Promise.reject(5); })();
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+    assert!(vm.async_tasks.is_empty());
+
+    let mut scope = vm.scope();
+    let mut settled = |name: &str| {
+        let value = scope.eval(name, Default::default()).unwrap().root(&mut scope);
+        let promise = value.downcast_ref::<Promise>().unwrap();
+        let state = match &*promise.state().borrow() {
+            PromiseState::Resolved(v) => Ok(v.clone()),
+            PromiseState::Rejected(v) => Err(v.clone()),
+            PromiseState::Pending { .. } => panic!("{name} is still pending"),
+        };
+        state
+    };
+    assert_eq!(settled("caught"), Ok(Value::number(2.0)));
+    assert_eq!(settled("afterAwait"), Err(Value::number(3.0)));
+    assert_eq!(settled("beforeAwait"), Err(Value::number(4.0)));
+    assert_eq!(settled("uncaught"), Err(Value::number(5.0)));
+}
+
+#[test]
+fn async_arrow_function() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    const o = {
+        value: 7,
+        run() {
+            const f = async (x) => {
+                const v = await Promise.resolve(x);
+                return v + this.value;
+            };
+            // Arrow functions have no own `this`, so the receiver is ignored
+            return f.call({ value: 100 }, 1);
+        }
+    };
+    const double = async x => await Promise.resolve(x * 2);
+
+    o.run().then(v => globalThis.fromMethod = v);
+    double(3).then(v => globalThis.doubled = v);
+    async () => {};
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+    assert!(vm.async_tasks.is_empty());
+
+    let mut scope = vm.scope();
+    let value = scope
+        .eval("[fromMethod, doubled]", Default::default())
+        .unwrap()
+        .root(&mut scope);
+    let from_method = value
+        .get_property(&mut scope, sym::zero.into())
+        .unwrap()
+        .root(&mut scope);
+    let doubled = value
+        .get_property(&mut scope, sym::one.into())
+        .unwrap()
+        .root(&mut scope);
+    assert_eq!(from_method, Value::number(8.0));
+    assert_eq!(doubled, Value::number(6.0));
+}
+
+#[test]
+fn async_generator_for_await_of() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    async function* numbers(count) {
+        for (let i = 1; i <= count; i++) {
+            const value = await Promise.resolve(i * 10);
+            yield value;
+        }
+        return "done";
+    }
+
+    class Source {
+        async *values() {
+            yield await Promise.resolve("a");
+            yield "b";
+        }
+    }
+
+    (async function() {
+        const seen = [];
+        for await (const value of numbers(3)) {
+            seen.push(value);
+        }
+        for await (const value of new Source().values()) {
+            seen.push(value);
+        }
+        // Sync iterables can be used as well
+        for await (const value of [1, 2]) {
+            seen.push(value);
+        }
+
+        const it = numbers(1);
+        const first = it.next();
+        const second = it.next();
+        const third = it.next();
+        seen.push((await first).value, (await second).value, (await third).done);
+
+        globalThis.result = seen.join(",");
+    })();
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+    assert!(vm.async_tasks.is_empty());
+
+    let mut scope = vm.scope();
+    let result = scope.eval("result", Default::default()).unwrap().root(&mut scope);
+    let Value::String(result) = result else {
+        panic!("result is not a string: {result:?}")
+    };
+    assert_eq!(result.res(&scope), "10,20,30,a,b,1,2,10,done,true");
+}
+
+#[test]
+fn async_generator_rejections() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    async function* values() {
+        try { await Promise.reject("awaited"); } catch (e) { yield e; }
+        // `yield` awaits its operand
+        yield Promise.resolve("yielded");
+        try { yield Promise.reject("rejected"); } catch (e) { yield e; }
+        await Promise.reject("uncaught");
+    }
+
+    (async function() {
+        const seen = [];
+        try {
+            for await (const value of values()) {
+                seen.push(value);
+            }
+        } catch (e) {
+            seen.push("caught " + e);
+        }
+        globalThis.result = seen.join(",");
+    })();
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+    assert!(vm.async_tasks.is_empty());
+
+    let mut scope = vm.scope();
+    let result = scope.eval("result", Default::default()).unwrap().root(&mut scope);
+    let Value::String(result) = result else {
+        panic!("result is not a string: {result:?}")
+    };
+    assert_eq!(result.res(&scope), "awaited,yielded,rejected,caught uncaught");
+}
+
+#[test]
+fn microtask_order() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.log = [];
+    Promise.resolve().then(() => log.push(1));
+    (async () => {
+        log.push("start");
+        // Awaiting a non-promise value resumes on a later tick
+        const value = await 5;
+        log.push("await " + value);
+        globalThis.awaited = await Promise.resolve(value + 1);
+    })();
+    log.push("sync");
+    Promise.resolve().then(() => log.push(2)).then(() => log.push(3));
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+    assert!(vm.async_tasks.is_empty());
+
+    let mut scope = vm.scope();
+    let result = scope
+        .eval("log.join() + '/' + awaited", Default::default())
+        .unwrap()
+        .root(&mut scope);
+    let Value::String(result) = result else {
+        panic!("result is not a string: {result:?}")
+    };
+    assert_eq!(result.res(&scope), "start,sync,1,await 5,2,3/6");
+}
+
+#[test]
+fn promise_combinators() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.log = [];
+    const pending = new Promise(() => {});
+    const rejected = new Promise((_, reject) => reject("bad"));
+
+    Promise.all([1, Promise.resolve(2), new Promise(r => r(3))]).then(v => log.push("all " + v.join()));
+    Promise.all([]).then(v => log.push("empty " + v.length));
+    Promise.race([pending, 5, 6]).then(v => log.push("race " + v));
+    Promise.allSettled([1, rejected]).then(v => {
+        log.push("allSettled " + v.map(x => x.status + ":" + (x.status === "fulfilled" ? x.value : x.reason)).join());
+    });
+    // The first rejection settles the combined promise, even if other inputs are still pending
+    globalThis.allRejected = Promise.all([1, rejected, pending]);
+    globalThis.raceRejected = Promise.race([rejected, 2]);
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+    vm.process_async_tasks();
+
+    let mut scope = vm.scope();
+    let result = scope
+        .eval("log.sort((a, b) => (a < b ? -1 : 1)).join(' | ')", Default::default())
+        .unwrap()
+        .root(&mut scope);
+    let Value::String(result) = result else {
+        panic!("result is not a string: {result:?}")
+    };
+    assert_eq!(
+        result.res(&scope),
+        "all 1,2,3 | allSettled fulfilled:1,rejected:bad | empty 0 | race 5"
+    );
+
+    for name in ["allRejected", "raceRejected"] {
+        let promise = scope.eval(name, Default::default()).unwrap().root(&mut scope);
+        let promise = promise.downcast_ref::<Promise>().unwrap();
+        match &*promise.state().borrow() {
+            PromiseState::Rejected(Value::String(reason)) => assert_eq!(reason.res(&scope), "bad", "{name}"),
+            state => panic!("{name} is not rejected: {state:?}"),
+        };
+    }
+}
+
+#[test]
+fn weakref_and_finalization_registry() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.cleanedUp = [];
+    globalThis.registry = new FinalizationRegistry(held => cleanedUp.push(held));
+    globalThis.kept = {};
+    (function() {
+        const target = {};
+        const token = {};
+        globalThis.weak = new WeakRef(target);
+        globalThis.keptWeak = new WeakRef(kept);
+        registry.register(target, "target");
+        registry.register(kept, "kept");
+        registry.register({}, "unregistered", token);
+        registry.unregister(token);
+        if (weak.deref() !== target) throw "deref() must return the target while it is alive";
+    })();
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+
+    fn check(vm: &mut Vm, code: &str) {
+        let value = vm.eval(code, Default::default()).unwrap().root(&mut vm.scope());
+        assert_eq!(value, Value::Boolean(true), "{code}");
+    }
+
+    vm.perform_gc();
+    check(&mut vm, "weak.deref() === undefined && keptWeak.deref() === kept");
+    // Cleanup callbacks only run as async tasks
+    check(&mut vm, "cleanedUp.length === 0");
+
+    vm.process_async_tasks();
+    check(&mut vm, "cleanedUp.join() === 'target'");
+}
+
+#[test]
+fn expose_gc() {
+    let mut vm = Vm::new(Default::default());
+    let value = vm.eval("typeof gc", Default::default()).unwrap().root(&mut vm.scope());
+    assert_eq!(value, Value::String(sym::undefined.into()));
+
+    let mut vm = Vm::new(
+        VmParams::new()
+            .set_expose_gc(true)
+            .set_initial_gc_object_threshold(usize::MAX),
+    );
+    vm.eval("for (let i = 0; i < 100; i++) ({ i });", Default::default())
+        .unwrap();
+    let before = vm.gc.node_count();
+    vm.eval("gc()", Default::default()).unwrap();
+    let after = vm.gc.node_count();
+    assert!(after + 100 <= before, "before: {before}, after: {after}");
+}
+
+#[test]
+fn globals_snapshot_restore() {
+    let mut base = Vm::new(Default::default());
+    base.eval(
+        r#"globalThis.config = { name: "base", limits: [1, 2], nested: { enabled: true } }; globalThis.counter = 0;"#,
+        Default::default(),
+    )
+    .unwrap();
+    let snapshot = base.snapshot_globals().unwrap();
+
+    let mut first = Vm::new(Default::default());
+    first.restore_globals(&snapshot).unwrap();
+    let mut second = Vm::new(Default::default());
+    second.restore_globals(&snapshot).unwrap();
+
+    first
+        .eval(
+            r#"counter++; config.name = "changed"; config.limits.push(3); config.nested.enabled = false;"#,
+            Default::default(),
+        )
+        .unwrap();
+
+    let value = first
+        .eval("counter + config.name + config.limits.length", Default::default())
+        .unwrap()
+        .root(&mut first.scope());
+    let mut scope = first.scope();
+    assert_eq!(value, Value::String(scope.intern("1changed3").into()));
+    drop(scope);
+
+    let value = second
+        .eval(
+            "counter + config.name + config.limits.length + config.nested.enabled + typeof Math.max",
+            Default::default(),
+        )
+        .unwrap()
+        .root(&mut second.scope());
+    let mut scope = second.scope();
+    assert_eq!(value, Value::String(scope.intern("0base2truefunction").into()));
+    drop(scope);
+
+    // Functions cannot be captured
+    base.eval("globalThis.f = function() {};", Default::default()).unwrap();
+    assert!(base.snapshot_globals().is_err());
+}
+
+#[test]
+fn iterate_from_rust() {
+    let mut vm = Vm::new(Default::default());
+    let mut scope = vm.scope();
+
+    let array = scope.eval("[1, 2, 3]", Default::default()).unwrap().root(&mut scope);
+    let values = scope.iterate(array).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(values, [Value::number(1.0), Value::number(2.0), Value::number(3.0)]);
+
+    let generator = scope
+        .eval("function* gen() { yield 'a'; yield 'b'; } gen()", Default::default())
+        .unwrap()
+        .root(&mut scope);
+    let values = scope
+        .iterate(generator)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let (a, b) = (scope.intern("a"), scope.intern("b"));
+    assert_eq!(values, [Value::String(a.into()), Value::String(b.into())]);
+
+    // Errors thrown by the iterator end the iteration
+    let throwing = scope
+        .eval("function* gen() { yield 1; throw 2; } gen()", Default::default())
+        .unwrap()
+        .root(&mut scope);
+    let values = scope.iterate(throwing).unwrap().collect::<Vec<_>>();
+    assert_eq!(values, [Ok(Value::number(1.0)), Err(Value::number(2.0))]);
+
+    assert!(scope.iterate(Value::number(1.0)).is_err());
+}
+
+#[test]
+fn export_all() {
+    fn import_callback(vm: &mut Vm, ty: StaticImportKind, path: JsString) -> Result<Unrooted, Unrooted> {
+        let mut scope = vm.scope();
+        let source = match path.res(&scope) {
+            "base" => "export const a = 1; export const b = 2; export default 3;",
+            "reexport" => r#"export * from "base"; export const c = 4;"#,
+            other => panic!("unknown module: {other}"),
+        };
+        Vm::evaluate_module(&mut scope, source, ty, Default::default())
+    }
+
+    let mut vm = Vm::new(VmParams::new().set_static_import_callback(import_callback));
+    let value = vm
+        .eval_with_mode(
+            r#"
+            import * as m from "reexport";
+            m.a === 1 && m.b === 2 && m.c === 4 && !("default" in m)
+            "#,
+            Default::default(),
+            dash_parser::ParseMode::Module,
+        )
+        .unwrap()
+        .root(&mut vm.scope());
+    assert_eq!(value, Value::Boolean(true));
+}
+
 macro_rules! simple_test {
     ($testname:ident, $code:expr, $expected:expr) => {
         #[test]
@@ -555,3 +1063,3241 @@ simple_test!(
 simple_test!(closure_default_param1, "((v = 1) => v)()", Value::number(1.));
 
 simple_test!(closure_default_param2, "((v = 1) => v)(2)", Value::number(2.));
+
+simple_test!(
+    array_find_last,
+    r#"
+    const arr = [1, 2, 3, 4, 5];
+    assert(arr.findLast(v => v < 2) === 1);
+    assert(arr.findLastIndex(v => v < 2) === 0);
+    assert(arr.findLast(v => v % 2 === 0) === 4);
+    assert(arr.findLastIndex(v => v % 2 === 0) === 3);
+    assert(arr.findLast(v => v > 10) === undefined);
+    assert(arr.findLastIndex(v => v > 10) === -1);
+
+    const indices = [];
+    arr.findLastIndex((v, i) => { indices.push(i); return false; });
+    assert(indices.join(',') === '4,3,2,1,0');
+
+    let caught;
+    try {
+        arr.findLast(() => { throw 'err'; });
+    } catch (e) {
+        caught = e;
+    }
+    assert(caught === 'err');
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn json_max_depth() {
+    let mut vm = Vm::new(VmParams::new().set_max_json_depth(16));
+    let mut scope = vm.scope();
+
+    let under = format!("{}{}", "[".repeat(16), "]".repeat(16));
+    let value = scope
+        .eval(&format!("JSON.parse('{under}')"), OptLevel::Basic)
+        .unwrap()
+        .root(&mut scope);
+    assert!(value.downcast_ref::<Array>().is_some());
+
+    let over = format!("{}{}", "[".repeat(17), "]".repeat(17));
+    let value = scope
+        .eval(
+            &format!("let r; try {{ JSON.parse('{over}') }} catch (e) {{ r = e instanceof SyntaxError }} r"),
+            OptLevel::Basic,
+        )
+        .unwrap()
+        .root(&mut scope);
+    assert_eq!(value, Value::Boolean(true));
+
+    // the default limit must not overflow the native stack on adversarial input
+    let mut vm = Vm::new(Default::default());
+    let mut scope = vm.scope();
+    let value = scope
+        .eval(
+            &format!(
+                "let r; try {{ JSON.parse('{}') }} catch (e) {{ r = e instanceof SyntaxError }} r",
+                "[".repeat(100_000)
+            ),
+            OptLevel::Basic,
+        )
+        .unwrap()
+        .root(&mut scope);
+    assert_eq!(value, Value::Boolean(true));
+    assert!(scope.stack.is_empty());
+}
+
+simple_test!(
+    json_reviver_replacer,
+    r#"
+    const parsed = JSON.parse('{"date": "2020-01-01", "nested": [1, {"n": 2}]}', (key, value) => {
+        if (key === 'date') return value.split('-').map(v => +v);
+        if (typeof value === 'number') return value * 10;
+        return value;
+    });
+    assert(parsed.date[0] === 2020 && parsed.date[2] === 1);
+    assert(parsed.nested[0] === 10);
+    assert(parsed.nested[1].n === 20);
+
+    const keys = [];
+    JSON.parse('{"a": [1], "b": 2}', (key, value) => { keys.push(key); return value; });
+    assert(keys.length === 4);
+    assert(keys[keys.length - 1] === '');
+    assert(keys.indexOf('0') < keys.indexOf('a'));
+
+    assert(JSON.stringify({ a: 1, b: 'x' }, (key, value) => key === 'b' ? undefined : value) === '{"a":1}');
+    assert(JSON.stringify({ a: 1, b: 2, c: 3 }, ['c']) === '{"c":3}');
+    assert(JSON.stringify({ a: { b: 1, c: 2 } }, ['a', 'b']) === '{"a":{"b":1}}');
+    assert(JSON.stringify([1, 'a', null, undefined, true]) === '[1,"a",null,null,true]');
+    assert(JSON.stringify(undefined) === undefined);
+
+    let caught;
+    const cyclic = {};
+    cyclic.self = cyclic;
+    try {
+        JSON.stringify(cyclic);
+    } catch (e) {
+        caught = e;
+    }
+    assert(caught instanceof TypeError);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    global_is_nan_is_finite,
+    r#"
+    assert(isNaN("foo"));
+    assert(!isNaN("42"));
+    assert(isNaN(undefined));
+    assert(!isNaN(null));
+    assert(isFinite("10"));
+    assert(!isFinite("foo"));
+    assert(!isFinite(Infinity));
+    assert(!Number.isNaN("foo"));
+    assert(!Number.isFinite("10"));
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    boolean_constructor,
+    r#"
+    assert(Boolean(0) === false);
+    assert(Boolean("x") === true);
+    assert(Boolean() === false);
+    assert(typeof Boolean(1) === 'boolean');
+
+    const boxed = new Boolean(false);
+    assert(typeof boxed === 'object');
+    assert(boxed.valueOf() === false);
+    assert(boxed.toString() === 'false');
+    assert(new Boolean(1).toString() === 'true');
+    assert(true.toString() === 'true');
+    assert(false.valueOf() === false);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    string_constructor_and_from_code,
+    r#"
+    assert(String(123) === '123');
+    assert(String(null) === 'null');
+    assert(String(undefined) === 'undefined');
+    assert(String([1, 2]) === '1,2');
+    assert(String() === '');
+    assert(String(Symbol('desc')) === 'Symbol(desc)');
+    assert(typeof new String('a') === 'object');
+
+    assert(String.fromCharCode(72, 105) === 'Hi');
+    assert(String.fromCharCode() === '');
+    assert(String.fromCharCode(0xD83D, 0xDE00) === String.fromCodePoint(0x1F600));
+    assert(String.fromCodePoint(72, 105) === 'Hi');
+
+    let caught;
+    try {
+        String.fromCodePoint(0x110000);
+    } catch (e) {
+        caught = e;
+    }
+    assert(caught instanceof RangeError);
+
+    caught = undefined;
+    try {
+        String.fromCodePoint(1.5);
+    } catch (e) {
+        caught = e;
+    }
+    assert(caught instanceof RangeError);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    number_constructor,
+    r#"
+    assert(Number("0x10") === 16);
+    assert(Number("0o17") === 15);
+    assert(Number("0b101") === 5);
+    assert(Number("  3.14 ") === 3.14);
+    assert(Number("\n42\t") === 42);
+    assert(Number("") === 0);
+    assert(Number("   ") === 0);
+    assert(Number("-Infinity") === -Infinity);
+    assert(Number("1e3") === 1000);
+    assert(Number.isNaN(Number("abc")));
+    assert(Number.isNaN(Number("inf")));
+    assert(Number.isNaN(Number("0x")));
+    assert(Number.isNaN(Number("-0x10")));
+    assert(Number() === 0);
+    assert(+"0x10" === 16);
+
+    const boxed = new Number(5);
+    assert(typeof boxed === 'object');
+    assert(boxed.valueOf() === 5);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    for_of_destructuring,
+    r#"
+    const entries = [["a", 1], ["b", 2]];
+    let s = "";
+    for (const [k, v] of entries) {
+        s += k + v;
+    }
+    assert(s === "a1b2");
+
+    const objs = [{ x: 1, y: 10 }, { x: 2, y: 20 }];
+    let sum = 0;
+    for (const { x } of objs) {
+        sum += x;
+    }
+    assert(sum === 3);
+
+    let aliased = 0;
+    for (let { y: z } of objs) {
+        aliased += z;
+    }
+    assert(aliased === 30);
+
+    function inner() {
+        let keys = "";
+        for (const [first] in { foo: 1, bar: 2 }) {
+            keys += first;
+        }
+        return keys.length;
+    }
+    assert(inner() === 2);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    destructuring_assignment,
+    r#"
+    let a = 1, b = 2;
+    [a, b] = [b, a];
+    assert(a === 2 && b === 1);
+
+    let x;
+    const obj = { x: 5, y: { z: 7 } };
+    const result = ({ x } = obj);
+    assert(x === 5);
+    assert(result === obj);
+
+    const target = {};
+    let z, c;
+    ({ y: { z }, x: target.p } = obj);
+    assert(z === 7);
+    assert(target.p === 5);
+
+    [, c, [a]] = [1, 2, [3]];
+    assert(c === 2 && a === 3);
+
+    let n = 1;
+    [n] = ["s"];
+    assert(n + 1 === "s1");
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    assignment_evaluation_order,
+    r#"
+    let log = "";
+    const obj = {};
+    function target() { log += "t"; return obj; }
+    function key() { log += "k"; return "p"; }
+    function value() { log += "v"; return 1; }
+
+    target()[key()] = value();
+    assert(log === "tkv");
+    assert(obj.p === 1);
+
+    log = "";
+    target()[key()] += value();
+    assert(log === "tkv");
+    assert(obj.p === 2);
+
+    log = "";
+    target().q = value();
+    assert(log === "tv");
+
+    let a, b, c;
+    a = b = c = 5;
+    assert(a === 5 && b === 5 && c === 5);
+
+    const r = obj.x = obj.y = obj[key()] = 3;
+    assert(r === 3 && obj.x === 3 && obj.y === 3 && obj.p === 3);
+
+    // Compound assignments read the target before evaluating the right-hand side
+    let n = 1;
+    n += (n = 5, 1);
+    assert(n === 2);
+
+    obj.p = 1;
+    obj.p += (obj.p = 10, 2);
+    assert(obj.p === 3);
+
+    log = "";
+    obj.p = 10;
+    target()[key()] *= (obj.p = 3, value());
+    assert(log === "tkv" && obj.p === 10);
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn parse_and_validate() {
+    let mut vm = Vm::new(Default::default());
+    assert!(vm.parse_and_validate("let x = [1, 2]; x.push(3);").is_empty());
+
+    let source = "let a = 1;\nlet = 2;\nlet b = 3;\nlet c = ;\n";
+    let errors = vm.parse_and_validate(source);
+    assert_eq!(errors.len(), 2, "{errors:?}");
+
+    let lines = errors
+        .iter()
+        .map(|e| {
+            let span = e.span().unwrap();
+            source[..span.lo as usize].matches('\n').count() + 1
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(lines, [2, 4], "{errors:?}");
+}
+
+#[test]
+fn parser_error_recovery() {
+    let mut vm = Vm::new(Default::default());
+
+    let source = r#"
+        let x = ;
+        console.log(x);
+        function f() {
+            let = 5;
+            return 1;
+        }
+        if (true) { let y = ) }
+        f();
+    "#;
+    let errors = vm.parse_and_validate(source);
+    assert_eq!(errors.len(), 3, "{errors:?}");
+
+    let mut spans = errors.iter().map(|e| e.span().unwrap().lo).collect::<Vec<_>>();
+    spans.dedup();
+    assert_eq!(spans.len(), 3);
+}
+
+simple_test!(
+    private_brand_check,
+    r#"
+    class Point {
+        #x = 1;
+        #move() { this.#x += 1; }
+        getX() { this.#move(); return this.#x; }
+        static isPoint(o) { return #x in o; }
+        static hasMove(o) { return #move in o; }
+    }
+
+    const p = new Point();
+    assert(p.getX() === 2);
+    assert(Point.isPoint(p));
+    assert(Point.hasMove(p));
+    assert(!Point.isPoint({}));
+    assert(!Point.isPoint({ x: 1 }));
+    assert(p.x === undefined);
+
+    // Private names are not observable as properties
+    assert(p['#x'] === undefined);
+    assert(!('#x' in p));
+    assert(!Point.isPoint({ '#x': 1 }));
+    assert(Object.keys(p).length === 0);
+    assert(JSON.stringify(p) === "{}");
+    assert(!Point.isPoint({ ...p }));
+
+    // Every class has its own private names, even if they are spelled the same
+    class Other {
+        #x = 1;
+        static isOther(o) { return #x in o; }
+        static hasInner(o) {
+            class Inner {
+                #x = 2;
+                static isInner(o) { return #x in o; }
+            }
+            return Inner.isInner(o);
+        }
+    }
+    assert(!Other.isOther(p) && !Point.isPoint(new Other()));
+    assert(!Other.hasInner(new Other()));
+    function makeClass() {
+        return class {
+            #y = 1;
+            static has(o) { return #y in o; }
+        };
+    }
+    const A = makeClass();
+    const B = makeClass();
+    assert(A.has(new A()) && !A.has(new B()));
+
+    // Private accessors and static members
+    class Counter {
+        static #count = 0;
+        #value = 2;
+        get #double() { return this.#value * 2; }
+        static next() { return ++Counter.#count; }
+        double() { return this.#double; }
+    }
+    assert(Counter.next() === 1 && Counter.next() === 2);
+    assert(new Counter().double() === 4);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    symbol_constructor,
+    r#"
+    const a = Symbol("a");
+    const b = Symbol("a");
+    assert(a !== b);
+    assert(a === a);
+    assert(typeof a === 'symbol');
+
+    assert(a.description === "a");
+    assert(Symbol().description === undefined);
+    assert(Symbol("").description === "");
+
+    assert(a.toString() === "Symbol(a)");
+    assert(Symbol().toString() === "Symbol()");
+    assert(Object(a).description === "a");
+
+    const obj = {};
+    obj[a] = 1;
+    obj[b] = 2;
+    assert(obj[a] === 1 && obj[b] === 2);
+
+    let threw = false;
+    try {
+        new Symbol();
+    } catch (e) {
+        threw = e instanceof TypeError;
+    }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    inline_caches,
+    r#"
+    function getX(o) { return o.x; }
+
+    const objects = [];
+    for (let i = 0; i < 100; i++) objects.push({ x: i, y: i * 2 });
+    let sum = 0;
+    for (const o of objects) sum += getX(o);
+    assert(sum === 4950);
+
+    // different shapes going through the same access
+    assert(getX({ y: 1, x: 2 }) === 2);
+    assert(getX({ x: 3, z: 4 }) === 3);
+    assert(getX({}) === undefined);
+
+    // the shape of an object changes after its cache entry was populated
+    const o = { x: 1 };
+    assert(getX(o) === 1);
+    o.y = 2;
+    assert(getX(o) === 1);
+    o.x = 3;
+    assert(getX(o) === 3);
+    delete o.x;
+    assert(getX(o) === undefined);
+    o.x = 4;
+    assert(getX(o) === 4);
+
+    // properties found on the prototype
+    class Point {
+        constructor(v) { this.v = v; }
+        method() { return this.v; }
+        get x() { return this.v * 10; }
+    }
+    function callMethod(p) { return p.method(); }
+
+    const points = [new Point(1), new Point(2)];
+    assert(callMethod(points[0]) === 1 && callMethod(points[1]) === 2);
+    assert(getX(points[0]) === 10 && getX(points[1]) === 20);
+
+    Point.prototype.method = function() { return -this.v; };
+    assert(callMethod(points[0]) === -1);
+
+    points[1].method = function() { return 42; };
+    assert(callMethod(points[1]) === 42);
+    assert(callMethod(points[0]) === -1);
+
+    points[0].__proto__ = { method() { return "swapped"; } };
+    assert(callMethod(points[0]) === "swapped");
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn shape_transitions() {
+    let mut vm = Vm::new(Default::default());
+    let mut scope = vm.scope();
+    let array = scope
+        .eval(
+            r#"
+        function make(x, y) {
+            const o = {};
+            o.x = x;
+            o.y = y;
+            return o;
+        }
+        const grow = { x: 1 };
+        grow.y = 2;
+        grow.z = 3;
+        const shrink = { x: 1, y: 2 };
+        delete shrink.y;
+        [{ x: 1, y: 2 }, { x: 3, y: 4 }, make(5, 6), make(7, 8), { y: 9, x: 10 }, JSON.parse('{"x":1,"y":2}'), grow, shrink]
+    "#,
+            OptLevel::Basic,
+        )
+        .unwrap()
+        .root(&mut scope);
+
+    let objects = (0..8)
+        .map(|i| {
+            let key = scope.intern_usize(i);
+            let value = array.get_property(&mut scope, key.into()).root(&mut scope).unwrap();
+            value.downcast_ref::<NamedObject>().unwrap().shape()
+        })
+        .collect::<Vec<_>>();
+    let [literal, literal2, made, made2, reordered, parsed, grow, shrink] = &objects[..] else {
+        unreachable!()
+    };
+
+    // objects built with the same properties in the same order share a shape, regardless of how they were built
+    assert_eq!(literal.id(), literal2.id());
+    assert_eq!(literal.id(), made.id());
+    assert_eq!(made.id(), made2.id());
+    assert_eq!(literal.id(), parsed.id());
+    assert_ne!(literal.id(), reordered.id());
+
+    let x = PropertyKey::String(scope.intern("x").into());
+    let y = PropertyKey::String(scope.intern("y").into());
+    let z = PropertyKey::String(scope.intern("z").into());
+    assert_eq!(literal.keys(), [x.clone(), y.clone()]);
+    assert_eq!(literal.lookup(&x), Some(0));
+    assert_eq!(literal.lookup(&y), Some(1));
+    assert_eq!(reordered.lookup(&x), Some(1));
+
+    // adding a property transitions from the shape with one property less
+    assert_eq!(grow.lookup(&z), Some(2));
+    assert_eq!(grow.keys(), [x.clone(), y, z]);
+    let parent = grow.parent().unwrap();
+    assert_eq!(parent.id(), literal.id());
+    assert_eq!(parent.parent().unwrap().keys(), std::slice::from_ref(&x));
+    assert!(parent.parent().unwrap().parent().unwrap().is_empty());
+
+    // deleting a property leaves the transition tree
+    assert!(shrink.is_dictionary());
+    assert!(shrink.parent().is_none());
+    assert_eq!(shrink.keys(), [x]);
+    assert_ne!(shrink.id(), parent.parent().unwrap().id());
+}
+
+simple_test!(
+    object_literal_property_order,
+    r#"
+    const o = { a: 1, b: 2, a: 3, c: 4 };
+    assert(o.a === 3);
+    const keys = Object.keys(o);
+    assert(keys.length === 3 && keys[0] === "a" && keys[1] === "b" && keys[2] === "c");
+
+    const accessors = { get x() { return 1; }, x: 2 };
+    assert(accessors.x === 2);
+    const getter = { x: 2, get x() { return 1; } };
+    assert(getter.x === 1);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_iterator_observes_mutation,
+    r#"
+    const array = [1, 2];
+    const seen = [];
+    for (const x of array) {
+        seen.push(x);
+        if (x < 4) array.push(x + 2);
+    }
+    assert(seen.length === 5 && seen[4] === 5);
+
+    const shrinking = [1, 2, 3];
+    const it = shrinking[Symbol.iterator]();
+    assert(it.next().value === 1);
+    shrinking.pop();
+    shrinking.pop();
+    assert(it.next().done);
+
+    // an exhausted iterator stays exhausted
+    shrinking.push(4, 5);
+    assert(it.next().done);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    constructors_without_new,
+    r#"
+    const array = Array(1, 2, 3);
+    assert(array.length === 3 && array[0] === 1 && array[2] === 3);
+    assert(new Array(1, 2, 3).length === 3);
+    assert(Array(3).length === 3 && Array(3)[0] === undefined);
+    assert(Array("3").length === 1 && Array("3")[0] === "3");
+    assert(Array().length === 0);
+
+    let threw = false;
+    try {
+        Array(1.5);
+    } catch (e) {
+        threw = e instanceof RangeError;
+    }
+    assert(threw);
+
+    const boxed = Object("x");
+    assert(typeof boxed === "object" && boxed instanceof String && boxed.length === 1);
+    const empty = Object();
+    assert(typeof empty === "object" && Object.keys(empty).length === 0);
+    assert(Object() !== Object());
+    assert(typeof Object(null) === "object" && typeof Object(undefined) === "object");
+    const obj = {};
+    assert(Object(obj) === obj && new Object(obj) === obj);
+
+    for (const ctor of [Map, Set, Promise]) {
+        let threw = false;
+        try {
+            ctor(() => {});
+        } catch (e) {
+            threw = e instanceof TypeError;
+        }
+        assert(threw);
+    }
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    math_integer_functions,
+    r#"
+    assert(Math.imul(0xffffffff, 5) === -5);
+    assert(Math.imul(3, 4) === 12);
+    assert(Math.imul(0x7fffffff, 2) === -2);
+    assert(Math.imul(-5, 12) === -60);
+
+    assert(Math.fround(1.1) === 1.100000023841858);
+    assert(Math.fround(5.5) === 5.5);
+    assert(Math.fround(2 ** 128) === Infinity);
+    assert(isNaN(Math.fround(NaN)));
+
+    assert(Math.clz32(1) === 31);
+    assert(Math.clz32(-1) === 0);
+    assert(Math.clz32(0) === 32);
+    assert(Math.clz32(2 ** 32) === 32);
+    assert(Math.clz32(Infinity) === 32);
+    assert(Math.clz32(0.5) === 32);
+    const clz32 = Math.clz32;
+    assert(clz32(2 ** 32 + 1) === 31);
+
+    assert(Math.trunc(-4.7) === -4);
+    assert(Math.log2(8) === 3);
+    assert((Infinity | 0) === 0);
+    assert((2 ** 32 + 5 | 0) === 5);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    trailing_commas,
+    r#"
+    function add(a, b,) { return a + b; }
+    assert(add(1, 2,) === 3);
+    const mul = (a, b,) => a * b;
+    assert(mul(3, 4,) === 12);
+    const obj = { a: 1, b: 2, method(x,) { return x; }, };
+    assert(obj.b === 2 && obj.method(5,) === 5);
+    const array = [1, 2,];
+    assert(array.length === 2);
+    const holes = [,,];
+    assert(holes.length === 2 && holes[0] === undefined);
+    assert([1, , 3,].length === 3);
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn trailing_comma_errors() {
+    let mut vm = Vm::new(Default::default());
+    for source in [
+        "f(,);",
+        "function f(,) {}",
+        "let o = {,};",
+        "let x = (1,);",
+        "f(1 2);",
+        "let a = [1 2];",
+    ] {
+        assert!(!vm.parse_and_validate(source).is_empty(), "{source}");
+    }
+}
+
+#[test]
+fn return_outside_function() {
+    use crate::eval::EvalError;
+    use dash_middle::parser::error::Error;
+
+    let mut vm = Vm::new(Default::default());
+    let mut scope = vm.scope();
+
+    let value = scope
+        .eval("function f() { return 1; } f()", OptLevel::Basic)
+        .unwrap()
+        .root(&mut scope);
+    assert_eq!(value, Value::number(1.0));
+
+    let value = scope
+        .eval(
+            "function g(c) { if (c) { { return 2; } } return 3; } g(true)",
+            OptLevel::Basic,
+        )
+        .unwrap()
+        .root(&mut scope);
+    assert_eq!(value, Value::number(2.0));
+
+    for source in ["return 1;", "if (true) { return; }"] {
+        match scope.eval(source, OptLevel::Basic) {
+            Err(EvalError::Middle(errs)) => assert!(matches!(errs[..], [Error::ReturnOutsideFunction(_)]), "{source}"),
+            _ => panic!("expected a compile error for {source}"),
+        }
+    }
+}
+
+#[test]
+fn script_and_module_parse_mode() {
+    use crate::eval::EvalError;
+    use dash_middle::parser::error::Error;
+    use dash_parser::ParseMode;
+
+    let source = "import * as m from 'm';";
+
+    let mut vm = Vm::new(Default::default());
+    let mut scope = vm.scope();
+    match scope.eval_with_mode(source, OptLevel::Basic, ParseMode::Script) {
+        Err(EvalError::Middle(errs)) => assert!(matches!(errs[..], [Error::ModuleSyntaxInScript(_)])),
+        _ => panic!("expected import in a script to be a syntax error"),
+    }
+    match scope.eval_with_mode("export default 1;", OptLevel::Basic, ParseMode::Script) {
+        Err(EvalError::Middle(errs)) => assert!(matches!(errs[..], [Error::ModuleSyntaxInScript(_)])),
+        _ => panic!("expected export in a script to be a syntax error"),
+    }
+
+    // Dynamic imports are allowed in scripts
+    let mut vm = Vm::new(Default::default());
+    let mut scope = vm.scope();
+    let errors = scope.parse_and_validate("import('m')");
+    assert!(errors.is_empty(), "{errors:?}");
+    scope
+        .eval_with_mode("let x = 1; delete x;", OptLevel::Basic, ParseMode::Script)
+        .unwrap();
+
+    // The same source parses fine as a module, and fails at import time since no module `m` exists
+    let mut vm = Vm::new(Default::default());
+    let mut scope = vm.scope();
+    match scope.eval_with_mode(source, OptLevel::Basic, ParseMode::Module) {
+        Err(EvalError::Exception(_)) => {}
+        other => panic!("expected the module to parse, got {other:?}"),
+    }
+
+    // Modules are always strict
+    match scope.eval_with_mode("let x = 1; delete x;", OptLevel::Basic, ParseMode::Module) {
+        Err(EvalError::Middle(errs)) => assert!(matches!(errs[..], [Error::DeleteOfIdentifierInStrictMode(_)])),
+        _ => panic!("expected deleting an identifier in a module to be a syntax error"),
+    }
+
+    // Code evaluated by embedders is a sloppy mode script by default
+    let mut vm = Vm::new(Default::default());
+    let value = vm
+        .eval(
+            "undeclared = 5; delete q; function f(a, a) { return a; } f(1, 2) + undeclared",
+            OptLevel::Basic,
+        )
+        .unwrap()
+        .root(&mut vm.scope());
+    assert_eq!(value, Value::number(7.0));
+}
+
+simple_test!(
+    new_on_expressions,
+    r#"
+    class A { constructor() { this.x = 1; } }
+    class B { constructor() { this.x = 2; } }
+    const ns = { Widget: class { constructor(v) { this.v = v; } }, inner: { Gadget: A } };
+    function getClass() { return B; }
+    let cond = false;
+
+    assert(new (cond ? A : B)().x === 2);
+    assert(new (getClass())().x === 2);
+
+    const w = new ns.Widget(5);
+    assert(w.v === 5);
+    assert(w instanceof ns.Widget);
+    assert(w !== ns);
+    assert(new ns.inner.Gadget().x === 1);
+    assert(new ns["Widget"](7).v === 7);
+    assert(new ns.inner.Gadget instanceof A);
+
+    function Factory() { return B; }
+    assert(new new Factory()().x === 2);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    own_keys_order,
+    r#"
+    const o = {};
+    o.b = 1;
+    o[10] = 2;
+    o.a = 3;
+    o[2] = 4;
+    o["01"] = 5;
+    o[0] = 6;
+    o["+1"] = 7;
+    o[4294967295] = 8;
+
+    const expected = "0,2,10,b,a,01,+1,4294967295";
+    assert(Object.keys(o).join(",") === expected, Object.keys(o).join(","));
+
+    const forIn = [];
+    for (const k in o) forIn.push(k);
+    assert(forIn.join(",") === expected, forIn.join(","));
+
+    const json = JSON.stringify({ z: 1, 5: 2, y: 3, 1: 4 });
+    assert(json === '{"1":4,"5":2,"z":1,"y":3}', json);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    for_in_prototype_chain,
+    r#"
+    const proto = { inherited: 1, shadowed: 2 };
+    const o = Object.create(proto);
+    o.own = 3;
+    o.shadowed = 4;
+    Object.defineProperty(o, "hidden", { value: 5, enumerable: false });
+    o[Symbol("s")] = 6;
+
+    const keys = [];
+    for (const k in o) keys.push(k);
+    assert(keys.join(",") === "own,shadowed,inherited", keys.join(","));
+
+    // A non-enumerable own property still hides an enumerable inherited one
+    const p = Object.create({ masked: 1 });
+    Object.defineProperty(p, "masked", { value: 2, enumerable: false });
+    for (const k in p) assert(false, k);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    catch_binding_destructuring,
+    r#"
+    try {
+        throw new Error("boom");
+    } catch ({ message }) {
+        assert(message === "boom");
+    }
+
+    try {
+        throw [1, 2];
+    } catch ([first, second]) {
+        assert(first === 1);
+        assert(second === 2);
+    }
+
+    try {
+        throw 3;
+    } catch (e) {
+        assert(e === 3);
+    }
+
+    // Catch parameters are only visible in the catch block
+    function scoped() {
+        var message = "outer";
+        try {
+            throw { message: "inner", code: 1 };
+        } catch ({ message, code }) {
+            assert(message === "inner" && code === 1);
+        }
+        assert(message === "outer");
+        assert(typeof code === "undefined");
+        assert(typeof e === "undefined");
+    }
+    scoped();
+    assert(typeof first === "undefined" && typeof e === "undefined");
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    group_by,
+    r#"
+    const byParity = Object.groupBy([1, 2, 3, 4, 5], (x) => (x % 2 === 0 ? "even" : "odd"));
+    assert(Object.keys(byParity).join(",") === "odd,even", Object.keys(byParity).join(","));
+    assert(byParity.odd.join(",") === "1,3,5");
+    assert(byParity.even.join(",") === "2,4");
+
+    const indices = Object.groupBy(["a", "b", "c"], (_, i) => i);
+    assert(indices[0][0] === "a" && indices[2][0] === "c");
+
+    const odd = Symbol("odd");
+    const even = Symbol("even");
+    const map = Map.groupBy([1, 2, 3, 4, 5], (x) => (x % 2 === 0 ? even : odd));
+    assert(map.get(odd).join(",") === "1,3,5");
+    assert(map.get(even).join(",") === "2,4");
+
+    assert(Object.keys(Object.groupBy([], (x) => x)).length === 0);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    string_iterator_code_points,
+    r#"
+    const chars = [];
+    for (const c of "a😀b") chars.push(c);
+    assert(chars.length === 3, chars.length);
+    assert(chars[0] === "a");
+    assert(chars[1] === "😀");
+    assert(chars[2] === "b");
+
+    const iter = "x😀"[Symbol.iterator]();
+    assert(iter.next().value === "x");
+    assert(iter.next().value === "😀");
+    const last = iter.next();
+    assert(last.done && last.value === undefined);
+    assert(iter[Symbol.iterator]() === iter);
+
+    assert("a😀b".codePointAt(1) === 0x1F600);
+    assert("a😀b".codePointAt(0) === 97);
+    assert("a😀b".codePointAt(10) === undefined);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    base64,
+    r#"
+    assert(btoa("") === "");
+    assert(btoa("f") === "Zg==");
+    assert(btoa("fo") === "Zm8=");
+    assert(btoa("foo") === "Zm9v");
+    assert(btoa("hello, world\xff") === "aGVsbG8sIHdvcmxk/w==", btoa("hello, world\xff"));
+
+    const input = "The quick brown fox \x00\x80\xfe";
+    assert(atob(btoa(input)) === input);
+    assert(atob("Zm8") === "fo");
+    assert(atob(" Zm9v\n") === "foo");
+
+    for (const invalid of ["Zm9v!", "Z", "Zm9v="]) {
+        let threw = false;
+        try {
+            atob(invalid);
+        } catch (e) {
+            threw = true;
+        }
+        assert(threw, invalid);
+    }
+
+    let threw = false;
+    try {
+        btoa("😀");
+    } catch (e) {
+        threw = true;
+    }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    text_encoding,
+    r#"
+    const encoder = new TextEncoder();
+    assert(encoder.encoding === "utf-8");
+    const bytes = encoder.encode("aé😀");
+    const expected = [0x61, 0xc3, 0xa9, 0xf0, 0x9f, 0x98, 0x80];
+    for (let i = 0; i < expected.length; i++) {
+        assert(bytes[i] === expected[i], `byte ${i}: ${bytes[i]}`);
+    }
+    assert(bytes[expected.length] === undefined);
+    assert(bytes instanceof Uint8Array);
+
+    const decoder = new TextDecoder();
+    assert(decoder.decode(bytes) === "aé😀");
+    assert(decoder.decode(encoder.encode("")) === "");
+
+    const invalid = new Uint8Array(new ArrayBuffer(3));
+    invalid[0] = 0x61;
+    invalid[1] = 0xff;
+    invalid[2] = 0x62;
+    assert(decoder.decode(invalid) === "a�b");
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_push_pop_shift_unshift,
+    r#"
+    const a = [1, 2];
+    assert(a.push(3) === 3);
+    assert(a.push(4, 5) === 5);
+    assert(a.push() === 5);
+    assert(a.join(",") === "1,2,3,4,5");
+
+    assert(a.pop() === 5);
+    assert(a.length === 4);
+    assert([].pop() === undefined);
+
+    assert(a.shift() === 1);
+    assert(a.join(",") === "2,3,4" && a.length === 3);
+    assert([].shift() === undefined);
+
+    assert(a.unshift(0, 1) === 5);
+    assert(a.join(",") === "0,1,2,3,4", a.join(","));
+    assert(a.unshift() === 5);
+
+    const arrayLike = { length: 0 };
+    assert(Array.prototype.push.call(arrayLike, "x", "y") === 2);
+    assert(arrayLike.length === 2 && arrayLike[1] === "y");
+    assert(Array.prototype.shift.call(arrayLike) === "x");
+    assert(arrayLike.length === 1 && arrayLike[0] === "y" && !(1 in arrayLike));
+    assert(Array.prototype.pop.call(arrayLike) === "y");
+    assert(arrayLike.length === 0 && !(0 in arrayLike));
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    for_loop_multiple_declarations,
+    r#"
+    const pairs = [];
+    for (let i = 0, j = 10; i < j; i++, j--) {
+        pairs.push(i + ":" + j);
+    }
+    assert(pairs.join(",") === "0:10,1:9,2:8,3:7,4:6", pairs.join(","));
+
+    let a = 0, b = 0, iterations = 0;
+    for (var k = 0; k < 3; a += 1, b += 2) {
+        k++;
+        iterations++;
+    }
+    assert(iterations === 3 && a === 3 && b === 6);
+
+    let x, y;
+    for (x = 1, y = 5; x < y; x++, y--);
+    assert(x === 3 && y === 3);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    map_set_from_iterables,
+    r#"
+    const map = new Map([["a", 1], ["b", 2]]);
+    assert(map.size() === 2 && map.get("a") === 1 && map.get("b") === 2);
+
+    function* gen() {
+        yield 1;
+        yield 2;
+        yield 1;
+    }
+    const unique = new Set(gen());
+    assert(unique.size() === 2 && unique.has(1) && unique.has(2));
+    assert(new Set("abca").size() === 3);
+    assert(new Set().size() === 0 && new Map(undefined).size() === 0);
+
+    let threw = false;
+    try {
+        new Map([1, 2]);
+    } catch (e) {
+        threw = e instanceof TypeError;
+    }
+    assert(threw);
+
+    threw = false;
+    try {
+        new Set(1);
+    } catch (e) {
+        threw = e instanceof TypeError;
+    }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    math_max_min_edge_cases,
+    r#"
+    assert(Number.isNaN(Math.max(1, NaN, 3)));
+    assert(Number.isNaN(Math.min(NaN, 1)));
+    assert(Number.isNaN(Math.max(1, "x")));
+
+    let coerced = 0;
+    const counter = { valueOf() { coerced++; return 1; } };
+    assert(Number.isNaN(Math.max(NaN, counter)) && coerced === 1);
+
+    const isNegativeZero = x => x === 0 && 1 / x === -Infinity;
+    const isPositiveZero = x => x === 0 && 1 / x === Infinity;
+    assert(isPositiveZero(Math.max(-0, 0)));
+    assert(isPositiveZero(Math.max(0, -0)));
+    assert(isNegativeZero(Math.max(-0, -0)));
+    assert(isNegativeZero(Math.min(-0, 0)));
+    assert(isNegativeZero(Math.min(0, -0)));
+    assert(isPositiveZero(Math.min(0, 0)));
+
+    assert(Math.max() === -Infinity);
+    assert(Math.min() === Infinity);
+    assert(Math.max(1, 5, 3) === 5 && Math.min(4, -2, 7) === -2);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    generator_next_value,
+    r#"
+    function* gen() {
+        const x = yield 1;
+        const y = (yield x + 1) * 2;
+        return x + y;
+    }
+
+    const it = gen();
+    assert(it.next("ignored").value === 1);
+    assert(it.next(10).value === 11);
+    const last = it.next(5);
+    assert(last.done && last.value === 20);
+    assert(it.next(1).done);
+
+    function* counter() {
+        let total = 0;
+        while (true) {
+            const n = yield total;
+            if (n === undefined) return total;
+            total += n;
+        }
+    }
+    const c = counter();
+    c.next();
+    c.next(3);
+    assert(c.next(4).value === 7);
+    assert(c.next().value === 7);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_index_of_numbers,
+    r#"
+    const numbers = [];
+    for (let i = 0; i < 10000; i++) numbers.push(i % 2 === 0 ? i : "" + i);
+    // An array-like object is always searched through the generic path
+    const arrayLike = { length: numbers.length };
+    for (let i = 0; i < numbers.length; i++) arrayLike[i] = numbers[i];
+
+    const indexOf = Array.prototype.indexOf;
+    const includes = Array.prototype.includes;
+    for (const search of [0, 9998, 5000, -0, 1, 9999, "9999", 10000, NaN]) {
+        assert(numbers.indexOf(search) === indexOf.call(arrayLike, search));
+        assert(numbers.indexOf(search, 5000) === indexOf.call(arrayLike, search, 5000));
+        assert(numbers.indexOf(search, -10) === indexOf.call(arrayLike, search, -10));
+        assert(numbers.includes(search) === includes.call(arrayLike, search));
+    }
+    assert(numbers.indexOf(9998) === 9998 && numbers.indexOf(9999) === -1);
+
+    // Getters are still invoked in order
+    let calls = 0;
+    const withGetter = [1, 2, 3];
+    Object.defineProperty(withGetter, 1, { get() { calls++; return 7; } });
+    assert(withGetter.indexOf(7) === 1 && calls === 1);
+    assert(withGetter.indexOf(1) === 0 && calls === 1);
+    assert(withGetter.includes(3) && calls === 2);
+
+    const holey = [1, , 3];
+    assert(holey.indexOf(3) === 2 && holey.includes(1));
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_includes_same_value_zero,
+    r#"
+    const values = [1, NaN, -0, "x"];
+    const arrayLike = { length: 4, 0: 1, 1: NaN, 2: -0, 3: "x" };
+    const indexOf = Array.prototype.indexOf;
+    const includes = Array.prototype.includes;
+
+    // includes uses SameValueZero, indexOf uses strict equality
+    assert(values.includes(NaN), 1);
+    assert(values.indexOf(NaN) === -1, 2);
+    assert(includes.call(arrayLike, NaN), 3);
+    assert(indexOf.call(arrayLike, NaN) === -1, 4);
+
+    // -0 and +0 are equal in both
+    for (const zero of [0, -0]) {
+        assert(values.includes(zero), 5);
+        assert(values.indexOf(zero) === 2, 6);
+        assert(includes.call(arrayLike, zero), 7);
+        assert(indexOf.call(arrayLike, zero) === 2, 8);
+    }
+    assert([0].includes(-0) && [-0].indexOf(0) === 0, 9);
+
+    // fromIndex
+    assert(!values.includes(NaN, 2), 10);
+    assert(values.includes(NaN, -3), 11);
+    assert(values.includes(1, -Infinity) && values.indexOf(1, -Infinity) === 0, 12);
+    assert(!values.includes("x", Infinity) && values.indexOf("x", Infinity) === -1, 13);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    structured_clone,
+    r#"
+    const shared = { n: 1 };
+    const bytes = new Uint8Array(new ArrayBuffer(3));
+    bytes[0] = 1;
+    bytes[2] = 3;
+    const original = {
+        list: [1, "two", { three: 3 }],
+        map: new Map([["key", shared]]),
+        unique: new Set([shared]),
+        bytes,
+        shared,
+    };
+    original.self = original;
+
+    const copy = structuredClone(original);
+    assert(copy !== original && copy.self === copy);
+    assert(copy.list !== original.list && copy.list.length === 3 && copy.list[2].three === 3);
+    assert(copy.map instanceof Map && copy.map.get("key") === copy.shared);
+    assert(copy.unique.has(copy.shared) && !copy.unique.has(shared));
+    assert(copy.bytes instanceof Uint8Array && copy.bytes[2] === 3);
+
+    copy.shared.n = 2;
+    copy.list.push(4);
+    copy.bytes[0] = 100;
+    copy.map.set("other", 1);
+    assert(shared.n === 1 && original.list.length === 3 && original.bytes[0] === 1);
+    assert(!original.map.has("other"));
+
+    assert(structuredClone(5) === 5 && structuredClone("s") === "s" && structuredClone(null) === null);
+
+    const date = new Date(1000);
+    const dateCopy = structuredClone(date);
+    assert(dateCopy instanceof Date && dateCopy !== date && dateCopy.getTime() === 1000);
+
+    const args = (function () { return structuredClone(arguments); })(1, "two");
+    assert(args[0] === 1 && args[1] === "two" && args.length === 2);
+
+    let threw = false;
+    try {
+        structuredClone({ f: function () {} });
+    } catch (e) {
+        threw = true;
+    }
+    assert(threw);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    class_extends,
+    r#"
+    class A {
+        constructor(x) { this.x = x; }
+        greet() { return "A" + this.x; }
+        static create() { return "A"; }
+    }
+
+    // Derived classes without a constructor call the superclass constructor with all arguments
+    class B extends A {}
+    assert(new B(3).x === 3, 1);
+    assert(new B(3) instanceof A, 2);
+
+    class C extends B {
+        constructor(x) { super(x * 2); this.y = 1; }
+        greet() { return "C" + super.greet(); }
+        static create() { return "C" + super.create(); }
+    }
+    const c = new C(3);
+    assert(c.x === 6 && c.y === 1, 3);
+    assert(c instanceof C && c instanceof B && c instanceof A, 4);
+    assert(c.greet() === "CA6", 5);
+    assert(C.create() === "CA", 6);
+
+    // Arrow functions use the `super` of the enclosing method
+    class D extends A {
+        greet() { return (() => super.greet())(); }
+    }
+    assert(new D(1).greet() === "A1", 7);
+
+    let err;
+    try { class E extends 5 {} } catch (e) { err = e; }
+    assert(err instanceof TypeError, 8);
+
+    err = undefined;
+    try {
+        new (class F extends A { constructor() { this.x = 1; super(); } })();
+    } catch (e) { err = e; }
+    assert(err instanceof ReferenceError, 9);
+
+    // The implicit constructor's parameter does not shadow outer bindings in field initializers
+    const args = "outer";
+    class G extends A { y = args; }
+    assert(new G(1).y === "outer", 10);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    derived_class_this,
+    r#"
+    class A { constructor(x) { this.x = x; } }
+    class B extends A {}
+    const b = new B(5);
+    assert(JSON.stringify(b) === '{"x":5}', 1);
+    assert(Object.getPrototypeOf(b) === B.prototype, 2);
+
+    // Field initializers of a derived class run once super() returns
+    class C extends B {
+        y = this.x + 1;
+        constructor(x) { super(x * 2); this.z = this.y; }
+    }
+    const c = new C(3);
+    assert(JSON.stringify(c) === '{"x":6,"y":7,"z":7}', 3);
+    assert(Object.getPrototypeOf(c) === C.prototype, 4);
+
+    // Builtin constructors create their own instance, which inherits from new.target.prototype
+    class E extends Error {
+        constructor(message) { super(message); this.extra = 1; }
+    }
+    const e = new E("boom");
+    assert(e instanceof E && e instanceof Error, 5);
+    assert(e.message === "boom" && e.extra === 1, 6);
+
+    let err;
+    try {
+        new (class extends A { constructor() { super(1); super(2); } })();
+    } catch (x) { err = x; }
+    assert(err instanceof ReferenceError, 7);
+
+    err = undefined;
+    try {
+        new (class extends A { constructor() { if (true) return; super(1); } })();
+    } catch (x) { err = x; }
+    assert(err instanceof ReferenceError, 8);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    object_destructuring_rest,
+    r#"
+    const { a, b, ...rest } = { a: 1, b: 2, c: 3, d: 4 };
+    assert(a === 1 && b === 2, 1);
+    assert(JSON.stringify(rest) === '{"c":3,"d":4}', 2);
+
+    // Renamed fields are excluded by their key, not their binding name
+    let { a: x, ...others } = { a: 1, x: 2 };
+    assert(x === 1 && JSON.stringify(others) === '{"x":2}', 3);
+    others = 5;
+    assert(others === 5, 4);
+
+    // Only own enumerable properties are copied
+    const source = Object.defineProperty(Object.create({ inherited: 1 }), "hidden", { value: 1, enumerable: false });
+    source.own = 2;
+    const { ...copy } = source;
+    assert(JSON.stringify(copy) === '{"own":2}', 5);
+    assert(Object.getPrototypeOf(copy) === Object.prototype, 6);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    reflect,
+    r#"
+    class Point {
+        constructor(x, y) {
+            this.x = x;
+            this.y = y;
+        }
+    }
+    class Other {}
+
+    const p = Reflect.construct(Point, [1, 2]);
+    assert(p instanceof Point && p.x === 1 && p.y === 2, 1);
+    const q = Reflect.construct(Point, { length: 1, 0: 3 }, Other);
+    assert(q instanceof Other && q.x === 3, 2);
+
+    const o = {};
+    assert(Reflect.defineProperty(o, "fixed", { value: 1 }), 3);
+    assert(!Reflect.defineProperty(o, "fixed", { value: 2 }), 4);
+    assert(!Reflect.defineProperty(o, "fixed", { value: 1, configurable: true }), 5);
+    assert(Reflect.defineProperty(o, "fixed", { value: 1 }), 6);
+    assert(o.fixed === 1, 7);
+    assert(Reflect.defineProperty(o, "free", { value: 1, configurable: true }), 8);
+    assert(Reflect.defineProperty(o, "free", { value: 2 }) && o.free === 2, 9);
+
+    assert(!Reflect.deleteProperty(o, "fixed") && o.fixed === 1, 10);
+    assert(Reflect.deleteProperty({ a: 1 }, "a"), 11);
+    assert(Reflect.deleteProperty({}, "missing"), 12);
+    const d = { a: 1 };
+    Reflect.deleteProperty(d, "a");
+    assert(!("a" in d), 13);
+
+    const proto = {};
+    assert(Reflect.getPrototypeOf(p) === Point.prototype, 14);
+    assert(Reflect.setPrototypeOf(o, proto) && Reflect.getPrototypeOf(o) === proto, 15);
+    assert(!Reflect.setPrototypeOf(proto, o), 16);
+
+    let threw = false;
+    try {
+        Reflect.getPrototypeOf(1);
+    } catch (e) {
+        threw = e instanceof TypeError;
+    }
+    assert(threw, 17);
+
+    for (const target of [() => {}, async function () {}, function* () {}]) {
+        threw = false;
+        try {
+            Reflect.construct(target, []);
+        } catch (e) {
+            threw = e instanceof TypeError;
+        }
+        assert(threw, 18);
+    }
+
+    // Redefining a non-writable value uses SameValue, which tells +0 and -0 apart
+    const zero = {};
+    Reflect.defineProperty(zero, "z", { value: 0 });
+    assert(!Reflect.defineProperty(zero, "z", { value: -0 }), 19);
+    Reflect.defineProperty(zero, "nan", { value: NaN });
+    assert(Reflect.defineProperty(zero, "nan", { value: NaN }), 20);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_destructuring_rest,
+    r#"
+    const [first, ...tail] = [1, 2, 3, 4];
+    assert(first === 1 && JSON.stringify(tail) === "[2,3,4]", 1);
+
+    // Holes skip elements
+    const [, , third] = [1, 2, 3];
+    assert(third === 3, 2);
+    const [a, , c, ...others] = [1, 2, 3, 4, 5];
+    assert(a === 1 && c === 3 && JSON.stringify(others) === "[4,5]", 3);
+
+    // The rest element is empty if there are no elements left
+    const [x, ...empty] = [1];
+    assert(x === 1 && Array.isArray(empty) && empty.length === 0, 4);
+    const [y, z, ...none] = [1];
+    assert(y === 1 && z === undefined && none.length === 0, 5);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    syntax_error_message,
+    r#"
+    function syntaxError(source) {
+        try {
+            eval(source);
+        } catch (e) {
+            assert(e instanceof SyntaxError, "not a SyntaxError: " + e);
+            return e.message;
+        }
+        throw "no error";
+    }
+
+    const unexpected = syntaxError("let a = 1;\nlet b = 2;\nlet c = (a + b));");
+    assert(unexpected.includes("Unexpected token ')'"), unexpected);
+    assert(unexpected.includes("at line 3"), unexpected);
+
+    const expected = syntaxError("if (true {}");
+    assert(expected.includes("'{'") && expected.includes("at line 1") && expected.includes("expected `)`"), expected);
+
+    assert(syntaxError("let x = [1, 2").includes("Unexpected end of input"), 1);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    computed_compound_assignment,
+    r#"
+    const arr = [1, 2, 3];
+    arr[0] += 10;
+    arr[1] *= 2;
+    arr[2] <<= 2;
+    assert(JSON.stringify(arr) === "[11,4,12]", 1);
+
+    const obj = { a: 1, b: 3, c: 5 };
+    const key = "a";
+    obj[key] += 1;
+    obj["b"] *= 3;
+    obj["c"] <<= 1;
+    assert(JSON.stringify(obj) === '{"a":2,"b":9,"c":10}', 2);
+
+    // The key is only evaluated once
+    let i = 0;
+    arr[i++] += 1;
+    assert(i === 1 && arr[0] === 12, 3);
+    let calls = 0;
+    obj[(calls++, "a")] *= 2;
+    assert(calls === 1 && obj.a === 4, 4);
+
+    // The value of the expression is the new value
+    assert((arr[1] += 1) === 5, 5);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    global_update_expression,
+    r#"
+    x = 5;
+    assert(x++ === 5, 1);
+    assert(x === 6, 2);
+    assert(++x === 7, 3);
+    assert(x-- === 7, 4);
+    assert(--x === 5, 5);
+    for (i = 0; i < 3; i++) {}
+    assert(i === 3 && globalThis.i === 3, 6);
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn sloppy_and_strict_this() {
+    use dash_parser::ParseMode;
+
+    let source = r#"
+    function sloppy() { return this; }
+    function strict() { "use strict"; return this; }
+    function strictOuter() { "use strict"; return function() { return this; }; }
+    const sloppyArrow = () => this;
+    const obj = { sloppy, strict };
+
+    sloppy() === globalThis
+        && strict() === undefined
+        && strictOuter()() === undefined
+        && sloppyArrow() === globalThis
+        && obj.sloppy() === obj
+        && obj.strict() === obj
+        && sloppy.call(null) === globalThis
+        && strict.call(null) === null
+        && new sloppy() !== globalThis
+        && new strict() instanceof strict
+    "#;
+
+    let mut vm = Vm::new(Default::default());
+    let scope = &mut vm.scope();
+    let value = scope
+        .eval_with_mode(source, OptLevel::Basic, ParseMode::Script)
+        .unwrap()
+        .root(scope);
+    assert_eq!(value, Value::Boolean(true));
+
+    // Scripts with a "use strict" directive and modules are strict
+    let value = scope
+        .eval_with_mode(
+            r#""use strict"; function f() { return this; } f() === undefined"#,
+            OptLevel::Basic,
+            ParseMode::Script,
+        )
+        .unwrap()
+        .root(scope);
+    assert_eq!(value, Value::Boolean(true));
+
+    let value = scope
+        .eval_with_mode(
+            "function f() { return this; } f() === undefined",
+            OptLevel::Basic,
+            ParseMode::Module,
+        )
+        .unwrap()
+        .root(scope);
+    assert_eq!(value, Value::Boolean(true));
+}
+
+#[test]
+fn strict_mode_semantics() {
+    use crate::eval::EvalError;
+    use dash_middle::parser::error::Error;
+    use dash_middle::sourcemap::Span;
+    use dash_parser::ParseMode;
+
+    let source = r#"
+    function sloppy() { sloppyGlobal = 1; return sloppyGlobal; }
+    function strict() {
+        "use strict";
+        try { strictGlobal = 1; } catch (e) { return e instanceof ReferenceError; }
+        return false;
+    }
+    function strictExisting() { "use strict"; sloppyGlobal = 2; return sloppyGlobal; }
+    function duplicateParams(a, a) { return a; }
+
+    sloppy() === 1
+        && strict()
+        && typeof strictGlobal === "undefined"
+        && strictExisting() === 2
+        && duplicateParams(1, 2) === 2
+    "#;
+
+    let mut vm = Vm::new(Default::default());
+    let scope = &mut vm.scope();
+    let value = scope
+        .eval_with_mode(source, OptLevel::Basic, ParseMode::Script)
+        .unwrap()
+        .root(scope);
+    assert_eq!(value, Value::Boolean(true));
+
+    for source in [
+        r#""use strict"; var x = 1; delete x;"#,
+        r#"function f() { "use strict"; delete f; }"#,
+    ] {
+        match scope.eval_with_mode(source, OptLevel::Basic, ParseMode::Script) {
+            Err(EvalError::Middle(errs)) => {
+                assert!(
+                    matches!(errs[..], [Error::DeleteOfIdentifierInStrictMode(_)]),
+                    "{source}"
+                )
+            }
+            _ => panic!("expected a compile error for {source}"),
+        }
+    }
+
+    match scope.eval_with_mode(
+        r#"function f(a, a) { "use strict"; }"#,
+        OptLevel::Basic,
+        ParseMode::Script,
+    ) {
+        // The error points at the second `a`
+        Err(EvalError::Middle(errs)) => assert!(matches!(
+            errs[..],
+            [Error::DuplicateParameterInStrictMode(Span { lo: 14, hi: 15 })]
+        )),
+        _ => panic!("expected duplicate parameters in strict mode to be a syntax error"),
+    }
+}
+
+#[test]
+fn compiled_scripts_are_sloppy_by_default() {
+    use dash_compiler::FunctionCompiler;
+    use dash_middle::interner::StringInterner;
+    use dash_parser::ParseMode;
+
+    let source = "(function() { return this === globalThis; })()";
+    let mut interner = StringInterner::new();
+    assert!(
+        !FunctionCompiler::compile_str(&mut interner, source, OptLevel::Basic)
+            .unwrap()
+            .is_strict
+    );
+    assert!(
+        FunctionCompiler::compile_str_with_mode(&mut interner, source, OptLevel::Basic, ParseMode::Module)
+            .unwrap()
+            .is_strict
+    );
+
+    let mut vm = Vm::new(Default::default());
+    let value = vm.eval(source, OptLevel::Basic).unwrap().root(&mut vm.scope());
+    assert_eq!(value, Value::Boolean(true));
+}
+
+simple_test!(
+    continue_in_switch,
+    r#"
+    // `continue` inside of a switch targets the enclosing loop, `break` targets the switch
+    let out = "";
+    for (let i = 0; i < 5; i++) {
+        switch (i % 3) {
+            case 0:
+                continue;
+            case 1:
+                out += "a";
+                break;
+            default:
+                out += "b";
+        }
+        out += i;
+    }
+    assert(out === "a1b2a4", 1);
+
+    let j = 0;
+    out = "";
+    while (j < 4) {
+        j++;
+        switch (j) {
+            case 2:
+                continue;
+        }
+        out += j;
+    }
+    assert(out === "134", 2);
+
+    out = "";
+    for (const x of [1, 2, 3]) {
+        switch (x) {
+            case 2:
+                switch (x) {
+                    default:
+                        continue;
+                }
+        }
+        out += x;
+    }
+    assert(out === "13", 3);
+
+    j = 0;
+    out = "";
+    do {
+        j++;
+        if (j === 2) continue;
+        out += j;
+    } while (j < 4);
+    assert(out === "134", 4);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    labeled_statements,
+    r#"
+    let out = "";
+    outer: for (let i = 0; i < 3; i++) {
+        for (let j = 0; j < 3; j++) {
+            if (j === 1) continue outer;
+            if (i === 2) break outer;
+            out += i + "" + j;
+        }
+    }
+    assert(out === "0010", 1);
+
+    out = "";
+    block: {
+        out += "a";
+        if (out.length === 1) break block;
+        out += "b";
+    }
+    assert(out === "a", 2);
+
+    out = "";
+    sw: switch (1) {
+        case 1:
+            for (;;) {
+                break sw;
+            }
+            out += "c";
+    }
+    assert(out === "", 3);
+
+    out = "";
+    let k = 0;
+    w: while (k < 5) {
+        k++;
+        do {
+            if (k % 2) continue w;
+        } while (false);
+        out += k;
+    }
+    assert(out === "24", 4);
+
+    out = "";
+    a: b: for (let i = 0; i < 3; i++) {
+        for (;;) {
+            if (i === 1) continue a;
+            if (i === 2) break b;
+            out += i;
+            continue b;
+        }
+    }
+    assert(out === "0", 5);
+
+    out = "";
+    c: d: {
+        out += "x";
+        break c;
+    }
+    assert(out === "x", 6);
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn invalid_labels() {
+    use crate::eval::EvalError;
+    use dash_middle::parser::error::Error;
+    use dash_parser::ParseMode;
+
+    let mut vm = Vm::new(Default::default());
+    let scope = &mut vm.scope();
+
+    for source in [
+        "a: { a: { } }",
+        "a: while (true) { a: for (;;) { } }",
+        "a: a: for (;;) { }",
+    ] {
+        match scope.eval_with_mode(source, OptLevel::Basic, ParseMode::Script) {
+            Err(EvalError::Middle(errs)) => assert!(matches!(errs[..], [Error::DuplicateLabel(_)]), "{source}"),
+            _ => panic!("expected a compile error for {source}"),
+        }
+    }
+
+    for source in ["while (true) { break a; }", "a: { } for (;;) { continue a; }"] {
+        match scope.eval_with_mode(source, OptLevel::Basic, ParseMode::Script) {
+            Err(EvalError::Middle(errs)) => assert!(matches!(errs[..], [Error::UndefinedLabel(_)]), "{source}"),
+            _ => panic!("expected a compile error for {source}"),
+        }
+    }
+}
+
+simple_test!(
+    nested_conditional_expr,
+    r#"
+    function grade(n) {
+        return n > 90 ? "A" : n > 80 ? "B" : n > 70 ? "C" : n > 60 ? "D" : "F";
+    }
+    assert(grade(95) + grade(85) + grade(75) + grade(65) + grade(10) === "ABCDF", 1);
+
+    // Nested in the `then` branch and in the condition
+    assert((true ? false ? 1 : 2 : 3) === 2, 2);
+    assert(((false ? 1 : 0) ? "a" : "b") === "b", 3);
+
+    // Deeply chained: n === 0 ? 0 : n === 1 ? 1 : ... : -1
+    let source = "";
+    for (let i = 0; i < 100; i++) {
+        source += "n === " + i + " ? " + i + " : ";
+    }
+    source += "-1";
+    const chain = eval("(n) => " + source);
+    assert(chain(0) === 0 && chain(57) === 57 && chain(99) === 99 && chain(100) === -1, 4);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    nested_branch_labels,
+    r#"
+    // Conditionals and logical expressions nested inside each other and in `if` conditions
+    // each have their own local labels
+    function f(a, b, c) {
+        let r = "";
+        if (a ? b : c) { r += "1"; } else if (a && (b ? c : !c)) { r += "2"; } else { r += "3"; }
+        r += (a && (b || c)) ? "x" : "y";
+        r += (a || b && c) ?? "z";
+        if ((a ? b && c : b || c) || (a ?? b)) r += "!";
+        return r;
+    }
+
+    const out = [];
+    for (const a of [true, false])
+        for (const b of [true, false])
+            for (const c of [true, false])
+                out.push(f(a, b, c));
+
+    const expected = "1xtrue!,1xtrue!,3xtrue!,2ytrue!,1ytrue!,3yfalse!,1yfalse!,3yfalse";
+    assert(out.join(",") === expected, out.join(","));
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_to_string,
+    r#"
+    assert([1, 2, 3].toString() === "1,2,3", 1);
+    assert(String([1, [2, 3]]) === "1,2,3", 2);
+    assert([1, null, undefined, 2].toString() === "1,,,2", 3);
+    assert([].toString() === "" && `${[4, [5]]}` === "4,5", 4);
+    assert([1, 2].join() === "1,2" && [null, undefined].join("-") === "-", 5);
+
+    // toString uses `join` and falls back to Object.prototype.toString if it is not callable
+    assert(Array.prototype.toString.call({ join() { return "joined"; } }) === "joined", 6);
+    assert(Array.prototype.toString.call({ join: 1 }) === "[object Object]", 7);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_literal_spread,
+    r#"
+    const a = [...[1, 2], 3];
+    assert(a.length === 3 && a.join() === "1,2,3", 1);
+
+    const xs = [5, 6];
+    const b = [1, ...xs, 2, ...xs];
+    assert(b.join() === "1,5,6,2,5,6", 2);
+
+    const c = [..."abc", ...""];
+    assert(c.length === 3 && c.join() === "a,b,c", 3);
+
+    function* gen() {
+        yield 1;
+        yield 2;
+    }
+    const d = [0, ...gen(), 3];
+    assert(d.length === 4 && d.join() === "0,1,2,3", 4);
+
+    const e = (function () { return [...arguments, 3]; })(1, 2);
+    assert(e.join() === "1,2,3", 5);
+
+    for (const value of [{ length: 2 }, 5, undefined]) {
+        let threw = false;
+        try {
+            [...value];
+        } catch (e) {
+            threw = e instanceof TypeError;
+        }
+        assert(threw, 6);
+    }
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    object_spread,
+    r#"
+    // Later keys override earlier ones
+    assert(JSON.stringify({ ...{ a: 1 }, a: 2 }) === '{"a":2}', 1);
+    assert(JSON.stringify({ a: 1, ...{ a: 2 } }) === '{"a":2}', 2);
+    assert(JSON.stringify({ ...{ a: 1, b: 2 }, x: 1 }) === '{"a":1,"b":2,"x":1}', 3);
+
+    // Spreading null and undefined is a no-op
+    assert(JSON.stringify({ ...null, ...undefined, b: 1 }) === '{"b":1}', 4);
+
+    // Only own enumerable properties are copied
+    const hidden = Object.defineProperty({ v: 1 }, "h", { value: 1, enumerable: false });
+    assert(JSON.stringify({ ...hidden }) === '{"v":1}', 5);
+    assert(JSON.stringify({ ...[1, 2] }) === '{"0":1,"1":2}', 6);
+    const child = Object.create({ inherited: 1 });
+    child.own = 2;
+    assert(JSON.stringify({ ...child }) === '{"own":2}', 7);
+
+    // Getters are evaluated once and copied as data properties
+    let calls = 0;
+    const copy = { ...{ get g() { return ++calls; } } };
+    assert(copy.g === 1 && copy.g === 1 && calls === 1, 8);
+    assert(Object.getOwnPropertyDescriptor(copy, "g").value === 1, 9);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    switch_evaluation_order,
+    r#"
+    let log = [];
+    function test(v) {
+        log.push("case" + v);
+        return v;
+    }
+    function discriminant() {
+        log.push("discriminant");
+        return 2;
+    }
+
+    switch (discriminant()) {
+        case test(1):
+            log.push("body1");
+        case test(2):
+            log.push("body2");
+        case test(3):
+            log.push("body3");
+            break;
+        case test(4):
+            log.push("body4");
+    }
+    assert(log.join() === "discriminant,case1,case2,body2,body3", 1);
+
+    log = [];
+    switch (discriminant()) {
+        case test(5):
+            break;
+        default:
+            log.push("default");
+    }
+    assert(log.join() === "discriminant,case5,default", 2);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    optional_chaining,
+    r#"
+    const o = {
+        a: { b: { c: 5 } },
+        n: null,
+        f() {
+            return this.a.b.c;
+        },
+    };
+    assert(({}).a?.b === undefined, 1);
+    assert(o.a?.b.c === 5, 2);
+    assert(o.x?.b.c.d.e === undefined, 3);
+    assert(o.n?.[0] === undefined, 4);
+    assert(o.a?.["b"]?.c === 5, 5);
+    assert(o.a.b?.c?.toString() === "5", 6);
+    assert((o.x?.b)?.c === undefined, 7);
+
+    // Optional calls
+    assert(o.f?.() === 5, 8);
+    assert(o.g?.() === undefined, 9);
+    assert(o?.f() === 5, 10);
+    assert(o.x?.y() === undefined, 11);
+    const fn = null;
+    assert(fn?.(1) === undefined, 12);
+    assert((() => 3)?.() === 3, 13);
+
+    // The rest of the chain is not evaluated if it short-circuits
+    let calls = 0;
+    const inc = () => ++calls;
+    o.x?.[inc()];
+    o.n?.(inc());
+    o.a?.[inc()];
+    assert(calls === 1, 14);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    optional_chaining_delete,
+    r#"
+    const nullish = null;
+    let missing;
+    assert(delete nullish?.b === true, 1);
+    assert(delete missing?.b.c === true, 2);
+
+    const obj = { b: 1, c: { d: 2 } };
+    assert(delete obj?.b === true && !("b" in obj), 3);
+    assert(delete obj.c?.d === true && !("d" in obj.c), 4);
+    assert(delete obj?.["c"] === true && !("c" in obj), 5);
+    assert(delete obj.x?.y === true, 6);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    template_literals,
+    r#"
+    const name = "world";
+    const x = 3;
+    assert(`a${1 + 1}b` === "a2b", 1);
+    assert(`hello ${name}!` === "hello world!", 2);
+    assert(`` === "" && `plain` === "plain", 3);
+    assert(`${x}${x}` === "33" && typeof `${x}` === "string", 4);
+    assert(`a\nb\t\`\${}` === "a\nb\t`${}", 5);
+
+    // Nested templates
+    assert(`${`${x}`}` === "3", 6);
+    assert(`n${`i${"j"}k`}m` === "nijkm", 7);
+
+    // Embedded expressions are converted using ToString, not ToPrimitive
+    const obj = {
+        valueOf() { return 1; },
+        toString() { return "s"; },
+    };
+    assert(`${obj}` === "s" && "" + obj === "1", 8);
+    assert(`${null}-${undefined}-${[1, 2]}` === "null-undefined-1,2", 9);
+
+    let threw = false;
+    try {
+        `${Symbol()}`;
+    } catch (e) {
+        threw = e instanceof TypeError;
+    }
+    assert(threw, 10);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_length_setter,
+    r#"
+    const a = [1, 2, 3, 4];
+    a.length = 2;
+    assert(a.length === 2 && a.join() === "1,2" && a[2] === undefined, 1);
+
+    const b = [1];
+    b.length = 3;
+    assert(b.length === 3 && b[0] === 1 && b[2] === undefined && !(2 in b), 2);
+
+    for (const invalid of [-1, 1.5, NaN, Infinity, 2 ** 32]) {
+        let threw = false;
+        try {
+            b.length = invalid;
+        } catch (e) {
+            threw = e instanceof RangeError;
+        }
+        assert(threw && b.length === 3, 3);
+    }
+
+    b.length = "1";
+    assert(b.length === 1, 4);
+
+    // The conversion can access the array that is being resized
+    b.length = { valueOf() { return b.length + b[0] + 1; } };
+    assert(b.length === 3 && b[0] === 1, 5);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    tagged_templates,
+    r#"
+    function raw(strings) {
+        return strings.raw[0];
+    }
+    assert(raw`\n${0}` === "\\n", 1);
+
+    function tag(strings, ...values) {
+        return strings.join("|") + "/" + values.join(",") + "/" + strings.length;
+    }
+    const x = 1;
+    assert(tag`a${x}b${x + 1}c` === "a|b|c/1,2/3", 2);
+    assert(tag`` === "//1", 3);
+    assert(tag`${x}` === "|/1/2", 4);
+
+    // The strings array is created once per call site
+    function id(strings) {
+        return strings;
+    }
+    function site() {
+        return id`same`;
+    }
+    assert(site() === site(), 5);
+    assert(id`same` !== id`same`, 6);
+    const objects = [];
+    for (let i = 0; i < 2; i++) {
+        objects.push(id`loop${i}`);
+    }
+    assert(objects[0] === objects[1], 7);
+
+    // Member expressions as tags are called with the correct `this`
+    const o = {
+        name: "o",
+        method(strings) {
+            return this.name + strings[0];
+        },
+    };
+    assert(o.method`x` === "ox", 8);
+
+    // The strings array and its raw strings are frozen
+    const strings = id`a${x}b`;
+    strings[0] = "changed";
+    strings.push("c");
+    strings.raw[0] = "changed";
+    strings.raw = [];
+    assert(strings[0] === "a" && strings.length === 2, 9);
+    assert(strings.raw[0] === "a" && strings.raw.length === 2, 10);
+    assert(Object.keys(strings).join() === "0,1", 11);
+    assert(!Object.getOwnPropertyDescriptor(strings, "0").writable, 12);
+    assert(!Object.getOwnPropertyDescriptor(strings, "raw").writable, 13);
+    assert(!Object.getOwnPropertyDescriptor(strings.raw, "length").writable, 14);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    arrow_functions,
+    r#"
+    const add = (a, b) => a + b;
+    const double = x => x * 2;
+    const block = () => {
+        return 3;
+    };
+    assert(add(1, 2) === 3, 1);
+    assert(double(4) === 8, 2);
+    assert(block() === 3, 3);
+
+    // `this` is captured from the enclosing function
+    const o = {
+        value: 42,
+        method() {
+            return [1, 2].map(() => this.value);
+        },
+    };
+    const values = o.method();
+    assert(values[0] === 42 && values[1] === 42, 4);
+
+    // So is `arguments`, even through nested arrow functions
+    function f() {
+        return (() => arguments[0])();
+    }
+    assert(f(7) === 7, 5);
+    function g() {
+        const h = () => () => arguments.length;
+        return h()();
+    }
+    assert(g(1, 2, 3) === 3, 6);
+    function n() {
+        const x = () => arguments[0];
+        return x(99);
+    }
+    assert(n(1) === 1, 7);
+
+    let captured;
+    function k() {
+        const a = async () => {
+            captured = arguments[1];
+        };
+        a();
+    }
+    k(4, 5);
+    assert(captured === 5, 8);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_index_assignment,
+    r#"
+    const a = [1, 2];
+    a[5] = 6;
+    assert(a.length === 6, 1);
+    assert(a[5] === 6, 2);
+    assert(a[3] === undefined, 3);
+
+    // The intervening indices are holes, not own properties
+    assert(!(3 in a), 4);
+    assert(5 in a, 5);
+    assert(Object.keys(a).join(",") === "0,1,5", Object.keys(a).join(","));
+    assert(Object.keys([1, 2]).join(",") === "0,1", 6);
+
+    const b = [];
+    b[2] = "x";
+    assert(b.length === 3, 7);
+    assert(Object.keys(b).join(",") === "2", 8);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    parse_int,
+    r#"
+    assert(parseInt("-0x1F") === -31, 1);
+    assert(parseInt("  +42px") === 42, 2);
+    assert(Number.isNaN(parseInt("0x", 16)), 3);
+    assert(parseInt("10", 2) === 2, 4);
+    assert(parseInt("0X1f", 16) === 31, 5);
+    assert(parseInt("0x10", 10) === 0, 6);
+    assert(parseInt("z", 36) === 35, 7);
+    assert(parseInt("11", 0) === 11, 8);
+    assert(Number.isNaN(parseInt("abc")), 9);
+    assert(Number.isNaN(parseInt("-")), 10);
+    assert(Number.isNaN(parseInt("1", 37)), 11);
+    assert(Number.isNaN(parseInt("1", 1)), 12);
+    assert(1 / parseInt("-0") === -Infinity, 13);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    generator_iteration,
+    r#"
+    function* g() {
+        yield 1;
+        yield 2;
+        return 3;
+    }
+
+    // The return value is not part of the iteration
+    const collected = [];
+    for (const x of g()) {
+        collected.push(x);
+    }
+    assert(collected.join(",") === "1,2", 1);
+    assert([...g()].join(",") === "1,2", 2);
+
+    const it = g();
+    it.next();
+    it.next();
+    const last = it.next();
+    assert(last.value === 3 && last.done === true, 3);
+    const after = it.next();
+    assert(after.value === undefined && after.done === true, 4);
+
+    function* squares(n) {
+        for (let i = 0; i < n; i++) {
+            yield i * i;
+        }
+    }
+    assert([...squares(4)].join(",") === "0,1,4,9", 5);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    object_literal_proto,
+    r#"
+    const base = {
+        greet() {
+            return "hi";
+        },
+    };
+    const o = { __proto__: base, x: 1 };
+    assert(Object.getPrototypeOf(o) === base, 1);
+    assert(o.greet() === "hi", 2);
+    assert(Object.keys(o).join(",") === "x", 3);
+
+    const empty = { __proto__: null };
+    assert(Object.getPrototypeOf(empty) === null, 4);
+
+    // Values other than objects and null are ignored
+    const primitive = { __proto__: 5 };
+    assert(Object.getPrototypeOf(primitive) === Object.prototype, 5);
+
+    // Computed and shorthand keys create a normal own property
+    const computed = { ["__proto__"]: 5 };
+    assert(Object.getPrototypeOf(computed) === Object.prototype, 6);
+    assert(Object.keys(computed).join(",") === "__proto__", 7);
+    const __proto__ = base;
+    const shorthand = { __proto__ };
+    assert(Object.getPrototypeOf(shorthand) === Object.prototype, 8);
+    assert(Object.keys(shorthand).join(",") === "__proto__", 9);
+
+    // Only one member may set the prototype
+    let threw = false;
+    try {
+        eval("({ __proto__: base, __proto__: null })");
+    } catch (e) {
+        threw = e instanceof SyntaxError;
+    }
+    assert(threw, 10);
+    const mixed = { __proto__: base, ["__proto__"]: 1, __proto__ };
+    assert(Object.getPrototypeOf(mixed) === base, 11);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_callback_this_arg,
+    r#"
+    const arr = [1, 2, 3];
+    const thisArg = { factor: 10 };
+
+    const mapped = arr.map(function (x) {
+        return x * this.factor;
+    }, thisArg);
+    assert(mapped.join(",") === "10,20,30", 1);
+
+    let seen = [];
+    arr.forEach(function (x, i, array) {
+        assert(this === thisArg, 2);
+        assert(array === arr, 3);
+        seen.push(x + ":" + i);
+    }, thisArg);
+    assert(seen.join(",") === "1:0,2:1,3:2", 4);
+
+    assert(arr.filter(function (x) { return x > this.factor / 5; }, thisArg).join(",") === "3", 5);
+    assert(arr.find(function (x, i, array) { return array === arr && this === thisArg; }, thisArg) === 1, 6);
+    assert(arr.some(function () { return this === thisArg; }, thisArg), 7);
+    assert(arr.every(function (x, i, array) { return array === arr; }), 8);
+
+    // Exactly (element, index, array) is passed
+    arr.findLastIndex(function () {
+        assert(arguments.length === 3, 9);
+    });
+    assert(arr.reduce((acc, x, i, array) => acc && array === arr, true), 10);
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn compile_result_statistics() {
+    use dash_compiler::FunctionCompiler;
+    use dash_middle::compiler::instruction::Instruction;
+    use dash_middle::compiler::instruction_iter::InstructionIter;
+    use dash_middle::interner::StringInterner;
+
+    let source = r#"
+    function add(a, b) {
+        return a + b + a;
+    }
+    const obj = { x: 1, [add(1, 2)]: 2, ...{ y: 3 } };
+    try {
+        for (let i = 0; i < 10; i++) {
+            obj.x = add(obj.x, i);
+        }
+    } catch (e) {
+        throw e;
+    } finally {
+        obj.x = 0;
+    }
+    "#;
+
+    let result = FunctionCompiler::compile_str(&mut StringInterner::new(), source, OptLevel::None).unwrap();
+    let histogram = result.opcode_histogram();
+
+    assert_eq!(histogram.get(&Instruction::Add), Some(&2));
+    assert_eq!(histogram.get(&Instruction::Try), Some(&1));
+    assert_eq!(histogram.get(&Instruction::FinallyEnd), Some(&1));
+    assert_eq!(histogram.get(&Instruction::ObjLit), Some(&2));
+    assert_eq!(histogram.get(&Instruction::Call), Some(&2));
+    assert_eq!(result.instruction_count(), histogram.values().sum::<usize>());
+    assert!(result.bytecode_size() > result.instruction_count());
+    assert!(result.constant_pool_size() >= 3);
+
+    // The whole buffer is walked, including the catch block
+    let (_, last) = InstructionIter::new(&result.instructions).last().unwrap();
+    assert_eq!(last, Instruction::Ret);
+}
+
+#[test]
+fn embedder_globals() {
+    use crate::value::function::native::CallContext;
+    use crate::value::ops::conversions::ValueConversion;
+
+    fn host_add(cx: CallContext) -> Result<Value, Value> {
+        let mut sum = 0.0;
+        for arg in &cx.args {
+            sum += arg.to_number(cx.scope)?;
+        }
+        Ok(Value::number(sum))
+    }
+
+    let mut vm = Vm::new(Default::default());
+    vm.set_global("answer", Value::number(42.0)).unwrap();
+    let host_add = vm.set_global_fn("hostAdd", host_add).unwrap();
+
+    // The returned handle keeps the function alive, even once the global no longer refers to it
+    vm.eval(
+        "globalThis.hostAddCopy = hostAdd; hostAdd = undefined;",
+        OptLevel::Basic,
+    )
+    .unwrap();
+    vm.perform_gc();
+    vm.eval("hostAdd = hostAddCopy; delete globalThis.hostAddCopy;", OptLevel::Basic)
+        .unwrap();
+
+    // Errors from the global object are returned instead of panicking
+    assert!(vm.set_global("constructor", Value::number(1.0)).is_err());
+
+    let mut scope = vm.scope();
+    let version = scope.intern("version");
+    host_add
+        .set_property(
+            &mut scope,
+            version.into(),
+            PropertyValue::static_default(Value::number(2.0)),
+        )
+        .unwrap();
+
+    let result = scope
+        .eval(
+            "hostAdd(answer, 1, 2) === 45 && hostAdd.version === 2 && hostAdd.name === 'hostAdd'",
+            OptLevel::Basic,
+        )
+        .unwrap()
+        .root(&mut scope);
+    assert_eq!(result, Value::Boolean(true));
+}
+
+simple_test!(
+    json_stringify_key_order,
+    r#"
+    const o = { b: 1, 2: "two", a: 2, 1: "one", "10": "ten", "01": "zero-one", "-1": "neg" };
+    o[Symbol("s")] = 3;
+    Object.defineProperty(o, "hidden", { value: 4, enumerable: false });
+    o.c = 3;
+    o[0] = "zero";
+    assert(
+        JSON.stringify(o) === '{"0":"zero","1":"one","2":"two","10":"ten","b":1,"a":2,"01":"zero-one","-1":"neg","c":3}',
+        1
+    );
+
+    const nested = { z: { 5: 5, y: 1, 3: 3 }, 4294967294: "max index", 4294967295: "not an index" };
+    assert(
+        JSON.stringify(nested) === '{"4294967294":"max index","z":{"3":3,"5":5,"y":1},"4294967295":"not an index"}',
+        2
+    );
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_join_large,
+    r#"
+    const len = 20000;
+    const arr = [];
+    for (let i = 0; i < len; i++) arr.push(i % 10);
+
+    const joined = arr.join("");
+    assert(joined.length === len, 1);
+    assert(joined.startsWith("012345678901"), 2);
+
+    const withSeparator = arr.join(", ");
+    assert(withSeparator.length === len + (len - 1) * 2, 3);
+    assert(withSeparator.endsWith("8, 9"), 4);
+
+    // toString uses the same buffer based join
+    assert(arr.toString().length === len * 2 - 1, 5);
+
+    const holes = [1, , null, undefined, 2];
+    assert(holes.join("-") === "1----2", 6);
+
+    // A huge length must not be used to reserve memory upfront
+    const huge = { length: 1e15 };
+    Object.defineProperty(huge, "0", { get() { throw "element"; } });
+    let thrown;
+    try { Array.prototype.join.call(huge, ", "); } catch (e) { thrown = e; }
+    assert(thrown === "element", 7);
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn bytecode_serialization() {
+    use dash_compiler::FunctionCompiler;
+    use dash_middle::compiler::format::DeserializeError;
+    use dash_middle::compiler::CompileResult;
+    use dash_middle::interner::StringInterner;
+
+    use crate::frame::Frame;
+
+    let source = r#"
+    function greet(name) {
+        const inner = suffix => "hello " + name + suffix;
+        return inner("!");
+    }
+    const matches = /d(a+)sh/.test("daaash");
+    const obj = { key: 1.5, flag: true, nothing: null, missing: undefined };
+    [greet("dash"), matches, obj.key, obj.flag, obj.nothing, obj.missing, greet.name].join();
+    "#;
+
+    // Intern some strings first so that the symbols of the compiled code differ from the ones in a fresh interner
+    let mut interner = StringInterner::new();
+    interner.intern("unrelated");
+    interner.intern("strings");
+    let compiled = FunctionCompiler::compile_str(&mut interner, source, OptLevel::Basic).unwrap();
+    let bytes = compiled.serialize(&interner).unwrap();
+
+    let mut vm = Vm::new(Default::default());
+    let deserialized = CompileResult::deserialize(&bytes, &mut vm.interner).unwrap();
+    let result = vm.execute_frame(Frame::from_compile_result(deserialized)).unwrap();
+
+    let scope = &mut vm.scope();
+    let result = result.into_value().root(scope);
+    let Value::String(result) = result else {
+        panic!("result is not a string: {result:?}")
+    };
+    assert_eq!(result.res(scope), "hello dash!,true,1.5,true,,,greet");
+
+    let mut invalid_version = bytes.clone();
+    invalid_version[0] ^= 0xff;
+    let mut interner = StringInterner::new();
+    assert!(matches!(
+        CompileResult::deserialize(&invalid_version, &mut interner),
+        Err(DeserializeError::InvalidVersion)
+    ));
+    assert!(matches!(
+        CompileResult::deserialize(&bytes[..2], &mut interner),
+        Err(DeserializeError::InvalidVersion)
+    ));
+    assert!(matches!(
+        CompileResult::deserialize(&bytes[..bytes.len() / 2], &mut interner),
+        Err(DeserializeError::Bincode(_))
+    ));
+}
+
+#[test]
+fn serialized_inline_caches_start_empty() {
+    use std::rc::Rc;
+
+    use dash_compiler::FunctionCompiler;
+    use dash_middle::compiler::constant::{Constant, Function, InlineCache};
+    use dash_middle::compiler::CompileResult;
+
+    use crate::frame::Frame;
+
+    fn find_function(compiled: &CompileResult) -> Rc<Function> {
+        compiled
+            .cp
+            .iter()
+            .find_map(|constant| match constant {
+                Constant::Function(fun) => Some(fun.clone()),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    let source = "function read(o) { return o.x; } read({ x: 1 }) + read({ x: 2 });";
+    let mut vm = Vm::new(Default::default());
+    let compiled = FunctionCompiler::compile_str(&mut vm.interner, source, OptLevel::Basic).unwrap();
+    vm.execute_frame(Frame::from_compile_result(compiled.clone())).unwrap();
+
+    let read = find_function(&compiled);
+    assert!(read
+        .inline_caches
+        .iter()
+        .any(|cache| cache.get() != InlineCache::default()));
+
+    let bytes = compiled.serialize(&vm.interner).unwrap();
+    let deserialized = CompileResult::deserialize(&bytes, &mut vm.interner).unwrap();
+    let deserialized_read = find_function(&deserialized);
+    assert_eq!(deserialized_read.inline_caches.len(), read.inline_caches.len());
+    assert!(deserialized_read
+        .inline_caches
+        .iter()
+        .all(|cache| cache.get() == InlineCache::default()));
+}
+
+simple_test!(
+    string_split_edge_cases,
+    r#"
+    function same(a, b) {
+        return a.length === b.length && a.every((v, i) => v === b[i]);
+    }
+
+    assert(same("a,b,c".split(",", 0), []), 1);
+    assert(same("".split(""), []), 2);
+    assert(same("abc".split(""), ["a", "b", "c"]), 3);
+    assert(same("abc".split("abc"), ["", ""]), 4);
+    assert(same("".split(","), [""]), 5);
+    assert(same("a,b,c".split(",", 2), ["a", "b"]), 6);
+    assert(same("abc".split("", 2), ["a", "b"]), 7);
+    assert(same("a,b".split(), ["a,b"]), 8);
+    assert(same("a,b".split(undefined, 0), []), 9);
+    assert(same("a,b,c".split(",", -1), ["a", "b", "c"]), 10);
+    assert(same("a,,b,".split(","), ["a", "", "b", ""]), 11);
+    assert(same("a😀b".split(""), ["a", "😀", "b"]), 12);
+    assert(same("1undefined2".split(undefined), ["1undefined2"]), 13);
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn instruction_limit() {
+    use crate::eval::EvalError;
+
+    let mut vm = Vm::new(Default::default());
+    vm.set_instruction_limit(Some(100000));
+
+    for source in [
+        "while (true) {}",
+        "while (true) { try { while (true) {} } catch (e) {} }",
+        "[1, 2, 3].map(() => { while (true) {} })",
+    ] {
+        let err = match vm.eval(source, OptLevel::Basic) {
+            Err(EvalError::Exception(err)) => err,
+            _ => panic!("expected an exception for {source}"),
+        };
+        assert_eq!(vm.remaining_instructions(), Some(0));
+
+        let mut scope = vm.scope();
+        let err = err.root(&mut scope);
+        let message = err
+            .get_property(&mut scope, sym::message.into())
+            .root(&mut scope)
+            .unwrap();
+        let Value::String(message) = message else {
+            panic!("message is not a string: {message:?}")
+        };
+        assert_eq!(message.res(&scope), "Instruction limit exceeded", "{source}");
+        drop(scope);
+
+        vm.set_instruction_limit(Some(100000));
+    }
+
+    // Short scripts run within the budget, which is shared across evaluations
+    vm.eval("let x = 0; for (let i = 0; i < 10; i++) x += i;", OptLevel::Basic)
+        .unwrap();
+    let remaining = vm.remaining_instructions().unwrap();
+    assert!(remaining > 0 && remaining < 100000);
+
+    vm.set_instruction_limit(None);
+    vm.eval("for (let i = 0; i < 100000; i++) {}", OptLevel::Basic).unwrap();
+    assert_eq!(vm.remaining_instructions(), None);
+}
+
+#[test]
+fn global_getter() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let counter = Rc::new(Cell::new(0));
+    let mut vm = Vm::new(Default::default());
+    vm.define_global_getter("counter", {
+        let counter = counter.clone();
+        move |_| {
+            counter.set(counter.get() + 1);
+            Ok(Value::number(f64::from(counter.get())))
+        }
+    })
+    .unwrap();
+
+    let mut scope = vm.scope();
+    let result = scope
+        .eval(
+            "const a = counter; const b = counter; a === 1 && b === 2 && counter === 3",
+            OptLevel::Basic,
+        )
+        .unwrap()
+        .root(&mut scope);
+    assert_eq!(result, Value::Boolean(true));
+    assert_eq!(counter.get(), 3);
+}
+
+#[test]
+fn interrupt_handle() {
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::eval::EvalError;
+
+    let mut vm = Vm::new(Default::default());
+    let handle = vm.interrupt_handle();
+
+    for source in [
+        "while (true) {}",
+        "globalThis.finallyRan = false; try { while (true) {} } catch (e) {} finally { finallyRan = true; }",
+        "[1, 2, 3].map(() => { for (;;) {} })",
+    ] {
+        let interrupter = thread::spawn({
+            let handle = handle.clone();
+            move || {
+                thread::sleep(Duration::from_millis(50));
+                handle.interrupt();
+            }
+        });
+
+        let err = match vm.eval(source, OptLevel::Basic) {
+            Err(EvalError::Exception(err)) => err,
+            _ => panic!("expected an exception for {source}"),
+        };
+        interrupter.join().unwrap();
+
+        let mut scope = vm.scope();
+        let err = err.root(&mut scope);
+        let message = err
+            .get_property(&mut scope, sym::message.into())
+            .root(&mut scope)
+            .unwrap();
+        let Value::String(message) = message else {
+            panic!("message is not a string: {message:?}")
+        };
+        assert_eq!(message.res(&scope), "Execution interrupted", "{source}");
+        drop(scope);
+
+        handle.reset();
+    }
+
+    let result = vm
+        .eval("let i = 0; while (i < 10) i++; !finallyRan && i", OptLevel::Basic)
+        .unwrap()
+        .root(&mut vm.scope());
+    assert_eq!(result, Value::number(10.0));
+}
+
+#[test]
+fn value_to_number() {
+    use crate::value::ops::conversions::ValueConversion;
+
+    let mut vm = Vm::new(Default::default());
+    let mut scope = vm.scope();
+
+    let padded = Value::String(scope.intern("  42 ").into());
+    let empty = Value::String(scope.intern("").into());
+    let hex = Value::String(scope.intern("0x1F").into());
+    let invalid = Value::String(scope.intern("12abc").into());
+    let object = scope.eval("({ valueOf() { return 7; } })", OptLevel::Basic).unwrap();
+    let object = object.root(&mut scope);
+
+    assert_eq!(padded.to_number(&mut scope).unwrap(), 42.0);
+    assert_eq!(Value::Boolean(true).to_number(&mut scope).unwrap(), 1.0);
+    assert_eq!(Value::null().to_number(&mut scope).unwrap(), 0.0);
+    assert_eq!(empty.to_number(&mut scope).unwrap(), 0.0);
+    assert_eq!(hex.to_number(&mut scope).unwrap(), 31.0);
+    assert!(invalid.to_number(&mut scope).unwrap().is_nan());
+    assert!(Value::undefined().to_number(&mut scope).unwrap().is_nan());
+    assert_eq!(object.to_number(&mut scope).unwrap(), 7.0);
+}
+
+simple_test!(
+    break_continue_in_try_finally,
+    r#"
+    let log = [];
+    for (let i = 0; i < 3; i++) {
+        try {
+            if (i === 1) continue;
+            log.push("try" + i);
+        } finally {
+            log.push("finally" + i);
+        }
+    }
+    assert(log.join() === "try0,finally0,finally1,try2,finally2", log.join());
+
+    log = [];
+    for (let i = 0; i < 3; i++) {
+        try {
+            if (i === 1) break;
+        } finally {
+            log.push("finally" + i);
+        }
+    }
+    assert(log.join() === "finally0,finally1", log.join());
+
+    log = [];
+    outer: for (let i = 0; i < 2; i++) {
+        try {
+            for (let j = 0; j < 2; j++) {
+                try {
+                    if (j === 1) continue outer;
+                } finally {
+                    log.push("inner" + i + j);
+                }
+            }
+        } finally {
+            log.push("outer" + i);
+        }
+    }
+    assert(log.join() === "inner00,inner01,outer0,inner10,inner11,outer1", log.join());
+
+    // Leaving a try block must not leave a stale catch handler behind
+    function stale() {
+        for (;;) {
+            try {
+                break;
+            } catch (e) {
+                return "stale";
+            }
+        }
+        throw "ok";
+    }
+    let result;
+    try {
+        result = stale();
+    } catch (e) {
+        result = e;
+    }
+    assert(result === "ok", result);
+
+    function breakInCatch() {
+        let count = 0;
+        for (;;) {
+            try {
+                throw 1;
+            } catch (e) {
+                break;
+            } finally {
+                count++;
+            }
+        }
+        return count;
+    }
+    assert(breakInCatch() === 1, "break in catch");
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    object_has_own,
+    r#"
+    const o = { a: 1 };
+    assert(Object.hasOwn(o, "a") === true, 1);
+    assert(Object.hasOwn(o, "toString") === false, 2);
+    assert(Object.hasOwn(Object.create(o), "a") === false, 3);
+
+    const bare = Object.create(null);
+    bare.x = 1;
+    assert(Object.hasOwn(bare, "x") === true, 4);
+    assert(Object.hasOwn(bare, "y") === false, 5);
+
+    const arr = [1, 2];
+    assert(Object.hasOwn(arr, 1) === true, 6);
+    assert(Object.hasOwn(arr, "0") === true, 7);
+    assert(Object.hasOwn(arr, 2) === false, 8);
+    assert(Object.hasOwn(arr, "push") === false, 9);
+    assert(Object.hasOwn("abc", "length") === true, 10);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    to_boolean_conversions,
+    r#"
+    let branch;
+    if ("") {
+        branch = "then";
+    } else {
+        branch = "else";
+    }
+    assert(branch === "else", 1);
+
+    if ([]) {
+        branch = "then";
+    } else {
+        branch = "else";
+    }
+    assert(branch === "then", 2);
+
+    const falsy = [0, -0, NaN, "", null, undefined, false];
+    for (const value of falsy) {
+        assert(!value, value);
+        assert((value ? 1 : 2) === 2, value);
+    }
+
+    const truthy = ["0", " ", 1, -1, Infinity, {}, [], function () {}, Symbol()];
+    for (const value of truthy) {
+        assert(!!value, 3);
+        assert((value || 1) === value, 4);
+    }
+
+    let n = 3;
+    let count = 0;
+    while (n) {
+        n--;
+        count++;
+    }
+    assert(count === 3, 5);
+
+    // Captured variables are stored as external values
+    let captured = "";
+    (() => captured)();
+    assert(!captured, 6);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    number_to_string_radix,
+    r#"
+    assert((255).toString(16) === "ff", 1);
+    assert((10).toString(2) === "1010", 2);
+    assert((-255).toString(36) === "-73", 3);
+    assert((255).toString("16") === "ff", 4);
+    assert((3.75).toString(16) === "3.c", 5);
+    assert((0.5).toString(2) === "0.1", 6);
+    assert((0.1).toString(3) === "0.0022002200220022002200220022002201", 7);
+    assert((1e21).toString(16) === "3635c9adc5dea00000", 8);
+    assert(NaN.toString(2) === "NaN", 9);
+    assert((-Infinity).toString(16) === "-Infinity", 10);
+    assert((255).toString() === "255", 11);
+    assert((255).toString(10) === "255", 12);
+    assert(new Number(255).toString(16) === "ff", 13);
+    assert(new Number(-1.5).toString() === "-1.5", 14);
+
+    for (const radix of [0, 1, 37, Infinity]) {
+        let error;
+        try {
+            (1).toString(radix);
+        } catch (e) {
+            error = e;
+        }
+        assert(error instanceof RangeError, radix);
+    }
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    for_loop_empty_body,
+    r#"
+    let i;
+    for (i = 0; i < 5; i++);
+    assert(i === 5, 1);
+
+    let count = 0;
+    for (let j = 0; j < 4; j++, count++);
+    assert(count === 4, 2);
+
+    // An empty body must not leave values on the stack
+    let n = 0;
+    for (; n < 100000; n++);
+    assert(n === 100000, 3);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    array_methods_skip_holes,
+    r#"
+    const arr = [1];
+    arr[5] = 6;
+
+    const visited = [];
+    arr.forEach((value, index) => visited.push(index));
+    assert(visited.join() === "0,5", visited.join());
+
+    const mapped = arr.map((value) => value * 2);
+    assert(mapped.length === 6, 1);
+    assert(!(3 in mapped), 2);
+    assert(mapped[0] === 2 && mapped[5] === 12, 3);
+
+    assert(arr.filter(() => true).length === 2, 4);
+    assert(!arr.some((value) => value === undefined), 5);
+    assert(arr.every((value) => value !== undefined), 6);
+    assert(arr.reduce((acc, value) => acc + value) === 7, 7);
+    assert([, , 3].reduce((acc, value) => acc + value) === 3, 8);
+    assert(arr.indexOf(undefined) === -1, 9);
+    assert(arr.lastIndexOf(undefined) === -1, 10);
+
+    // `includes`, `join` and `fill` treat holes as undefined
+    assert(arr.includes(undefined), 11);
+    assert(arr.join("-") === "1-----6", 12);
+    assert(new Array(3).fill(0).join() === "0,0,0", 13);
+
+    // Holes of array-like objects are preserved as well
+    const mapped = Array.prototype.map.call({ length: 3, 1: 2 }, (value) => value * 2);
+    assert(mapped.length === 3 && !(0 in mapped) && mapped[1] === 4 && !(2 in mapped), 14);
+
+    // The result must not be pre-sized from the length, which would try to reserve memory for every hole
+    let thrown;
+    try {
+        Array.prototype.map.call({ length: 2 ** 32 - 1, 0: 1 }, () => { throw "callback"; });
+    } catch (e) { thrown = e; }
+    assert(thrown === "callback", 15);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    date_components,
+    r#"
+    assert(new Date(0).toISOString() === "1970-01-01T00:00:00.000Z", 1);
+
+    const date = new Date(1700000000123);
+    assert(date.getTime() === 1700000000123, 2);
+    assert(date.getFullYear() === 2023, 3);
+    assert(date.getMonth() === 10, 4);
+    assert(date.getDate() === 14, 5);
+    assert(date.getHours() === 22, 6);
+    assert(date.getMinutes() === 13, 7);
+    assert(date.getSeconds() === 20, 8);
+    assert(date.toISOString() === "2023-11-14T22:13:20.123Z", 9);
+    assert(new Date(date).getTime() === date.getTime(), 10);
+
+    assert(new Date(-1).toISOString() === "1969-12-31T23:59:59.999Z", 11);
+    assert(new Date(-62198755200000).toISOString() === "-000001-01-01T00:00:00.000Z", 12);
+
+    const invalid = new Date(8.64e15 + 1);
+    assert(Number.isNaN(invalid.getTime()) && Number.isNaN(invalid.getFullYear()), 13);
+    let error;
+    try {
+        invalid.toISOString();
+    } catch (e) {
+        error = e;
+    }
+    assert(error instanceof RangeError, 14);
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn date_uses_time_callback() {
+    let mut vm = Vm::new(VmParams::new().set_time_millis_callback(|_| Ok(86_400_000)));
+    let result = vm
+        .eval(
+            "Date.now() === 86400000 && new Date().toISOString() === '1970-01-02T00:00:00.000Z'",
+            OptLevel::Basic,
+        )
+        .unwrap()
+        .root(&mut vm.scope());
+    assert_eq!(result, Value::Boolean(true));
+}
+
+simple_test!(
+    regexp_string_methods,
+    r#"
+    assert(/\d+/.test("abc123"), 1);
+    assert("a1b2".replace(/\d/g, "x") === "axbx", 2);
+    assert("a1b2".replace(/\d/, "x") === "axb2", 3);
+    assert("John Smith".replace(/(\w+)\s(\w+)/, "$2, $1 ($&) $$") === "Smith, John (John Smith) $", 4);
+    assert("abc".replace(/b/g, (m, offset) => m.toUpperCase() + offset) === "aB1c", 5);
+
+    assert(JSON.stringify("a1b22c333".match(/\d+/g)) === '["1","22","333"]', 6);
+    assert(JSON.stringify("a1b22c333".match(/(\d)(\d)/)) === '["22","2","2"]', 7);
+    assert("abc".match(/x/g) === null, 8);
+
+    assert("hello world".search(/o/) === 4, 9);
+    assert("hello".search(/z/) === -1, 10);
+    assert("hello".search("l") === 2, 11);
+
+    assert(JSON.stringify("a1b2c3".split(/\d/)) === '["a","b","c",""]', 12);
+    assert(JSON.stringify("a1b2c3".split(/(\d)/, 4)) === '["a","1","b","2"]', 13);
+    assert(JSON.stringify("abc".split(/(?:)/)) === '["a","b","c"]', 14);
+
+    assert(/HELLO/i.test("say hello") && /[a-c]+/i.test("XBX"), 15);
+    assert(JSON.stringify("one\ntwo".match(/^\w+$/gm)) === '["one","two"]', 16);
+    assert(!/^two/.test("one\ntwo"), 17);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    regexp_global_last_index,
+    r#"
+    const regex = /o/g;
+    const string = "foo boo";
+
+    assert(regex.exec(string)[0] === "o" && regex.lastIndex === 2, 1);
+    assert(regex.exec(string) !== null && regex.lastIndex === 3, 2);
+
+    let count = 0;
+    while (regex.exec(string) !== null) count++;
+    assert(count === 2 && regex.lastIndex === 0, 3);
+
+    regex.lastIndex = 6;
+    assert(regex.test(string) && regex.lastIndex === 7, 4);
+    assert(!regex.test(string) && regex.lastIndex === 0, 5);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    regexp_non_ascii_indices,
+    r#"
+    assert("é".replace(/./g, "x") === "x", 1);
+    assert(JSON.stringify("éa".match(/./g)) === '["é","a"]', 2);
+    assert("aéb".search(/b/) === 2, 3);
+    assert("😀b".search(/b/) === 2, 4);
+    assert("aéb".replace(/b/, (m, offset) => offset) === "aé2", 5);
+    assert(JSON.stringify("aéb".split(/é/)) === '["a","b"]', 6);
+
+    const re = /x?/g;
+    re.lastIndex = 1;
+    assert(re.exec("é")[0] === "" && re.lastIndex === 1, 7);
+
+    const b = /b/g;
+    assert(b.exec("ébéb") !== null && b.lastIndex === 2, 8);
+    assert(b.exec("ébéb") !== null && b.lastIndex === 4, 9);
+    b.lastIndex = 5;
+    assert(b.exec("ébéb") === null && b.lastIndex === 0, 10);
+    "#,
+    Value::undefined()
+);
+
+simple_test!(
+    boxed_primitive_unwrapping,
+    r#"
+    assert(new Number(5) + 1 === 6, 1);
+    assert(new Number(5) - new Number(2) === 3, 2);
+    assert(new Boolean(true) == 1, 3);
+    assert(new String("a") + "b" === "ab", 4);
+
+    assert(new Number(5).valueOf() === 5, 5);
+    assert(new Boolean(false).valueOf() === false, 6);
+    assert(new String("x").valueOf() === "x", 7);
+    assert(typeof new String("x").toString() === "string", 8);
+
+    let error;
+    try {
+        String.prototype.valueOf.call({});
+    } catch (e) {
+        error = e;
+    }
+    assert(error instanceof TypeError, 9);
+    "#,
+    Value::undefined()
+);
+
+#[test]
+fn weakmap_and_weakset() {
+    let mut vm = Vm::new(Default::default());
+    vm.eval(
+        r#"
+    globalThis.weakMap = new WeakMap();
+    globalThis.weakSet = new WeakSet();
+    globalThis.collected = [];
+    globalThis.registry = new FinalizationRegistry(held => collected.push(held));
+    globalThis.kept = {};
+    (function() {
+        const key = {};
+        const value = {};
+        const chained = {};
+        globalThis.weakKey = new WeakRef(key);
+        weakMap.set(key, value);
+        // Values are kept alive through their key, including keys of other entries
+        weakMap.set(kept, chained);
+        weakMap.set(chained, { name: "chained" });
+        weakSet.add(key);
+        weakSet.add(kept);
+        registry.register(value, "value");
+        if (!weakMap.has(key) || weakMap.get(key) !== value || !weakSet.has(key)) throw "entries must be present";
+    })();
+    "#,
+        Default::default(),
+    )
+    .unwrap();
+
+    fn check(vm: &mut Vm, code: &str) {
+        let value = vm.eval(code, Default::default()).unwrap().root(&mut vm.scope());
+        assert_eq!(value, Value::Boolean(true), "{code}");
+    }
+
+    vm.perform_gc();
+    check(&mut vm, "weakKey.deref() === undefined");
+    check(
+        &mut vm,
+        "weakMap.get(weakMap.get(kept)).name === 'chained' && weakSet.has(kept)",
+    );
+    vm.process_async_tasks();
+    check(&mut vm, "collected.join() === 'value'");
+
+    check(
+        &mut vm,
+        r#"
+    const key = {};
+    weakMap.set(key, 1);
+    weakSet.add(key);
+    weakMap.delete(key) && !weakMap.has(key) && weakMap.get(key) === undefined && !weakMap.delete(key)
+        && weakSet.delete(key) && !weakSet.has(key) && !weakMap.has(1) && !weakSet.has("a")
+    "#,
+    );
+
+    for code in [
+        "weakMap.set(1, 2)",
+        "weakSet.add('a')",
+        "new WeakMap([[1, 2]])",
+        "WeakMap.prototype.get.call({}, {})",
+    ] {
+        let code =
+            format!("(() => {{ try {{ {code}; return false }} catch (e) {{ return e instanceof TypeError }} }})()");
+        check(&mut vm, &code);
+    }
+}
@@ -18,6 +18,8 @@ impl Arguments {
     pub fn new(vm: &mut LocalScope, args: impl IntoIterator<IntoIter = impl ExactSizeIterator<Item = Value>>) -> Self {
         let args = args.into_iter();
         let len = args.len();
+        let symbol_iterator = vm.statics.symbol_iterator.clone();
+        let values = vm.statics.array_values.clone();
 
         Self {
             object: NamedObject::null_with_values(
@@ -28,11 +30,16 @@ impl Arguments {
                             PropertyValue::static_non_enumerable(v),
                         )
                     })
-                    .chain([(
-                        PropertyKey::String(sym::length.into()),
-                        PropertyValue::static_default(Value::number(len as f64)),
-                    )])
-                    .collect(),
+                    .chain([
+                        (
+                            PropertyKey::String(sym::length.into()),
+                            PropertyValue::static_default(Value::number(len as f64)),
+                        ),
+                        (
+                            PropertyKey::Symbol(symbol_iterator),
+                            PropertyValue::static_non_enumerable(Value::Object(values)),
+                        ),
+                    ]),
             ),
         }
     }
@@ -62,6 +62,10 @@ impl<T> HoleyArray<T> {
         &self.0
     }
 
+    pub fn inner_mut(&mut self) -> &mut [Element<T>] {
+        &mut self.0
+    }
+
     /// Checks if there are any holes in this array
     pub fn has_hole(&self) -> bool {
         self.0.iter().any(|e| matches!(e, Element::Hole { .. }))
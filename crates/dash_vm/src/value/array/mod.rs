@@ -129,6 +129,8 @@ unsafe impl crate::gc::trace::Trace for ArrayInner<PropertyValue> {
 #[derive(Debug, Trace)]
 pub struct Array {
     pub items: RefCell<ArrayInner<PropertyValue>>,
+    /// Whether this array has been frozen, in which case its elements, length and properties can no longer change
+    frozen: Cell<bool>,
     obj: NamedObject,
 }
 
@@ -145,6 +147,7 @@ impl Array {
     pub fn from_vec(vm: &Vm, items: Vec<PropertyValue>) -> Self {
         Self {
             items: RefCell::new(ArrayInner::NonHoley(items)),
+            frozen: Cell::new(false),
             obj: get_named_object(vm),
         }
     }
@@ -152,6 +155,7 @@ impl Array {
     pub fn from_possibly_holey(vm: &Vm, elements: Vec<Element<PropertyValue>>) -> Self {
         Self {
             items: RefCell::new(ArrayInner::Holey(elements.into())),
+            frozen: Cell::new(false),
             obj: get_named_object(vm),
         }
     }
@@ -160,6 +164,7 @@ impl Array {
     pub fn with_hole(vm: &Vm, len: usize) -> Self {
         Self {
             items: RefCell::new(ArrayInner::Holey(HoleyArray::from(vec![Element::Hole { count: len }]))),
+            frozen: Cell::new(false),
             obj: get_named_object(vm),
         }
     }
@@ -208,9 +213,31 @@ impl Array {
     pub fn with_obj(obj: NamedObject) -> Self {
         Self {
             items: RefCell::new(ArrayInner::NonHoley(Vec::new())),
+            frozen: Cell::new(false),
             obj,
         }
     }
+
+    /// Freezes this array, making all of its elements non-writable and non-configurable.
+    /// Any further writes or deletions are silently ignored.
+    pub fn freeze(&self) {
+        self.frozen.set(true);
+
+        let mut items = self.items.borrow_mut();
+        let freeze = |value: &mut PropertyValue| value.descriptor &= PropertyDataDescriptor::ENUMERABLE;
+        match &mut *items {
+            ArrayInner::NonHoley(v) => v.iter_mut().for_each(freeze),
+            ArrayInner::Holey(v) => v.inner_mut().iter_mut().for_each(|element| {
+                if let Element::Value(value) = element {
+                    freeze(value);
+                }
+            }),
+        }
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.get()
+    }
 }
 
 impl Object for Array {
@@ -225,45 +252,53 @@ impl Object for Array {
             if key.sym() == sym::length {
                 return Ok(Some(PropertyValue {
                     kind: PropertyValueKind::Static(Value::number(items.len() as f64)),
-                    descriptor: PropertyDataDescriptor::WRITABLE,
+                    descriptor: if self.frozen.get() {
+                        PropertyDataDescriptor::empty()
+                    } else {
+                        PropertyDataDescriptor::WRITABLE
+                    },
                 }));
             }
 
             if let Ok(index) = key.res(sc).parse::<usize>() {
                 if index < MAX_LENGTH {
-                    if let Some(element) = items.get(index) {
-                        match element {
-                            MaybeHoley::Some(v) => return Ok(Some(v.clone())),
-                            MaybeHoley::Hole => return Ok(Some(PropertyValue::static_default(Value::undefined()))),
-                        }
+                    // Holes are not own properties of the array
+                    if let Some(MaybeHoley::Some(v)) = items.get(index) {
+                        return Ok(Some(v.clone()));
                     }
                 }
             }
         }
 
-        self.obj.get_property_descriptor(sc, key)
+        self.obj.get_own_property_descriptor(sc, key)
     }
 
     fn set_property(&self, sc: &mut LocalScope, key: PropertyKey, value: PropertyValue) -> Result<(), Value> {
-        if let PropertyKey::String(key) = &key {
-            let mut items = self.items.borrow_mut();
+        if self.frozen.get() {
+            return Ok(());
+        }
 
+        if let PropertyKey::String(key) = &key {
             if key.sym() == sym::length {
                 // TODO: this shouldnt be undefined
                 let value = value.kind().get_or_apply(sc, Value::undefined()).root(sc)?;
-                let new_len = value.to_number(sc)? as usize;
+                let number = value.to_number(sc)?;
+                let new_len = number as usize;
 
-                if new_len > MAX_LENGTH {
+                // The new length must be an integer that fits in a u32, i.e. ToUint32(value) === ToNumber(value)
+                if new_len as f64 != number || new_len > MAX_LENGTH {
                     throw!(sc, RangeError, "Invalid array length");
                 }
 
-                items.resize(new_len);
+                // Converting the value can run user code that accesses this array,
+                // so the items must only be borrowed afterwards
+                self.items.borrow_mut().resize(new_len);
                 return Ok(());
             }
 
             if let Ok(index) = key.res(sc).parse::<usize>() {
                 if index < MAX_LENGTH {
-                    items.set(index, value);
+                    self.items.borrow_mut().set(index, value);
                     return Ok(());
                 }
             }
@@ -273,6 +308,10 @@ impl Object for Array {
     }
 
     fn delete_property(&self, sc: &mut LocalScope, key: PropertyKey) -> Result<Unrooted, Value> {
+        if self.frozen.get() {
+            return Ok(Unrooted::new(Value::undefined()));
+        }
+
         if let PropertyKey::String(key) = &key {
             if key.sym() == sym::length {
                 return Ok(Unrooted::new(Value::undefined()));
@@ -311,14 +350,30 @@ impl Object for Array {
 
     fn own_keys(&self, sc: &mut LocalScope<'_>) -> Result<Vec<Value>, Value> {
         let items = self.items.borrow();
-        Ok(array_like_keys(sc, items.len()).collect())
+        match &*items {
+            ArrayInner::NonHoley(v) => Ok(array_like_keys(sc, v.len()).collect()),
+            ArrayInner::Holey(v) => {
+                // Only list the indices of elements that are present
+                let mut keys = Vec::new();
+                let mut index = 0;
+                for element in v.inner() {
+                    if let Element::Value(_) = element {
+                        keys.push(Value::String(sc.intern_usize(index).into()));
+                    }
+                    index += element.elements();
+                }
+                keys.push(Value::String(sym::length.into()));
+                Ok(keys)
+            }
+        }
     }
 }
 
 #[derive(Debug, Trace)]
 pub struct ArrayIterator {
     index: Cell<usize>,
-    length: usize,
+    /// Set once the end was reached, after which the iterator stays exhausted even if the array grows
+    done: Cell<bool>,
     value: Value,
     obj: NamedObject,
 }
@@ -353,11 +408,9 @@ impl Object for ArrayIterator {
 
 impl ArrayIterator {
     pub fn new(sc: &mut LocalScope, value: Value) -> Result<Self, Value> {
-        let length = value.length_of_array_like(sc)?;
-
         Ok(ArrayIterator {
             index: Cell::new(0),
-            length,
+            done: Cell::new(false),
             value,
             obj: NamedObject::with_prototype_and_constructor(
                 sc.statics.array_iterator_prototype.clone(),
@@ -369,20 +422,27 @@ impl ArrayIterator {
     pub fn empty() -> Self {
         Self {
             index: Cell::new(0),
-            length: 0,
+            done: Cell::new(true),
             value: Value::null(),
             obj: NamedObject::null(),
         }
     }
 
+    /// Returns the next element, reading the length of the array on every call so that mutations during iteration are observed
     pub fn next(&self, sc: &mut LocalScope) -> Result<Option<Unrooted>, Unrooted> {
+        if self.done.get() {
+            return Ok(None);
+        }
+
         let index = self.index.get();
+        let length = self.value.length_of_array_like(sc)?;
 
-        if index < self.length {
+        if index < length {
             self.index.set(index + 1);
             let index = sc.intern_usize(index);
             self.value.get_property(sc, index.into()).map(Some)
         } else {
+            self.done.set(true);
             Ok(None)
         }
     }
@@ -414,6 +474,10 @@ pub fn spec_array_set_property(
 ) -> Result<(), Value> {
     // specialize array path
     if let Some(arr) = target.downcast_ref::<Array>() {
+        if arr.frozen.get() {
+            return Ok(());
+        }
+
         let mut inner = arr.items.borrow_mut();
 
         if index < MAX_LENGTH {
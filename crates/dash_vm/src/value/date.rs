@@ -0,0 +1,46 @@
+use dash_proc_macro::Trace;
+
+use crate::delegate;
+use crate::Vm;
+
+use super::object::{NamedObject, Object};
+
+/// A `Date` object, which holds a point in time as the number of milliseconds since the Unix epoch.
+///
+/// The time value is `NaN` for invalid dates.
+#[derive(Debug, Trace)]
+pub struct Date {
+    time: f64,
+    obj: NamedObject,
+}
+
+impl Date {
+    pub fn new(vm: &Vm, time: f64) -> Self {
+        let prototype = vm.statics.date_prototype.clone();
+        let ctor = vm.statics.date_ctor.clone();
+        Self {
+            time,
+            obj: NamedObject::with_prototype_and_constructor(prototype, ctor),
+        }
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+}
+
+impl Object for Date {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        as_any,
+        apply,
+        own_keys
+    );
+}
@@ -0,0 +1,115 @@
+use std::cell::RefCell;
+
+use crate::delegate;
+use crate::gc::handle::Handle;
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::Vm;
+
+use super::object::{NamedObject, Object};
+use super::Value;
+
+#[derive(Debug)]
+struct FinalizationCell {
+    /// The registered object. This is a weak reference.
+    target: Handle,
+    /// The value passed to the cleanup callback once the target is collected
+    held_value: Value,
+    /// The token that can be used to unregister this cell. This is a weak reference.
+    unregister_token: Option<Handle>,
+}
+
+/// A registry of objects that calls a cleanup callback after any of them has been collected.
+#[derive(Debug)]
+pub struct FinalizationRegistry {
+    cleanup: Option<Handle>,
+    cells: RefCell<Vec<FinalizationCell>>,
+    obj: NamedObject,
+}
+
+unsafe impl Trace for FinalizationRegistry {
+    fn trace(&self, cx: &mut TraceCtxt<'_>) {
+        // Targets and unregister tokens are weak references, only the held values are kept alive
+        self.cleanup.trace(cx);
+        for cell in self.cells.borrow().iter() {
+            cell.held_value.trace(cx);
+        }
+        self.obj.trace(cx);
+    }
+}
+
+impl FinalizationRegistry {
+    pub fn new(vm: &Vm, cleanup: Handle) -> Self {
+        let prototype = vm.statics.finalization_registry_prototype.clone();
+        let ctor = vm.statics.finalization_registry_ctor.clone();
+        Self::with_obj(
+            Some(cleanup),
+            NamedObject::with_prototype_and_constructor(prototype, ctor),
+        )
+    }
+
+    pub fn with_obj(cleanup: Option<Handle>, obj: NamedObject) -> Self {
+        Self {
+            cleanup,
+            cells: RefCell::new(Vec::new()),
+            obj,
+        }
+    }
+
+    pub fn cleanup(&self) -> Option<Handle> {
+        self.cleanup.clone()
+    }
+
+    pub fn register(&self, target: Handle, held_value: Value, unregister_token: Option<Handle>) {
+        self.cells.borrow_mut().push(FinalizationCell {
+            target,
+            held_value,
+            unregister_token,
+        });
+    }
+
+    /// Removes all cells registered with the given token. Returns whether any cell was removed.
+    pub fn unregister(&self, token: &Handle) -> bool {
+        let mut cells = self.cells.borrow_mut();
+        let len = cells.len();
+        cells.retain(|cell| cell.unregister_token.as_ref() != Some(token));
+        cells.len() != len
+    }
+
+    /// Removes the cells whose target was not marked in the last mark phase, as they are about to be collected,
+    /// and returns their held values.
+    pub(crate) fn take_collected(&self) -> Vec<Value> {
+        let mut collected = Vec::new();
+        self.cells.borrow_mut().retain_mut(|cell| {
+            if !cell.target.interior_flags().is_marked() {
+                collected.push(cell.held_value.clone());
+                return false;
+            }
+
+            if cell
+                .unregister_token
+                .as_ref()
+                .is_some_and(|token| !token.interior_flags().is_marked())
+            {
+                cell.unregister_token = None;
+            }
+            true
+        });
+        collected
+    }
+}
+
+impl Object for FinalizationRegistry {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        as_any,
+        apply,
+        own_keys
+    );
+}
@@ -1,27 +1,42 @@
 use dash_proc_macro::Trace;
 
 use crate::gc::handle::Handle;
-use crate::gc::interner::sym;
+use crate::js_std::generator::{resume, GeneratorResumeKind, GeneratorResumeResult};
+use crate::js_std::promise::subscribe;
 use crate::localscope::LocalScope;
-use crate::value::object::{NamedObject, Object, PropertyKey};
+use crate::value::object::{NamedObject, Object};
 use crate::value::promise::{wrap_promise, Promise};
-use crate::value::root_ext::RootErrExt;
-use crate::value::{Root, Typeof, Unrooted, Value, ValueContext};
+use crate::value::{Typeof, Unrooted, Value, ValueContext};
 use crate::{delegate, PromiseAction, Vm};
 
-use super::generator::{as_generator, GeneratorFunction, GeneratorState};
+use super::generator::GeneratorFunction;
 use super::user::UserFunction;
 
 #[derive(Debug, Trace)]
 pub struct AsyncFunction {
     /// The properties of generator functions are very similar to async functions, so we can build upon generators
     inner: GeneratorFunction,
+    /// The lexically captured `this` value of async arrow functions, used in place of the receiver
+    bound_this: Option<Value>,
+    /// The lexically captured `arguments` object of async arrow functions
+    bound_arguments: Option<Handle>,
 }
 
 impl AsyncFunction {
     pub fn new(fun: UserFunction) -> Self {
         Self {
             inner: GeneratorFunction::new(fun),
+            bound_this: None,
+            bound_arguments: None,
+        }
+    }
+
+    /// Creates an async arrow function that always runs with the given `this` value and `arguments` object
+    pub fn arrow(fun: UserFunction, this: Value, arguments: Option<Handle>) -> Self {
+        Self {
+            inner: GeneratorFunction::new(fun),
+            bound_this: Some(this),
+            bound_arguments: arguments,
         }
     }
 
@@ -33,60 +48,34 @@ impl AsyncFunction {
         args: Vec<Value>,
         is_constructor_call: bool,
     ) -> Result<Value, Unrooted> {
-        let generator_iter = self
-            .inner
-            .handle_function_call(scope, callee, this, args, is_constructor_call)?;
-
-        let result = scope
-            .statics
-            .generator_iterator_next
-            .clone()
-            .apply(scope, generator_iter.clone(), Vec::new())
-            .root(scope)
-            .and_then(|result| {
-                result
-                    .get_property(scope, PropertyKey::String(sym::value.into()))
-                    .root(scope)
-            });
-
-        match &result {
-            Ok(value) => {
-                let is_done = as_generator(scope, &generator_iter)
-                    .map(|gen| matches!(&*gen.state().borrow(), GeneratorState::Finished))?;
-
-                if is_done {
-                    // Promise in resolved state
-                    let promise = wrap_promise(scope, value.clone());
-                    Ok(promise)
-                } else {
-                    // Promise in pending state
-                    let final_promise = Promise::new(scope);
-                    let final_promise = scope.register(final_promise);
-                    let then_task = ThenTask::new(scope, generator_iter.clone(), final_promise.clone());
-                    let then_task = scope.register(then_task);
-
-                    let promise = Value::Object(final_promise);
-
-                    scope
-                        .statics
-                        .promise_then
-                        .clone()
-                        .apply(
-                            scope,
-                            match result {
-                                Ok(value) => value,
-                                Err(value) => value,
-                            },
-                            vec![Value::Object(then_task)],
-                        )
-                        .root_err(scope)?;
-
-                    Ok(promise)
-                }
+        let generator_iter = match &self.bound_this {
+            Some(bound_this) => self.inner.handle_function_call_with_arguments(
+                scope,
+                callee,
+                bound_this.clone(),
+                args,
+                self.bound_arguments.clone(),
+                is_constructor_call,
+            )?,
+            None => self
+                .inner
+                .handle_function_call(scope, callee, this, args, is_constructor_call)?,
+        };
+
+        match resume(scope, &generator_iter, GeneratorResumeKind::Next(Value::undefined())) {
+            // Promise in resolved state
+            Ok(GeneratorResumeResult::Return(value)) => Ok(wrap_promise(scope, value)),
+            Ok(GeneratorResumeResult::Await(value) | GeneratorResumeResult::Yield(value)) => {
+                // Promise in pending state
+                let final_promise = Promise::new(scope);
+                let final_promise = scope.register(final_promise);
+                await_value(scope, generator_iter, final_promise.clone(), value);
+                Ok(Value::Object(final_promise))
             }
+            // Exceptions thrown by the async function reject the returned promise instead of propagating to the caller
             Err(value) => {
-                let promise = wrap_promise(scope, value.clone());
-                Err(promise.into())
+                let promise = Promise::rejected(scope, value);
+                Ok(Value::Object(scope.register(promise)))
             }
         }
     }
@@ -96,22 +85,36 @@ impl AsyncFunction {
     }
 }
 
-/// A callable object that is passed to `.then()` on awaited promises.
+/// Resumes the async function once the awaited value settles.
+/// Values that are not promises are treated as already resolved, so the function resumes on the next tick.
+fn await_value(scope: &mut LocalScope, generator_iter: Value, final_promise: Handle, value: Value) {
+    let (on_resolve, on_reject) = {
+        let t1 = ThenTask::new(scope, generator_iter.clone(), final_promise.clone(), false);
+        let t2 = ThenTask::new(scope, generator_iter, final_promise, true);
+        (scope.register(t1), scope.register(t2))
+    };
+    subscribe(scope, value, on_resolve, on_reject);
+}
+
+/// A callable object that is subscribed to awaited promises.
 /// Calling this will drive the async function to the next await or return point.
 #[derive(Debug, Trace)]
 pub struct ThenTask {
     /// The inner generator iterator of the async function
     generator_iter: Value,
     final_promise: Handle,
+    /// Whether this handles the rejection of the awaited promise, in which case the reason is thrown at the `await`
+    rejected: bool,
     obj: NamedObject,
 }
 
 impl ThenTask {
-    pub fn new(vm: &Vm, generator_iter: Value, final_promise: Handle) -> Self {
+    pub fn new(vm: &Vm, generator_iter: Value, final_promise: Handle, rejected: bool) -> Self {
         Self {
             generator_iter,
-            obj: NamedObject::new(vm),
             final_promise,
+            rejected,
+            obj: NamedObject::new(vm),
         }
     }
 }
@@ -137,60 +140,25 @@ impl Object for ThenTask {
         _this: Value,
         args: Vec<Value>,
     ) -> Result<Unrooted, Unrooted> {
-        let promise_value = args.first().unwrap_or_undefined();
-
-        // Call GeneratorIterator.prototype.next on the generator of async function
-        // TODO: this probably wont work because when it gets to an await point, the generator doesnt know how to handle it
-        let value = scope
-            .statics
-            .generator_iterator_next
-            .clone()
-            .apply(scope, self.generator_iter.clone(), vec![promise_value])
-            .root(scope)
-            .and_then(|result| {
-                result
-                    .get_property(scope, PropertyKey::String(sym::value.into()))
-                    .root(scope)
-            });
-
-        // - Repeat what we are doing above.
-        // Check if generator iterator is done:
-        //   - If yes, we can resolve the inner promise.
-        //   - If no, promisify value and attach then handler
-        // If value is an error, we can reject promise.
-
-        match value {
-            Ok(value) => {
-                let is_done = as_generator(scope, &self.generator_iter)
-                    .map(|gen| matches!(&*gen.state().borrow(), GeneratorState::Finished))?;
-
-                if is_done {
-                    // Promise in resolved state
-                    // TODO: value might be a promise
-                    scope.drive_promise(
-                        PromiseAction::Resolve,
-                        self.final_promise.as_any().downcast_ref::<Promise>().unwrap(),
-                        vec![value],
-                    );
-                } else {
-                    let then_task = ThenTask::new(scope, self.generator_iter.clone(), self.final_promise.clone());
-                    let then_task = scope.register(then_task);
-                    let value = wrap_promise(scope, value);
-
-                    scope
-                        .statics
-                        .promise_then
-                        .clone()
-                        .apply(scope, value, vec![Value::Object(then_task)])?;
-                }
+        let settled = args.first().unwrap_or_undefined();
+        let kind = match self.rejected {
+            true => GeneratorResumeKind::Throw(settled),
+            false => GeneratorResumeKind::Next(settled),
+        };
+        let final_promise = self.final_promise.as_any().downcast_ref::<Promise>().unwrap();
+
+        match resume(scope, &self.generator_iter, kind) {
+            Ok(GeneratorResumeResult::Return(value)) => {
+                // Promise in resolved state
+                // TODO: value might be a promise
+                scope.drive_promise(PromiseAction::Resolve, final_promise, vec![value]);
+            }
+            Ok(GeneratorResumeResult::Await(value) | GeneratorResumeResult::Yield(value)) => {
+                await_value(scope, self.generator_iter.clone(), self.final_promise.clone(), value);
             }
             Err(value) => {
                 // Promise in rejected state
-                scope.drive_promise(
-                    PromiseAction::Reject,
-                    self.final_promise.as_any().downcast_ref::<Promise>().unwrap(),
-                    vec![value],
-                );
+                scope.drive_promise(PromiseAction::Reject, final_promise, vec![value]);
             }
         }
 
@@ -0,0 +1,263 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+use dash_proc_macro::Trace;
+
+use crate::gc::handle::Handle;
+use crate::js_std::generator::{create_generator_value, resume, GeneratorResumeKind, GeneratorResumeResult};
+use crate::js_std::promise::subscribe;
+use crate::localscope::LocalScope;
+use crate::value::object::{NamedObject, Object};
+use crate::value::promise::Promise;
+use crate::value::{Typeof, Unrooted, Value, ValueContext};
+use crate::{delegate, PromiseAction, Vm};
+
+use super::generator::GeneratorFunction;
+use super::user::UserFunction;
+
+#[derive(Debug, Trace)]
+pub struct AsyncGeneratorFunction {
+    /// Async generators are driven by a regular generator, which suspends at both `yield` and `await`
+    inner: GeneratorFunction,
+}
+
+impl AsyncGeneratorFunction {
+    pub fn new(function: UserFunction) -> Self {
+        Self {
+            inner: GeneratorFunction::new(function),
+        }
+    }
+
+    pub fn inner(&self) -> &GeneratorFunction {
+        &self.inner
+    }
+
+    pub(crate) fn handle_function_call(
+        &self,
+        scope: &mut LocalScope,
+        callee: Handle,
+        this: Value,
+        args: Vec<Value>,
+        is_constructor_call: bool,
+    ) -> Result<Value, Unrooted> {
+        let generator = self
+            .inner
+            .handle_function_call(scope, callee.clone(), this, args, is_constructor_call)?;
+
+        let iter = AsyncGeneratorIterator::new(scope, callee, generator);
+        Ok(Value::Object(scope.register(iter)))
+    }
+}
+
+/// The object returned by calling an async generator function.
+///
+/// Calls to `next()` are queued and processed one after another.
+/// Each call returns a promise that is resolved once the generator reaches the next `yield` or returns.
+#[derive(Debug, Trace)]
+pub struct AsyncGeneratorIterator {
+    /// The inner generator iterator
+    generator: Value,
+    /// Pending `next()` calls: the value passed to `next()` and the promise returned by it
+    queue: RefCell<VecDeque<(Value, Handle)>>,
+    /// Whether the generator is currently executing or suspended at an `await`
+    running: Cell<bool>,
+    obj: NamedObject,
+}
+
+impl AsyncGeneratorIterator {
+    pub fn new(vm: &Vm, function: Handle, generator: Value) -> Self {
+        let proto = vm.statics.async_generator_iterator_prototype.clone();
+
+        Self {
+            generator,
+            queue: RefCell::new(VecDeque::new()),
+            running: Cell::new(false),
+            obj: NamedObject::with_prototype_and_constructor(proto, function),
+        }
+    }
+
+    /// Queues a `next()` call and starts processing it, unless the generator is already running
+    pub(crate) fn enqueue(
+        &self,
+        scope: &mut LocalScope,
+        this: Handle,
+        arg: Value,
+        promise: Handle,
+    ) -> Result<(), Value> {
+        self.queue.borrow_mut().push_back((arg, promise));
+
+        if self.running.get() {
+            // The request will be processed once the current one is done
+            return Ok(());
+        }
+
+        self.run(scope, this, None)
+    }
+
+    /// Resumes the generator for the queued requests, until it is suspended at an `await` or `yield`
+    /// or no requests are left.
+    ///
+    /// `resumption` is how the `await` or `yield` the generator is currently suspended at completes, if it is resumed
+    /// because an awaited value settled rather than for the next request.
+    fn run(
+        &self,
+        scope: &mut LocalScope,
+        this: Handle,
+        mut resumption: Option<GeneratorResumeKind>,
+    ) -> Result<(), Value> {
+        loop {
+            let Some((arg, _)) = self.queue.borrow().front().cloned() else {
+                self.running.set(false);
+                return Ok(());
+            };
+            self.running.set(true);
+
+            let kind = resumption.take().unwrap_or(GeneratorResumeKind::Next(arg));
+            let (result, done) = match resume(scope, &self.generator, kind) {
+                Ok(GeneratorResumeResult::Await(value)) => {
+                    await_value(scope, this, value, false);
+                    return Ok(());
+                }
+                Ok(GeneratorResumeResult::Yield(value)) => {
+                    // `yield` awaits its operand before passing it to the consumer
+                    await_value(scope, this, value, true);
+                    return Ok(());
+                }
+                Ok(GeneratorResumeResult::Return(value)) => (Ok(value), true),
+                Err(value) => (Err(value), true),
+            };
+
+            self.settle_request(scope, result, done)?;
+        }
+    }
+
+    /// Settles the promise returned by the oldest pending `next()` call
+    fn settle_request(&self, scope: &mut LocalScope, result: Result<Value, Value>, done: bool) -> Result<(), Value> {
+        let (_, promise) = self
+            .queue
+            .borrow_mut()
+            .pop_front()
+            .expect("a request is pending while the generator runs");
+        let promise = promise.as_any().downcast_ref::<Promise>().unwrap();
+
+        match result {
+            Ok(value) => {
+                let value = create_generator_value(scope, done, Some(value))?;
+                scope.drive_promise(PromiseAction::Resolve, promise, vec![value]);
+            }
+            Err(value) => scope.drive_promise(PromiseAction::Reject, promise, vec![value]),
+        }
+
+        Ok(())
+    }
+}
+
+/// Continues running the generator once the value it awaits or yields settles
+fn await_value(scope: &mut LocalScope, generator_iter: Handle, value: Value, yielded: bool) {
+    let (on_resolve, on_reject) = {
+        let t1 = AwaitTask::new(scope, generator_iter.clone(), yielded, false);
+        let t2 = AwaitTask::new(scope, generator_iter, yielded, true);
+        (scope.register(t1), scope.register(t2))
+    };
+    subscribe(scope, value, on_resolve, on_reject);
+}
+
+impl Object for AsyncGeneratorIterator {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        scope: &mut LocalScope,
+        callee: Handle,
+        this: Value,
+        args: Vec<Value>,
+    ) -> Result<Unrooted, Unrooted> {
+        self.obj.apply(scope, callee, this, args)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn type_of(&self) -> Typeof {
+        Typeof::Object
+    }
+}
+
+/// A callable object that is subscribed to values awaited or yielded in an async generator.
+/// Calling this resumes the generator with the settled value, or passes a yielded value to the consumer.
+#[derive(Debug, Trace)]
+struct AwaitTask {
+    /// The [`AsyncGeneratorIterator`] suspended at the `await` or `yield`
+    generator_iter: Handle,
+    /// Whether the value is the operand of a `yield` rather than an `await`
+    yielded: bool,
+    /// Whether this handles the rejection of the value, in which case the reason is thrown into the generator
+    rejected: bool,
+    obj: NamedObject,
+}
+
+impl AwaitTask {
+    fn new(vm: &Vm, generator_iter: Handle, yielded: bool, rejected: bool) -> Self {
+        Self {
+            generator_iter,
+            yielded,
+            rejected,
+            obj: NamedObject::new(vm),
+        }
+    }
+}
+
+impl Object for AwaitTask {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        as_any,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        scope: &mut LocalScope,
+        _callee: Handle,
+        _this: Value,
+        args: Vec<Value>,
+    ) -> Result<Unrooted, Unrooted> {
+        let value = args.first().unwrap_or_undefined();
+        let this = self.generator_iter.clone();
+        let generator_iter = this.as_any().downcast_ref::<AsyncGeneratorIterator>().unwrap();
+
+        if self.rejected {
+            generator_iter.run(scope, this.clone(), Some(GeneratorResumeKind::Throw(value)))?;
+        } else if self.yielded {
+            // The generator stays suspended at the `yield` until the next request
+            generator_iter.settle_request(scope, Ok(value), false)?;
+            generator_iter.run(scope, this.clone(), None)?;
+        } else {
+            generator_iter.run(scope, this.clone(), Some(GeneratorResumeKind::Next(value)))?;
+        }
+
+        Ok(Value::undefined().into())
+    }
+
+    fn type_of(&self) -> Typeof {
+        Typeof::Function
+    }
+}
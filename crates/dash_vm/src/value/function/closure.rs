@@ -1,6 +1,7 @@
 use dash_proc_macro::Trace;
 
 use crate::dispatch::HandleResult;
+use crate::gc::handle::Handle;
 use crate::localscope::LocalScope;
 use crate::value::{Unrooted, Value};
 
@@ -10,6 +11,8 @@ use super::user::UserFunction;
 pub struct Closure {
     pub fun: UserFunction,
     pub this: Value,
+    /// The `arguments` object of the enclosing function, which arrow functions capture lexically
+    pub arguments: Option<Handle>,
 }
 
 impl Closure {
@@ -20,9 +23,13 @@ impl Closure {
         args: Vec<Value>,
         is_constructor_call: bool,
     ) -> Result<Unrooted, Unrooted> {
-        let ret = self
-            .fun
-            .handle_function_call(scope, self.this.clone(), args, is_constructor_call)?;
+        let ret = self.fun.handle_function_call_with_arguments(
+            scope,
+            self.this.clone(),
+            args,
+            self.arguments.clone(),
+            is_constructor_call,
+        )?;
 
         Ok(match ret {
             HandleResult::Return(v) => v,
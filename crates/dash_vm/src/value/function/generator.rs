@@ -33,9 +33,9 @@ impl GeneratorFunction {
         &self,
         scope: &mut LocalScope,
         callee: Handle,
-        _this: Value,
+        this: Value,
         args: Vec<Value>,
-        _is_constructor_call: bool,
+        is_constructor_call: bool,
     ) -> Result<Value, Unrooted> {
         let mut arguments = None;
         if self.function.inner().references_arguments {
@@ -44,6 +44,20 @@ impl GeneratorFunction {
             arguments = Some(args);
         }
 
+        self.handle_function_call_with_arguments(scope, callee, this, args, arguments, is_constructor_call)
+    }
+
+    /// Creates the generator iterator with an already constructed `arguments` object,
+    /// such as the one lexically captured by async arrow functions
+    pub(crate) fn handle_function_call_with_arguments(
+        &self,
+        scope: &mut LocalScope,
+        callee: Handle,
+        this: Value,
+        args: Vec<Value>,
+        arguments: Option<Handle>,
+        _is_constructor_call: bool,
+    ) -> Result<Value, Unrooted> {
         // Handle edge cases such as provided_args != expected_args
         // by delegating to the usual arg handling logic that occurs with normal user functions
         let args = {
@@ -55,7 +69,7 @@ impl GeneratorFunction {
             scope.stack.drain(sp..).collect::<Vec<_>>()
         };
 
-        let iter = GeneratorIterator::new(callee, scope, args, arguments, Vec::new());
+        let iter = GeneratorIterator::new(callee, scope, this, args, arguments, Vec::new());
         Ok(Value::Object(scope.register(iter)))
     }
 }
@@ -101,6 +115,8 @@ unsafe impl Trace for GeneratorState {
 #[derive(Debug, Trace)]
 pub struct GeneratorIterator {
     function: Handle,
+    /// The `this` value the generator function was called with
+    this: Value,
     obj: NamedObject,
     state: RefCell<GeneratorState>,
 }
@@ -109,6 +125,7 @@ impl GeneratorIterator {
     pub fn new(
         function: Handle,
         vm: &Vm,
+        this: Value,
         stack: Vec<Value>,
         arguments: Option<Handle>,
         try_blocks: Vec<TryBlock>,
@@ -118,6 +135,7 @@ impl GeneratorIterator {
 
         Self {
             function,
+            this,
             obj: NamedObject::with_prototype_and_constructor(proto, ctor),
             state: RefCell::new(GeneratorState::Running {
                 ip: 0,
@@ -131,6 +149,7 @@ impl GeneratorIterator {
     pub fn empty(function: Handle) -> Self {
         Self {
             function,
+            this: Value::undefined(),
             obj: NamedObject::null(),
             state: RefCell::new(GeneratorState::Finished),
         }
@@ -144,6 +163,10 @@ impl GeneratorIterator {
         self.function.clone()
     }
 
+    pub fn this(&self) -> Value {
+        self.this.clone()
+    }
+
     pub fn did_run(&self) -> bool {
         self.state.borrow().did_run()
     }
@@ -15,6 +15,7 @@ use crate::value::arguments::Arguments;
 use crate::Vm;
 
 use self::r#async::AsyncFunction;
+use self::async_generator::AsyncGeneratorFunction;
 use self::closure::Closure;
 use self::generator::GeneratorFunction;
 use self::native::{CallContext, NativeFunction};
@@ -27,6 +28,7 @@ use super::string::JsString;
 use super::{Root, Typeof, Unrooted, Value};
 
 pub mod r#async;
+pub mod async_generator;
 pub mod bound;
 pub mod closure;
 pub mod generator;
@@ -38,6 +40,7 @@ pub enum FunctionKind {
     User(UserFunction),
     Generator(GeneratorFunction),
     Async(AsyncFunction),
+    AsyncGenerator(AsyncGeneratorFunction),
     Closure(Closure),
 }
 
@@ -47,6 +50,7 @@ unsafe impl Trace for FunctionKind {
             Self::User(user) => user.trace(cx),
             Self::Generator(generator) => generator.trace(cx),
             Self::Async(async_) => async_.trace(cx),
+            Self::AsyncGenerator(generator) => generator.trace(cx),
             Self::Native(_) => {}
             Self::Closure(user) => user.trace(cx),
         }
@@ -90,6 +94,7 @@ impl fmt::Debug for FunctionKind {
             Self::User(..) => f.write_str("UserFunction"),
             Self::Generator(..) => f.write_str("GeneratorFunction"),
             Self::Async(..) => f.write_str("AsyncFunction"),
+            Self::AsyncGenerator(..) => f.write_str("AsyncGeneratorFunction"),
             Self::Closure(..) => f.write_str("closure"),
         }
     }
@@ -193,6 +198,9 @@ fn handle_call(
         FunctionKind::Generator(fun) => fun
             .handle_function_call(scope, callee, this, args, is_constructor_call)
             .map(Into::into),
+        FunctionKind::AsyncGenerator(fun) => fun
+            .handle_function_call(scope, callee, this, args, is_constructor_call)
+            .map(Into::into),
         FunctionKind::Closure(fun) => fun.handle_function_call(scope, this, args, is_constructor_call),
     }
 }
@@ -1,13 +1,21 @@
+use std::any::Any;
+use std::fmt;
+
 use dash_middle::interner::Symbol;
 
+use crate::delegate;
 use crate::gc::handle::Handle;
+use crate::gc::trace::{Trace, TraceCtxt};
 use crate::localscope::LocalScope;
-use crate::value::Value;
+use crate::value::object::{NamedObject, Object};
+use crate::value::{Typeof, Unrooted, Value};
+use crate::Vm;
 
 use super::{Function, FunctionKind};
 
 // TODO: return Unrooted?
 pub type NativeFunction = fn(cx: CallContext) -> Result<Value, Value>;
+pub type NativeClosureFn = dyn Fn(CallContext) -> Result<Value, Value>;
 
 pub fn register_native_fn(sc: &mut LocalScope<'_>, name: Symbol, fun: NativeFunction) -> Handle {
     let fun = Function::new(sc, Some(name.into()), FunctionKind::Native(fun));
@@ -41,3 +49,69 @@ impl<'s, 'c> CallContext<'s, 'c> {
         }
     }
 }
+
+/// A native function backed by a Rust closure, which unlike [`NativeFunction`] can capture state.
+///
+/// Captured values are not traced by the garbage collector, so the closure must not hold on to any handles.
+pub struct NativeClosure {
+    fun: Box<NativeClosureFn>,
+    obj: NamedObject,
+}
+
+impl NativeClosure {
+    pub fn new(vm: &Vm, fun: impl Fn(CallContext) -> Result<Value, Value> + 'static) -> Self {
+        let (proto, ctor) = (&vm.statics.function_proto, &vm.statics.function_ctor);
+        Self {
+            fun: Box::new(fun),
+            obj: NamedObject::with_prototype_and_constructor(proto.clone(), ctor.clone()),
+        }
+    }
+}
+
+impl fmt::Debug for NativeClosure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativeClosure")
+            .field("obj", &self.obj)
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe impl Trace for NativeClosure {
+    fn trace(&self, cx: &mut TraceCtxt<'_>) {
+        self.obj.trace(cx);
+    }
+}
+
+impl Object for NativeClosure {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        scope: &mut LocalScope,
+        _callee: Handle,
+        this: Value,
+        args: Vec<Value>,
+    ) -> Result<Unrooted, Unrooted> {
+        (self.fun)(CallContext::call(args, scope, this))
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn type_of(&self) -> Typeof {
+        Typeof::Function
+    }
+}
@@ -5,6 +5,7 @@ use dash_proc_macro::Trace;
 
 use crate::dispatch::HandleResult;
 use crate::frame::Frame;
+use crate::gc::handle::Handle;
 use crate::localscope::LocalScope;
 use crate::value::arguments::Arguments;
 use crate::value::{ExternalValue, Root, Value};
@@ -37,8 +38,6 @@ impl UserFunction {
         args: Vec<Value>,
         is_constructor_call: bool,
     ) -> Result<HandleResult, Value> {
-        let sp = scope.stack.len();
-
         let mut arguments = None;
         if self.inner.references_arguments {
             let args = Arguments::new(scope, args.iter().cloned());
@@ -46,6 +45,21 @@ impl UserFunction {
             arguments = Some(args);
         }
 
+        self.handle_function_call_with_arguments(scope, this, args, arguments, is_constructor_call)
+    }
+
+    /// Calls this function with an already constructed `arguments` object,
+    /// such as the one lexically captured by arrow functions
+    pub(crate) fn handle_function_call_with_arguments(
+        &self,
+        scope: &mut LocalScope,
+        this: Value,
+        args: Vec<Value>,
+        arguments: Option<Handle>,
+        is_constructor_call: bool,
+    ) -> Result<HandleResult, Value> {
+        let sp = scope.stack.len();
+
         extend_stack_from_args(args, self.inner.params, scope, self.inner.rest_local.is_some());
 
         let mut frame = Frame::from_function(Some(this), self, is_constructor_call, false, arguments);
@@ -0,0 +1,74 @@
+use crate::gc::interner::sym;
+use crate::localscope::LocalScope;
+use crate::throw;
+
+use super::{Root, Typeof, Value};
+
+/// Drives a JavaScript iterator from Rust, created with [`LocalScope::iterate`].
+///
+/// Every item is a `Result`, since calling `next()` or reading its result can throw.
+/// Iteration ends after the first error.
+pub struct JsIterator<'a, 'vm> {
+    scope: &'a mut LocalScope<'vm>,
+    iterator: Value,
+    next: Value,
+    done: bool,
+}
+
+impl<'vm> JsIterator<'_, 'vm> {
+    /// Returns the scope this iterator borrows, so that it can still be used in the body of a loop
+    pub fn scope(&mut self) -> &mut LocalScope<'vm> {
+        self.scope
+    }
+
+    fn step(&mut self) -> Result<Option<Value>, Value> {
+        let scope = &mut *self.scope;
+        let result = self.next.apply(scope, self.iterator.clone(), Vec::new()).root(scope)?;
+        if result
+            .get_property(scope, sym::done.into())
+            .root(scope)?
+            .is_truthy(scope)
+        {
+            return Ok(None);
+        }
+        result.get_property(scope, sym::value.into()).root(scope).map(Some)
+    }
+}
+
+impl Iterator for JsIterator<'_, '_> {
+    type Item = Result<Value, Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = self.step().transpose();
+        self.done = !matches!(item, Some(Ok(_)));
+        item
+    }
+}
+
+impl<'vm> LocalScope<'vm> {
+    /// Starts iterating over a value using the iterator protocol, i.e. by calling its `@@iterator` method
+    /// and then repeatedly calling `next()` on the returned iterator.
+    ///
+    /// Throws a `TypeError` if the value is not iterable.
+    pub fn iterate(&mut self, iterable: Value) -> Result<JsIterator<'_, 'vm>, Value> {
+        let symbol_iterator = self.statics.symbol_iterator.clone();
+        let iterator_fn = iterable.get_property(self, symbol_iterator.into()).root(self)?;
+        if !matches!(iterator_fn.type_of(), Typeof::Function) {
+            throw!(self, TypeError, "Value is not iterable");
+        }
+
+        let iterator = iterator_fn.apply(self, iterable, Vec::new()).root(self)?;
+        let next = iterator.get_property(self, sym::next.into()).root(self)?;
+
+        Ok(JsIterator {
+            scope: self,
+            iterator,
+            next,
+            done: false,
+        })
+    }
+}
@@ -48,6 +48,14 @@ impl Map {
         self.inner.borrow_mut().clear();
     }
 
+    pub fn entries(&self) -> Vec<(Value, Value)> {
+        self.inner
+            .borrow()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+
     pub fn size(&self) -> usize {
         self.inner.borrow().len()
     }
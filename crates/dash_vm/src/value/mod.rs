@@ -3,9 +3,12 @@ pub mod array;
 pub mod arraybuffer;
 pub mod boxed;
 pub mod conversions;
+pub mod date;
 pub mod error;
+pub mod finalization_registry;
 pub mod function;
 pub mod inspect;
+pub mod iterator;
 pub mod map;
 pub mod object;
 pub mod ops;
@@ -13,7 +16,11 @@ pub mod primitive;
 pub mod promise;
 pub mod regex;
 pub mod set;
+pub mod shape;
 pub mod typedarray;
+pub mod weakmap;
+pub mod weakref;
+pub mod weakset;
 
 use std::any::TypeId;
 use std::ops::ControlFlow;
@@ -34,6 +41,7 @@ use crate::value::primitive::{Null, Undefined};
 use crate::{delegate, throw};
 
 use self::function::r#async::AsyncFunction;
+use self::function::async_generator::AsyncGeneratorFunction;
 use self::function::closure::Closure;
 use self::function::generator::GeneratorFunction;
 use self::function::user::UserFunction;
@@ -69,6 +77,11 @@ pub enum Value {
     External(ExternalValue),
 }
 
+// The JIT backend hardcodes the layout of a value as `{ i8, i64, i64 }`,
+// so growing any of the variants must not go unnoticed
+#[cfg(feature = "jit")]
+const _: () = assert!(std::mem::size_of::<Value>() == 24);
+
 impl Object for Value {
     fn get_own_property_descriptor(
         &self,
@@ -423,11 +436,22 @@ impl Value {
                 let kind = match ty {
                     ParserFunctionKind::Function(Asyncness::Yes) => FunctionKind::Async(AsyncFunction::new(fun)),
                     ParserFunctionKind::Function(Asyncness::No) => FunctionKind::User(fun),
-                    ParserFunctionKind::Arrow => FunctionKind::Closure(Closure {
+                    ParserFunctionKind::Arrow(Asyncness::No) => FunctionKind::Closure(Closure {
                         fun,
                         this: sc.active_frame().this.clone().unwrap_or_undefined(),
+                        arguments: sc.active_frame().arguments.clone(),
                     }),
-                    ParserFunctionKind::Generator => FunctionKind::Generator(GeneratorFunction::new(fun)),
+                    ParserFunctionKind::Arrow(Asyncness::Yes) => FunctionKind::Async(AsyncFunction::arrow(
+                        fun,
+                        sc.active_frame().this.clone().unwrap_or_undefined(),
+                        sc.active_frame().arguments.clone(),
+                    )),
+                    ParserFunctionKind::Generator(Asyncness::No) => {
+                        FunctionKind::Generator(GeneratorFunction::new(fun))
+                    }
+                    ParserFunctionKind::Generator(Asyncness::Yes) => {
+                        FunctionKind::AsyncGenerator(AsyncGeneratorFunction::new(fun))
+                    }
                 };
 
                 let function = Function::new(sc, name, kind);
@@ -544,7 +568,7 @@ impl Value {
             Value::Object(_) => true,
             Value::Undefined(_) => false,
             Value::Null(_) => false,
-            Value::External(_) => todo!(),
+            Value::External(e) => e.inner().is_truthy(sc),
         }
     }
 
@@ -2,13 +2,13 @@ use std::any::Any;
 use std::cell::RefCell;
 use std::fmt::Debug;
 use std::hash::BuildHasherDefault;
+use std::rc::Rc;
 
 use crate::gc::interner::sym;
 use crate::gc::persistent::Persistent;
 use crate::gc::trace::{Trace, TraceCtxt};
 use bitflags::bitflags;
 use dash_proc_macro::Trace;
-use hashbrown::hash_map::Entry;
 use rustc_hash::FxHasher;
 
 use crate::gc::handle::Handle;
@@ -17,6 +17,7 @@ use crate::{throw, Vm};
 
 use super::ops::conversions::ValueConversion;
 use super::primitive::{PrimitiveCapabilities, Symbol};
+use super::shape::Shape;
 use super::string::JsString;
 use super::{Root, Typeof, Unrooted, Value, ValueContext};
 
@@ -211,7 +212,9 @@ macro_rules! delegate {
 pub struct NamedObject {
     prototype: RefCell<Option<Handle>>,
     constructor: RefCell<Option<Handle>>,
-    values: RefCell<ObjectMap<PropertyKey, PropertyValue>>,
+    shape: RefCell<Rc<Shape>>,
+    /// Property values, indexed by the slots of the shape
+    values: RefCell<Vec<PropertyValue>>,
 }
 
 // TODO: optimization opportunity: some kind of Number variant for faster indexing without .to_string()
@@ -506,22 +509,61 @@ impl PropertyKey {
             other => Ok(PropertyKey::String(other.to_js_string(sc)?)),
         }
     }
+
+    /// Returns the numeric value of this key if it is an array index,
+    /// i.e. the canonical string form of an integer in the range `0..2^32 - 1`
+    pub fn as_array_index(&self, sc: &LocalScope) -> Option<u32> {
+        let s = self.as_string()?.res(sc);
+        // Reject non-canonical forms such as `01` or `+1`, which are ordinary string keys
+        if !s.bytes().all(|b| b.is_ascii_digit()) || (s.len() > 1 && s.starts_with('0')) {
+            return None;
+        }
+        let index = s.parse::<u32>().ok()?;
+        (index != u32::MAX).then_some(index)
+    }
+}
+
+/// Orders property keys the way the spec requires for own keys:
+/// array indices in ascending numeric order, then string keys and then symbols, both in insertion order
+fn ordered_own_keys<'a>(sc: &LocalScope, keys: impl IntoIterator<Item = &'a PropertyKey>) -> Vec<Value> {
+    let mut indices = Vec::new();
+    let mut strings = Vec::new();
+    let mut symbols = Vec::new();
+
+    for key in keys {
+        match key {
+            PropertyKey::String(_) => match key.as_array_index(sc) {
+                Some(index) => indices.push((index, key.as_value())),
+                None => strings.push(key.as_value()),
+            },
+            // Private names are not observable through reflection
+            PropertyKey::Symbol(symbol) if symbol.is_private() => {}
+            PropertyKey::Symbol(_) => symbols.push(key.as_value()),
+        }
+    }
+
+    indices.sort_by_key(|&(index, _)| index);
+    indices
+        .into_iter()
+        .map(|(_, key)| key)
+        .chain(strings)
+        .chain(symbols)
+        .collect()
 }
 
 impl NamedObject {
     pub fn new(vm: &Vm) -> Self {
-        Self::with_values(vm, ObjectMap::default())
+        Self::with_values(vm, [])
     }
 
-    pub fn with_values(vm: &Vm, values: ObjectMap<PropertyKey, PropertyValue>) -> Self {
+    /// Creates an object with the given properties, added in iteration order
+    pub fn with_values(vm: &Vm, values: impl IntoIterator<Item = (PropertyKey, PropertyValue)>) -> Self {
         let objp = vm.statics.object_prototype.clone();
         let objc = vm.statics.object_ctor.clone(); // TODO: function_ctor instead
 
-        Self {
-            prototype: RefCell::new(Some(objp)),
-            constructor: RefCell::new(Some(objc)),
-            values: RefCell::new(values),
-        }
+        let this = Self::with_prototype_and_constructor(objp, objc);
+        this.extend(values);
+        this
     }
 
     /// Creates an empty object with a null prototype
@@ -529,28 +571,75 @@ impl NamedObject {
         Self {
             prototype: RefCell::new(None),
             constructor: RefCell::new(None),
-            values: RefCell::new(ObjectMap::default()),
+            shape: RefCell::new(Shape::empty()),
+            values: RefCell::new(Vec::new()),
         }
     }
 
-    pub fn null_with_values(values: ObjectMap<PropertyKey, PropertyValue>) -> Self {
-        Self {
-            prototype: RefCell::new(None),
-            constructor: RefCell::new(None),
-            values: RefCell::new(values),
-        }
+    pub fn null_with_values(values: impl IntoIterator<Item = (PropertyKey, PropertyValue)>) -> Self {
+        let this = Self::null();
+        this.extend(values);
+        this
     }
 
     pub fn with_prototype_and_constructor(prototype: Handle, ctor: Handle) -> Self {
         Self {
             constructor: RefCell::new(Some(ctor)),
             prototype: RefCell::new(Some(prototype)),
-            values: RefCell::new(ObjectMap::default()),
+            shape: RefCell::new(Shape::empty()),
+            values: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn extend(&self, values: impl IntoIterator<Item = (PropertyKey, PropertyValue)>) {
+        for (key, value) in values {
+            self.set_raw_property(key, value);
         }
     }
 
     pub fn get_raw_property(&self, pk: PropertyKey) -> Option<PropertyValue> {
-        self.values.borrow().get(&pk).cloned()
+        let slot = self.shape.borrow().lookup(&pk)?;
+        self.values.borrow().get(slot as usize).cloned()
+    }
+
+    /// Defines an own property, bypassing special keys such as `__proto__` and the writable flag of an existing property.
+    ///
+    /// Redefining a property keeps its position.
+    pub fn set_raw_property(&self, pk: PropertyKey, value: PropertyValue) {
+        let mut shape = self.shape.borrow_mut();
+        let mut values = self.values.borrow_mut();
+        match shape.lookup(&pk) {
+            Some(slot) => values[slot as usize] = value,
+            None => {
+                Shape::add_property(&mut shape, pk);
+                values.push(value);
+            }
+        }
+    }
+
+    /// Returns the shape that describes the layout of this object's own properties
+    pub fn shape(&self) -> Rc<Shape> {
+        self.shape.borrow().clone()
+    }
+
+    /// Returns the id of the current shape of this object
+    pub fn shape_id(&self) -> u64 {
+        self.shape.borrow().id()
+    }
+
+    /// Returns the slot that the own property `key` is stored in
+    pub fn lookup_slot(&self, key: &PropertyKey) -> Option<u32> {
+        self.shape.borrow().lookup(key)
+    }
+
+    /// Returns the property stored in `slot`
+    pub fn get_slot(&self, slot: u32) -> Option<PropertyValue> {
+        self.values.borrow().get(slot as usize).cloned()
+    }
+
+    /// Returns the prototype of this object, without going through [`Object::get_prototype`]
+    pub fn raw_prototype(&self) -> Option<Handle> {
+        self.prototype.borrow().clone()
     }
 }
 
@@ -559,8 +648,10 @@ unsafe impl Trace for NamedObject {
         let Self {
             prototype,
             constructor,
+            shape,
             values,
         } = self;
+        shape.borrow().keys().trace(cx);
         values.trace(cx);
         prototype.trace(cx);
         constructor.trace(cx);
@@ -589,12 +680,7 @@ impl Object for NamedObject {
             }
         };
 
-        let values = self.values.borrow();
-        if let Some(value) = values.get(&key).cloned() {
-            return Ok(Some(value));
-        }
-
-        Ok(None)
+        Ok(self.get_raw_property(key))
     }
 
     fn set_property(&self, sc: &mut LocalScope, key: PropertyKey, value: PropertyValue) -> Result<(), Value> {
@@ -622,21 +708,26 @@ impl Object for NamedObject {
 
         // TODO: check if we are invoking a setter
 
-        let mut map = self.values.borrow_mut();
-        match map.entry(key) {
-            Entry::Occupied(mut entry) => {
-                if entry.get().descriptor.contains(PropertyDataDescriptor::WRITABLE) {
-                    entry.insert(value);
+        let mut shape = self.shape.borrow_mut();
+        let mut values = self.values.borrow_mut();
+        match shape.lookup(&key) {
+            Some(slot) => {
+                let entry = &mut values[slot as usize];
+                if entry.descriptor.contains(PropertyDataDescriptor::WRITABLE) {
+                    *entry = value;
                 }
             }
-            Entry::Vacant(vacant) => drop(vacant.insert(value)),
+            None => {
+                Shape::add_property(&mut shape, key);
+                values.push(value);
+            }
         }
         Ok(())
     }
 
     fn delete_property(&self, sc: &mut LocalScope, key: PropertyKey) -> Result<Unrooted, Value> {
-        let mut values = self.values.borrow_mut();
-        let value = values.remove(&key);
+        let slot = Shape::remove_property(&mut self.shape.borrow_mut(), &key);
+        let value = slot.map(|slot| self.values.borrow_mut().remove(slot as usize));
 
         match value.map(PropertyValue::into_kind) {
             Some(PropertyValueKind::Static(value)) => {
@@ -695,9 +786,9 @@ impl Object for NamedObject {
         }
     }
 
-    fn own_keys(&self, _: &mut LocalScope<'_>) -> Result<Vec<Value>, Value> {
-        let values = self.values.borrow();
-        Ok(values.keys().map(PropertyKey::as_value).collect())
+    fn own_keys(&self, sc: &mut LocalScope<'_>) -> Result<Vec<Value>, Value> {
+        let shape = self.shape.borrow();
+        Ok(ordered_own_keys(sc, shape.keys().iter()))
     }
 }
 
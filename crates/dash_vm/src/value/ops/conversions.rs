@@ -60,7 +60,16 @@ pub trait ValueConversion {
 
     fn to_int32(&self, sc: &mut LocalScope) -> Result<i32, Value> {
         let n = self.to_number(sc)?;
-        Ok(n as i64 as i32)
+        if !n.is_finite() {
+            return Ok(0);
+        }
+
+        // Reduce modulo 2^32 first so that the integer conversion wraps instead of saturating
+        Ok((n.trunc() % 4294967296.0) as i64 as i32)
+    }
+
+    fn to_uint32(&self, sc: &mut LocalScope) -> Result<u32, Value> {
+        self.to_int32(sc).map(|n| n as u32)
     }
 }
 
@@ -80,10 +89,7 @@ impl ValueConversion for Value {
             Value::Undefined(_) => Ok(f64::NAN),
             Value::Null(_) => Ok(0.0),
             Value::Boolean(b) => Ok(*b as i8 as f64),
-            Value::String(s) => match s.len(sc) {
-                0 => Ok(0.0),
-                _ => Ok(s.res(sc).parse::<f64>().unwrap_or(f64::NAN)),
-            },
+            Value::String(s) => ValueConversion::to_number(s, sc),
             Value::Symbol(_) => throw!(sc, TypeError, "Cannot convert symbol to number"),
             Value::Object(o) => object_to_number(self, o, sc),
             Value::External(o) => object_to_number(self, &o.inner, sc),
@@ -99,7 +105,7 @@ impl ValueConversion for Value {
             Value::String(s) => Ok(!s.res(sc).is_empty()),
             Value::Symbol(_) => Ok(true),
             Value::Object(_) => Ok(true),
-            _ => todo!(), // TODO: implement other cases
+            Value::External(e) => e.inner().to_boolean(sc),
         }
     }
 
@@ -230,3 +236,48 @@ impl Value {
         throw!(sc, TypeError, "Failed to convert to primitive")
     }
 }
+
+/// Converts a string to a number, as specified by StringToNumber
+pub fn string_to_number(s: &str) -> f64 {
+    fn parse_radix(digits: &str, radix: u32) -> f64 {
+        if digits.is_empty() {
+            return f64::NAN;
+        }
+
+        let mut value = 0.0;
+        for c in digits.chars() {
+            match c.to_digit(radix) {
+                Some(digit) => value = value * radix as f64 + digit as f64,
+                None => return f64::NAN,
+            }
+        }
+        value
+    }
+
+    let s = s.trim_matches(|c: char| c.is_whitespace() || c == '\u{feff}');
+
+    match s.get(..2) {
+        Some("0x" | "0X") => return parse_radix(&s[2..], 16),
+        Some("0o" | "0O") => return parse_radix(&s[2..], 8),
+        Some("0b" | "0B") => return parse_radix(&s[2..], 2),
+        _ => {}
+    }
+
+    match s {
+        "" => 0.0,
+        "Infinity" | "+Infinity" => f64::INFINITY,
+        "-Infinity" => f64::NEG_INFINITY,
+        _ => {
+            // Rust's float parser is more lenient than the spec and accepts things like `inf` or `NaN`
+            let is_decimal_literal = s
+                .bytes()
+                .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'+' | b'-'));
+
+            if is_decimal_literal {
+                s.parse().unwrap_or(f64::NAN)
+            } else {
+                f64::NAN
+            }
+        }
+    }
+}
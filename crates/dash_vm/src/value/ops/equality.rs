@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 
 use crate::localscope::LocalScope;
+use crate::value::primitive::Number;
 use crate::value::Value;
 
 use super::conversions::ValueConversion;
@@ -88,6 +89,24 @@ pub fn strict_eq(left: &Value, right: &Value) -> bool {
     }
 }
 
+/// The SameValue comparison: strict equality, except that `NaN` is equal to itself and `+0` is not equal to `-0`
+pub fn same_value(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(Number(l)), Value::Number(Number(r))) => {
+            l.to_bits() == r.to_bits() || (l.is_nan() && r.is_nan())
+        }
+        _ => strict_eq(left, right),
+    }
+}
+
+/// The SameValueZero comparison: strict equality, except that `NaN` is equal to itself
+pub fn same_value_zero(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(Number(l)), Value::Number(Number(r))) => l == r || (l.is_nan() && r.is_nan()),
+        _ => strict_eq(left, right),
+    }
+}
+
 pub fn ne(left: &Value, right: &Value, sc: &mut LocalScope) -> Result<bool, Value> {
     eq(left, right, sc).map(|v| !v)
 }
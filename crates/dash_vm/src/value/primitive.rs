@@ -1,5 +1,6 @@
 use std::any::Any;
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{self, AtomicU64};
 use std::{fmt, iter};
 
 use dash_middle::interner;
@@ -373,16 +374,61 @@ impl Object for Null {
 // TODO: rename to JsSymbol
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Trace)]
 pub struct Symbol {
-    description: JsString,
+    /// A unique id that gives every symbol its own identity, regardless of the description.
+    /// This is 64 bits wide so that the counter cannot realistically wrap around and hand out an id twice.
+    ///
+    /// The highest bit marks private symbols, which back the private names of classes
+    /// and are never exposed to JavaScript code. It is part of the id rather than a separate field,
+    /// so that symbols don't make [`Value`] any larger.
+    id: u64,
+    description: Option<JsString>,
 }
 
+const PRIVATE_SYMBOL_BIT: u64 = 1 << 63;
+
 impl Symbol {
+    /// Returns the description of this symbol, or an empty string if it has none
     pub fn sym(&self) -> interner::Symbol {
-        self.description.sym()
+        self.description.map_or(sym::empty, |d| d.sym())
     }
 
     pub fn new(description: JsString) -> Self {
-        Symbol { description }
+        Self::with_description(Some(description))
+    }
+
+    /// Creates a new, unique symbol with an optional description
+    pub fn with_description(description: Option<JsString>) -> Self {
+        Self::create(description, false)
+    }
+
+    /// Creates a new, unique private symbol for a private name such as `#x`.
+    /// Properties keyed by private symbols are not listed in an object's own keys.
+    pub fn private(description: JsString) -> Self {
+        Self::create(Some(description), true)
+    }
+
+    fn create(description: Option<JsString>, private: bool) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        let id = NEXT_ID.fetch_add(1, atomic::Ordering::Relaxed);
+        Symbol {
+            id: if private { id | PRIVATE_SYMBOL_BIT } else { id },
+            description,
+        }
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.id & PRIVATE_SYMBOL_BIT != 0
+    }
+
+    pub fn description(&self) -> Option<JsString> {
+        self.description
+    }
+
+    /// Returns the string representation of this symbol, i.e. `Symbol(description)`
+    pub fn descriptive_string(&self, sc: &mut LocalScope) -> JsString {
+        let description = format!("Symbol({})", sc.interner.resolve(self.sym()));
+        sc.intern(description).into()
     }
 }
 
@@ -3,17 +3,22 @@ use std::cell::Cell;
 use dash_proc_macro::Trace;
 use dash_regex::{Flags, ParsedRegex};
 
+use crate::gc::interner::sym;
 use crate::gc::trace::{Trace, TraceCtxt};
+use crate::localscope::LocalScope;
 use crate::{delegate, Vm};
 
-use super::object::{NamedObject, Object};
+use super::object::{NamedObject, Object, PropertyDataDescriptor, PropertyKey, PropertyValue, PropertyValueKind};
+use super::ops::conversions::ValueConversion;
 use super::string::JsString;
+use super::{Root, Unrooted, Value};
 
 #[derive(Debug)]
 pub struct RegExpInner {
     pub regex: ParsedRegex,
     pub flags: Flags,
     pub source: JsString,
+    /// The `lastIndex` property, in UTF-16 code units
     pub last_index: Cell<usize>,
 }
 
@@ -64,12 +69,37 @@ impl RegExp {
 }
 
 impl Object for RegExp {
+    fn get_own_property_descriptor(
+        &self,
+        sc: &mut LocalScope,
+        key: PropertyKey,
+    ) -> Result<Option<PropertyValue>, Unrooted> {
+        if let (Some(inner), PropertyKey::String(s)) = (&self.inner, &key) {
+            if s.sym() == sym::lastIndex {
+                return Ok(Some(PropertyValue {
+                    kind: PropertyValueKind::Static(Value::number(inner.last_index.get() as f64)),
+                    descriptor: PropertyDataDescriptor::WRITABLE,
+                }));
+            }
+        }
+
+        self.object.get_own_property_descriptor(sc, key)
+    }
+
+    fn set_property(&self, sc: &mut LocalScope, key: PropertyKey, value: PropertyValue) -> Result<(), Value> {
+        if let (Some(inner), PropertyKey::String(s)) = (&self.inner, &key) {
+            if s.sym() == sym::lastIndex {
+                let value = value.kind().get_or_apply(sc, Value::undefined()).root(sc)?;
+                inner.last_index.set(value.to_length_u(sc)?);
+                return Ok(());
+            }
+        }
+
+        self.object.set_property(sc, key, value)
+    }
+
     delegate!(
         object,
-        get_own_property_descriptor,
-        get_property,
-        get_property_descriptor,
-        set_property,
         delete_property,
         set_prototype,
         get_prototype,
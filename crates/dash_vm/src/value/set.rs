@@ -44,6 +44,10 @@ impl Set {
         self.inner.borrow_mut().clear();
     }
 
+    pub fn values(&self) -> Vec<Value> {
+        self.inner.borrow().iter().cloned().collect()
+    }
+
     pub fn size(&self) -> usize {
         self.inner.borrow().len()
     }
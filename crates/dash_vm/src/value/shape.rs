@@ -0,0 +1,200 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::object::{ObjectMap, PropertyKey};
+
+/// Objects with more properties than this stop sharing shapes and get their own, mutable shape instead,
+/// so that building large objects doesn't keep copying the layout
+const MAX_SHARED_PROPERTIES: usize = 64;
+
+static SHAPE_ID: AtomicU64 = AtomicU64::new(1);
+
+fn next_shape_id() -> u64 {
+    SHAPE_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+thread_local! {
+    static EMPTY_SHAPE: Rc<Shape> = Rc::new(Shape::new(ObjectMap::default(), Vec::new(), None, false));
+}
+
+/// Describes the property layout of an object: which properties it has, and in which slot each property is stored.
+///
+/// Objects that had the same properties added in the same order share a shape, and adding a property
+/// transitions to the next shape in the tree. Every distinct layout has a unique id that is never reused,
+/// which makes it usable as a key for inline caches.
+#[derive(Debug)]
+pub struct Shape {
+    id: u64,
+    slots: ObjectMap<PropertyKey, u32>,
+    /// The keys in slot order
+    keys: Vec<PropertyKey>,
+    /// The shape that this shape was transitioned from.
+    /// This keeps the transition chain alive for as long as an object uses any shape in it.
+    parent: Option<Rc<Shape>>,
+    /// Shapes reached by adding a property to this shape.
+    /// Always empty for dictionary shapes. Shapes remove themselves from their parent's transitions when dropped.
+    transitions: RefCell<ObjectMap<PropertyKey, Weak<Shape>>>,
+    /// Dictionary shapes are owned by a single object and are modified in place
+    dictionary: bool,
+}
+
+impl Shape {
+    fn new(
+        slots: ObjectMap<PropertyKey, u32>,
+        keys: Vec<PropertyKey>,
+        parent: Option<Rc<Shape>>,
+        dictionary: bool,
+    ) -> Self {
+        Self {
+            id: next_shape_id(),
+            slots,
+            keys,
+            parent,
+            transitions: RefCell::new(ObjectMap::default()),
+            dictionary,
+        }
+    }
+
+    /// Returns the shape of an object without any properties
+    pub fn empty() -> Rc<Self> {
+        EMPTY_SHAPE.with(Rc::clone)
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn is_dictionary(&self) -> bool {
+        self.dictionary
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the shape that this shape was transitioned from, if it is part of the shared transition tree
+    pub fn parent(&self) -> Option<&Rc<Shape>> {
+        self.parent.as_ref()
+    }
+
+    /// Returns the slot that the property is stored in
+    pub fn lookup(&self, key: &PropertyKey) -> Option<u32> {
+        self.slots.get(key).copied()
+    }
+
+    /// Returns the keys of this shape in slot (and therefore insertion) order
+    pub fn keys(&self) -> &[PropertyKey] {
+        &self.keys
+    }
+
+    /// Returns the shape with `key` added in the next slot
+    pub fn add_property(this: &mut Rc<Self>, key: PropertyKey) {
+        if this.dictionary {
+            Self::make_unique(this).insert(key);
+            return;
+        }
+
+        let next = this.transitions.borrow().get(&key).and_then(Weak::upgrade);
+        if let Some(next) = next {
+            *this = next;
+            return;
+        }
+
+        let mut slots = this.slots.clone();
+        let mut keys = this.keys.clone();
+        slots.insert(key.clone(), keys.len() as u32);
+        keys.push(key.clone());
+
+        let dictionary = keys.len() > MAX_SHARED_PROPERTIES;
+        let next = if dictionary {
+            Rc::new(Self::new(slots, keys, None, true))
+        } else {
+            let next = Rc::new(Self::new(slots, keys, Some(this.clone()), false));
+            this.transitions.borrow_mut().insert(key, Rc::downgrade(&next));
+            next
+        };
+        *this = next;
+    }
+
+    /// Returns the shape with `key` removed and the slots after it shifted down by one.
+    /// The result is always a dictionary shape.
+    pub fn remove_property(this: &mut Rc<Self>, key: &PropertyKey) -> Option<u32> {
+        let slot = this.lookup(key)?;
+        let shape = Self::make_unique(this);
+
+        shape.keys.remove(slot as usize);
+        shape.slots.remove(key);
+        for s in shape.slots.values_mut() {
+            if *s > slot {
+                *s -= 1;
+            }
+        }
+
+        Some(slot)
+    }
+
+    /// Turns this shape into a dictionary shape owned by the caller with a new id, since its layout is about to change
+    fn make_unique(this: &mut Rc<Self>) -> &mut Self {
+        if !this.dictionary || Rc::get_mut(this).is_none() {
+            *this = Rc::new(Self::new(this.slots.clone(), this.keys.clone(), None, true));
+        } else {
+            let shape = Rc::get_mut(this).unwrap();
+            shape.id = next_shape_id();
+        }
+
+        Rc::get_mut(this).unwrap()
+    }
+
+    fn insert(&mut self, key: PropertyKey) {
+        self.slots.insert(key.clone(), self.keys.len() as u32);
+        self.keys.push(key);
+    }
+}
+
+impl Drop for Shape {
+    fn drop(&mut self) {
+        // Remove the now dead transition to this shape, so that transition maps don't grow unboundedly
+        // when many short-lived objects get distinct properties added
+        if let (Some(parent), Some(key)) = (&self.parent, self.keys.last()) {
+            let mut transitions = parent.transitions.borrow_mut();
+            if transitions.get(key).is_some_and(|next| next.strong_count() == 0) {
+                transitions.remove(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::gc::interner::sym;
+    use crate::value::object::{ObjectMap, PropertyKey};
+
+    use super::Shape;
+
+    #[test]
+    fn dropped_shapes_are_removed_from_transitions() {
+        let root = Rc::new(Shape::new(ObjectMap::default(), Vec::new(), None, false));
+
+        let mut a = root.clone();
+        Shape::add_property(&mut a, PropertyKey::String(sym::length.into()));
+        let mut b = root.clone();
+        Shape::add_property(&mut b, PropertyKey::String(sym::name.into()));
+        assert_eq!(root.transitions.borrow().len(), 2);
+
+        // shapes still in use by another object keep their transition
+        let a2 = a.clone();
+        drop(a);
+        assert_eq!(root.transitions.borrow().len(), 2);
+
+        drop(a2);
+        drop(b);
+        assert!(root.transitions.borrow().is_empty());
+    }
+}
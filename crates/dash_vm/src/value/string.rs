@@ -1,13 +1,17 @@
+use std::any::Any;
+use std::cell::Cell;
+
 use dash_middle::interner::sym;
 use dash_proc_macro::Trace;
 
+use crate::gc::handle::Handle;
 use crate::gc::interner::Symbol;
 use crate::localscope::LocalScope;
-use crate::throw;
 use crate::value::boxed::String as BoxedString;
+use crate::{delegate, throw};
 
-use super::object::{Object, PropertyKey, PropertyValue};
-use super::ops::conversions::{PreferredType, ValueConversion};
+use super::object::{NamedObject, Object, PropertyKey, PropertyValue};
+use super::ops::conversions::{string_to_number, PreferredType, ValueConversion};
 use super::primitive::{array_like_keys, PrimitiveCapabilities};
 use super::{Typeof, Unrooted, Value};
 
@@ -48,11 +52,7 @@ impl ValueConversion for JsString {
     }
 
     fn to_number(&self, sc: &mut LocalScope) -> Result<f64, Value> {
-        if self.sym == sym::empty {
-            Ok(0.0)
-        } else {
-            Ok(self.res(sc).parse().unwrap_or(f64::NAN))
-        }
+        Ok(string_to_number(self.res(sc)))
     }
 
     fn to_boolean(&self, sc: &mut LocalScope<'_>) -> Result<bool, Value> {
@@ -155,3 +155,69 @@ impl PrimitiveCapabilities for JsString {
         Value::String(*self)
     }
 }
+
+/// Iterates over the code points of a string, so that a surrogate pair yields a single string
+#[derive(Debug, Trace)]
+pub struct StringIterator {
+    /// Byte offset of the next code point
+    position: Cell<usize>,
+    string: JsString,
+    obj: NamedObject,
+}
+
+impl Object for StringIterator {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        own_keys
+    );
+
+    fn apply(
+        &self,
+        scope: &mut LocalScope,
+        callee: Handle,
+        this: Value,
+        args: Vec<Value>,
+    ) -> Result<Unrooted, Unrooted> {
+        self.obj.apply(scope, callee, this, args)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl StringIterator {
+    pub fn new(sc: &LocalScope, string: JsString) -> Self {
+        Self {
+            position: Cell::new(0),
+            string,
+            obj: NamedObject::with_prototype_and_constructor(
+                sc.statics.string_iterator_prototype.clone(),
+                sc.statics.object_ctor.clone(),
+            ),
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            position: Cell::new(0),
+            string: sym::empty.into(),
+            obj: NamedObject::null(),
+        }
+    }
+
+    /// Returns the next code point as a string, or `None` if the end of the string was reached
+    pub fn next(&self, sc: &mut LocalScope) -> Option<JsString> {
+        let position = self.position.get();
+        let c = self.string.res(sc).get(position..)?.chars().next()?;
+        self.position.set(position + c.len_utf8());
+        Some(sc.intern_char(c).into())
+    }
+}
@@ -0,0 +1,92 @@
+use std::cell::RefCell;
+
+use ahash::HashMap;
+
+use crate::delegate;
+use crate::gc::handle::Handle;
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::Vm;
+
+use super::object::{NamedObject, Object};
+use super::Value;
+
+/// A map whose keys are objects that are held weakly.
+///
+/// Keys are not traced, and a value is only kept alive for as long as its key is (see [`WeakMap::trace_live_values`]).
+/// Entries of collected keys are removed by the VM after the mark phase.
+#[derive(Debug)]
+pub struct WeakMap {
+    entries: RefCell<HashMap<Handle, Value>>,
+    obj: NamedObject,
+}
+
+unsafe impl Trace for WeakMap {
+    fn trace(&self, cx: &mut TraceCtxt<'_>) {
+        // Entries are traced separately by the VM, once it is known which keys are alive
+        self.obj.trace(cx);
+    }
+}
+
+impl WeakMap {
+    pub fn new(vm: &Vm) -> Self {
+        let prototype = vm.statics.weakmap_prototype.clone();
+        let ctor = vm.statics.weakmap_ctor.clone();
+        Self {
+            entries: RefCell::new(HashMap::default()),
+            obj: NamedObject::with_prototype_and_constructor(prototype, ctor),
+        }
+    }
+
+    pub fn get(&self, key: &Handle) -> Option<Value> {
+        self.entries.borrow().get(key).cloned()
+    }
+
+    pub fn set(&self, key: Handle, value: Value) {
+        self.entries.borrow_mut().insert(key, value);
+    }
+
+    pub fn has(&self, key: &Handle) -> bool {
+        self.entries.borrow().contains_key(key)
+    }
+
+    pub fn delete(&self, key: &Handle) -> bool {
+        self.entries.borrow_mut().remove(key).is_some()
+    }
+
+    /// Traces the values of all entries whose key has been marked so far, and returns the number of such entries.
+    ///
+    /// Tracing values can mark more keys, so the VM calls this repeatedly until the number stops growing.
+    pub(crate) fn trace_live_values(&self, cx: &mut TraceCtxt<'_>) -> usize {
+        let mut count = 0;
+        for (key, value) in self.entries.borrow().iter() {
+            if key.interior_flags().is_marked() {
+                value.trace(cx);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Removes the entries whose key was not marked in the last mark phase, as it is about to be collected
+    pub(crate) fn remove_collected(&self) {
+        self.entries
+            .borrow_mut()
+            .retain(|key, _| key.interior_flags().is_marked());
+    }
+}
+
+impl Object for WeakMap {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        as_any,
+        apply,
+        own_keys
+    );
+}
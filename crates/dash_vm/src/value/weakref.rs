@@ -0,0 +1,74 @@
+use std::cell::RefCell;
+
+use crate::delegate;
+use crate::gc::handle::Handle;
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::Vm;
+
+use super::object::{NamedObject, Object};
+
+/// A weak reference to an object.
+///
+/// The target is intentionally not traced, so a `WeakRef` does not keep it alive.
+/// Once the target has been collected, the VM clears it and `deref()` returns undefined.
+#[derive(Debug)]
+pub struct WeakRef {
+    target: RefCell<Option<Handle>>,
+    obj: NamedObject,
+}
+
+unsafe impl Trace for WeakRef {
+    fn trace(&self, cx: &mut TraceCtxt<'_>) {
+        // The target is a weak reference and must not be traced
+        self.obj.trace(cx);
+    }
+}
+
+impl WeakRef {
+    pub fn new(vm: &Vm, target: Handle) -> Self {
+        let prototype = vm.statics.weakref_prototype.clone();
+        let ctor = vm.statics.weakref_ctor.clone();
+        Self::with_obj(
+            Some(target),
+            NamedObject::with_prototype_and_constructor(prototype, ctor),
+        )
+    }
+
+    pub fn with_obj(target: Option<Handle>, obj: NamedObject) -> Self {
+        Self {
+            target: RefCell::new(target),
+            obj,
+        }
+    }
+
+    /// Returns the target, or `None` if it has been collected
+    pub fn target(&self) -> Option<Handle> {
+        self.target.borrow().clone()
+    }
+
+    /// Clears the target if it was not marked in the last mark phase, as it is about to be collected.
+    /// Returns whether the target is still alive.
+    pub(crate) fn clear_if_collected(&self) -> bool {
+        let mut target = self.target.borrow_mut();
+        if target.as_ref().is_some_and(|t| !t.interior_flags().is_marked()) {
+            *target = None;
+        }
+        target.is_some()
+    }
+}
+
+impl Object for WeakRef {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        as_any,
+        apply,
+        own_keys
+    );
+}
@@ -0,0 +1,72 @@
+use std::cell::RefCell;
+
+use ahash::HashSet;
+
+use crate::delegate;
+use crate::gc::handle::Handle;
+use crate::gc::trace::{Trace, TraceCtxt};
+use crate::Vm;
+
+use super::object::{NamedObject, Object};
+
+/// A set of objects that are held weakly.
+///
+/// Values are not traced and are removed by the VM once they have been collected.
+#[derive(Debug)]
+pub struct WeakSet {
+    values: RefCell<HashSet<Handle>>,
+    obj: NamedObject,
+}
+
+unsafe impl Trace for WeakSet {
+    fn trace(&self, cx: &mut TraceCtxt<'_>) {
+        // The values are weak references and must not be traced
+        self.obj.trace(cx);
+    }
+}
+
+impl WeakSet {
+    pub fn new(vm: &Vm) -> Self {
+        let prototype = vm.statics.weakset_prototype.clone();
+        let ctor = vm.statics.weakset_ctor.clone();
+        Self {
+            values: RefCell::new(HashSet::default()),
+            obj: NamedObject::with_prototype_and_constructor(prototype, ctor),
+        }
+    }
+
+    pub fn add(&self, value: Handle) {
+        self.values.borrow_mut().insert(value);
+    }
+
+    pub fn has(&self, value: &Handle) -> bool {
+        self.values.borrow().contains(value)
+    }
+
+    pub fn delete(&self, value: &Handle) -> bool {
+        self.values.borrow_mut().remove(value)
+    }
+
+    /// Removes the values that were not marked in the last mark phase, as they are about to be collected
+    pub(crate) fn remove_collected(&self) {
+        self.values
+            .borrow_mut()
+            .retain(|value| value.interior_flags().is_marked());
+    }
+}
+
+impl Object for WeakSet {
+    delegate!(
+        obj,
+        get_own_property_descriptor,
+        get_property,
+        get_property_descriptor,
+        set_property,
+        delete_property,
+        set_prototype,
+        get_prototype,
+        as_any,
+        apply,
+        own_keys
+    );
+}
@@ -1,3 +1,4 @@
+use dash_middle::compiler::CompileResult;
 use dash_vm::eval::EvalError;
 use dash_vm::frame::Frame;
 use dash_vm::localscope::LocalScope;
@@ -82,7 +83,7 @@ impl ExternalVm {
 
     pub fn eval_serialized(&mut self, serialized: Uint8Array) -> Result<JsValue, String> {
         let bytecode = serialized.to_vec();
-        let deserialized = dash_middle::compiler::format::deserialize(&bytecode).map_err(|e| format!("{e:?}"))?;
+        let deserialized = CompileResult::deserialize(&bytecode, &mut self.0.interner).map_err(|e| format!("{e:?}"))?;
         let frame = Frame::from_compile_result(deserialized);
         match self.0.execute_frame(frame) {
             Ok(x) => Ok(JsValue::from(x.into_value())),
@@ -1,5 +1,6 @@
 use dash_compiler::FunctionCompiler;
 use dash_middle::compiler::StaticImportKind;
+use dash_middle::interner::StringInterner;
 use dash_middle::parser::statement::{FuncId, VariableDeclarationName};
 use dash_optimizer::consteval::ConstFunctionEvalCtx;
 use dash_optimizer::type_infer::TypeInferCtx;
@@ -115,8 +116,9 @@ pub fn debug(s: &str, o: OptLevel, em: Emit) -> String {
 
 #[wasm_bindgen]
 pub fn compile(s: &str, o: OptLevel) -> Result<js_sys::Uint8Array, String> {
-    let cmp = FunctionCompiler::compile_str(s, o.into()).map_err(|e| format!("{e:?}"))?;
-    dash_middle::compiler::format::serialize(cmp)
+    let interner = &mut StringInterner::new();
+    let cmp = FunctionCompiler::compile_str(interner, s, o.into()).map_err(|e| format!("{e:?}"))?;
+    cmp.serialize(interner)
         .map(|v| {
             let u8 = js_sys::Uint8Array::new_with_length(v.len() as u32);
             u8.copy_from(&v);